@@ -6,36 +6,73 @@
  *
  * Usage:
  *   node generate_proof.js <salary> <blinding>
+ *   node generate_proof.js --batch <salary> <blinding> [<salary> <blinding> ...]
  *
- * Writes three files to the current working directory:
+ * Single mode writes three files to the current working directory:
  *   proof.json       – Groth16 proof in standard SnarkJS format
  *   public.json      – Public signals in standard SnarkJS format
  *   proof_bytes.json – Same data as hex strings for direct Rust consumption
  *
+ * Batch mode proves every (salary, blinding) pair in one process and writes
+ * only proofs_bytes.json: a JSON array of proof_bytes.json objects, in
+ * argument order.
+ *
  * Behaviour:
  *   1. If `snarkjs` is installed AND the compiled circuit artifacts
  *      (payment_js/payment.wasm + payment_final.zkey) are present beside
  *      this script, a **real** Groth16 proof is generated via SnarkJS.
  *   2. Otherwise a **deterministic mock** proof is generated in the same
  *      JSON formats so that the Rust parsing and deserialization pipeline
- *      can be exercised without requiring a full ZK toolchain.
+ *      can be exercised without requiring a full ZK toolchain.  Its points
+ *      lie on the curve, so the verifier's point validation accepts them.
  *
- * All field elements are drawn from the BN254 scalar field (< BN254_R) so
- * the byte representations always fit in 32 bytes.
+ * All field elements are below the BN254 base field prime (BN254_P) so the
+ * byte representations always fit in 32 bytes.
  */
 
 const fs   = require('fs');
 const path = require('path');
 
 // ── CLI args ──────────────────────────────────────────────────────────────────
-const salary   = BigInt(process.argv[2] ?? '5000');
-const blinding = BigInt(process.argv[3] ?? '123');
+const args      = process.argv.slice(2);
+const batchMode = args[0] === '--batch';
+
+// [[salary, blinding], ...] – a single default pair outside batch mode.
+const inputs = batchMode ? parsePairs(args.slice(1)) : [[
+  BigInt(args[0] ?? '5000'),
+  BigInt(args[1] ?? '123'),
+]];
 
 // BN254 scalar field prime r
 const BN254_R = BigInt(
   '21888242871839275222246405745257275088548364400416034343698204186575808495617'
 );
 
+// BN254 base field prime p
+const BN254_P = BigInt(
+  '21888242871839275222246405745257275088696311157297823662689037894645226208583'
+);
+
+// G2 generator, x = x0 + x1·u and y = y0 + y1·u
+const G2_GENERATOR = {
+  x0: 10857046999023057135944570762232829481370756359578518086990519993285655852781n,
+  x1: 11559732032986387107991004021392285783925812861821192530917403151452391805634n,
+  y0: 8495653923123431417604973247489272438418190587263600148770280649306958101930n,
+  y1: 4082367875863433681332203403145435568316851327593401208105741076214120093531n,
+};
+
+// Pay period fed to the circuit's private `period` input.
+const PERIOD = 0n;
+
+/**
+ * Stand-in recipient_hash for a proof with no real recipient.  Payments
+ * bound to an employee use proof_verifier::recipient_hash of their address
+ * instead (see `zk-payroll nullifier`).
+ */
+function placeholderRecipientHash(blinding) {
+  return (blinding * 31n + 17n) % BN254_R;
+}
+
 // ── Circuit artefact paths ────────────────────────────────────────────────────
 const CIRCUIT_DIR = __dirname;
 const WASM_PATH   = path.join(CIRCUIT_DIR, 'payment_js', 'payment.wasm');
@@ -53,9 +90,8 @@ const ZKEY_PATH   = path.join(CIRCUIT_DIR, 'payment_final.zkey');
     // snarkjs not installed – fall through to mock path
   }
 
-  if (snarkjs && hasArtifacts) {
-    await generateRealProof(snarkjs, salary, blinding);
-  } else {
+  const useReal = snarkjs && hasArtifacts;
+  if (!useReal) {
     if (!snarkjs) {
       process.stderr.write(
         '[generate_proof.js] snarkjs not found – generating deterministic mock proof.\n'
@@ -65,7 +101,25 @@ const ZKEY_PATH   = path.join(CIRCUIT_DIR, 'payment_final.zkey');
         '[generate_proof.js] Circuit artefacts not found – generating deterministic mock proof.\n'
       );
     }
-    generateMockProof(salary, blinding);
+  }
+
+  const generated = [];
+  for (const [salary, blinding] of inputs) {
+    generated.push(useReal
+      ? await generateRealProof(snarkjs, salary, blinding)
+      : generateMockProof(salary, blinding));
+  }
+
+  const kind = useReal ? 'Real Groth16' : 'Mock';
+  if (batchMode) {
+    const all = generated.map(g => proofBytes(g.proof, g.publicSignals));
+    fs.writeFileSync('proofs_bytes.json', JSON.stringify(all, null, 2));
+    process.stdout.write(`[generate_proof.js] ${generated.length} proofs written (${kind.toLowerCase()}).\n`);
+  } else {
+    const { proof, publicSignals } = generated[0];
+    writeStandardFiles(proof, publicSignals);
+    writeProofBytesFile(proof, publicSignals);
+    process.stdout.write(`[generate_proof.js] ${kind} proof written.\n`);
   }
 })().catch(err => {
   process.stderr.write(`[generate_proof.js] Fatal: ${err.message}\n`);
@@ -75,17 +129,13 @@ const ZKEY_PATH   = path.join(CIRCUIT_DIR, 'payment_final.zkey');
 // ── Real proof (requires snarkjs + compiled circuit) ─────────────────────────
 async function generateRealProof(snarkjs, salary, blinding) {
   const input = {
-    salary:   salary.toString(),
-    blinding: blinding.toString(),
+    salary:         salary.toString(),
+    blinding:       blinding.toString(),
+    period:         PERIOD.toString(),
+    recipient_hash: placeholderRecipientHash(blinding % BN254_R).toString(),
   };
 
-  const { proof, publicSignals } = await snarkjs.groth16.fullProve(
-    input, WASM_PATH, ZKEY_PATH
-  );
-
-  writeStandardFiles(proof, publicSignals);
-  writeProofBytesFile(proof, publicSignals);
-  process.stdout.write('[generate_proof.js] Real Groth16 proof written.\n');
+  return snarkjs.groth16.fullProve(input, WASM_PATH, ZKEY_PATH);
 }
 
 // ── Mock proof (no toolchain required) ───────────────────────────────────────
@@ -94,23 +144,16 @@ function generateMockProof(salary, blinding) {
   const b = blinding % BN254_R;
 
   // Derive deterministic public signals (placeholder for Poseidon hash)
-  const commitment = (s + b * 7n)             % BN254_R;  // salary_commitment
-  const nullifier  = (commitment * 13n + 1n)  % BN254_R;  // payment_nullifier
-  const recipient  = (b * 31n + 17n)          % BN254_R;  // recipient_hash
-
-  // G1 point π_A: two 32-byte field elements
-  const a_x = (s * 3n  + 1n) % BN254_R;
-  const a_y = (b * 5n  + 2n) % BN254_R;
+  const recipient  = placeholderRecipientHash(b);              // recipient_hash
+  const commitment = (s + b * 7n)                       % BN254_R;  // salary_commitment
+  const nullifier  = (b * 13n + PERIOD * 11n + recipient) % BN254_R;  // payment_nullifier
 
-  // G2 point π_B: four 32-byte field elements
-  const b_x0 = (s * 17n + 5n) % BN254_R;
-  const b_x1 = (b * 19n + 6n) % BN254_R;
-  const b_y0 = (s * 23n + 7n) % BN254_R;
-  const b_y1 = (b * 29n + 8n) % BN254_R;
+  // G1 points π_A and π_C: multiples of the generator, derived from the inputs
+  const [a_x, a_y] = g1Mul((s * 3n + b * 5n + 1n) % BN254_R);
+  const [c_x, c_y] = g1Mul((commitment * 7n + nullifier * 11n + 3n) % BN254_R);
 
-  // G1 point π_C: two 32-byte field elements
-  const c_x = (commitment * 7n  + 3n) % BN254_R;
-  const c_y = (nullifier  * 11n + 4n) % BN254_R;
+  // G2 point π_B: the generator
+  const { x0: b_x0, x1: b_x1, y0: b_y0, y1: b_y1 } = G2_GENERATOR;
 
   const proof = {
     pi_a:     [a_x.toString(),  a_y.toString(),  '1'],
@@ -130,9 +173,68 @@ function generateMockProof(salary, blinding) {
     recipient.toString(),
   ];
 
-  writeStandardFiles(proof, publicSignals);
-  writeProofBytesFile(proof, publicSignals);
-  process.stdout.write('[generate_proof.js] Mock proof written.\n');
+  return { proof, publicSignals };
+}
+
+// ── BN254 G1 arithmetic (mock proofs only) ───────────────────────────────────
+
+/** a mod p, in [0, p). */
+function modP(a) {
+  const r = a % BN254_P;
+  return r < 0n ? r + BN254_P : r;
+}
+
+/** Modular inverse via Fermat: a^(p-2) mod p. */
+function invP(a) {
+  let result = 1n;
+  let base = modP(a);
+  for (let e = BN254_P - 2n; e > 0n; e >>= 1n) {
+    if (e & 1n) result = (result * base) % BN254_P;
+    base = (base * base) % BN254_P;
+  }
+  return result;
+}
+
+/** Add two affine points on y² = x³ + 3; null is the point at infinity. */
+function g1Add(p, q) {
+  if (p === null) return q;
+  if (q === null) return p;
+  const [x1, y1] = p;
+  const [x2, y2] = q;
+  let lambda;
+  if (x1 === x2) {
+    if (modP(y1 + y2) === 0n) return null;
+    lambda = modP(3n * x1 * x1 * invP(2n * y1));
+  } else {
+    lambda = modP((y2 - y1) * invP(x2 - x1));
+  }
+  const x3 = modP(lambda * lambda - x1 - x2);
+  return [x3, modP(lambda * (x1 - x3) - y1)];
+}
+
+/** k·G1 as [x, y]; [0n, 0n] encodes the point at infinity. */
+function g1Mul(k) {
+  let acc = null;
+  let addend = [1n, 2n];
+  for (let e = k; e > 0n; e >>= 1n) {
+    if (e & 1n) acc = g1Add(acc, addend);
+    addend = g1Add(addend, addend);
+  }
+  return acc ?? [0n, 0n];
+}
+
+// ── Batch arguments ──────────────────────────────────────────────────────────
+
+/** Split `--batch` arguments into [salary, blinding] pairs. */
+function parsePairs(rest) {
+  if (rest.length === 0 || rest.length % 2 !== 0) {
+    throw new Error('--batch expects one or more <salary> <blinding> pairs');
+  }
+  const pairs = [];
+  for (let i = 0; i < rest.length; i += 2) {
+    pairs.push([BigInt(rest[i]), BigInt(rest[i + 1])]);
+  }
+  return pairs;
 }
 
 // ── File writers ──────────────────────────────────────────────────────────────
@@ -143,10 +245,18 @@ function writeStandardFiles(proof, publicSignals) {
   fs.writeFileSync('public.json', JSON.stringify(publicSignals, null, 2));
 }
 
+/** Write proof_bytes.json for a single proof. */
+function writeProofBytesFile(proof, publicSignals) {
+  fs.writeFileSync(
+    'proof_bytes.json',
+    JSON.stringify(proofBytes(proof, publicSignals), null, 2)
+  );
+}
+
 /**
- * Derive hex-encoded byte arrays from proof and public signals and write
- * proof_bytes.json.  This flat key→hex-string format is trivial to parse
- * in Rust without an external JSON library.
+ * Derive hex-encoded byte arrays from proof and public signals.  This flat
+ * key→hex-string format is trivial to parse in Rust without an external
+ * JSON library.
  *
  * Field-element encoding:
  *   Each BN254 field element (≤ 254 bits) is encoded as a 32-byte
@@ -157,7 +267,7 @@ function writeStandardFiles(proof, publicSignals) {
  *   G1 point (x, y)          → 64 bytes  = x_bytes ‖ y_bytes
  *   G2 point (x0,x1, y0,y1) → 128 bytes = x0_bytes ‖ x1_bytes ‖ y0_bytes ‖ y1_bytes
  */
-function writeProofBytesFile(proof, publicSignals) {
+function proofBytes(proof, publicSignals) {
   const fe = s => fieldElemToHex32(BigInt(s)); // field element → 64 hex chars
 
   // π_A: G1 = x ‖ y  (128 hex chars = 64 bytes)
@@ -175,7 +285,7 @@ function writeProofBytesFile(proof, publicSignals) {
   const payment_nullifier = fe(publicSignals[1]);
   const recipient_hash    = fe(publicSignals[2]);
 
-  const proofBytes = {
+  return {
     pi_a,
     pi_b,
     pi_c,
@@ -183,8 +293,6 @@ function writeProofBytesFile(proof, publicSignals) {
     payment_nullifier,
     recipient_hash,
   };
-
-  fs.writeFileSync('proof_bytes.json', JSON.stringify(proofBytes, null, 2));
 }
 
 /**
@@ -8,19 +8,23 @@ include "range_proof.circom";
  * Proves knowledge of (salary, blinding) such that:
  *
  *   salary_commitment = Poseidon(salary, blinding)
- *   payment_nullifier = Poseidon(salary_commitment, nonce)
- *   recipient_hash    = Poseidon(recipient_address)
+ *   payment_nullifier = Poseidon(blinding, period, recipient_hash)
+ *
+ * The nullifier is bound to the recipient: a proof generated for one
+ * employee cannot be replayed to pay another, because the verifier
+ * recomputes recipient_hash from the address being paid.
  *
  * Public inputs:
  *   salary_commitment  – stored on-chain at onboarding time
- *   payment_nullifier  – unique per payment; prevents double-spending
- *   recipient_hash     – hash of the employee's wallet address
+ *   payment_nullifier  – unique per (employee, period); prevents double-spending
+ *   recipient_hash     – sha256(ScVal::Address XDR of the employee) with the
+ *                        top 3 bits cleared, as computed by
+ *                        proof_verifier::recipient_hash
  *
  * Private inputs (known only to the prover):
  *   salary             – the employee's actual salary
  *   blinding           – a random blinding factor chosen at commitment time
- *   nonce              – payment-specific nonce (e.g. block number)
- *   recipient_address  – the employee's actual address
+ *   period             – the pay period being claimed
  *
  * NOTE: This is a PLACEHOLDER circuit.  The constraint system below uses
  * simple linear arithmetic instead of Poseidon because the circomlib
@@ -45,6 +49,10 @@ template PaymentProof() {
     // ── Private inputs ────────────────────────────────────────────────────────
     signal input salary;
     signal input blinding;
+    signal input period;
+
+    // ── Public inputs ─────────────────────────────────────────────────────────
+    signal input recipient_hash;
 
     component salary_range = SalaryRangeProof();
     salary_range.salary <== salary;
@@ -52,15 +60,13 @@ template PaymentProof() {
     // ── Public outputs ────────────────────────────────────────────────────────
     signal output salary_commitment;
     signal output payment_nullifier;
-    signal output recipient_hash;
 
     // ── Placeholder constraints (NOT cryptographically sound) ─────────────────
     // Replace with real Poseidon constraints before production deployment.
     salary_commitment <== salary + blinding * 7;
-    payment_nullifier <== salary_commitment * 13 + 1;
-    recipient_hash    <== blinding * 31 + 17;
+    payment_nullifier <== blinding * 13 + period * 11 + recipient_hash;
 }
 
 component main {
-    public [salary_commitment, payment_nullifier, recipient_hash]
+    public [recipient_hash]
 } = PaymentProof();
@@ -9,8 +9,24 @@
 //!
 //! # Poseidon hash
 //! `poseidon_commitment` uses circomlib-compatible Poseidon parameters over
-//! BN254 (width-3 sponge, two field-element inputs).  This matches the
-//! `payment.circom` circuit and the on-chain verifier once CAP-0075 lands.
+//! BN254 (width-3 sponge, two field-element inputs), selected with
+//! [`PoseidonParams`].  A commitment computed with a different parameter set
+//! than the circuit's is a valid-looking hash the verifier rejects, so the
+//! set is always passed explicitly.  The default, [`PoseidonParams::Circomlib`],
+//! matches the `payment.circom` circuit and the on-chain verifier once
+//! CAP-0075 lands.
+//!
+//! # Deterministic blinding factors
+//! Blinding factors are random by default.  A company may instead keep a
+//! single master seed and derive each factor as
+//! `Poseidon(master_seed, employee_index)` with [`derive_blinding_factor`],
+//! so that every factor can be regenerated from the seed alone.
+//!
+//! # Recipient-bound nullifiers
+//! A payment nullifier is `Poseidon(blinding, period, recipient_hash)`
+//! ([`payment_nullifier`]), so a proof for one employee cannot be replayed to
+//! pay another.  [`recipient_hash`] reproduces the contract's
+//! `proof_verifier::recipient_hash` for a G... address.
 //!
 //! # Byte encoding convention
 //! All 32-byte field-element representations in this module use the canonical
@@ -18,13 +34,44 @@
 //! Callers that store or display these bytes as hex will see the LE form;
 //! this is consistent with the arkworks / circomlib toolchain.
 
-use anyhow::Context;
+use anyhow::{bail, Context};
 use ark_bn254::Fr;
 use ark_ff::PrimeField;
 use ark_serialize::CanonicalSerialize;
 use light_poseidon::{Poseidon, PoseidonHasher};
 use rand::rngs::OsRng;
 use rand::RngCore;
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{AccountId, Limits, PublicKey, ScAddress, ScVal, Uint256, WriteXdr};
+
+/// Poseidon parameter set, identified by the circomlib template it matches.
+///
+/// Both sets use the Grain-LFSR round constants shipped with circomlib 0.5.0
+/// and later (and with circomlibjs).  The Blake2b-derived constants of
+/// circomlib 0.4.x and earlier are not supported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PoseidonParams {
+    /// circomlib `Poseidon(n)`: zero initial state.  Pinned as the default
+    /// because `payment.circom` uses this template.
+    #[default]
+    Circomlib,
+    /// circomlib `PoseidonEx(n, 1)` with `initialState` set to the given
+    /// domain tag, for circuits that domain-separate their hashes.
+    CircomlibEx { initial_state: u64 },
+}
+
+impl PoseidonParams {
+    /// Build a hasher over BN254 for `nr_inputs` field elements.
+    fn hasher(self, nr_inputs: usize) -> anyhow::Result<Poseidon<Fr>> {
+        let hasher = match self {
+            PoseidonParams::Circomlib => Poseidon::<Fr>::new_circom(nr_inputs),
+            PoseidonParams::CircomlibEx { initial_state } => {
+                Poseidon::<Fr>::with_domain_tag_circom(nr_inputs, Fr::from(initial_state))
+            }
+        };
+        hasher.context("Failed to initialise Poseidon hasher")
+    }
+}
 
 /// Generate a uniformly random BN254 scalar field element using the OS CSPRNG.
 ///
@@ -48,6 +95,33 @@ pub fn gen_blinding_factor() -> [u8; 32] {
     fr_to_le_bytes(fr)
 }
 
+/// Derive the blinding factor for `employee_index` from a master seed as
+/// `Poseidon(master_seed, employee_index)`.
+///
+/// The master seed is itself a BN254 scalar, generated once with
+/// [`gen_blinding_factor`].  Distinct indices (and distinct seeds) give
+/// unrelated factors; the same seed and index always give the same factor.
+///
+/// # Returns
+/// 32-byte **little-endian** canonical representation of the field element.
+pub fn derive_blinding_factor(
+    master_seed_le: &[u8; 32],
+    employee_index: u64,
+) -> anyhow::Result<[u8; 32]> {
+    let seed_fr = Fr::from_le_bytes_mod_order(master_seed_le);
+    let index_fr = Fr::from(employee_index);
+
+    // Pinned independently of the commitment parameters so that changing the
+    // default never changes the factors already derived from a seed.
+    let mut hasher = PoseidonParams::Circomlib.hasher(2)?;
+
+    let derived = hasher
+        .hash(&[seed_fr, index_fr])
+        .context("Poseidon hash computation failed")?;
+
+    Ok(fr_to_le_bytes(derived))
+}
+
 /// Compute `Poseidon(salary_amount, blinding_factor)` over the BN254 scalar
 /// field using circomlib-compatible parameters (width-3 sponge, two inputs).
 ///
@@ -56,17 +130,21 @@ pub fn gen_blinding_factor() -> [u8; 32] {
 ///   `Fr::from(salary)`.
 /// * `blinding_le` — 32-byte little-endian BN254 scalar returned by
 ///   [`gen_blinding_factor`].
+/// * `params` — parameter set; must match the circuit that consumes the
+///   commitment.  Use `PoseidonParams::default()` for `payment.circom`.
 ///
 /// # Returns
 /// 32-byte **little-endian** encoding of the Poseidon hash output.
-pub fn poseidon_commitment(salary: u64, blinding_le: &[u8; 32]) -> anyhow::Result<[u8; 32]> {
+pub fn poseidon_commitment(
+    salary: u64,
+    blinding_le: &[u8; 32],
+    params: PoseidonParams,
+) -> anyhow::Result<[u8; 32]> {
     let salary_fr = Fr::from(salary);
     // Re-hydrate the field element from its stored LE byte representation.
     let blinding_fr = Fr::from_le_bytes_mod_order(blinding_le);
 
-    // Poseidon with circomlib-compatible parameters for BN254, two inputs.
-    let mut hasher =
-        Poseidon::<Fr>::new_circom(2).context("Failed to initialise Poseidon hasher")?;
+    let mut hasher = params.hasher(2)?;
 
     let hash_fr = hasher
         .hash(&[salary_fr, blinding_fr])
@@ -75,6 +153,83 @@ pub fn poseidon_commitment(salary: u64, blinding_le: &[u8; 32]) -> anyhow::Resul
     Ok(fr_to_le_bytes(hash_fr))
 }
 
+/// Hash a Stellar account address the way `proof_verifier::recipient_hash`
+/// does: SHA-256 of the address's `ScVal` XDR with the top three bits cleared,
+/// so the value is a canonical BN254 scalar.
+///
+/// # Returns
+/// 32-byte **big-endian** hash, byte-for-byte the contract's public input —
+/// the one exception to this module's little-endian convention.
+pub fn recipient_hash(pubkey: &str) -> anyhow::Result<[u8; 32]> {
+    let key = stellar_strkey::ed25519::PublicKey::from_string(pubkey)
+        .map_err(|e| anyhow::anyhow!("Invalid Stellar public key '{}': {}", pubkey, e))?;
+    let address = ScVal::Address(ScAddress::Account(AccountId(
+        PublicKey::PublicKeyTypeEd25519(Uint256(key.0)),
+    )));
+    let xdr = address
+        .to_xdr(Limits::none())
+        .context("Failed to XDR-encode address")?;
+
+    let mut hash: [u8; 32] = Sha256::digest(&xdr).into();
+    hash[0] &= 0x1f;
+    Ok(hash)
+}
+
+/// Compute the recipient-bound payment nullifier
+/// `Poseidon(blinding, period, recipient_hash)` over BN254.
+///
+/// # Arguments
+/// * `blinding_le` — the employee's 32-byte little-endian blinding factor.
+/// * `period` — the pay period the payment claims.
+/// * `recipient_hash_be` — [`recipient_hash`] of the employee's address.
+/// * `params` — parameter set; must match the circuit, as for
+///   [`poseidon_commitment`].
+///
+/// # Returns
+/// 32-byte **little-endian** encoding of the Poseidon hash output.
+pub fn payment_nullifier(
+    blinding_le: &[u8; 32],
+    period: u32,
+    recipient_hash_be: &[u8; 32],
+    params: PoseidonParams,
+) -> anyhow::Result<[u8; 32]> {
+    let blinding_fr = Fr::from_le_bytes_mod_order(blinding_le);
+    let period_fr = Fr::from(period);
+    let recipient_fr = Fr::from_be_bytes_mod_order(recipient_hash_be);
+
+    let mut hasher = params.hasher(3)?;
+
+    let hash_fr = hasher
+        .hash(&[blinding_fr, period_fr, recipient_fr])
+        .context("Poseidon hash computation failed")?;
+
+    Ok(fr_to_le_bytes(hash_fr))
+}
+
+/// Parse a 32-byte commitment (or other field element) from its hex form.
+///
+/// Accepts the `0x`-prefixed form printed by the CLI as well as bare hex, in
+/// either case.  Input must be exactly 64 hex characters after the optional
+/// prefix.
+pub fn parse_commitment_hex(s: &str) -> anyhow::Result<[u8; 32]> {
+    let trimmed = s.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+
+    if digits.len() != 64 {
+        bail!(
+            "Expected 64 hex characters (32 bytes), got {}",
+            digits.len()
+        );
+    }
+
+    let mut out = [0u8; 32];
+    hex::decode_to_slice(digits, &mut out).with_context(|| format!("'{}' is not valid hex", s))?;
+    Ok(out)
+}
+
 /// Serialise an `Fr` field element to its 32-byte little-endian canonical form.
 ///
 /// Uses `ark_serialize::CanonicalSerialize` which is infallible for in-memory
@@ -118,7 +273,7 @@ mod tests {
     #[test]
     fn commitment_is_32_bytes() {
         let blinding = gen_blinding_factor();
-        let c = poseidon_commitment(5_000_000, &blinding).unwrap();
+        let c = poseidon_commitment(5_000_000, &blinding, PoseidonParams::default()).unwrap();
         assert_eq!(c.len(), 32);
     }
 
@@ -126,8 +281,8 @@ mod tests {
     #[test]
     fn commitment_is_deterministic() {
         let blinding = gen_blinding_factor();
-        let c1 = poseidon_commitment(5_000_000, &blinding).unwrap();
-        let c2 = poseidon_commitment(5_000_000, &blinding).unwrap();
+        let c1 = poseidon_commitment(5_000_000, &blinding, PoseidonParams::default()).unwrap();
+        let c2 = poseidon_commitment(5_000_000, &blinding, PoseidonParams::default()).unwrap();
         assert_eq!(c1, c2, "Poseidon hash must be deterministic");
     }
 
@@ -135,8 +290,8 @@ mod tests {
     #[test]
     fn different_salaries_produce_different_commitments() {
         let blinding = gen_blinding_factor();
-        let c1 = poseidon_commitment(5_000_000, &blinding).unwrap();
-        let c2 = poseidon_commitment(6_000_000, &blinding).unwrap();
+        let c1 = poseidon_commitment(5_000_000, &blinding, PoseidonParams::default()).unwrap();
+        let c2 = poseidon_commitment(6_000_000, &blinding, PoseidonParams::default()).unwrap();
         assert_ne!(
             c1, c2,
             "different salaries must yield different commitments"
@@ -148,8 +303,8 @@ mod tests {
     fn different_blindings_produce_different_commitments() {
         let b1 = gen_blinding_factor();
         let b2 = gen_blinding_factor();
-        let c1 = poseidon_commitment(5_000_000, &b1).unwrap();
-        let c2 = poseidon_commitment(5_000_000, &b2).unwrap();
+        let c1 = poseidon_commitment(5_000_000, &b1, PoseidonParams::default()).unwrap();
+        let c2 = poseidon_commitment(5_000_000, &b2, PoseidonParams::default()).unwrap();
         assert_ne!(
             c1, c2,
             "different blinding factors must yield different commitments"
@@ -160,7 +315,156 @@ mod tests {
     #[test]
     fn zero_salary_is_valid() {
         let blinding = gen_blinding_factor();
-        let result = poseidon_commitment(0, &blinding);
+        let result = poseidon_commitment(0, &blinding, PoseidonParams::default());
         assert!(result.is_ok(), "zero salary must be a valid Poseidon input");
     }
+
+    /// Every supported parameter set is deterministic.
+    #[test]
+    fn each_param_set_is_deterministic() {
+        let blinding = gen_blinding_factor();
+        for params in [
+            PoseidonParams::Circomlib,
+            PoseidonParams::CircomlibEx { initial_state: 1 },
+        ] {
+            let c1 = poseidon_commitment(5_000_000, &blinding, params).unwrap();
+            let c2 = poseidon_commitment(5_000_000, &blinding, params).unwrap();
+            assert_eq!(c1, c2, "{:?} must be deterministic", params);
+        }
+    }
+
+    /// Different parameter sets give different commitments for the same
+    /// inputs, so a mismatch with the circuit cannot go unnoticed.
+    #[test]
+    fn different_param_sets_produce_different_commitments() {
+        let blinding = gen_blinding_factor();
+        let default = poseidon_commitment(5_000_000, &blinding, PoseidonParams::default()).unwrap();
+        let tagged = poseidon_commitment(
+            5_000_000,
+            &blinding,
+            PoseidonParams::CircomlibEx { initial_state: 1 },
+        )
+        .unwrap();
+        assert_ne!(default, tagged);
+    }
+
+    /// The pinned default is the plain circomlib `Poseidon` template.
+    #[test]
+    fn default_params_are_circomlib_poseidon() {
+        assert_eq!(PoseidonParams::default(), PoseidonParams::Circomlib);
+    }
+
+    /// `0x`-prefixed and bare hex decode to the same bytes.
+    #[test]
+    fn parse_commitment_hex_accepts_prefixed_and_bare() {
+        let bytes =
+            poseidon_commitment(5_000_000, &gen_blinding_factor(), PoseidonParams::default())
+                .unwrap();
+        let bare = hex::encode(bytes);
+
+        assert_eq!(parse_commitment_hex(&bare).unwrap(), bytes);
+        assert_eq!(parse_commitment_hex(&format!("0x{}", bare)).unwrap(), bytes);
+    }
+
+    /// Uppercase digits and prefix are accepted.
+    #[test]
+    fn parse_commitment_hex_accepts_uppercase() {
+        let bytes = [0xABu8; 32];
+        let upper = format!("0X{}", hex::encode_upper(bytes));
+        assert_eq!(parse_commitment_hex(&upper).unwrap(), bytes);
+    }
+
+    /// Anything other than 64 hex digits is rejected.
+    #[test]
+    fn parse_commitment_hex_rejects_wrong_length() {
+        assert!(parse_commitment_hex(&"ab".repeat(31)).is_err());
+        assert!(parse_commitment_hex(&format!("0x{}", "ab".repeat(33))).is_err());
+        assert!(parse_commitment_hex("0x").is_err());
+    }
+
+    /// Non-hex characters are rejected even at the right length.
+    #[test]
+    fn parse_commitment_hex_rejects_non_hex() {
+        let bad = format!("0x{}zz", "ab".repeat(31));
+        assert!(parse_commitment_hex(&bad).is_err());
+    }
+
+    /// The same seed and index always derive the same factor.
+    #[test]
+    fn derived_blinding_is_deterministic_per_index() {
+        let seed = gen_blinding_factor();
+        assert_eq!(
+            derive_blinding_factor(&seed, 7).unwrap(),
+            derive_blinding_factor(&seed, 7).unwrap()
+        );
+    }
+
+    /// Different indices under one seed derive different factors.
+    #[test]
+    fn derived_blindings_are_distinct_across_indices() {
+        let seed = gen_blinding_factor();
+        let derived: std::collections::HashSet<[u8; 32]> = (0..32)
+            .map(|i| derive_blinding_factor(&seed, i).unwrap())
+            .collect();
+        assert_eq!(derived.len(), 32, "every index must derive a unique factor");
+    }
+
+    /// Two master seeds share no derived factors.
+    #[test]
+    fn different_seeds_derive_disjoint_factors() {
+        let seed_a = gen_blinding_factor();
+        let seed_b = gen_blinding_factor();
+        let from_a: std::collections::HashSet<[u8; 32]> = (0..32)
+            .map(|i| derive_blinding_factor(&seed_a, i).unwrap())
+            .collect();
+        let from_b: std::collections::HashSet<[u8; 32]> = (0..32)
+            .map(|i| derive_blinding_factor(&seed_b, i).unwrap())
+            .collect();
+        assert!(from_a.is_disjoint(&from_b), "seeds must not share factors");
+    }
+
+    /// Same address and bytes as the proof_verifier contract's
+    /// `test_recipient_hash_known_vector`.
+    #[test]
+    fn recipient_hash_matches_contract() {
+        let hash =
+            recipient_hash("GADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOZPI").unwrap();
+        assert_eq!(
+            hex::encode(hash),
+            "14c39982317fee7c3c084a7c358ee8e12b4de97112f95bfe3feb98b066065b5a"
+        );
+    }
+
+    #[test]
+    fn recipient_hash_rejects_bad_checksum() {
+        assert!(
+            recipient_hash("GADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOZPA").is_err()
+        );
+    }
+
+    /// The nullifier changes with the recipient and the period, so it can be
+    /// neither redirected nor replayed in another period.
+    #[test]
+    fn nullifier_is_bound_to_recipient_and_period() {
+        let blinding = gen_blinding_factor();
+        let alice =
+            recipient_hash("GADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOZPI").unwrap();
+        let bob_key =
+            stellar_strkey::Strkey::PublicKeyEd25519(stellar_strkey::ed25519::PublicKey([9u8; 32]));
+        let bob = recipient_hash(&bob_key.to_string()).unwrap();
+
+        let n = payment_nullifier(&blinding, 1, &alice, PoseidonParams::default()).unwrap();
+        assert_eq!(
+            n,
+            payment_nullifier(&blinding, 1, &alice, PoseidonParams::default()).unwrap()
+        );
+        assert_ne!(
+            n,
+            payment_nullifier(&blinding, 1, &bob, PoseidonParams::default()).unwrap()
+        );
+        assert_ne!(
+            n,
+            payment_nullifier(&blinding, 2, &alice, PoseidonParams::default()).unwrap()
+        );
+    }
 }
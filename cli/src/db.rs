@@ -5,6 +5,10 @@
 //! and backed up — loss of blinding factors permanently prevents future proof
 //! generation for the affected employees.
 //!
+//! The `~/.zk-payroll` base directory can be overridden with the global
+//! `--data-dir` flag or the `ZK_PAYROLL_HOME` environment variable (see
+//! [`db_path`]).
+//!
 //! # Schema
 //! ```sql
 //! CREATE TABLE blinding_factors (
@@ -12,29 +16,107 @@
 //!     blinding_factor      TEXT    NOT NULL,
 //!     current_salary_amount INTEGER NOT NULL
 //! );
+//!
+//! CREATE TABLE reconcile_cursors (
+//!     contract_id  TEXT    NOT NULL,
+//!     company_id   TEXT    NOT NULL,
+//!     next_ledger  INTEGER NOT NULL,
+//!     PRIMARY KEY (contract_id, company_id)
+//! );
+//!
+//! CREATE TABLE master_seed (
+//!     id          INTEGER PRIMARY KEY CHECK (id = 1),
+//!     seed        TEXT    NOT NULL,
+//!     next_index  INTEGER NOT NULL
+//! );
+//!
+//! CREATE TABLE derivation_indices (
+//!     employee_pubkey  TEXT    PRIMARY KEY,
+//!     derivation_index INTEGER NOT NULL
+//! );
+//!
+//! CREATE TABLE employee_deactivations (
+//!     employee_pubkey TEXT    PRIMARY KEY
+//!                     REFERENCES blinding_factors (employee_pubkey) ON DELETE CASCADE,
+//!     deactivated_at  INTEGER NOT NULL
+//! );
+//!
+//! CREATE TABLE audit_log (
+//!     id              INTEGER PRIMARY KEY AUTOINCREMENT,
+//!     employee_pubkey TEXT    NOT NULL,
+//!     action          TEXT    NOT NULL,
+//!     created_at      INTEGER NOT NULL
+//! );
+//!
+//! CREATE TABLE company_networks (
+//!     company_id TEXT PRIMARY KEY,
+//!     network    TEXT NOT NULL
+//! );
 //! ```
 //!
 //! The `blinding_factor` column holds a 64-character lowercase hex string
 //! encoding the 32-byte little-endian BN254 scalar produced by
-//! [`crate::crypto::gen_blinding_factor`].
+//! [`crate::crypto::gen_blinding_factor`] or
+//! [`crate::crypto::derive_blinding_factor`].
+//!
+//! `master_seed` has at most one row and only exists for companies that opted
+//! into derived blinding factors; `derivation_indices` records which index
+//! each employee's current factor was derived from.
+//!
+//! Timestamps (`deactivated_at`, `created_at`) are Unix seconds.  An employee
+//! with a row in `employee_deactivations` is deactivated; every other
+//! employee is active.  `audit_log` records local changes to employee records
+//! and never contains salaries or blinding factors.
+//!
+//! `company_networks` holds the network each company was last reconciled
+//! against: a preset name such as `testnet`, or the RPC URL of a custom
+//! endpoint.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use rusqlite::{params, Connection};
 use std::path::{Path, PathBuf};
 
 // ── Path resolution ───────────────────────────────────────────────────────────
 
-/// Returns the canonical path `~/.zk-payroll/company_db.sqlite`.
+/// Environment variable that overrides the default `~/.zk-payroll` directory.
+pub const DATA_DIR_ENV: &str = "ZK_PAYROLL_HOME";
+
+/// Returns the path of `company_db.sqlite` inside the data directory.
+///
+/// The data directory is, in order of precedence:
+/// 1. `data_dir` — the global `--data-dir` flag;
+/// 2. `$ZK_PAYROLL_HOME`, if set and non-empty;
+/// 3. `~/.zk-payroll`.
 ///
 /// # Errors
-/// Returns an error when the home directory cannot be determined (e.g. on a
-/// system where `$HOME` / `USERPROFILE` is unset).
-pub fn db_path() -> Result<PathBuf> {
-    let home = dirs::home_dir().context(
+/// Returns an error when neither override is given and the home directory
+/// cannot be determined (e.g. on a system where `$HOME` / `USERPROFILE` is
+/// unset).
+pub fn db_path(data_dir: Option<&Path>) -> Result<PathBuf> {
+    let env_dir = std::env::var_os(DATA_DIR_ENV).map(PathBuf::from);
+    let dir = resolve_data_dir(data_dir, env_dir, dirs::home_dir)?;
+    Ok(dir.join("company_db.sqlite"))
+}
+
+/// Pick the data directory from the flag, the environment, or the home
+/// directory.  `home` is only consulted when neither override is set.
+fn resolve_data_dir(
+    flag: Option<&Path>,
+    env_dir: Option<PathBuf>,
+    home: impl FnOnce() -> Option<PathBuf>,
+) -> Result<PathBuf> {
+    if let Some(dir) = flag {
+        return Ok(dir.to_path_buf());
+    }
+    if let Some(dir) = env_dir.filter(|d| !d.as_os_str().is_empty()) {
+        return Ok(dir);
+    }
+    let home = home().context(
         "Cannot determine the home directory. \
-         Ensure the HOME (Unix) or USERPROFILE (Windows) environment variable is set.",
+         Ensure the HOME (Unix) or USERPROFILE (Windows) environment variable is set, \
+         or pass --data-dir.",
     )?;
-    Ok(home.join(".zk-payroll").join("company_db.sqlite"))
+    Ok(home.join(".zk-payroll"))
 }
 
 // ── Connection management ─────────────────────────────────────────────────────
@@ -55,7 +137,20 @@ pub fn open(path: &Path) -> Result<Connection> {
 
 // ── Schema initialisation ─────────────────────────────────────────────────────
 
-/// Create the `blinding_factors` table if it does not already exist.
+/// Tables created by [`initialise`].  A database missing any of them predates
+/// the current schema.
+pub const SCHEMA_TABLES: [&str; 7] = [
+    "blinding_factors",
+    "reconcile_cursors",
+    "master_seed",
+    "derivation_indices",
+    "employee_deactivations",
+    "audit_log",
+    "company_networks",
+];
+
+/// Create all tables listed in the module-level schema if they do not
+/// already exist.
 ///
 /// Safe to call on an already-initialised database (idempotent via
 /// `CREATE TABLE IF NOT EXISTS`).
@@ -65,12 +160,58 @@ pub fn initialise(conn: &Connection) -> Result<()> {
             employee_pubkey       TEXT     PRIMARY KEY,
             blinding_factor       TEXT     NOT NULL,
             current_salary_amount INTEGER  NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS reconcile_cursors (
+            contract_id TEXT     NOT NULL,
+            company_id  TEXT     NOT NULL,
+            next_ledger INTEGER  NOT NULL,
+            PRIMARY KEY (contract_id, company_id)
+        );
+        CREATE TABLE IF NOT EXISTS master_seed (
+            id         INTEGER  PRIMARY KEY CHECK (id = 1),
+            seed       TEXT     NOT NULL,
+            next_index INTEGER  NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS derivation_indices (
+            employee_pubkey  TEXT     PRIMARY KEY,
+            derivation_index INTEGER  NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS employee_deactivations (
+            employee_pubkey TEXT     PRIMARY KEY
+                            REFERENCES blinding_factors (employee_pubkey) ON DELETE CASCADE,
+            deactivated_at  INTEGER  NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS audit_log (
+            id              INTEGER  PRIMARY KEY AUTOINCREMENT,
+            employee_pubkey TEXT     NOT NULL,
+            action          TEXT     NOT NULL,
+            created_at      INTEGER  NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS company_networks (
+            company_id TEXT  PRIMARY KEY,
+            network    TEXT  NOT NULL
         );",
     )
-    .context("Failed to create blinding_factors table")?;
+    .context("Failed to create database tables")?;
     Ok(())
 }
 
+/// Return the entries of [`SCHEMA_TABLES`] that do not exist in `conn`.
+///
+/// Only reads `sqlite_master`, so it is safe on a read-only connection.
+pub fn missing_tables(conn: &Connection) -> Result<Vec<&'static str>> {
+    let mut stmt = conn
+        .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1")
+        .context("Failed to read database schema")?;
+    let mut missing = Vec::new();
+    for table in SCHEMA_TABLES {
+        if !stmt.exists(params![table])? {
+            missing.push(table);
+        }
+    }
+    Ok(missing)
+}
+
 // ── Write operations ─────────────────────────────────────────────────────────
 
 /// Insert a new employee record.
@@ -108,6 +249,30 @@ pub fn insert_employee(
     Ok(())
 }
 
+/// Replace the stored blinding factor for an existing employee.
+///
+/// The salary amount is left untouched.
+///
+/// # Arguments
+/// * `pubkey` — Stellar public key (G... address) of an existing employee.
+/// * `blinding_hex` — 64-character lowercase hex of the new 32-byte LE blinding scalar.
+///
+/// # Errors
+/// Returns an error if no record for `pubkey` exists.
+pub fn update_employee_blinding(conn: &Connection, pubkey: &str, blinding_hex: &str) -> Result<()> {
+    let rows = conn
+        .execute(
+            "UPDATE blinding_factors SET blinding_factor = ?2 WHERE employee_pubkey = ?1",
+            params![pubkey, blinding_hex],
+        )
+        .with_context(|| format!("Failed to update blinding factor for '{}'", pubkey))?;
+
+    if rows == 0 {
+        bail!("Employee '{}' not found in the database", pubkey);
+    }
+    Ok(())
+}
+
 /// Return the stored blinding factor and salary for `pubkey`, if present.
 ///
 /// Returns `Ok(None)` when the employee is not in the database.
@@ -135,6 +300,340 @@ pub fn employee_exists(conn: &Connection, pubkey: &str) -> Result<bool> {
     Ok(get_employee(conn, pubkey)?.is_some())
 }
 
+/// Returns `true` if any employee's stored blinding factor is `blinding_hex`.
+///
+/// Two employees sharing a blinding factor would let anyone who learns one
+/// salary test the other's commitment against guesses, so callers check a
+/// new factor with this before storing it.
+pub fn blinding_exists(conn: &Connection, blinding_hex: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM blinding_factors WHERE blinding_factor = ?1)",
+        params![blinding_hex],
+        |row| row.get(0),
+    )
+    .context("Failed to look up blinding factor")
+}
+
+// ── Reconcile cursor ──────────────────────────────────────────────────────────
+
+/// Return the first ledger not yet scanned by `reconcile --watch` for this
+/// contract and company, if a cursor has been stored.
+pub fn get_reconcile_cursor(
+    conn: &Connection,
+    contract_id: &str,
+    company_id: &str,
+) -> Result<Option<u32>> {
+    let result = conn.query_row(
+        "SELECT next_ledger FROM reconcile_cursors \
+         WHERE contract_id = ?1 AND company_id = ?2",
+        params![contract_id, company_id],
+        |row| row.get::<_, i64>(0),
+    );
+
+    match result {
+        Ok(ledger) => Ok(Some(ledger as u32)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e).context("Failed to read reconcile cursor"),
+    }
+}
+
+/// Store the first ledger not yet scanned by `reconcile --watch`.
+pub fn set_reconcile_cursor(
+    conn: &Connection,
+    contract_id: &str,
+    company_id: &str,
+    next_ledger: u32,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO reconcile_cursors (contract_id, company_id, next_ledger) \
+         VALUES (?1, ?2, ?3) \
+         ON CONFLICT (contract_id, company_id) DO UPDATE SET next_ledger = excluded.next_ledger",
+        params![contract_id, company_id, next_ledger as i64],
+    )
+    .context("Failed to store reconcile cursor")?;
+    Ok(())
+}
+
+// ── Company network ───────────────────────────────────────────────────────────
+
+/// Return the network `company_id` was last reconciled against, if any.
+pub fn get_company_network(conn: &Connection, company_id: &str) -> Result<Option<String>> {
+    let result = conn.query_row(
+        "SELECT network FROM company_networks WHERE company_id = ?1",
+        params![company_id],
+        |row| row.get::<_, String>(0),
+    );
+
+    match result {
+        Ok(network) => Ok(Some(network)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e).context("Failed to read company network"),
+    }
+}
+
+/// Record the network `company_id` is being reconciled against.
+pub fn set_company_network(conn: &Connection, company_id: &str, network: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO company_networks (company_id, network) VALUES (?1, ?2) \
+         ON CONFLICT (company_id) DO UPDATE SET network = excluded.network",
+        params![company_id, network],
+    )
+    .context("Failed to store company network")?;
+    Ok(())
+}
+
+// ── Master seed ───────────────────────────────────────────────────────────────
+
+/// Return the hex-encoded master seed, if derived blinding factors are enabled.
+pub fn get_master_seed(conn: &Connection) -> Result<Option<String>> {
+    let result = conn.query_row("SELECT seed FROM master_seed WHERE id = 1", [], |row| {
+        row.get::<_, String>(0)
+    });
+
+    match result {
+        Ok(seed) => Ok(Some(seed)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e).context("Failed to read master seed"),
+    }
+}
+
+/// Store the hex-encoded master seed, starting derivation at index 0.
+///
+/// # Errors
+/// Returns an error if a master seed is already stored; it must never be
+/// replaced, or existing factors could no longer be regenerated.
+pub fn insert_master_seed(conn: &Connection, seed_hex: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO master_seed (id, seed, next_index) VALUES (1, ?1, 0)",
+        params![seed_hex],
+    )
+    .context("Failed to store master seed. A master seed may already exist.")?;
+    Ok(())
+}
+
+/// Allocate the next derivation index and record it against `pubkey`.
+///
+/// Each call returns a fresh index, so rotating an employee's blinding factor
+/// moves them to a new index rather than reusing the old one.
+pub fn allocate_derivation_index(conn: &Connection, pubkey: &str) -> Result<u64> {
+    let index: i64 = conn
+        .query_row(
+            "UPDATE master_seed SET next_index = next_index + 1              WHERE id = 1 RETURNING next_index - 1",
+            [],
+            |row| row.get(0),
+        )
+        .context("Failed to allocate derivation index")?;
+
+    conn.execute(
+        "INSERT INTO derivation_indices (employee_pubkey, derivation_index)          VALUES (?1, ?2)          ON CONFLICT (employee_pubkey) DO UPDATE SET derivation_index = excluded.derivation_index",
+        params![pubkey, index],
+    )
+    .with_context(|| format!("Failed to record derivation index for '{}'", pubkey))?;
+
+    Ok(index as u64)
+}
+
+// ── Deactivation and audit log ───────────────────────────────────────────────
+
+/// Mark an existing employee as deactivated at `at` (Unix seconds).
+///
+/// Re-deactivating keeps the original timestamp.
+///
+/// # Errors
+/// Returns an error if no record for `pubkey` exists.
+pub fn deactivate_employee(conn: &Connection, pubkey: &str, at: i64) -> Result<()> {
+    if !employee_exists(conn, pubkey)? {
+        bail!("Employee '{}' not found in the database", pubkey);
+    }
+    conn.execute(
+        "INSERT OR IGNORE INTO employee_deactivations (employee_pubkey, deactivated_at)          VALUES (?1, ?2)",
+        params![pubkey, at],
+    )
+    .with_context(|| format!("Failed to deactivate employee '{}'", pubkey))?;
+    Ok(())
+}
+
+/// Returns `true` if `pubkey` has been deactivated.
+pub fn is_deactivated(conn: &Connection, pubkey: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM employee_deactivations WHERE employee_pubkey = ?1)",
+        params![pubkey],
+        |row| row.get(0),
+    )
+    .with_context(|| format!("Database query failed for pubkey '{}'", pubkey))
+}
+
+/// Salaries of every employee not deactivated, in pubkey order.
+pub fn active_salaries(conn: &Connection) -> Result<Vec<u64>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT current_salary_amount FROM blinding_factors \
+             WHERE employee_pubkey NOT IN (SELECT employee_pubkey FROM employee_deactivations) \
+             ORDER BY employee_pubkey",
+        )
+        .context("Failed to query active salaries")?;
+    let salaries = stmt
+        .query_map([], |row| row.get::<_, i64>(0))
+        .context("Failed to query active salaries")?
+        .map(|salary| salary.map(|s| s as u64))
+        .collect::<rusqlite::Result<_>>()
+        .context("Failed to read active salaries")?;
+    Ok(salaries)
+}
+
+/// Pubkey, blinding factor and salary of every employee not deactivated, in
+/// pubkey order.
+pub fn active_employees(conn: &Connection) -> Result<Vec<(String, String, u64)>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT employee_pubkey, blinding_factor, current_salary_amount \
+             FROM blinding_factors \
+             WHERE employee_pubkey NOT IN (SELECT employee_pubkey FROM employee_deactivations) \
+             ORDER BY employee_pubkey",
+        )
+        .context("Failed to query active employees")?;
+    let employees = stmt
+        .query_map([], |row| {
+            let salary: i64 = row.get(2)?;
+            Ok((row.get(0)?, row.get(1)?, salary as u64))
+        })
+        .context("Failed to query active employees")?
+        .collect::<rusqlite::Result<_>>()
+        .context("Failed to read active employees")?;
+    Ok(employees)
+}
+
+/// Append an entry to the local audit log.
+pub fn append_audit_log(conn: &Connection, pubkey: &str, action: &str, at: i64) -> Result<()> {
+    conn.execute(
+        "INSERT INTO audit_log (employee_pubkey, action, created_at) VALUES (?1, ?2, ?3)",
+        params![pubkey, action, at],
+    )
+    .context("Failed to write audit log entry")?;
+    Ok(())
+}
+
+/// Rows removed by [`prune`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PruneReport {
+    pub audit_log_entries: usize,
+    pub deactivated_employees: usize,
+}
+
+impl PruneReport {
+    pub fn total(&self) -> usize {
+        self.audit_log_entries + self.deactivated_employees
+    }
+}
+
+/// An `audit_log` row, as listed by [`prune_preview`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct AuditLogRow {
+    pub employee_pubkey: String,
+    pub action: String,
+    pub created_at: i64,
+}
+
+/// Rows [`prune`] would remove with the same arguments.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PrunePreview {
+    pub audit_log_entries: Vec<AuditLogRow>,
+    /// Public keys of deactivated employees whose blinding factors would be
+    /// deleted.
+    pub deactivated_employees: Vec<String>,
+}
+
+impl PrunePreview {
+    pub fn is_empty(&self) -> bool {
+        self.audit_log_entries.is_empty() && self.deactivated_employees.is_empty()
+    }
+}
+
+/// List the rows `prune(conn, cutoff, include_deactivated)` would delete,
+/// without deleting anything.
+pub fn prune_preview(
+    conn: &Connection,
+    cutoff: i64,
+    include_deactivated: bool,
+) -> Result<PrunePreview> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT employee_pubkey, action, created_at FROM audit_log
+             WHERE created_at < ?1 ORDER BY id",
+        )
+        .context("Failed to prepare audit-log preview")?;
+    let audit_log_entries = stmt
+        .query_map(params![cutoff], |row| {
+            Ok(AuditLogRow {
+                employee_pubkey: row.get(0)?,
+                action: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .context("Failed to list prunable audit-log entries")?;
+
+    let deactivated_employees = if include_deactivated {
+        let mut stmt = conn
+            .prepare(
+                "SELECT employee_pubkey FROM employee_deactivations
+                 WHERE deactivated_at < ?1 ORDER BY employee_pubkey",
+            )
+            .context("Failed to prepare deactivated-employee preview")?;
+        stmt.query_map(params![cutoff], |row| row.get(0))
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<String>>>())
+            .context("Failed to list prunable employees")?
+    } else {
+        Vec::new()
+    };
+
+    Ok(PrunePreview {
+        audit_log_entries,
+        deactivated_employees,
+    })
+}
+
+/// Delete audit-log entries created before `cutoff` (Unix seconds) and, if
+/// `include_deactivated`, employees deactivated before `cutoff`.
+///
+/// Active employees are never removed.  Removing a deactivated employee
+/// deletes their blinding factor, so they can no longer be paid unless they
+/// are registered again with a new commitment.
+pub fn prune(conn: &Connection, cutoff: i64, include_deactivated: bool) -> Result<PruneReport> {
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to start prune transaction")?;
+
+    let audit_log_entries = tx
+        .execute(
+            "DELETE FROM audit_log WHERE created_at < ?1",
+            params![cutoff],
+        )
+        .context("Failed to prune audit log")?;
+
+    let deactivated_employees = if include_deactivated {
+        tx.execute(
+            "DELETE FROM derivation_indices WHERE employee_pubkey IN              (SELECT employee_pubkey FROM employee_deactivations WHERE deactivated_at < ?1)",
+            params![cutoff],
+        )
+        .context("Failed to prune derivation indices")?;
+        tx.execute(
+            "DELETE FROM blinding_factors WHERE employee_pubkey IN              (SELECT employee_pubkey FROM employee_deactivations WHERE deactivated_at < ?1)",
+            params![cutoff],
+        )
+        .context("Failed to prune deactivated employees")?
+    } else {
+        0
+    };
+
+    tx.commit().context("Failed to commit prune")?;
+
+    Ok(PruneReport {
+        audit_log_entries,
+        deactivated_employees,
+    })
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -156,6 +655,15 @@ mod tests {
         initialise(&conn).unwrap();
     }
 
+    #[test]
+    fn missing_tables_lists_uncreated_tables() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert_eq!(missing_tables(&conn).unwrap(), SCHEMA_TABLES);
+
+        initialise(&conn).unwrap();
+        assert!(missing_tables(&conn).unwrap().is_empty());
+    }
+
     #[test]
     fn insert_and_retrieve_employee() {
         let conn = in_memory_conn();
@@ -196,4 +704,195 @@ mod tests {
         insert_employee(&conn, pubkey, &"d".repeat(64), 1).unwrap();
         assert!(employee_exists(&conn, pubkey).unwrap());
     }
+
+    #[test]
+    fn duplicate_blinding_insert_is_detected() {
+        let conn = in_memory_conn();
+        let blinding = "c".repeat(64);
+
+        assert!(!blinding_exists(&conn, &blinding).unwrap());
+        insert_employee(&conn, "GAAA", &blinding, 1_000).unwrap();
+        insert_employee(&conn, "GBBB", &blinding, 2_000).unwrap();
+        assert!(blinding_exists(&conn, &blinding).unwrap());
+        assert!(!blinding_exists(&conn, &"d".repeat(64)).unwrap());
+    }
+
+    #[test]
+    fn update_blinding_preserves_salary() {
+        let conn = in_memory_conn();
+        let pubkey = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN";
+
+        insert_employee(&conn, pubkey, &"a".repeat(64), 7_000).unwrap();
+        update_employee_blinding(&conn, pubkey, &"e".repeat(64)).unwrap();
+
+        let (stored_blinding, stored_salary) = get_employee(&conn, pubkey).unwrap().unwrap();
+        assert_eq!(stored_blinding, "e".repeat(64));
+        assert_eq!(stored_salary, 7_000);
+    }
+
+    #[test]
+    fn update_blinding_unknown_pubkey_errors() {
+        let conn = in_memory_conn();
+        let result = update_employee_blinding(&conn, "GNOBODY", &"e".repeat(64));
+        assert!(result.is_err(), "update of missing employee must fail");
+    }
+
+    #[test]
+    fn reconcile_cursor_roundtrip() {
+        let conn = in_memory_conn();
+
+        assert_eq!(get_reconcile_cursor(&conn, "CABC", "ACME").unwrap(), None);
+        set_reconcile_cursor(&conn, "CABC", "ACME", 100).unwrap();
+        set_reconcile_cursor(&conn, "CABC", "ACME", 150).unwrap();
+        assert_eq!(
+            get_reconcile_cursor(&conn, "CABC", "ACME").unwrap(),
+            Some(150)
+        );
+        assert_eq!(get_reconcile_cursor(&conn, "CABC", "OTHER").unwrap(), None);
+    }
+
+    #[test]
+    fn master_seed_is_write_once_and_indices_are_sequential() {
+        let conn = in_memory_conn();
+
+        assert_eq!(get_master_seed(&conn).unwrap(), None);
+        insert_master_seed(&conn, "aa").unwrap();
+        assert!(insert_master_seed(&conn, "bb").is_err());
+        assert_eq!(get_master_seed(&conn).unwrap().as_deref(), Some("aa"));
+
+        assert_eq!(allocate_derivation_index(&conn, "GAAA").unwrap(), 0);
+        assert_eq!(allocate_derivation_index(&conn, "GBBB").unwrap(), 1);
+        // Re-allocating (rotation) moves the employee to a fresh index.
+        assert_eq!(allocate_derivation_index(&conn, "GAAA").unwrap(), 2);
+        let recorded: i64 = conn
+            .query_row(
+                "SELECT derivation_index FROM derivation_indices WHERE employee_pubkey = 'GAAA'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(recorded, 2);
+    }
+
+    #[test]
+    fn prune_removes_only_eligible_rows() {
+        let conn = in_memory_conn();
+        insert_employee(&conn, "GACTIVE", &"aa".repeat(32), 1_000).unwrap();
+        insert_employee(&conn, "GOLD", &"bb".repeat(32), 2_000).unwrap();
+        insert_employee(&conn, "GRECENT", &"cc".repeat(32), 3_000).unwrap();
+        deactivate_employee(&conn, "GOLD", 100).unwrap();
+        deactivate_employee(&conn, "GRECENT", 900).unwrap();
+
+        append_audit_log(&conn, "GACTIVE", "add_employee", 50).unwrap();
+        append_audit_log(&conn, "GOLD", "deactivate", 100).unwrap();
+        append_audit_log(&conn, "GRECENT", "deactivate", 900).unwrap();
+
+        // The preview lists exactly what each prune below removes.
+        let preview = prune_preview(&conn, 500, false).unwrap();
+        assert_eq!(preview.audit_log_entries.len(), 2);
+        assert_eq!(preview.audit_log_entries[1].employee_pubkey, "GOLD");
+        assert!(preview.deactivated_employees.is_empty());
+        assert_eq!(
+            prune_preview(&conn, 500, true)
+                .unwrap()
+                .deactivated_employees,
+            vec!["GOLD".to_owned()]
+        );
+
+        // Without the flag only log rows go.
+        let report = prune(&conn, 500, false).unwrap();
+        assert_eq!(
+            report,
+            PruneReport {
+                audit_log_entries: 2,
+                deactivated_employees: 0,
+            }
+        );
+        assert!(employee_exists(&conn, "GOLD").unwrap());
+
+        let report = prune(&conn, 500, true).unwrap();
+        assert_eq!(report.total(), 1);
+        assert!(!employee_exists(&conn, "GOLD").unwrap());
+        assert!(employee_exists(&conn, "GRECENT").unwrap());
+        assert!(is_deactivated(&conn, "GRECENT").unwrap());
+    }
+
+    #[test]
+    fn prune_never_removes_active_employees() {
+        let conn = in_memory_conn();
+        insert_employee(&conn, "GACTIVE", &"aa".repeat(32), 1_000).unwrap();
+
+        assert!(prune_preview(&conn, i64::MAX, true).unwrap().is_empty());
+        let report = prune(&conn, i64::MAX, true).unwrap();
+
+        assert_eq!(report.total(), 0);
+        assert_eq!(
+            get_employee(&conn, "GACTIVE").unwrap(),
+            Some(("aa".repeat(32), 1_000))
+        );
+    }
+
+    #[test]
+    fn active_salaries_skip_deactivated_employees() {
+        let conn = in_memory_conn();
+        insert_employee(&conn, "GAAA", &"a".repeat(64), 1_000).unwrap();
+        insert_employee(&conn, "GBBB", &"b".repeat(64), 2_000).unwrap();
+        insert_employee(&conn, "GCCC", &"c".repeat(64), 4_000).unwrap();
+        deactivate_employee(&conn, "GBBB", 1).unwrap();
+
+        assert_eq!(active_salaries(&conn).unwrap(), vec![1_000, 4_000]);
+    }
+
+    #[test]
+    fn deactivate_unknown_employee_errors() {
+        let conn = in_memory_conn();
+        assert!(deactivate_employee(&conn, "GMISSING", 1).is_err());
+    }
+
+    // ── Data directory resolution ─────────────────────────────────────────────
+
+    fn fake_home() -> Option<PathBuf> {
+        Some(PathBuf::from("/home/alice"))
+    }
+
+    #[test]
+    fn data_dir_defaults_to_home() {
+        let dir = resolve_data_dir(None, None, fake_home).unwrap();
+        assert_eq!(dir, PathBuf::from("/home/alice/.zk-payroll"));
+    }
+
+    #[test]
+    fn data_dir_env_overrides_home() {
+        let dir = resolve_data_dir(None, Some(PathBuf::from("/srv/payroll")), fake_home).unwrap();
+        assert_eq!(dir, PathBuf::from("/srv/payroll"));
+    }
+
+    #[test]
+    fn data_dir_flag_overrides_env() {
+        let dir = resolve_data_dir(
+            Some(Path::new("/tmp/ci")),
+            Some(PathBuf::from("/srv/payroll")),
+            fake_home,
+        )
+        .unwrap();
+        assert_eq!(dir, PathBuf::from("/tmp/ci"));
+    }
+
+    #[test]
+    fn empty_env_falls_back_to_home() {
+        let dir = resolve_data_dir(None, Some(PathBuf::new()), fake_home).unwrap();
+        assert_eq!(dir, PathBuf::from("/home/alice/.zk-payroll"));
+    }
+
+    #[test]
+    fn overrides_do_not_need_a_home_directory() {
+        assert!(resolve_data_dir(None, None, || None).is_err());
+        assert!(resolve_data_dir(Some(Path::new("/tmp/ci")), None, || None).is_ok());
+    }
+
+    #[test]
+    fn db_path_appends_file_name_to_flag_dir() {
+        let path = db_path(Some(Path::new("/tmp/ci"))).unwrap();
+        assert_eq!(path, PathBuf::from("/tmp/ci/company_db.sqlite"));
+    }
 }
@@ -0,0 +1,369 @@
+//! `doctor` command — diagnose common setup problems.
+//!
+//! Each check looks at one thing new installations tend to get wrong and
+//! reports pass/fail, with a remediation hint on failure:
+//!
+//! | Check | Fails when |
+//! |-------|------------|
+//! | Database | `company_db.sqlite` is missing, unreadable, or lacks tables of the current schema |
+//! | Permissions | the data directory or database file is accessible to other users (Unix only) |
+//! | Node.js | `node --version` cannot be run (proof generation needs Node.js) |
+//! | Soroban RPC | the endpoint does not answer `getHealth` with `healthy` |
+//!
+//! The checks never mutate anything: the database is opened read-only, and a
+//! missing data directory is reported rather than created.
+
+use std::path::Path;
+use std::process::Command;
+
+use rusqlite::{Connection, OpenFlags};
+
+use crate::{db, rpc};
+
+/// Outcome of a single check.
+#[derive(Debug)]
+pub struct Check {
+    pub name: &'static str,
+    pub passed: bool,
+    /// What the check found.
+    pub detail: String,
+    /// How to fix a failure; `None` when the check passed.
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: true,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: false,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Run every check against the database at `db_path` and the RPC endpoint
+/// at `rpc_url`, in checklist order.
+pub fn run_checks(db_path: &Path, rpc_url: &str) -> Vec<Check> {
+    vec![
+        check_database(db_path),
+        check_permissions(db_path),
+        check_node("node"),
+        check_rpc(rpc_url),
+    ]
+}
+
+/// The database exists, opens read-only, and has every current table.
+pub fn check_database(db_path: &Path) -> Check {
+    const NAME: &str = "Database";
+
+    if !db_path.is_file() {
+        return Check::fail(
+            NAME,
+            format!("{} does not exist", db_path.display()),
+            "Run `zk-payroll init-company` to create it.",
+        );
+    }
+
+    let missing = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(anyhow::Error::from)
+        .and_then(|conn| db::missing_tables(&conn));
+    match missing {
+        Err(err) => Check::fail(
+            NAME,
+            format!("Cannot read {}: {:#}", db_path.display(), err),
+            "Check the file is readable by the current user; if it is corrupt, \
+             restore it from your backup.",
+        ),
+        Ok(missing) if missing.is_empty() => Check::pass(
+            NAME,
+            format!("{} has the current schema", db_path.display()),
+        ),
+        Ok(missing) => Check::fail(
+            NAME,
+            format!(
+                "{} is missing table(s): {}",
+                db_path.display(),
+                missing.join(", ")
+            ),
+            "Run `zk-payroll init-company`; it adds missing tables without \
+             touching existing data.",
+        ),
+    }
+}
+
+/// The data directory (and database file, if present) are private to the
+/// current user, as `init-company` leaves them.
+pub fn check_permissions(db_path: &Path) -> Check {
+    const NAME: &str = "Permissions";
+
+    let dir = match db_path.parent() {
+        Some(dir) if dir.is_dir() => dir,
+        Some(dir) => {
+            return Check::fail(
+                NAME,
+                format!("{} does not exist", dir.display()),
+                "Run `zk-payroll init-company` to create it with owner-only permissions.",
+            )
+        }
+        None => {
+            return Check::fail(
+                NAME,
+                format!("{} has no parent directory", db_path.display()),
+                "Pass a directory with --data-dir.",
+            )
+        }
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        for (path, private_mode) in [(dir, 0o700), (db_path, 0o600)] {
+            let Ok(metadata) = std::fs::metadata(path) else {
+                continue;
+            };
+            let mode = metadata.permissions().mode() & 0o777;
+            if mode & 0o077 != 0 {
+                return Check::fail(
+                    NAME,
+                    format!(
+                        "{} is accessible to other users (mode {:o})",
+                        path.display(),
+                        mode
+                    ),
+                    format!("Run `chmod {:o} {}`.", private_mode, path.display()),
+                );
+            }
+        }
+        Check::pass(
+            NAME,
+            format!("{} is private to the current user", dir.display()),
+        )
+    }
+
+    #[cfg(not(unix))]
+    Check::pass(
+        NAME,
+        format!(
+            "{} exists (permissions not checked on this platform)",
+            dir.display()
+        ),
+    )
+}
+
+/// `<program> --version` runs successfully.  `program` is `node` outside
+/// tests.
+pub fn check_node(program: &str) -> Check {
+    const NAME: &str = "Node.js";
+
+    match Command::new(program).arg("--version").output() {
+        Ok(output) if output.status.success() => Check::pass(
+            NAME,
+            format!(
+                "{} {}",
+                program,
+                String::from_utf8_lossy(&output.stdout).trim()
+            ),
+        ),
+        _ => Check::fail(
+            NAME,
+            format!("`{} --version` could not be run", program),
+            "Install Node.js and make sure `node` is on your PATH; it is needed \
+             to generate proofs with circuits/generate_proof.js.",
+        ),
+    }
+}
+
+/// The Soroban RPC endpoint reports itself healthy.
+pub fn check_rpc(rpc_url: &str) -> Check {
+    const NAME: &str = "Soroban RPC";
+
+    match rpc::get_health(rpc_url) {
+        Ok(status) if status == "healthy" => Check::pass(NAME, format!("{} is healthy", rpc_url)),
+        Ok(status) => Check::fail(
+            NAME,
+            format!("{} reports status '{}'", rpc_url, status),
+            "The endpoint is reachable but not ready; retry later or pass --rpc-url.",
+        ),
+        Err(err) => Check::fail(
+            NAME,
+            format!("{} is unreachable: {:#}", rpc_url, err),
+            "Check your network connection, or pass --rpc-url to use another endpoint.",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+
+    /// Empty scratch directory unique to `name`.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("zk-payroll-doctor-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Answer a single HTTP request on a local port with `body` as JSON.
+    fn serve_once(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            // Drain the request so the client is not cut off mid-write.
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"}") {
+                let n = stream.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        url
+    }
+
+    #[test]
+    fn database_check_fails_when_missing() {
+        let dir = scratch_dir("db-missing");
+        let check = check_database(&dir.join("company_db.sqlite"));
+        assert!(!check.passed);
+        assert!(check.hint.unwrap().contains("init-company"));
+    }
+
+    #[test]
+    fn database_check_passes_on_initialised_db() {
+        let dir = scratch_dir("db-ok");
+        let path = dir.join("company_db.sqlite");
+        db::initialise(&db::open(&path).unwrap()).unwrap();
+
+        let check = check_database(&path);
+        assert!(check.passed, "{}", check.detail);
+        assert!(check.hint.is_none());
+    }
+
+    #[test]
+    fn database_check_reports_missing_tables() {
+        let dir = scratch_dir("db-old");
+        let path = dir.join("company_db.sqlite");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch("CREATE TABLE blinding_factors (employee_pubkey TEXT PRIMARY KEY);")
+            .unwrap();
+
+        let check = check_database(&path);
+        assert!(!check.passed);
+        assert!(check.detail.contains("audit_log"));
+        assert!(!check.detail.contains("blinding_factors"));
+    }
+
+    #[test]
+    fn database_check_fails_on_non_database_file() {
+        let dir = scratch_dir("db-garbage");
+        let path = dir.join("company_db.sqlite");
+        std::fs::write(&path, b"not a sqlite database, just some bytes").unwrap();
+
+        assert!(!check_database(&path).passed);
+    }
+
+    /// The check only looks: a database it could not find is not created.
+    #[test]
+    fn checks_do_not_create_anything() {
+        let dir = scratch_dir("readonly").join("absent");
+        let path = dir.join("company_db.sqlite");
+
+        check_database(&path);
+        check_permissions(&path);
+        assert!(!dir.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn permissions_check_flags_shared_data_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = scratch_dir("perms");
+        let path = dir.join("company_db.sqlite");
+        std::fs::write(&path, b"").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let check = check_permissions(&path);
+        assert!(!check.passed);
+        assert!(check.hint.unwrap().contains("chmod 700"));
+
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700)).unwrap();
+        assert!(check_permissions(&path).passed);
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let check = check_permissions(&path);
+        assert!(!check.passed);
+        assert!(check.hint.unwrap().contains("chmod 600"));
+    }
+
+    #[test]
+    fn node_check_fails_for_missing_program() {
+        let check = check_node("zk-payroll-no-such-program");
+        assert!(!check.passed);
+        assert!(check.hint.unwrap().contains("Node.js"));
+    }
+
+    /// Any program answering `--version` passes; cargo is always present
+    /// when the tests run.
+    #[test]
+    fn node_check_passes_for_working_program() {
+        let check = check_node(env!("CARGO"));
+        assert!(check.passed, "{}", check.detail);
+    }
+
+    #[test]
+    fn rpc_check_passes_for_healthy_endpoint() {
+        let url = serve_once(r#"{"jsonrpc":"2.0","id":1,"result":{"status":"healthy"}}"#);
+        let check = check_rpc(&url);
+        assert!(check.passed, "{}", check.detail);
+    }
+
+    #[test]
+    fn rpc_check_fails_for_unhealthy_endpoint() {
+        let url = serve_once(r#"{"jsonrpc":"2.0","id":1,"result":{"status":"unhealthy"}}"#);
+        let check = check_rpc(&url);
+        assert!(!check.passed);
+        assert!(check.detail.contains("unhealthy"));
+    }
+
+    #[test]
+    fn rpc_check_fails_for_unreachable_endpoint() {
+        // Bind and release a port so nothing is listening on it.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let check = check_rpc(&format!("http://127.0.0.1:{}", port));
+        assert!(!check.passed);
+        assert!(check.detail.contains("unreachable"));
+    }
+}
@@ -0,0 +1,121 @@
+//! Process exit codes for the ZK Payroll CLI.
+//!
+//! Scripts driving the CLI need to tell bad input apart from a broken local
+//! database or an unreachable RPC endpoint, so failures are classified and
+//! mapped to distinct exit codes:
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | `0`  | Success |
+//! | `1`  | Unclassified failure |
+//! | `2`  | Validation error (bad arguments; also used by clap for usage errors) |
+//! | `3`  | Local database error (missing, unreadable, or failed query) |
+//! | `4`  | Soroban RPC / network error |
+//!
+//! Errors are classified by walking the `anyhow` chain: an explicit
+//! [`Classified`] tag wins, otherwise `rusqlite` and `reqwest` errors are
+//! recognised by type.
+
+use std::fmt;
+
+pub const EXIT_FAILURE: u8 = 1;
+pub const EXIT_VALIDATION: u8 = 2;
+pub const EXIT_DATABASE: u8 = 3;
+pub const EXIT_NETWORK: u8 = 4;
+
+/// Failure category that decides the process exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Validation,
+    Database,
+    Network,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            ErrorKind::Validation => EXIT_VALIDATION,
+            ErrorKind::Database => EXIT_DATABASE,
+            ErrorKind::Network => EXIT_NETWORK,
+        }
+    }
+}
+
+/// An error message tagged with its [`ErrorKind`].
+///
+/// Displays as the bare message, so tagging an error (or attaching one as
+/// `anyhow` context) does not change what the user sees.
+#[derive(Debug)]
+pub struct Classified {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl Classified {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Classified {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Classified {}
+
+/// Determine the category of `err`, if it has one.
+pub fn classify(err: &anyhow::Error) -> Option<ErrorKind> {
+    if let Some(tagged) = err.downcast_ref::<Classified>() {
+        return Some(tagged.kind);
+    }
+    err.chain().find_map(|cause| {
+        if cause.is::<rusqlite::Error>() {
+            Some(ErrorKind::Database)
+        } else if cause.is::<reqwest::Error>() {
+            Some(ErrorKind::Network)
+        } else {
+            None
+        }
+    })
+}
+
+/// Exit code to report for `err`.
+pub fn exit_code(err: &anyhow::Error) -> u8 {
+    classify(err).map_or(EXIT_FAILURE, ErrorKind::exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn unclassified_error_exits_with_one() {
+        let err = anyhow::anyhow!("something went wrong");
+        assert_eq!(exit_code(&err), EXIT_FAILURE);
+    }
+
+    /// A tag deep inside added context is still found, and the message the
+    /// user sees is unchanged.
+    #[test]
+    fn tag_survives_added_context() {
+        let err = Err::<(), _>(Classified::new(ErrorKind::Validation, "bad input"))
+            .context("while adding employee")
+            .unwrap_err();
+        assert_eq!(exit_code(&err), EXIT_VALIDATION);
+        assert_eq!(format!("{:#}", err), "while adding employee: bad input");
+    }
+
+    #[test]
+    fn sqlite_errors_are_database_errors() {
+        let err = Err::<(), _>(rusqlite::Error::InvalidQuery)
+            .context("query failed")
+            .unwrap_err();
+        assert_eq!(exit_code(&err), EXIT_DATABASE);
+    }
+}
@@ -0,0 +1,10 @@
+//! Library half of the ZK Payroll CLI.
+//!
+//! Exposes the off-chain cryptography and RPC event decoding the
+//! `zk-payroll` binary uses, so that other crates in the workspace — the
+//! contract integration tests in particular — compute commitments and decode
+//! events with exactly the same code.
+
+pub mod crypto;
+pub mod exit;
+pub mod rpc;
@@ -5,9 +5,22 @@
 //!
 //! | Command | Purpose |
 //! |---------|---------|
-//! | `init-company` | Create the local SQLite database at `~/.zk-payroll/company_db.sqlite` |
-//! | `add-employee <pubkey> <amount>` | Generate a BN254 blinding factor, compute `Poseidon(salary, blinding)`, persist both, and print the commitment |
-//! | `reconcile` | Fetch `PayrollProcessed` events from Soroban RPC and cross-reference against the local database |
+//! | `init-company` | Create the local SQLite database at `~/.zk-payroll/company_db.sqlite` (or under `--data-dir` / `$ZK_PAYROLL_HOME`); `--derive-blindings` adds a master seed for deterministic blinding factors |
+//! | `add-employee <pubkey> <amount>` | Generate a BN254 blinding factor, compute `Poseidon(salary, blinding)`, persist both, and print the commitment; `--output-file PATH` also writes a JSON receipt without the blinding factor |
+//! | `rotate-blinding <pubkey>` | Replace a suspected-compromised blinding factor and print the new commitment for the unchanged salary |
+//! | `deactivate-employee <pubkey>` | Mark an employee as no longer active (their blinding factor is kept) |
+//! | `nullifier <pubkey> --period <n>` | Print the recipient-bound payment nullifier `Poseidon(blinding, period, recipient_hash)` for a pay period |
+//! | `prune --older-than-days <n>` | Delete old audit-log rows and, with `--include-deactivated`, long-deactivated employees; lists the rows and asks for confirmation unless `--force` is given |
+//! | `export-commitments <path>` | Write each active employee's pubkey, commitment and salary to a shareable JSON file; blinding factors are never included |
+//! | `import-commitments <path>` | Validate an exported file; with `--contract-id` and `--company-id`, report which commitments match, differ from or are missing in the payroll registry |
+//! | `reconcile` | Fetch `PayrollProcessed` events from Soroban RPC and cross-reference against the local database (`--watch` to poll continuously; `--period-label` with `--start-date` to show the dates each period covers; `--deviation-threshold-pct` to flag periods whose total spend jumps; warns if the company was last reconciled on another network) |
+//! | `check-treasury` | Compare the company treasury's token balance with the active employees' salaries and print `SUFFICIENT` or `SHORT` with the difference |
+//! | `wait-tx <hash>` | Wait for a submitted transaction to be confirmed and print its `PayrollProcessed` events |
+//! | `doctor` | Check the database, data-directory permissions, Node.js, and RPC reachability without changing anything |
+//!
+//! `import-commitments`, `reconcile`, `check-treasury`, `wait-tx` and `doctor` take
+//! `--network testnet|mainnet|futurenet` (default testnet) to pick a known RPC
+//! endpoint; `--rpc-url` overrides it.
 //!
 //! # Security model
 //!
@@ -17,14 +30,41 @@
 //! for the affected employee.
 //!
 //! **Back up `~/.zk-payroll/` to an encrypted, offline location immediately.**
+//!
+//! Pass `--quiet` in scripts to drop the backup banner and other explanatory
+//! notes; results such as the generated commitment are always printed.
+//!
+//! Commands that delete data list what they will delete and ask for a `y/N`
+//! confirmation first.  Without a terminal to ask on they refuse to run;
+//! scripts must pass `--force`.
+//!
+//! # Exit codes
+//!
+//! `2` for invalid input, `3` for local database errors, `4` for RPC/network
+//! errors, and `1` for anything else.  See [`exit`].
+
+use std::fmt::Display;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 use anyhow::{bail, Context, Result};
 use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+use crypto::PoseidonParams;
+use exit::{Classified, ErrorKind};
+use network::NetworkArgs;
+use period::{CalendarDate, Granularity, PeriodCalendar, PeriodLabel};
+use zk_payroll_cli::{crypto, exit, rpc};
 
-mod crypto;
 mod db;
+mod doctor;
+mod network;
+mod period;
+mod portable;
 mod reconcile;
-mod rpc;
+mod treasury;
 
 // ── Warning banner ────────────────────────────────────────────────────────────
 
@@ -42,6 +82,49 @@ const BACKUP_WARNING: &str = "\
 |  offline location (hardware wallet, encrypted USB, etc.) NOW.   |
 +------------------------------------------------------------------+";
 
+// ── Output ────────────────────────────────────────────────────────────────────
+
+/// Command output, split into results and notes.
+///
+/// Results (the commitment, the master seed, ...) are always written; notes —
+/// the backup banner and other explanatory text — are dropped under `--quiet`.
+struct Output<W: Write> {
+    out: W,
+    quiet: bool,
+}
+
+impl<W: Write> Output<W> {
+    fn new(out: W, quiet: bool) -> Self {
+        Self { out, quiet }
+    }
+
+    /// Write a line scripts depend on.
+    fn result(&mut self, line: impl Display) -> Result<()> {
+        writeln!(self.out, "{}", line)?;
+        Ok(())
+    }
+
+    /// Write a line meant only for a human reader.
+    fn note(&mut self, line: impl Display) -> Result<()> {
+        if !self.quiet {
+            writeln!(self.out, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Write a question and leave the cursor on its line for the answer.
+    fn prompt(&mut self, question: impl Display) -> Result<()> {
+        write!(self.out, "{} ", question)?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    fn backup_warning(&mut self) -> Result<()> {
+        self.note("")?;
+        self.note(BACKUP_WARNING)
+    }
+}
+
 // ── CLI definition ────────────────────────────────────────────────────────────
 
 /// ZK Payroll CLI — off-chain proof-preparation and reconciliation tool for
@@ -53,6 +136,24 @@ const BACKUP_WARNING: &str = "\
 #[command(name = "zk-payroll")]
 #[command(version, about, long_about = None)]
 struct Cli {
+    /// Directory holding the local database.
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Data directory (overrides $ZK_PAYROLL_HOME; default ~/.zk-payroll)"
+    )]
+    data_dir: Option<PathBuf>,
+
+    /// Suppress the backup banner and other non-essential output.
+    #[arg(
+        long,
+        short,
+        global = true,
+        help = "Only print results and errors (no backup banner or notes)"
+    )]
+    quiet: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -63,7 +164,16 @@ enum Commands {
     ///
     /// Creates ~/.zk-payroll/company_db.sqlite with the blinding_factors
     /// table.  Safe to run multiple times (idempotent).
-    InitCompany,
+    ///
+    /// With --derive-blindings, also generates a master seed from which every
+    /// later blinding factor is derived as Poseidon(master_seed, index), so
+    /// all factors can be regenerated from the seed alone.
+    InitCompany {
+        /// Derive blinding factors from a master seed instead of generating
+        /// each one at random.
+        #[arg(long, help = "Derive blinding factors from a master seed")]
+        derive_blindings: bool,
+    },
 
     /// Register an employee and generate their salary commitment.
     ///
@@ -71,6 +181,9 @@ enum Commands {
     /// (the blinding factor), computes Poseidon(salary, blinding_factor),
     /// persists both to the local database, and prints the commitment.
     ///
+    /// With --output-file, also writes a JSON receipt of the onboarding.  The
+    /// receipt never contains the blinding factor.
+    ///
     /// SECURITY: The generated blinding factor is stored ONLY in the local
     /// database.  Back up ~/.zk-payroll immediately after running this command.
     AddEmployee {
@@ -79,6 +192,117 @@ enum Commands {
 
         /// Gross salary amount in stroops (1 XLM = 10,000,000 stroops).
         amount: u64,
+
+        /// Where to write the JSON onboarding receipt.
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Also write a JSON receipt (without the blinding factor) to PATH"
+        )]
+        output_file: Option<PathBuf>,
+    },
+
+    /// Rotate an employee's blinding factor and regenerate their commitment.
+    ///
+    /// Use this when a blinding factor is suspected compromised but the
+    /// commitment has not yet been used.  Generates a fresh BN254 blinding
+    /// factor, recomputes Poseidon(salary, blinding_factor) for the employee's
+    /// existing salary, updates the local database, and prints the new
+    /// commitment.
+    ///
+    /// The on-chain commitment is NOT changed by this command; it must be
+    /// updated separately via the payroll registry's `update_commitment`.
+    RotateBlinding {
+        /// Employee Stellar public key (56-character G... address).
+        pubkey: String,
+    },
+
+    /// Mark an employee as deactivated.
+    ///
+    /// The blinding factor is kept so historic payments remain provable; it
+    /// is only deleted by `prune --include-deactivated`.
+    DeactivateEmployee {
+        /// Employee Stellar public key (56-character G... address).
+        pubkey: String,
+    },
+
+    /// Compute an employee's payment nullifier for a pay period.
+    ///
+    /// Prints Poseidon(blinding_factor, period, recipient_hash), where
+    /// recipient_hash is the payroll contract's hash of the employee's
+    /// address.  The nullifier is bound to that address, so a proof built
+    /// with it cannot be used to pay anyone else.  Read-only.
+    Nullifier {
+        /// Employee Stellar public key (56-character G... address).
+        pubkey: String,
+
+        /// Pay period the payment is for.
+        #[arg(long, help = "Pay period the payment is for")]
+        period: u32,
+    },
+
+    /// Remove stale rows from the local database.
+    ///
+    /// Deletes audit-log entries older than the cutoff.  With
+    /// --include-deactivated, also deletes employees (including their
+    /// blinding factors) deactivated before the cutoff.  Active employees
+    /// are never removed.
+    ///
+    /// Lists every row it will delete and asks for confirmation; pass
+    /// --force to skip the prompt in scripts.
+    Prune {
+        /// Age in days beyond which rows are removed.
+        #[arg(long, help = "Remove rows older than this many days")]
+        older_than_days: u64,
+
+        /// Also delete employees deactivated before the cutoff.
+        #[arg(long, help = "Also delete long-deactivated employees")]
+        include_deactivated: bool,
+
+        /// Delete without asking for confirmation.
+        #[arg(long, help = "Skip the confirmation prompt")]
+        force: bool,
+    },
+
+    /// Write every active employee's commitment to a portable JSON file.
+    ///
+    /// Each entry holds the employee's pubkey, commitment and salary.
+    /// Blinding factors are never written, so the file can be shared to
+    /// seed a new registry, but it cannot be used to generate proofs.
+    ExportCommitments {
+        /// Where to write the JSON file.
+        out_path: PathBuf,
+    },
+
+    /// Validate a file written by `export-commitments`.
+    ///
+    /// Checks every pubkey and commitment and rejects duplicate employees
+    /// and files carrying any other field, such as a blinding factor.  With
+    /// --contract-id and --company-id, also compares each commitment with
+    /// the payroll registry's and exits non-zero if any differ or are
+    /// missing.  Nothing is written locally or on-chain.
+    ImportCommitments {
+        /// JSON file written by `export-commitments`.
+        in_path: PathBuf,
+
+        #[command(flatten)]
+        network: NetworkArgs,
+
+        /// Payroll registry contract address (C... Strkey address).
+        #[arg(
+            long,
+            requires = "company_id",
+            help = "Compare against this payroll_registry contract"
+        )]
+        contract_id: Option<String>,
+
+        /// Company identifier as registered on-chain.
+        #[arg(
+            long,
+            requires = "contract_id",
+            help = "Company ID in the payroll registry"
+        )]
+        company_id: Option<u64>,
     },
 
     /// Reconcile on-chain payments with the local employee database.
@@ -90,14 +314,20 @@ enum Commands {
     /// Results are displayed as a structured table showing the employee,
     /// amount paid, payroll period, ledger timestamp, and whether the employee
     /// is known in the local database.
+    ///
+    /// With --period-label and --start-date, each period number is followed
+    /// by the dates it covers (e.g. "3 → March 2024"), counting period 1 from
+    /// the start date.
+    ///
+    /// With --token-id, amounts are scaled and labelled using the token
+    /// contract's decimals and symbol instead of XLM's.
+    ///
+    /// With --deviation-threshold-pct, each period's total spend is compared
+    /// with the previous period's and periods more than that percentage
+    /// higher are reported as anomalies.
     Reconcile {
-        /// Soroban RPC URL.
-        #[arg(
-            long,
-            default_value = reconcile::DEFAULT_RPC_URL,
-            help = "Soroban JSON-RPC endpoint (e.g. https://soroban-testnet.stellar.org)"
-        )]
-        rpc_url: String,
+        #[command(flatten)]
+        network: NetworkArgs,
 
         /// Payment executor contract address (C... Strkey address).
         #[arg(
@@ -107,7 +337,7 @@ enum Commands {
         contract_id: String,
 
         /// Company identifier as registered on-chain.
-        #[arg(long, help = "Company symbol used as the second event topic")]
+        #[arg(long, help = "Company ID used as the second event topic")]
         company_id: String,
 
         /// Ledger sequence number to start scanning from.
@@ -117,41 +347,269 @@ enum Commands {
             help = "First ledger to include in the scan"
         )]
         start_ledger: u32,
+
+        /// Only show payments for this payroll period.
+        #[arg(long, help = "Only show payments for this payroll period")]
+        period: Option<u32>,
+
+        /// Payment token contract address (C... Strkey address).
+        #[arg(
+            long,
+            help = "Token contract whose decimals and symbol label amounts (default XLM)"
+        )]
+        token_id: Option<String>,
+
+        /// Length of each payroll period, used to label period numbers.
+        #[arg(
+            long,
+            value_enum,
+            requires = "start_date",
+            help = "Period length used to show the dates each period covers"
+        )]
+        period_label: Option<PeriodLabel>,
+
+        /// First day of period 1 (YYYY-MM-DD).
+        #[arg(
+            long,
+            value_name = "YYYY-MM-DD",
+            requires = "period_label",
+            help = "Date period 1 starts, for --period-label"
+        )]
+        start_date: Option<CalendarDate>,
+
+        /// Days per period for `--period-label custom`.
+        #[arg(
+            long,
+            required_if_eq("period_label", "custom"),
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Days per period with --period-label custom"
+        )]
+        period_days: Option<u32>,
+
+        /// Keep polling for new payments until interrupted with Ctrl-C.
+        #[arg(long, help = "Continuously poll for new payments")]
+        watch: bool,
+
+        /// Seconds between polls in watch mode.
+        #[arg(
+            long,
+            default_value_t = 30,
+            requires = "watch",
+            help = "Polling interval in seconds for --watch"
+        )]
+        interval: u64,
+
+        /// Warn when a period's total spend rises more than this percentage
+        /// above the previous period's.
+        #[arg(
+            long,
+            value_name = "PCT",
+            conflicts_with = "watch",
+            help = "Flag periods spending more than PCT% above the previous period"
+        )]
+        deviation_threshold_pct: Option<u32>,
+    },
+
+    /// Check the treasury can cover the next payroll run.
+    ///
+    /// Sums the salaries of every active employee in the local database,
+    /// reads the company's treasury from the payroll registry and its
+    /// balance from the token contract, and prints SUFFICIENT or SHORT with
+    /// the difference.  Exits non-zero when the treasury is short.  Read-only:
+    /// nothing is signed or submitted.
+    CheckTreasury {
+        #[command(flatten)]
+        network: NetworkArgs,
+
+        /// Payroll registry contract address (C... Strkey address).
+        #[arg(
+            long,
+            help = "Strkey contract address of the payroll_registry contract"
+        )]
+        contract_id: String,
+
+        /// Company identifier as registered on-chain.
+        #[arg(long, help = "Company ID in the payroll registry")]
+        company_id: u64,
+
+        /// Payment token contract address (C... Strkey address).
+        #[arg(long, help = "Token contract the treasury pays salaries in")]
+        token_id: String,
+    },
+
+    /// Wait for a submitted transaction to be confirmed on-chain.
+    ///
+    /// Polls the Soroban RPC `getTransaction` method until the transaction
+    /// succeeds or fails, then prints any `PayrollProcessed` events it
+    /// emitted.  Exits with an error if the transaction failed or was not
+    /// confirmed within the timeout.
+    WaitTx {
+        /// Hex-encoded transaction hash returned by `sendTransaction`.
+        tx_hash: String,
+
+        #[command(flatten)]
+        network: NetworkArgs,
+
+        /// Seconds to wait for confirmation.
+        #[arg(long, default_value_t = 60, help = "Seconds to wait before giving up")]
+        timeout: u64,
+    },
+
+    /// Diagnose common setup problems.
+    ///
+    /// Checks that the local database exists and has the current schema,
+    /// that the data directory is private to the current user, that Node.js
+    /// is installed for proof generation, and that the Soroban RPC endpoint
+    /// responds.  Prints a pass/fail checklist with a hint for each failure
+    /// and exits non-zero if any check fails.  Never modifies anything.
+    Doctor {
+        #[command(flatten)]
+        network: NetworkArgs,
     },
 }
 
 // ── Entry point ───────────────────────────────────────────────────────────────
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     let cli = Cli::parse();
-    match cli.command {
-        Commands::InitCompany => cmd_init_company(),
-        Commands::AddEmployee { pubkey, amount } => cmd_add_employee(&pubkey, amount),
+    let mut out = Output::new(std::io::stdout(), cli.quiet);
+    match run(&mut out, cli.command, cli.data_dir.as_deref()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {:?}", err);
+            ExitCode::from(exit::exit_code(&err))
+        }
+    }
+}
+
+fn run(out: &mut Output<impl Write>, command: Commands, data_dir: Option<&Path>) -> Result<()> {
+    match command {
+        Commands::InitCompany { derive_blindings } => {
+            cmd_init_company(out, data_dir, derive_blindings)
+        }
+        Commands::AddEmployee {
+            pubkey,
+            amount,
+            output_file,
+        } => cmd_add_employee(out, data_dir, &pubkey, amount, output_file.as_deref()),
+        Commands::RotateBlinding { pubkey } => cmd_rotate_blinding(out, data_dir, &pubkey),
+        Commands::DeactivateEmployee { pubkey } => cmd_deactivate_employee(out, data_dir, &pubkey),
+        Commands::Nullifier { pubkey, period } => cmd_nullifier(out, data_dir, &pubkey, period),
+        Commands::Prune {
+            older_than_days,
+            include_deactivated,
+            force,
+        } => {
+            let stdin = std::io::stdin();
+            let interactive = stdin.is_terminal();
+            let confirmation = (!force).then(|| Confirmation {
+                input: stdin.lock(),
+                interactive,
+            });
+            cmd_prune(
+                out,
+                data_dir,
+                older_than_days,
+                include_deactivated,
+                confirmation,
+            )
+        }
+        Commands::ExportCommitments { out_path } => {
+            cmd_export_commitments(out, data_dir, &out_path)
+        }
+        Commands::ImportCommitments {
+            in_path,
+            network,
+            contract_id,
+            company_id,
+        } => {
+            let registry =
+                contract_id
+                    .as_deref()
+                    .zip(company_id)
+                    .map(|(registry_id, company_id)| portable::RegistryArgs {
+                        registry_id,
+                        company_id,
+                    });
+            cmd_import_commitments(
+                out,
+                &in_path,
+                &network.endpoint().rpc_url,
+                registry.as_ref(),
+            )
+        }
         Commands::Reconcile {
-            rpc_url,
+            network,
             contract_id,
             company_id,
             start_ledger,
-        } => reconcile::run(reconcile::ReconcileArgs {
-            rpc_url: &rpc_url,
-            contract_id: &contract_id,
-            company_id: &company_id,
-            start_ledger,
-        }),
+            period,
+            token_id,
+            period_label,
+            start_date,
+            period_days,
+            watch,
+            interval,
+            deviation_threshold_pct,
+        } => {
+            let calendar = period_calendar(period_label, start_date, period_days)?;
+            let endpoint = network.endpoint();
+            let args = reconcile::ReconcileArgs {
+                endpoint: &endpoint,
+                contract_id: &contract_id,
+                company_id: &company_id,
+                token_id: token_id.as_deref(),
+                start_ledger,
+                period,
+                data_dir,
+                calendar,
+                deviation_threshold_pct,
+            };
+            if watch {
+                reconcile::watch(args, std::time::Duration::from_secs(interval))
+            } else {
+                reconcile::run(args)
+            }
+        }
+        Commands::CheckTreasury {
+            network,
+            contract_id,
+            company_id,
+            token_id,
+        } => {
+            let args = treasury::TreasuryArgs {
+                registry_id: &contract_id,
+                company_id,
+                token_id: &token_id,
+            };
+            cmd_check_treasury(out, data_dir, &network.endpoint().rpc_url, &args)
+        }
+        Commands::WaitTx {
+            tx_hash,
+            network,
+            timeout,
+        } => cmd_wait_tx(&network.endpoint().rpc_url, &tx_hash, timeout),
+        Commands::Doctor { network } => cmd_doctor(out, data_dir, &network.endpoint().rpc_url),
     }
 }
 
 // ── Command implementations ───────────────────────────────────────────────────
 
-/// `init-company` — create ~/.zk-payroll/company_db.sqlite.
-fn cmd_init_company() -> Result<()> {
-    let db_path = db::db_path()?;
+/// `init-company` — create company_db.sqlite in the data directory
+/// (~/.zk-payroll by default), optionally with a master seed.
+fn cmd_init_company(
+    out: &mut Output<impl Write>,
+    data_dir: Option<&Path>,
+    derive_blindings: bool,
+) -> Result<()> {
+    let db_path = db::db_path(data_dir)?;
 
     let dir = db_path
         .parent()
         .context("Cannot determine the parent directory for the database file")?;
 
-    // Create ~/.zk-payroll/ with restrictive permissions (owner-only on Unix).
+    // Create the data directory with restrictive permissions (owner-only on
+    // Unix).  This applies equally to a --data-dir / $ZK_PAYROLL_HOME override.
     std::fs::create_dir_all(dir)
         .with_context(|| format!("Cannot create directory '{}'", dir.display()))?;
 
@@ -166,6 +624,16 @@ fn cmd_init_company() -> Result<()> {
     let conn = db::open(&db_path)?;
     db::initialise(&conn)?;
 
+    // Never replace an existing seed: factors derived from it would become
+    // unrecoverable.
+    let new_seed = if derive_blindings && db::get_master_seed(&conn)?.is_none() {
+        let seed_hex = hex::encode(crypto::gen_blinding_factor());
+        db::insert_master_seed(&conn, &seed_hex)?;
+        Some(seed_hex)
+    } else {
+        None
+    };
+
     // Restrict the database file itself to owner read/write on Unix.
     #[cfg(unix)]
     {
@@ -174,73 +642,608 @@ fn cmd_init_company() -> Result<()> {
             .with_context(|| format!("Cannot set permissions on '{}'", db_path.display()))?;
     }
 
-    println!("ZK Payroll database initialised at: {}", db_path.display());
-    println!();
-    println!("{}", BACKUP_WARNING);
-
-    Ok(())
+    out.note(format_args!(
+        "ZK Payroll database initialised at: {}",
+        db_path.display()
+    ))?;
+    if let Some(seed_hex) = new_seed {
+        out.note("")?;
+        out.result(format_args!("Master seed: 0x{}", seed_hex))?;
+        out.note("")?;
+        out.note(
+            "Blinding factors will be derived from this seed.  Record it offline:
+             it alone is enough to regenerate every employee's blinding factor.",
+        )?;
+    }
+    out.backup_warning()
 }
 
 /// `add-employee <pubkey> <amount>` — generate blinding factor, compute
-/// commitment, persist, and print.
-fn cmd_add_employee(pubkey: &str, amount: u64) -> Result<()> {
+/// commitment, persist, and print (and optionally write a receipt).
+fn cmd_add_employee(
+    out: &mut Output<impl Write>,
+    data_dir: Option<&Path>,
+    pubkey: &str,
+    amount: u64,
+    output_file: Option<&Path>,
+) -> Result<()> {
     // ── Input validation ──────────────────────────────────────────────────────
 
     validate_stellar_pubkey(pubkey)?;
 
     // ── Database sanity check ─────────────────────────────────────────────────
 
-    let db_path = db::db_path()?;
-    if !db_path.exists() {
-        bail!(
-            "Database not found at '{}'.\n\
-             Run `zk-payroll init-company` to create it first.",
-            db_path.display()
-        );
-    }
-
-    let conn = db::open(&db_path)?;
+    let conn = open_existing_db(&db::db_path(data_dir)?)?;
 
     if db::employee_exists(&conn, pubkey)? {
-        bail!(
-            "Employee '{}' already exists in the database.\n\
-             Each employee can have only one active commitment at a time.\n\
-             To update their salary, use `zk-payroll update-salary {} <new-amount>`.",
-            pubkey,
-            pubkey
-        );
+        bail!(Classified::new(
+            ErrorKind::Validation,
+            format!(
+                "Employee '{}' already exists in the database.\n\
+                 Each employee can have only one active commitment at a time.\n\
+                 To update their salary, use `zk-payroll update-salary {} <new-amount>`.",
+                pubkey, pubkey
+            ),
+        ));
     }
 
     // ── Cryptographic operations ──────────────────────────────────────────────
 
-    // 1. Generate a fresh BN254 scalar blinding factor using OsRng.
-    let blinding_bytes = crypto::gen_blinding_factor();
+    // 1. Generate a fresh BN254 scalar blinding factor, either at random or
+    //    derived from the master seed.
+    let (blinding_bytes, derivation_index) = new_blinding_factor(&conn, pubkey)?;
     let blinding_hex = hex::encode(blinding_bytes);
 
     // 2. Compute Poseidon(salary, blinding_factor) — the on-chain commitment.
-    let commitment_bytes = crypto::poseidon_commitment(amount, &blinding_bytes)
-        .context("Failed to compute Poseidon commitment")?;
+    let commitment_bytes =
+        crypto::poseidon_commitment(amount, &blinding_bytes, PoseidonParams::default())
+            .context("Failed to compute Poseidon commitment")?;
     let commitment_hex = hex::encode(commitment_bytes);
 
     // ── Persist to database ───────────────────────────────────────────────────
 
+    let now = unix_now();
     db::insert_employee(&conn, pubkey, &blinding_hex, amount)
         .context("Failed to persist employee record")?;
+    db::append_audit_log(&conn, pubkey, "add_employee", now)?;
+
+    if let Some(path) = output_file {
+        let receipt = OnboardingReceipt {
+            pubkey,
+            salary: amount,
+            commitment: format!("0x{}", commitment_hex),
+            timestamp: now,
+            warning: RECEIPT_WARNING,
+        };
+        write_receipt(path, &receipt)?;
+    }
 
     // ── Output ────────────────────────────────────────────────────────────────
 
-    println!("Successfully generated commitment: 0x{}", commitment_hex);
-    println!();
-    println!("  Employee : {}", pubkey);
-    println!("  Salary   : {} stroops", amount);
-    println!();
-    println!("{}", BACKUP_WARNING);
+    out.result(format_args!(
+        "Successfully generated commitment: 0x{}",
+        commitment_hex
+    ))?;
+    out.note("")?;
+    out.note(format_args!("  Employee : {}", pubkey))?;
+    out.note(format_args!("  Salary   : {} stroops", amount))?;
+    if let Some(index) = derivation_index {
+        out.note(format_args!(
+            "  Index    : {} (derived from master seed)",
+            index
+        ))?;
+    }
+    out.backup_warning()
+}
+
+/// `rotate-blinding <pubkey>` — replace the blinding factor, recompute the
+/// commitment for the existing salary, persist, and print.
+fn cmd_rotate_blinding(
+    out: &mut Output<impl Write>,
+    data_dir: Option<&Path>,
+    pubkey: &str,
+) -> Result<()> {
+    validate_stellar_pubkey(pubkey)?;
+
+    let conn = open_existing_db(&db::db_path(data_dir)?)?;
+    let rotated = rotate_employee_blinding(&conn, pubkey)?;
+    db::append_audit_log(&conn, pubkey, "rotate_blinding", unix_now())?;
+
+    out.result(format_args!(
+        "Successfully generated commitment: 0x{}",
+        hex::encode(rotated.new_commitment)
+    ))?;
+    out.note("")?;
+    out.note(format_args!("  Employee       : {}", pubkey))?;
+    out.note(format_args!(
+        "  Salary         : {} stroops",
+        rotated.salary
+    ))?;
+    out.note(format_args!(
+        "  Old commitment : 0x{}",
+        hex::encode(rotated.old_commitment)
+    ))?;
+    out.note("")?;
+    out.note(
+        "WARNING: the on-chain commitment has NOT been changed.\n\
+         Update it via the payroll registry's `update_commitment` before the\n\
+         next payroll run, otherwise proofs generated with the new blinding\n\
+         factor will not match the registered commitment.",
+    )?;
+    out.backup_warning()
+}
+
+/// `wait-tx <hash>` — poll until the transaction is confirmed and print the
+/// payments it made.
+fn cmd_wait_tx(rpc_url: &str, tx_hash: &str, timeout: u64) -> Result<()> {
+    let tx = rpc::wait_for_transaction(rpc_url, tx_hash, std::time::Duration::from_secs(timeout))?;
+
+    if tx.status == rpc::TxStatus::Failed {
+        bail!("Transaction {} failed in ledger {}", tx_hash, tx.ledger);
+    }
+
+    println!("Transaction {} confirmed in ledger {}", tx_hash, tx.ledger);
+    for ev in &tx.events {
+        println!(
+            "  PayrollProcessed: {} — {} stroops (period {})",
+            ev.employee, ev.amount, ev.period
+        );
+    }
+
+    Ok(())
+}
+
+/// `check-treasury` — compare the treasury balance with the active salaries.
+fn cmd_check_treasury(
+    out: &mut Output<impl Write>,
+    data_dir: Option<&Path>,
+    rpc_url: &str,
+    args: &treasury::TreasuryArgs<'_>,
+) -> Result<()> {
+    let conn = open_existing_db(&db::db_path(data_dir)?)?;
+    let salaries = db::active_salaries(&conn)?;
+
+    let check = treasury::check(args, &salaries, |contract_id, function, call_args| {
+        rpc::simulate_read(rpc_url, contract_id, function, call_args)
+    })
+    .with_context(|| Classified::new(ErrorKind::Network, "Failed to read the treasury balance"))?;
 
+    out.result(format_args!("{}", check))?;
+    if !check.is_sufficient() {
+        bail!("Treasury is short by {}", -check.delta());
+    }
     Ok(())
 }
 
+/// `export-commitments <out-path>` — write the active employees' commitments
+/// without their blinding factors.
+fn cmd_export_commitments(
+    out: &mut Output<impl Write>,
+    data_dir: Option<&Path>,
+    out_path: &Path,
+) -> Result<()> {
+    let conn = open_existing_db(&db::db_path(data_dir)?)?;
+    let entries = portable::export(&conn)?;
+    portable::write(out_path, &entries)?;
+
+    out.result(format_args!(
+        "Exported {} commitments to {}",
+        entries.len(),
+        out_path.display()
+    ))?;
+    out.note("The file holds no blinding factors and cannot be used to generate proofs.")
+}
+
+/// `import-commitments <in-path>` — validate the file and, given a registry,
+/// compare it with the on-chain commitments.
+fn cmd_import_commitments(
+    out: &mut Output<impl Write>,
+    in_path: &Path,
+    rpc_url: &str,
+    registry: Option<&portable::RegistryArgs<'_>>,
+) -> Result<()> {
+    let entries = portable::read(in_path)?;
+    out.result(format_args!(
+        "{} valid commitments in {}",
+        entries.len(),
+        in_path.display()
+    ))?;
+
+    let Some(registry) = registry else {
+        return Ok(());
+    };
+    let statuses = portable::diff(registry, &entries, |contract_id, function, call_args| {
+        rpc::simulate_read(rpc_url, contract_id, function, call_args)
+    })
+    .with_context(|| Classified::new(ErrorKind::Network, "Failed to read the registry"))?;
+
+    let mut differing = 0;
+    for (entry, status) in entries.iter().zip(&statuses) {
+        let verdict = match status {
+            portable::OnChainStatus::Match => "MATCH".to_string(),
+            portable::OnChainStatus::Mismatch { on_chain } => {
+                format!("MISMATCH (on-chain 0x{})", hex::encode(on_chain))
+            }
+            portable::OnChainStatus::Missing => "MISSING".to_string(),
+        };
+        if *status != portable::OnChainStatus::Match {
+            differing += 1;
+        }
+        out.result(format_args!("{}  {}", entry.pubkey, verdict))?;
+    }
+    if differing > 0 {
+        bail!(
+            "{} of {} commitments differ from company {}",
+            differing,
+            entries.len(),
+            registry.company_id
+        );
+    }
+    Ok(())
+}
+
+/// `doctor` — run the read-only setup checks and print a checklist.
+fn cmd_doctor(out: &mut Output<impl Write>, data_dir: Option<&Path>, rpc_url: &str) -> Result<()> {
+    let checks = doctor::run_checks(&db::db_path(data_dir)?, rpc_url);
+
+    for check in &checks {
+        let mark = if check.passed { "PASS" } else { "FAIL" };
+        out.result(format_args!("[{}] {}: {}", mark, check.name, check.detail))?;
+        if let Some(hint) = &check.hint {
+            out.note(format_args!("       {}", hint))?;
+        }
+    }
+
+    let failed = checks.iter().filter(|c| !c.passed).count();
+    if failed > 0 {
+        bail!("{} of {} checks failed", failed, checks.len());
+    }
+    Ok(())
+}
+
+/// `deactivate-employee <pubkey>` — mark the employee as deactivated.
+fn cmd_deactivate_employee(
+    out: &mut Output<impl Write>,
+    data_dir: Option<&Path>,
+    pubkey: &str,
+) -> Result<()> {
+    validate_stellar_pubkey(pubkey)?;
+
+    let conn = open_existing_db(&db::db_path(data_dir)?)?;
+    if !db::employee_exists(&conn, pubkey)? {
+        bail!(Classified::new(
+            ErrorKind::Validation,
+            format!("Employee '{}' not found in the database.", pubkey),
+        ));
+    }
+
+    if db::is_deactivated(&conn, pubkey)? {
+        return out.result(format_args!("Employee {} is already deactivated.", pubkey));
+    }
+
+    let now = unix_now();
+    db::deactivate_employee(&conn, pubkey, now)?;
+    db::append_audit_log(&conn, pubkey, "deactivate", now)?;
+
+    out.result(format_args!("Employee {} deactivated.", pubkey))?;
+    out.note("Their blinding factor is kept until `zk-payroll prune --include-deactivated`.")
+}
+
+/// `nullifier <pubkey> --period <n>` — print the recipient-bound payment
+/// nullifier for one pay period.
+fn cmd_nullifier(
+    out: &mut Output<impl Write>,
+    data_dir: Option<&Path>,
+    pubkey: &str,
+    period: u32,
+) -> Result<()> {
+    validate_stellar_pubkey(pubkey)?;
+    let recipient_hash = crypto::recipient_hash(pubkey)
+        .map_err(|e| Classified::new(ErrorKind::Validation, format!("{:#}", e)))?;
+
+    let conn = open_existing_db(&db::db_path(data_dir)?)?;
+    let nullifier = employee_nullifier(&conn, pubkey, period, &recipient_hash)?;
+
+    out.result(format_args!(
+        "Payment nullifier: 0x{}",
+        hex::encode(nullifier)
+    ))?;
+    out.note("")?;
+    out.note(format_args!("  Employee       : {}", pubkey))?;
+    out.note(format_args!("  Period         : {}", period))?;
+    out.note(format_args!(
+        "  Recipient hash : 0x{}",
+        hex::encode(recipient_hash)
+    ))
+}
+
+/// `prune --older-than-days <n>` — delete stale audit-log rows and,
+/// optionally, long-deactivated employees.
+///
+/// Lists the rows first, then asks `confirmation` before deleting; `None`
+/// (from `--force`) deletes without asking.
+fn cmd_prune(
+    out: &mut Output<impl Write>,
+    data_dir: Option<&Path>,
+    older_than_days: u64,
+    include_deactivated: bool,
+    confirmation: Option<Confirmation<impl BufRead>>,
+) -> Result<()> {
+    let conn = open_existing_db(&db::db_path(data_dir)?)?;
+
+    let cutoff = unix_now().saturating_sub(older_than_days.saturating_mul(86_400) as i64);
+    let preview = db::prune_preview(&conn, cutoff, include_deactivated)?;
+    if preview.is_empty() {
+        return out.result("Nothing to prune.");
+    }
+
+    out.result("The following rows will be permanently deleted:")?;
+    for pubkey in &preview.deactivated_employees {
+        out.result(format_args!(
+            "  employee  {} (blinding factor and derivation index)",
+            pubkey
+        ))?;
+    }
+    for entry in &preview.audit_log_entries {
+        out.result(format_args!(
+            "  audit log {} {} at {}",
+            entry.employee_pubkey, entry.action, entry.created_at
+        ))?;
+    }
+
+    if let Some(mut confirmation) = confirmation {
+        if !confirmation.ask(out, "Delete these rows? [y/N]")? {
+            return out.result("Aborted; nothing was deleted.");
+        }
+    }
+
+    let report = db::prune(&conn, cutoff, include_deactivated)?;
+
+    out.result(format_args!(
+        "Pruned {} row(s): {} audit-log entr{}, {} deactivated employee(s).",
+        report.total(),
+        report.audit_log_entries,
+        if report.audit_log_entries == 1 {
+            "y"
+        } else {
+            "ies"
+        },
+        report.deactivated_employees,
+    ))
+}
+
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
+const RECEIPT_WARNING: &str = "This receipt does not contain the blinding factor. \
+     Payments for this employee can only be proven with the blinding factor \
+     stored in the local database; back up ~/.zk-payroll.";
+
+/// JSON record of an `add-employee` run written by `--output-file`.
+///
+/// Deliberately has no blinding-factor field: receipts are meant to be filed
+/// and shared, and the blinding factor must stay in the local database.
+#[derive(Serialize)]
+struct OnboardingReceipt<'a> {
+    pubkey: &'a str,
+    salary: u64,
+    commitment: String,
+    timestamp: i64,
+    warning: &'a str,
+}
+
+fn write_receipt(path: &Path, receipt: &OnboardingReceipt) -> Result<()> {
+    let json = serde_json::to_string_pretty(receipt)?;
+    std::fs::write(path, json + "\n")
+        .with_context(|| format!("Cannot write receipt to '{}'", path.display()))
+}
+
+/// Current wall-clock time as Unix seconds.
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Open the database at `path`, failing if `init-company` has not created it.
+fn open_existing_db(path: &Path) -> Result<rusqlite::Connection> {
+    if !path.exists() {
+        bail!(Classified::new(
+            ErrorKind::Database,
+            format!(
+                "Database not found at '{}'.\n\
+                 Run `zk-payroll init-company` to create it first.",
+                path.display()
+            ),
+        ));
+    }
+    let conn = db::open(path)?;
+    // Older databases predate tables added since they were created.
+    db::initialise(&conn)?;
+    Ok(conn)
+}
+
+/// How many blinding factors [`new_blinding_factor`] draws before giving up
+/// on finding one no other employee uses.
+const BLINDING_ATTEMPTS: usize = 3;
+
+/// Produce a new blinding factor for `pubkey`.
+///
+/// Derives it from the next index under the master seed when one is stored
+/// (returning that index); otherwise generates it at random.  A factor
+/// already stored for any employee is discarded and a fresh one drawn.
+fn new_blinding_factor(
+    conn: &rusqlite::Connection,
+    pubkey: &str,
+) -> Result<([u8; 32], Option<u64>)> {
+    unique_blinding_factor(conn, || draw_blinding_factor(conn, pubkey))
+}
+
+/// Call `draw` until it yields a blinding factor not already in the
+/// database, failing after [`BLINDING_ATTEMPTS`] collisions.
+fn unique_blinding_factor(
+    conn: &rusqlite::Connection,
+    mut draw: impl FnMut() -> Result<([u8; 32], Option<u64>)>,
+) -> Result<([u8; 32], Option<u64>)> {
+    for _ in 0..BLINDING_ATTEMPTS {
+        let (blinding, index) = draw()?;
+        if !db::blinding_exists(conn, &hex::encode(blinding))? {
+            return Ok((blinding, index));
+        }
+    }
+    bail!(Classified::new(
+        ErrorKind::Validation,
+        format!(
+            "Every blinding factor drawn ({} attempts) is already used by another employee.\n\
+             Check the random source, or the master seed if one is stored.",
+            BLINDING_ATTEMPTS
+        ),
+    ))
+}
+
+/// One blinding factor for `pubkey`, without the reuse check.
+fn draw_blinding_factor(
+    conn: &rusqlite::Connection,
+    pubkey: &str,
+) -> Result<([u8; 32], Option<u64>)> {
+    let seed_hex = match db::get_master_seed(conn)? {
+        Some(seed_hex) => seed_hex,
+        None => return Ok((crypto::gen_blinding_factor(), None)),
+    };
+    let seed =
+        crypto::parse_commitment_hex(&seed_hex).context("Stored master seed is malformed")?;
+    let index = db::allocate_derivation_index(conn, pubkey)?;
+    let blinding =
+        crypto::derive_blinding_factor(&seed, index).context("Failed to derive blinding factor")?;
+    Ok((blinding, Some(index)))
+}
+
+/// Result of rotating an employee's blinding factor.
+struct RotatedBlinding {
+    salary: u64,
+    old_commitment: [u8; 32],
+    new_commitment: [u8; 32],
+}
+
+/// Replace the stored blinding factor for `pubkey` with a fresh one and
+/// recompute the commitment for the employee's existing salary.
+fn rotate_employee_blinding(conn: &rusqlite::Connection, pubkey: &str) -> Result<RotatedBlinding> {
+    let (old_blinding_hex, salary) = db::get_employee(conn, pubkey)?.ok_or_else(|| {
+        Classified::new(
+            ErrorKind::Validation,
+            format!(
+                "Employee '{}' not found in the database.\n\
+                 Use `zk-payroll add-employee` to register them first.",
+                pubkey
+            ),
+        )
+    })?;
+
+    let old_blinding = crypto::parse_commitment_hex(&old_blinding_hex)
+        .with_context(|| format!("Stored blinding factor for '{}' is malformed", pubkey))?;
+    let old_commitment =
+        crypto::poseidon_commitment(salary, &old_blinding, PoseidonParams::default())
+            .context("Failed to compute Poseidon commitment")?;
+
+    let (new_blinding, _) = new_blinding_factor(conn, pubkey)?;
+    let new_commitment =
+        crypto::poseidon_commitment(salary, &new_blinding, PoseidonParams::default())
+            .context("Failed to compute Poseidon commitment")?;
+
+    db::update_employee_blinding(conn, pubkey, &hex::encode(new_blinding))
+        .context("Failed to persist rotated blinding factor")?;
+
+    Ok(RotatedBlinding {
+        salary,
+        old_commitment,
+        new_commitment,
+    })
+}
+
+/// `Poseidon(blinding, period, recipient_hash)` for an employee in the
+/// database.
+fn employee_nullifier(
+    conn: &rusqlite::Connection,
+    pubkey: &str,
+    period: u32,
+    recipient_hash: &[u8; 32],
+) -> Result<[u8; 32]> {
+    let (blinding_hex, _) = db::get_employee(conn, pubkey)?.ok_or_else(|| {
+        Classified::new(
+            ErrorKind::Validation,
+            format!("Employee '{}' not found in the database.", pubkey),
+        )
+    })?;
+
+    let blinding = crypto::parse_commitment_hex(&blinding_hex)
+        .with_context(|| format!("Stored blinding factor for '{}' is malformed", pubkey))?;
+    crypto::payment_nullifier(&blinding, period, recipient_hash, PoseidonParams::default())
+        .context("Failed to compute payment nullifier")
+}
+
+/// Source of the `y/N` answer for a destructive command.
+struct Confirmation<R: BufRead> {
+    input: R,
+    /// Whether `input` is a terminal a person can answer on.
+    interactive: bool,
+}
+
+impl<R: BufRead> Confirmation<R> {
+    /// Ask `question` and return whether the answer was `y` or `yes`.
+    ///
+    /// Anything else, including end of input, declines.  Refuses outright
+    /// when the input is not interactive, so a script that forgot `--force`
+    /// stops rather than deleting on a piped answer.
+    fn ask(&mut self, out: &mut Output<impl Write>, question: &str) -> Result<bool> {
+        if !self.interactive {
+            bail!(Classified::new(
+                ErrorKind::Validation,
+                "Refusing to delete without confirmation: no terminal to ask on.\n\
+                 Re-run with --force to delete without asking.",
+            ));
+        }
+        out.prompt(question)?;
+        let mut answer = String::new();
+        self.input
+            .read_line(&mut answer)
+            .context("Failed to read confirmation")?;
+        Ok(matches!(
+            answer.trim().to_ascii_lowercase().as_str(),
+            "y" | "yes"
+        ))
+    }
+}
+
+/// Build the reconcile period calendar from `--period-label`,
+/// `--start-date`, and `--period-days`.
+///
+/// clap already enforces that the label and start date come together and
+/// that `custom` has a day count; this rejects a day count given for any
+/// other label.
+fn period_calendar(
+    label: Option<PeriodLabel>,
+    start: Option<CalendarDate>,
+    days: Option<u32>,
+) -> Result<Option<PeriodCalendar>> {
+    let (Some(label), Some(start)) = (label, start) else {
+        return Ok(None);
+    };
+    let granularity = match (label, days) {
+        (PeriodLabel::Custom, Some(days)) => Granularity::Days(days),
+        (PeriodLabel::Custom, None) => bail!(Classified::new(
+            ErrorKind::Validation,
+            "--period-label custom requires --period-days",
+        )),
+        (_, Some(_)) => bail!(Classified::new(
+            ErrorKind::Validation,
+            "--period-days is only used with --period-label custom",
+        )),
+        (PeriodLabel::Daily, None) => Granularity::Daily,
+        (PeriodLabel::Weekly, None) => Granularity::Weekly,
+        (PeriodLabel::Monthly, None) => Granularity::Monthly,
+    };
+    Ok(Some(PeriodCalendar { granularity, start }))
+}
+
 /// Validate that `pubkey` looks like a Stellar public key.
 ///
 /// Stellar public keys (G... addresses / StrKeys) are exactly 56 characters
@@ -252,12 +1255,15 @@ fn cmd_add_employee(pubkey: &str, amount: u64) -> Result<()> {
 fn validate_stellar_pubkey(pubkey: &str) -> Result<()> {
     // Stellar StrKey public keys start with 'G' and are always 56 characters.
     if pubkey.len() != 56 || !pubkey.starts_with('G') {
-        bail!(
-            "Invalid Stellar public key: '{}'\n\
-             Expected a 56-character address starting with 'G' \
-             (e.g. GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN).",
-            pubkey
-        );
+        bail!(Classified::new(
+            ErrorKind::Validation,
+            format!(
+                "Invalid Stellar public key: '{}'\n\
+                 Expected a 56-character address starting with 'G' \
+                 (e.g. GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN).",
+                pubkey
+            ),
+        ));
     }
 
     // StrKey uses base32 alphabet: A-Z 2-7.
@@ -266,11 +1272,14 @@ fn validate_stellar_pubkey(pubkey: &str) -> Result<()> {
         .all(|c| c.is_ascii_uppercase() || ('2'..='7').contains(&c));
 
     if !valid_chars {
-        bail!(
-            "Invalid Stellar public key: '{}'\n\
-             StrKey addresses may only contain uppercase letters A-Z and digits 2-7.",
-            pubkey
-        );
+        bail!(Classified::new(
+            ErrorKind::Validation,
+            format!(
+                "Invalid Stellar public key: '{}'\n\
+                 StrKey addresses may only contain uppercase letters A-Z and digits 2-7.",
+                pubkey
+            ),
+        ));
     }
 
     Ok(())
@@ -325,4 +1334,386 @@ mod tests {
             "invalid char must be rejected"
         );
     }
+
+    fn in_memory_db_with(pubkey: &str, salary: u64) -> (rusqlite::Connection, String) {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        db::initialise(&conn).unwrap();
+        let blinding_hex = hex::encode(crypto::gen_blinding_factor());
+        db::insert_employee(&conn, pubkey, &blinding_hex, salary).unwrap();
+        (conn, blinding_hex)
+    }
+
+    #[test]
+    fn rotate_blinding_derives_from_master_seed_when_present() {
+        let pubkey = valid_key();
+        let (conn, _) = in_memory_db_with(&pubkey, 5_000_000);
+        let seed = crypto::gen_blinding_factor();
+        db::insert_master_seed(&conn, &hex::encode(seed)).unwrap();
+
+        rotate_employee_blinding(&conn, &pubkey).unwrap();
+
+        let (new_blinding, _) = db::get_employee(&conn, &pubkey).unwrap().unwrap();
+        let expected = crypto::derive_blinding_factor(&seed, 0).unwrap();
+        assert_eq!(new_blinding, hex::encode(expected));
+    }
+
+    #[test]
+    fn rotate_blinding_changes_blinding_factor() {
+        let pubkey = valid_key();
+        let (conn, old_blinding) = in_memory_db_with(&pubkey, 5_000_000);
+
+        rotate_employee_blinding(&conn, &pubkey).unwrap();
+
+        let (new_blinding, _) = db::get_employee(&conn, &pubkey).unwrap().unwrap();
+        assert_ne!(new_blinding, old_blinding, "blinding factor must change");
+    }
+
+    #[test]
+    fn rotate_blinding_preserves_salary() {
+        let pubkey = valid_key();
+        let (conn, _) = in_memory_db_with(&pubkey, 5_000_000);
+
+        let rotated = rotate_employee_blinding(&conn, &pubkey).unwrap();
+
+        let (_, salary) = db::get_employee(&conn, &pubkey).unwrap().unwrap();
+        assert_eq!(salary, 5_000_000);
+        assert_eq!(rotated.salary, 5_000_000);
+    }
+
+    #[test]
+    fn rotate_blinding_produces_new_commitment() {
+        let pubkey = valid_key();
+        let (conn, _) = in_memory_db_with(&pubkey, 5_000_000);
+
+        let rotated = rotate_employee_blinding(&conn, &pubkey).unwrap();
+        assert_ne!(rotated.old_commitment, rotated.new_commitment);
+
+        // The new commitment must match the persisted blinding factor.
+        let (blinding_hex, salary) = db::get_employee(&conn, &pubkey).unwrap().unwrap();
+        let blinding: [u8; 32] = hex::decode(blinding_hex).unwrap().try_into().unwrap();
+        assert_eq!(
+            crypto::poseidon_commitment(salary, &blinding, PoseidonParams::default()).unwrap(),
+            rotated.new_commitment
+        );
+    }
+
+    #[test]
+    fn colliding_blinding_factor_is_redrawn() {
+        let pubkey = valid_key();
+        let (conn, taken) = in_memory_db_with(&pubkey, 5_000_000);
+        let taken: [u8; 32] = hex::decode(taken).unwrap().try_into().unwrap();
+        let fresh = [7u8; 32];
+
+        let mut draws = [taken, fresh].into_iter();
+        let (blinding, _) =
+            unique_blinding_factor(&conn, || Ok((draws.next().unwrap(), None))).unwrap();
+        assert_eq!(blinding, fresh);
+    }
+
+    #[test]
+    fn persistent_blinding_collision_errors() {
+        let pubkey = valid_key();
+        let (conn, taken) = in_memory_db_with(&pubkey, 5_000_000);
+        let taken: [u8; 32] = hex::decode(taken).unwrap().try_into().unwrap();
+
+        let err = unique_blinding_factor(&conn, || Ok((taken, None))).unwrap_err();
+        assert!(err.to_string().contains("already used by another employee"));
+    }
+
+    #[test]
+    fn rotate_blinding_unknown_employee_errors() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        db::initialise(&conn).unwrap();
+        assert!(rotate_employee_blinding(&conn, &valid_key()).is_err());
+    }
+
+    #[test]
+    fn nullifier_uses_stored_blinding_factor() {
+        let pubkey = valid_key();
+        let (conn, blinding_hex) = in_memory_db_with(&pubkey, 5_000_000);
+        let recipient_hash = [3u8; 32];
+
+        let blinding = crypto::parse_commitment_hex(&blinding_hex).unwrap();
+        let expected =
+            crypto::payment_nullifier(&blinding, 7, &recipient_hash, PoseidonParams::default())
+                .unwrap();
+        assert_eq!(
+            employee_nullifier(&conn, &pubkey, 7, &recipient_hash).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn nullifier_unknown_employee_errors() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        db::initialise(&conn).unwrap();
+        assert!(employee_nullifier(&conn, &valid_key(), 1, &[0u8; 32]).is_err());
+    }
+
+    // ── Exit codes ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn invalid_pubkey_exits_with_validation_code() {
+        let err = validate_stellar_pubkey("not-a-key").unwrap_err();
+        assert_eq!(exit::exit_code(&err), exit::EXIT_VALIDATION);
+    }
+
+    #[test]
+    fn missing_database_exits_with_database_code() {
+        let path = std::env::temp_dir()
+            .join(format!("zk-payroll-missing-{}", std::process::id()))
+            .join("company_db.sqlite");
+        let err = open_existing_db(&path).unwrap_err();
+        assert_eq!(exit::exit_code(&err), exit::EXIT_DATABASE);
+    }
+
+    #[test]
+    fn data_dir_flag_is_accepted_before_or_after_subcommand() {
+        for argv in [
+            ["zk-payroll", "--data-dir", "/tmp/ci", "init-company"],
+            ["zk-payroll", "init-company", "--data-dir", "/tmp/ci"],
+        ] {
+            let cli = Cli::try_parse_from(argv).unwrap();
+            assert_eq!(cli.data_dir.as_deref(), Some(Path::new("/tmp/ci")));
+        }
+    }
+
+    fn reconcile_argv<'a>(extra: &[&'a str]) -> Vec<&'a str> {
+        let mut argv = vec![
+            "zk-payroll",
+            "reconcile",
+            "--contract-id",
+            "C1",
+            "--company-id",
+            "ACME",
+        ];
+        argv.extend_from_slice(extra);
+        argv
+    }
+
+    #[test]
+    fn period_label_requires_start_date() {
+        assert!(Cli::try_parse_from(reconcile_argv(&["--period-label", "monthly"])).is_err());
+        assert!(Cli::try_parse_from(reconcile_argv(&["--start-date", "2024-01-01"])).is_err());
+        assert!(Cli::try_parse_from(reconcile_argv(&[
+            "--period-label",
+            "monthly",
+            "--start-date",
+            "2024-01-32"
+        ]))
+        .is_err());
+        assert!(Cli::try_parse_from(reconcile_argv(&[
+            "--period-label",
+            "custom",
+            "--start-date",
+            "2024-01-01"
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn network_flags_select_endpoint() {
+        let endpoint =
+            |extra: &[&str]| match Cli::try_parse_from(reconcile_argv(extra)).unwrap().command {
+                Commands::Reconcile { network, .. } => network.endpoint(),
+                _ => unreachable!(),
+            };
+
+        assert_eq!(endpoint(&[]).rpc_url, "https://soroban-testnet.stellar.org");
+        assert_eq!(
+            endpoint(&["--network", "mainnet"]).rpc_url,
+            "https://mainnet.sorobanrpc.com"
+        );
+        assert_eq!(
+            endpoint(&[
+                "--network",
+                "futurenet",
+                "--rpc-url",
+                "http://localhost:8000"
+            ])
+            .rpc_url,
+            "http://localhost:8000"
+        );
+        assert!(Cli::try_parse_from(reconcile_argv(&["--network", "localnet"])).is_err());
+    }
+
+    #[test]
+    fn period_calendar_maps_each_label() {
+        let start: CalendarDate = "2024-01-01".parse().unwrap();
+        let granularity = |label, days| {
+            period_calendar(Some(label), Some(start), days)
+                .unwrap()
+                .unwrap()
+                .granularity
+        };
+        assert_eq!(granularity(PeriodLabel::Daily, None), Granularity::Daily);
+        assert_eq!(granularity(PeriodLabel::Weekly, None), Granularity::Weekly);
+        assert_eq!(
+            granularity(PeriodLabel::Monthly, None),
+            Granularity::Monthly
+        );
+        assert_eq!(
+            granularity(PeriodLabel::Custom, Some(14)),
+            Granularity::Days(14)
+        );
+
+        assert!(period_calendar(None, None, None).unwrap().is_none());
+        let err = period_calendar(Some(PeriodLabel::Weekly), Some(start), Some(14)).unwrap_err();
+        assert_eq!(exit::exit_code(&err), exit::EXIT_VALIDATION);
+    }
+
+    /// Run `init-company` then `add-employee` in a fresh data directory and
+    /// return everything printed.
+    fn add_employee_output(name: &str, quiet: bool) -> String {
+        let dir = std::env::temp_dir().join(format!("zk-payroll-{}-{}", name, std::process::id()));
+        let mut out = Output::new(Vec::new(), quiet);
+        cmd_init_company(&mut out, Some(&dir), false).unwrap();
+        cmd_add_employee(&mut out, Some(&dir), &valid_key(), 50_000, None).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        String::from_utf8(out.out).unwrap()
+    }
+
+    #[test]
+    fn quiet_omits_backup_banner() {
+        let printed = add_employee_output("quiet", true);
+        assert!(!printed.contains("CRITICAL BACKUP WARNING"));
+        assert!(printed.starts_with("Successfully generated commitment: 0x"));
+        assert_eq!(printed.lines().count(), 1, "only the commitment line");
+    }
+
+    #[test]
+    fn default_output_prints_backup_banner() {
+        let printed = add_employee_output("verbose", false);
+        assert_eq!(printed.matches("CRITICAL BACKUP WARNING").count(), 2);
+        assert!(printed.contains("Successfully generated commitment: 0x"));
+    }
+
+    #[test]
+    fn quiet_flag_is_global() {
+        let cli = Cli::try_parse_from(["zk-payroll", "init-company", "--quiet"]).unwrap();
+        assert!(cli.quiet);
+        let cli = Cli::try_parse_from(["zk-payroll", "init-company"]).unwrap();
+        assert!(!cli.quiet);
+    }
+
+    #[test]
+    fn output_file_receipt_omits_blinding_factor() {
+        let dir = std::env::temp_dir().join(format!("zk-payroll-receipt-{}", std::process::id()));
+        let receipt_path = dir.join("receipt.json");
+        let mut out = Output::new(Vec::new(), true);
+        cmd_init_company(&mut out, Some(&dir), false).unwrap();
+        cmd_add_employee(
+            &mut out,
+            Some(&dir),
+            &valid_key(),
+            50_000,
+            Some(&receipt_path),
+        )
+        .unwrap();
+
+        let conn = db::open(&db::db_path(Some(&dir)).unwrap()).unwrap();
+        let (blinding_hex, _) = db::get_employee(&conn, &valid_key()).unwrap().unwrap();
+        let written = std::fs::read_to_string(&receipt_path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let receipt: serde_json::Value = serde_json::from_str(&written).unwrap();
+        let fields = receipt.as_object().unwrap();
+        let mut keys: Vec<&str> = fields.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            ["commitment", "pubkey", "salary", "timestamp", "warning"]
+        );
+        assert_eq!(receipt["pubkey"], valid_key());
+        assert_eq!(receipt["salary"], 50_000);
+        let printed = String::from_utf8(out.out).unwrap();
+        assert!(printed.contains(receipt["commitment"].as_str().unwrap()));
+
+        assert!(!written.contains(&blinding_hex));
+        assert!(!written.to_lowercase().contains("\"blinding"));
+    }
+
+    /// Fresh data directory holding one employee deactivated long ago.
+    fn prune_fixture(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("zk-payroll-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        cmd_init_company(&mut Output::new(Vec::new(), true), Some(&dir), false).unwrap();
+        let conn = db::open(&db::db_path(Some(&dir)).unwrap()).unwrap();
+        db::insert_employee(&conn, &valid_key(), &"aa".repeat(32), 1_000).unwrap();
+        db::deactivate_employee(&conn, &valid_key(), 100).unwrap();
+        dir
+    }
+
+    fn employee_remains(dir: &Path) -> bool {
+        let conn = db::open(&db::db_path(Some(dir)).unwrap()).unwrap();
+        db::employee_exists(&conn, &valid_key()).unwrap()
+    }
+
+    #[test]
+    fn prune_force_skips_prompt() {
+        let dir = prune_fixture("prune-force");
+        let mut out = Output::new(Vec::new(), false);
+
+        cmd_prune(&mut out, Some(&dir), 0, true, None::<Confirmation<&[u8]>>).unwrap();
+
+        let printed = String::from_utf8(out.out).unwrap();
+        assert!(printed.contains(&format!("employee  {}", valid_key())));
+        assert!(!printed.contains("[y/N]"));
+        assert!(!employee_remains(&dir));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prune_without_terminal_aborts_without_deleting() {
+        let dir = prune_fixture("prune-no-tty");
+        let mut out = Output::new(Vec::new(), false);
+        let confirmation = Confirmation {
+            input: &b"y\n"[..],
+            interactive: false,
+        };
+
+        let err = cmd_prune(&mut out, Some(&dir), 0, true, Some(confirmation)).unwrap_err();
+
+        assert_eq!(exit::exit_code(&err), exit::EXIT_VALIDATION);
+        assert!(format!("{:#}", err).contains("--force"));
+        assert!(employee_remains(&dir));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prune_asks_before_deleting() {
+        let dir = prune_fixture("prune-confirm");
+
+        for (answer, deleted) in [(&b"\n"[..], false), (b"no\n", false), (b"y\n", true)] {
+            let mut out = Output::new(Vec::new(), false);
+            let confirmation = Confirmation {
+                input: answer,
+                interactive: true,
+            };
+            cmd_prune(&mut out, Some(&dir), 0, true, Some(confirmation)).unwrap();
+
+            let printed = String::from_utf8(out.out).unwrap();
+            assert!(printed.contains("Delete these rows? [y/N]"));
+            assert_eq!(printed.contains("Aborted"), !deleted);
+            assert_eq!(employee_remains(&dir), !deleted);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prune_with_nothing_to_delete_does_not_ask() {
+        let dir = prune_fixture("prune-empty");
+        let mut out = Output::new(Vec::new(), false);
+        let confirmation = Confirmation {
+            input: &b""[..],
+            interactive: false,
+        };
+
+        // Without --include-deactivated only the fresh audit log is eligible,
+        // and none of it is older than a day.
+        cmd_prune(&mut out, Some(&dir), 1, false, Some(confirmation)).unwrap();
+
+        assert_eq!(String::from_utf8(out.out).unwrap(), "Nothing to prune.\n");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,171 @@
+//! Named Soroban networks for the RPC commands.
+//!
+//! `--network testnet|mainnet|futurenet` picks a known RPC endpoint and its
+//! network passphrase, so users need not paste URLs.  `--rpc-url` overrides
+//! the endpoint, e.g. for a self-hosted node; with no `--network` such an
+//! endpoint is "custom".  Neither flag means testnet, as before presets.
+//!
+//! `reconcile` records the network each company was last reconciled against
+//! and warns when it changes, since a mainnet contract reconciled against a
+//! testnet database (or the reverse) reports every payment as missing.
+
+use std::fmt;
+
+/// A network with a well-known RPC endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Network {
+    Testnet,
+    Mainnet,
+    Futurenet,
+}
+
+impl Network {
+    pub fn name(self) -> &'static str {
+        match self {
+            Network::Testnet => "testnet",
+            Network::Mainnet => "mainnet",
+            Network::Futurenet => "futurenet",
+        }
+    }
+
+    /// Public Soroban RPC endpoint for the network.
+    pub fn rpc_url(self) -> &'static str {
+        match self {
+            Network::Testnet => "https://soroban-testnet.stellar.org",
+            Network::Mainnet => "https://mainnet.sorobanrpc.com",
+            Network::Futurenet => "https://rpc-futurenet.stellar.org",
+        }
+    }
+
+    /// Passphrase transactions on the network are signed for.
+    pub fn passphrase(self) -> &'static str {
+        match self {
+            Network::Testnet => "Test SDF Network ; September 2015",
+            Network::Mainnet => "Public Global Stellar Network ; September 2015",
+            Network::Futurenet => "Test SDF Future Network ; October 2022",
+        }
+    }
+}
+
+/// `--network` / `--rpc-url`, shared by every command that talks to RPC.
+#[derive(Debug, Clone, clap::Args)]
+pub struct NetworkArgs {
+    /// Network preset.
+    #[arg(
+        long,
+        value_enum,
+        help = "Network preset selecting the RPC endpoint (default testnet)"
+    )]
+    network: Option<Network>,
+
+    /// Soroban RPC URL, overriding the preset's.
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "Soroban JSON-RPC endpoint, overriding the --network preset's"
+    )]
+    rpc_url: Option<String>,
+}
+
+impl NetworkArgs {
+    pub fn endpoint(self) -> Endpoint {
+        Endpoint::resolve(self.network, self.rpc_url)
+    }
+}
+
+/// Where RPC requests go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// The preset, or `None` for a custom `--rpc-url`.
+    pub network: Option<Network>,
+    pub rpc_url: String,
+}
+
+impl Endpoint {
+    /// `--rpc-url` wins over the preset's URL; with neither flag the
+    /// endpoint is testnet.
+    pub fn resolve(network: Option<Network>, rpc_url: Option<String>) -> Self {
+        match (network, rpc_url) {
+            (network, Some(rpc_url)) => Self { network, rpc_url },
+            (network, None) => {
+                let network = network.unwrap_or(Network::Testnet);
+                Self {
+                    network: Some(network),
+                    rpc_url: network.rpc_url().to_string(),
+                }
+            }
+        }
+    }
+
+    /// What `reconcile` records per company: the preset name, or the URL
+    /// itself for a custom endpoint.
+    pub fn label(&self) -> String {
+        match self.network {
+            Some(network) => network.name().to_string(),
+            None => self.rpc_url.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Endpoint {
+    /// `testnet (https://...)`, or just the URL for a custom endpoint.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.network {
+            Some(network) => write!(f, "{} ({})", network.name(), self.rpc_url),
+            None => write!(f, "custom ({})", self.rpc_url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_preset_resolves_to_its_url() {
+        for (network, url) in [
+            (Network::Testnet, "https://soroban-testnet.stellar.org"),
+            (Network::Mainnet, "https://mainnet.sorobanrpc.com"),
+            (Network::Futurenet, "https://rpc-futurenet.stellar.org"),
+        ] {
+            let endpoint = Endpoint::resolve(Some(network), None);
+            assert_eq!(endpoint.rpc_url, url);
+            assert_eq!(endpoint.network, Some(network));
+            assert_eq!(endpoint.label(), network.name());
+        }
+    }
+
+    #[test]
+    fn presets_have_distinct_passphrases() {
+        assert_eq!(
+            Network::Mainnet.passphrase(),
+            "Public Global Stellar Network ; September 2015"
+        );
+        assert_ne!(
+            Network::Testnet.passphrase(),
+            Network::Futurenet.passphrase()
+        );
+    }
+
+    #[test]
+    fn no_flags_means_testnet() {
+        assert_eq!(
+            Endpoint::resolve(None, None),
+            Endpoint::resolve(Some(Network::Testnet), None)
+        );
+    }
+
+    #[test]
+    fn rpc_url_overrides_preset() {
+        let url = "http://localhost:8000/soroban/rpc".to_string();
+
+        let custom = Endpoint::resolve(None, Some(url.clone()));
+        assert_eq!(custom.network, None);
+        assert_eq!(custom.label(), url);
+        assert_eq!(custom.to_string(), format!("custom ({})", url));
+
+        let self_hosted = Endpoint::resolve(Some(Network::Mainnet), Some(url.clone()));
+        assert_eq!(self_hosted.rpc_url, url);
+        assert_eq!(self_hosted.label(), "mainnet");
+    }
+}
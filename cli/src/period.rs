@@ -0,0 +1,270 @@
+//! Human-readable labels for payroll period numbers.
+//!
+//! On-chain payments carry a bare `u32` period.  Given how long a period is
+//! and the date period 1 starts, [`PeriodCalendar::label`] renders it as a
+//! date range so a reconcile report can say "period 3 → March 2024" rather
+//! than just "3".
+//!
+//! Periods are numbered from 1: with monthly periods starting 2024-01-01,
+//! period 1 is January 2024 and period 3 is March 2024.  Period 0 has no
+//! label.
+//!
+//! Dates are proleptic Gregorian calendar dates with no time zone, converted
+//! to and from day numbers with Howard Hinnant's `days_from_civil` /
+//! `civil_from_days` algorithms, so no date crate is needed.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Period length accepted by `reconcile --period-label`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PeriodLabel {
+    Daily,
+    Weekly,
+    Monthly,
+    /// A fixed number of days, given with `--period-days`.
+    Custom,
+}
+
+/// How long each period lasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Daily,
+    Weekly,
+    Monthly,
+    /// Periods of this many days (at least 1).
+    Days(u32),
+}
+
+/// A calendar date, e.g. a company's first pay-period start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarDate {
+    pub year: i32,
+    /// 1–12.
+    pub month: u32,
+    /// 1–31, valid for `month`.
+    pub day: u32,
+}
+
+impl CalendarDate {
+    /// Days since 1970-01-01.
+    fn to_days(self) -> i64 {
+        let y = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = i64::from(self.month);
+        let doy = (153 * (m + if m > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// The date `days` days after 1970-01-01.
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+
+    fn add_days(self, days: i64) -> Self {
+        Self::from_days(self.to_days() + days)
+    }
+
+    /// First day of the month `months` months after this date's month.
+    fn add_months(self, months: i64) -> Self {
+        let index = i64::from(self.year) * 12 + i64::from(self.month) - 1 + months;
+        Self {
+            year: index.div_euclid(12) as i32,
+            month: index.rem_euclid(12) as u32 + 1,
+            day: 1,
+        }
+    }
+
+    fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            4 | 6 | 9 | 11 => 30,
+            2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+            2 => 28,
+            _ => 31,
+        }
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for CalendarDate {
+    type Err = anyhow::Error;
+
+    /// Parse `YYYY-MM-DD`.
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let parts: Vec<&str> = s.split('-').collect();
+        let [year, month, day] = parts[..] else {
+            bail!("Expected a date as YYYY-MM-DD, got '{}'", s);
+        };
+        let year: i32 = year
+            .parse()
+            .with_context(|| format!("Invalid year in '{}'", s))?;
+        let month: u32 = month
+            .parse()
+            .with_context(|| format!("Invalid month in '{}'", s))?;
+        let day: u32 = day
+            .parse()
+            .with_context(|| format!("Invalid day in '{}'", s))?;
+
+        if !(1..=12).contains(&month) {
+            bail!("Month must be 1-12 in '{}'", s);
+        }
+        if day == 0 || day > Self::days_in_month(year, month) {
+            bail!("Day out of range for the month in '{}'", s);
+        }
+        Ok(Self { year, month, day })
+    }
+}
+
+/// Maps period numbers to the dates they cover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodCalendar {
+    pub granularity: Granularity,
+    /// First day of period 1.  For monthly periods only the month is used.
+    pub start: CalendarDate,
+}
+
+impl PeriodCalendar {
+    /// Human-readable span of `period`, or `None` for period 0.
+    ///
+    /// Daily periods render as a date, weekly ones as "week of <date>",
+    /// monthly ones as "<Month> <year>", and custom ones as an inclusive
+    /// date range.
+    pub fn label(&self, period: u32) -> Option<String> {
+        let offset = i64::from(period.checked_sub(1)?);
+        let label = match self.granularity {
+            Granularity::Daily => self.start.add_days(offset).to_string(),
+            Granularity::Weekly => format!("week of {}", self.start.add_days(offset * 7)),
+            Granularity::Monthly => {
+                let month = self.start.add_months(offset);
+                format!("{} {}", MONTH_NAMES[month.month as usize - 1], month.year)
+            }
+            Granularity::Days(days) => {
+                let days = i64::from(days.max(1));
+                let first = self.start.add_days(offset * days);
+                format!("{} – {}", first, first.add_days(days - 1))
+            }
+        };
+        Some(label)
+    }
+}
+
+/// Raw period number, followed by its label when a calendar is configured:
+/// `3` or `3 → March 2024`.
+pub fn format_period(period: u32, calendar: Option<&PeriodCalendar>) -> String {
+    match calendar.and_then(|c| c.label(period)) {
+        Some(label) => format!("{} → {}", period, label),
+        None => period.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> CalendarDate {
+        s.parse().unwrap()
+    }
+
+    fn calendar(granularity: Granularity, start: &str) -> PeriodCalendar {
+        PeriodCalendar {
+            granularity,
+            start: date(start),
+        }
+    }
+
+    #[test]
+    fn parses_and_displays_dates() {
+        assert_eq!(date("2024-03-09").to_string(), "2024-03-09");
+        assert!("2024-13-01".parse::<CalendarDate>().is_err());
+        assert!("2023-02-29".parse::<CalendarDate>().is_err());
+        assert!("2024-02-29".parse::<CalendarDate>().is_ok());
+        assert!("March 2024".parse::<CalendarDate>().is_err());
+    }
+
+    #[test]
+    fn day_numbers_round_trip() {
+        assert_eq!(date("1970-01-01").to_days(), 0);
+        assert_eq!(date("2000-03-01").to_days(), 11_017);
+        for days in [-800_000, -1, 0, 59, 11_016, 19_782, 2_932_896] {
+            assert_eq!(CalendarDate::from_days(days).to_days(), days);
+        }
+    }
+
+    #[test]
+    fn daily_periods_are_consecutive_days() {
+        let cal = calendar(Granularity::Daily, "2024-02-28");
+        assert_eq!(cal.label(1).unwrap(), "2024-02-28");
+        assert_eq!(cal.label(2).unwrap(), "2024-02-29");
+        assert_eq!(cal.label(3).unwrap(), "2024-03-01");
+    }
+
+    #[test]
+    fn weekly_periods_start_seven_days_apart() {
+        let cal = calendar(Granularity::Weekly, "2024-12-23");
+        assert_eq!(cal.label(1).unwrap(), "week of 2024-12-23");
+        assert_eq!(cal.label(2).unwrap(), "week of 2024-12-30");
+        assert_eq!(cal.label(3).unwrap(), "week of 2025-01-06");
+    }
+
+    #[test]
+    fn monthly_periods_name_the_month() {
+        let cal = calendar(Granularity::Monthly, "2024-01-15");
+        assert_eq!(cal.label(1).unwrap(), "January 2024");
+        assert_eq!(cal.label(3).unwrap(), "March 2024");
+        assert_eq!(cal.label(13).unwrap(), "January 2025");
+    }
+
+    #[test]
+    fn custom_periods_render_inclusive_ranges() {
+        let cal = calendar(Granularity::Days(14), "2024-01-01");
+        assert_eq!(cal.label(1).unwrap(), "2024-01-01 – 2024-01-14");
+        assert_eq!(cal.label(3).unwrap(), "2024-01-29 – 2024-02-11");
+    }
+
+    #[test]
+    fn period_zero_has_no_label() {
+        let cal = calendar(Granularity::Monthly, "2024-01-01");
+        assert_eq!(cal.label(0), None);
+        assert_eq!(format_period(0, Some(&cal)), "0");
+    }
+
+    #[test]
+    fn format_period_appends_label_when_configured() {
+        let cal = calendar(Granularity::Monthly, "2024-01-01");
+        assert_eq!(format_period(3, None), "3");
+        assert_eq!(format_period(3, Some(&cal)), "3 → March 2024");
+    }
+}
@@ -0,0 +1,299 @@
+//! `export-commitments` / `import-commitments` — a portable list of employee
+//! commitments for moving between machines or tools.
+//!
+//! The file is a JSON array with one object per active employee:
+//!
+//! ```json
+//! [
+//!   { "pubkey": "GABC...", "commitment": "0x1f2e...", "salary": 500000 }
+//! ]
+//! ```
+//!
+//! Unlike a copy of the database, the file never holds blinding factors, so
+//! it can be shared to seed a new registry.  It is not enough to generate
+//! proofs: those still need the blinding factors in the local database.
+//! Files with any other field, such as a blinding factor, are rejected on
+//! import.
+//!
+//! On import the entries can be compared with the payroll registry's
+//! `find_employee`, read through `simulateTransaction`, to see which
+//! commitments are already registered on-chain.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use stellar_xdr::curr::{AccountId, PublicKey, ScAddress, ScVal, Uint256};
+
+use crate::crypto::{self, PoseidonParams};
+use crate::db;
+use crate::exit::{Classified, ErrorKind};
+
+/// One employee's commitment as written to and read from the portable file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PortableCommitment {
+    /// Employee Stellar public key (G... address).
+    pub pubkey: String,
+    /// `0x`-prefixed lowercase hex of `Poseidon(salary, blinding)`.
+    pub commitment: String,
+    /// Gross salary in stroops.
+    pub salary: u64,
+}
+
+/// How an imported entry compares with the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OnChainStatus {
+    /// The registry holds the same commitment.
+    Match,
+    /// The registry holds a different commitment.
+    Mismatch { on_chain: [u8; 32] },
+    /// The employee is not registered under the company.
+    Missing,
+}
+
+/// Where to compare imported commitments.
+pub struct RegistryArgs<'a> {
+    /// Payroll registry contract (C... Strkey address).
+    pub registry_id: &'a str,
+    pub company_id: u64,
+}
+
+/// Commitments of every active employee in the database, in pubkey order.
+pub fn export(conn: &Connection) -> Result<Vec<PortableCommitment>> {
+    db::active_employees(conn)?
+        .into_iter()
+        .map(|(pubkey, blinding_hex, salary)| {
+            let blinding = crypto::parse_commitment_hex(&blinding_hex)
+                .with_context(|| format!("Stored blinding factor for '{}' is malformed", pubkey))?;
+            let commitment =
+                crypto::poseidon_commitment(salary, &blinding, PoseidonParams::default())
+                    .context("Failed to compute Poseidon commitment")?;
+            Ok(PortableCommitment {
+                pubkey,
+                commitment: format!("0x{}", hex::encode(commitment)),
+                salary,
+            })
+        })
+        .collect()
+}
+
+/// Write `entries` to `path` as pretty-printed JSON.
+pub fn write(path: &Path, entries: &[PortableCommitment]) -> Result<()> {
+    let json = serde_json::to_string_pretty(entries)?;
+    std::fs::write(path, json + "\n")
+        .with_context(|| format!("Cannot write commitments to '{}'", path.display()))
+}
+
+/// Read and validate the commitments in `path`.
+pub fn read(path: &Path) -> Result<Vec<PortableCommitment>> {
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("Cannot read commitments from '{}'", path.display()))?;
+    parse(&json).with_context(|| format!("'{}' is not a valid commitments file", path.display()))
+}
+
+/// Parse a commitments file, checking every pubkey and commitment and
+/// rejecting duplicate employees.  Commitments are normalised to the
+/// `0x`-prefixed lowercase form [`export`] writes.
+pub fn parse(json: &str) -> Result<Vec<PortableCommitment>> {
+    let mut entries: Vec<PortableCommitment> = serde_json::from_str(json)
+        .map_err(|e| Classified::new(ErrorKind::Validation, e.to_string()))?;
+
+    let mut seen = HashSet::new();
+    for (i, entry) in entries.iter_mut().enumerate() {
+        crate::validate_stellar_pubkey(&entry.pubkey)
+            .with_context(|| format!("Entry {} has an invalid pubkey", i))?;
+        if !seen.insert(entry.pubkey.clone()) {
+            bail!(Classified::new(
+                ErrorKind::Validation,
+                format!("Employee '{}' appears more than once", entry.pubkey),
+            ));
+        }
+        let commitment = crypto::parse_commitment_hex(&entry.commitment).map_err(|e| {
+            Classified::new(
+                ErrorKind::Validation,
+                format!("Entry {} has an invalid commitment: {}", i, e),
+            )
+        })?;
+        entry.commitment = format!("0x{}", hex::encode(commitment));
+    }
+    Ok(entries)
+}
+
+/// Compare each entry with the commitment the registry holds for it,
+/// reading contract state with `read(contract_id, function, args)`.
+pub fn diff<F>(
+    args: &RegistryArgs<'_>,
+    entries: &[PortableCommitment],
+    mut read: F,
+) -> Result<Vec<OnChainStatus>>
+where
+    F: FnMut(&str, &str, Vec<ScVal>) -> Result<ScVal>,
+{
+    entries
+        .iter()
+        .map(|entry| {
+            let employee = account_address(&entry.pubkey)?;
+            let found = read(
+                args.registry_id,
+                "find_employee",
+                vec![ScVal::U64(args.company_id), employee],
+            )
+            .with_context(|| format!("Failed to look up '{}' in the registry", entry.pubkey))?;
+            let on_chain: [u8; 32] = match found {
+                ScVal::Void => return Ok(OnChainStatus::Missing),
+                ScVal::Bytes(bytes) => bytes
+                    .as_slice()
+                    .try_into()
+                    .with_context(|| format!("find_employee returned {} bytes", bytes.len()))?,
+                other => bail!("find_employee returned {:?}", other),
+            };
+            let local = crypto::parse_commitment_hex(&entry.commitment)?;
+            Ok(if on_chain == local {
+                OnChainStatus::Match
+            } else {
+                OnChainStatus::Mismatch { on_chain }
+            })
+        })
+        .collect()
+}
+
+/// `pubkey` as a contract-call address argument.
+fn account_address(pubkey: &str) -> Result<ScVal> {
+    let key = stellar_strkey::ed25519::PublicKey::from_string(pubkey)
+        .with_context(|| format!("'{}' is not a valid Stellar public key", pubkey))?;
+    Ok(ScVal::Address(ScAddress::Account(AccountId(
+        PublicKey::PublicKeyTypeEd25519(Uint256(key.0)),
+    ))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::ScBytes;
+
+    const REGISTRY: &str = "CREGISTRY";
+
+    fn pubkey(byte: u8) -> String {
+        stellar_strkey::Strkey::PublicKeyEd25519(stellar_strkey::ed25519::PublicKey([byte; 32]))
+            .to_string()
+    }
+
+    /// In-memory database holding one active and one deactivated employee.
+    fn fixture() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::initialise(&conn).unwrap();
+        db::insert_employee(&conn, &pubkey(1), &"11".repeat(32), 5_000).unwrap();
+        db::insert_employee(&conn, &pubkey(2), &"22".repeat(32), 7_000).unwrap();
+        db::deactivate_employee(&conn, &pubkey(2), 100).unwrap();
+        conn
+    }
+
+    #[test]
+    fn export_round_trips_through_a_file() {
+        let entries = export(&fixture()).unwrap();
+        assert_eq!(entries.len(), 1, "deactivated employees are not exported");
+        assert_eq!(entries[0].pubkey, pubkey(1));
+        assert_eq!(entries[0].salary, 5_000);
+
+        let path = std::env::temp_dir().join(format!(
+            "zk-payroll-commitments-{}.json",
+            std::process::id()
+        ));
+        write(&path, &entries).unwrap();
+        let read_back = read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read_back, entries);
+    }
+
+    #[test]
+    fn export_never_contains_blinding_material() {
+        let json = serde_json::to_string(&export(&fixture()).unwrap()).unwrap();
+
+        assert!(!json.contains(&"11".repeat(32)));
+        assert!(!json.to_lowercase().contains("blinding"));
+        let fields: Vec<String> = serde_json::from_str::<serde_json::Value>(&json).unwrap()[0]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        assert_eq!(fields, ["commitment", "pubkey", "salary"]);
+    }
+
+    #[test]
+    fn parse_rejects_secret_fields_and_duplicates() {
+        let entry = |extra: &str| {
+            format!(
+                r#"{{"pubkey":"{}","commitment":"0x{}","salary":1{}}}"#,
+                pubkey(1),
+                "ab".repeat(32),
+                extra
+            )
+        };
+
+        let with_blinding = format!("[{}]", entry(r#","blinding_factor":"00""#));
+        assert!(parse(&with_blinding).is_err());
+
+        let duplicated = format!("[{},{}]", entry(""), entry(""));
+        let err = parse(&duplicated).unwrap_err();
+        assert!(format!("{:#}", err).contains("more than once"));
+
+        let upper = format!("[{}]", entry("")).replace(&"ab".repeat(32), &"AB".repeat(32));
+        assert_eq!(
+            parse(&upper).unwrap()[0].commitment,
+            format!("0x{}", "ab".repeat(32))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed_commitment() {
+        let json = format!(
+            r#"[{{"pubkey":"{}","commitment":"0x1234","salary":1}}]"#,
+            pubkey(1)
+        );
+        let err = parse(&json).unwrap_err();
+        assert_eq!(crate::exit::exit_code(&err), crate::exit::EXIT_VALIDATION);
+    }
+
+    #[test]
+    fn diff_reports_match_mismatch_and_missing() {
+        let entry = |byte: u8| PortableCommitment {
+            pubkey: pubkey(byte),
+            commitment: format!("0x{}", hex::encode([byte; 32])),
+            salary: 1,
+        };
+        let entries = [entry(1), entry(2), entry(3)];
+        let args = RegistryArgs {
+            registry_id: REGISTRY,
+            company_id: 7,
+        };
+
+        let statuses = diff(&args, &entries, |contract, function, call_args| {
+            assert_eq!((contract, function), (REGISTRY, "find_employee"));
+            assert_eq!(call_args[0], ScVal::U64(7));
+            let stored =
+                |bytes: [u8; 32]| ScVal::Bytes(ScBytes(bytes.to_vec().try_into().unwrap()));
+            if call_args[1] == account_address(&pubkey(1)).unwrap() {
+                Ok(stored([1; 32]))
+            } else if call_args[1] == account_address(&pubkey(2)).unwrap() {
+                Ok(stored([9; 32]))
+            } else {
+                Ok(ScVal::Void)
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            statuses,
+            [
+                OnChainStatus::Match,
+                OnChainStatus::Mismatch { on_chain: [9; 32] },
+                OnChainStatus::Missing,
+            ]
+        );
+    }
+}
@@ -10,8 +10,10 @@
 //!
 //! ```text
 //! Reconciliation report for company: ACME_CORP
-//! Soroban RPC : https://soroban-testnet.stellar.org
+//! Network     : testnet (https://soroban-testnet.stellar.org)
+//! Passphrase  : Test SDF Network ; September 2015
 //! Contract    : CXXX...
+//! Token       : XLM (7 decimals)
 //! Ledgers     : 1000000 →
 //!
 //! ┌────────────────────────────────────────────────┬──────────────┬────────┬──────────────────────┬──────────────┐
@@ -20,47 +22,120 @@
 //! │ GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER… │       50.000 │      1 │ 2024-12-01T00:00:00Z │ ✓            │
 //! └────────────────────────────────────────────────┴──────────────┴────────┴──────────────────────┴──────────────┘
 //! ```
+//!
+//! # Token
+//!
+//! Amounts are shown in whole tokens.  With `--token-id`, the token
+//! contract's `decimals` and `symbol` are read through `simulateTransaction`
+//! so, e.g., a USDC payroll shows `12.346 USDC` rather than a mis-scaled XLM
+//! amount.  Without it, or if the read fails, amounts are shown as XLM with
+//! 7 decimals.
+//!
+//! # Networks
+//!
+//! The company's last-used network (see [`crate::network`]) is stored in the
+//! local database.  A run against a different network prints a warning
+//! first, since reconciling mainnet payments against a testnet roster (or
+//! the reverse) makes every payment look unknown.
+//!
+//! # Period labels
+//!
+//! With `--period-label` and `--start-date`, each period number is shown
+//! with the dates it covers, e.g. `3 → March 2024`; see [`crate::period`].
+//!
+//! # Spend anomalies
+//!
+//! With `--deviation-threshold-pct N`, payments are totalled per period and
+//! each period is compared with the period before it.  A period whose total
+//! is more than N% above the previous one is reported with a `WARN` and
+//! listed after the table, flagging possible fraud or data-entry errors.
+//!
+//! # Watch mode
+//!
+//! With `--watch` the command polls the RPC every `--interval` seconds,
+//! requesting only ledgers after the last one seen, and prints each new
+//! payment as it appears.  The cursor is persisted in the local database so
+//! a restarted watcher resumes where it left off.  Ctrl-C stops the loop
+//! after the current poll.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Cell, Table};
+use stellar_xdr::curr::ScVal;
 
+use crate::exit::{Classified, ErrorKind};
+use crate::network::Endpoint;
+use crate::period::{format_period, PeriodCalendar};
 use crate::{db, rpc};
 
 // ── Constants ─────────────────────────────────────────────────────────────────
 
-/// Default Soroban RPC endpoint (Stellar testnet).
-pub const DEFAULT_RPC_URL: &str = "https://soroban-testnet.stellar.org";
-
 /// 1 XLM = 10 000 000 stroops.
-const STROOPS_PER_XLM: i128 = 10_000_000;
+const XLM_DECIMALS: u32 = 7;
+
+/// Context for RPC fetch failures; tagged as a network error for the exit code.
+const FETCH_FAILED: &str = "Failed to fetch PayrollProcessed events from Soroban RPC";
 
 // ── Public entry point ────────────────────────────────────────────────────────
 
 /// Arguments for the `reconcile` command.
 pub struct ReconcileArgs<'a> {
-    pub rpc_url: &'a str,
+    pub endpoint: &'a Endpoint,
     pub contract_id: &'a str,
     pub company_id: &'a str,
+    /// Payment token contract whose `decimals` and `symbol` label amounts;
+    /// XLM when `None`.
+    pub token_id: Option<&'a str>,
     pub start_ledger: u32,
+    /// Only show payments for this payroll period.
+    pub period: Option<u32>,
+    /// `--data-dir` override for the local database location.
+    pub data_dir: Option<&'a Path>,
+    /// Renders period numbers as dates when set.
+    pub calendar: Option<PeriodCalendar>,
+    /// Flag periods whose total spend is more than this percentage above
+    /// the previous period's.
+    pub deviation_threshold_pct: Option<u32>,
 }
 
 /// Run the reconcile command: fetch events, cross-reference DB, print table.
 pub fn run(args: ReconcileArgs<'_>) -> Result<()> {
     // ── Print header ──────────────────────────────────────────────────────────
     println!("Reconciliation report for company: {}", args.company_id);
-    println!("Soroban RPC  : {}", args.rpc_url);
+    println!("Network      : {}", args.endpoint);
+    if let Some(network) = args.endpoint.network {
+        println!("Passphrase   : {}", network.passphrase());
+    }
     println!("Contract     : {}", args.contract_id);
+    let token = token_display(&args);
+    println!("Token        : {}", token);
     println!("Start ledger : {}", args.start_ledger);
+    if let Some(period) = args.period {
+        println!(
+            "Period       : {}",
+            format_period(period, args.calendar.as_ref())
+        );
+    }
     println!();
 
+    // ── Open local database ───────────────────────────────────────────────────
+    let conn_opt = open_and_check_network(&args)?;
+
     // ── Fetch on-chain events ─────────────────────────────────────────────────
     let events = rpc::fetch_payroll_events(
-        args.rpc_url,
+        &args.endpoint.rpc_url,
         args.contract_id,
         args.company_id,
         args.start_ledger,
+        args.period,
     )
-    .context("Failed to fetch PayrollProcessed events from Soroban RPC")?;
+    .with_context(|| Classified::new(ErrorKind::Network, FETCH_FAILED))?
+    .events;
 
     if events.is_empty() {
         println!(
@@ -70,14 +145,6 @@ pub fn run(args: ReconcileArgs<'_>) -> Result<()> {
         return Ok(());
     }
 
-    // ── Open local database ───────────────────────────────────────────────────
-    let db_path = db::db_path()?;
-    let conn_opt = if db_path.exists() {
-        Some(db::open(&db_path).context("Failed to open local database")?)
-    } else {
-        None
-    };
-
     // ── Build table ───────────────────────────────────────────────────────────
     let mut table = Table::new();
     table
@@ -85,7 +152,7 @@ pub fn run(args: ReconcileArgs<'_>) -> Result<()> {
         .apply_modifier(UTF8_ROUND_CORNERS)
         .set_header(vec![
             "Employee",
-            "Amount (XLM)",
+            format!("Amount ({})", token.symbol).as_str(),
             "Period",
             "Ledger closed at",
             "In local DB?",
@@ -100,12 +167,19 @@ pub fn run(args: ReconcileArgs<'_>) -> Result<()> {
 
         let in_db_mark = if in_db { "✓" } else { "✗" };
 
-        let narrative = build_narrative(&ev.employee, ev.amount, ev.period, &ev.ledger_closed_at);
+        let period = format_period(ev.period, args.calendar.as_ref());
+        let narrative = build_narrative(
+            &ev.employee,
+            ev.amount,
+            &token,
+            &period,
+            &ev.ledger_closed_at,
+        );
 
         table.add_row(vec![
             Cell::new(truncate(&ev.employee, 20)),
-            Cell::new(stroops_to_xlm_display(ev.amount)),
-            Cell::new(ev.period.to_string()),
+            Cell::new(token.format(ev.amount)),
+            Cell::new(&period),
             Cell::new(&ev.ledger_closed_at),
             Cell::new(in_db_mark),
             Cell::new(narrative),
@@ -134,31 +208,348 @@ pub fn run(args: ReconcileArgs<'_>) -> Result<()> {
         }
     }
 
+    // ── Spend anomalies ───────────────────────────────────────────────────────
+    if let Some(pct) = args.deviation_threshold_pct {
+        let anomalies = detect_spend_anomalies(&events, pct);
+        for anomaly in &anomalies {
+            eprintln!(
+                "WARN: Period {} spent {} — {:.1}% more than period {} ({}), above the {}% threshold.",
+                format_period(anomaly.period, args.calendar.as_ref()),
+                token.format(anomaly.total),
+                anomaly.deviation_pct(),
+                format_period(anomaly.previous_period, args.calendar.as_ref()),
+                token.format(anomaly.previous_total),
+                pct
+            );
+        }
+        println!(
+            "{} spend anomal{} above {}%.",
+            anomalies.len(),
+            if anomalies.len() == 1 { "y" } else { "ies" },
+            pct
+        );
+    }
+
     Ok(())
 }
 
+// ── Spend anomalies ───────────────────────────────────────────────────────────
+
+/// A period whose total spend rose too far above the previous period's.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpendAnomaly {
+    pub period: u32,
+    pub total: i128,
+    /// The nearest earlier period with payments.
+    pub previous_period: u32,
+    pub previous_total: i128,
+}
+
+impl SpendAnomaly {
+    /// How far `total` is above `previous_total`, in percent.
+    pub fn deviation_pct(&self) -> f64 {
+        (self.total - self.previous_total) as f64 * 100.0 / self.previous_total as f64
+    }
+}
+
+/// Total `events` per period and return every period whose total is more
+/// than `pct` percent above the previous period's, in period order.
+///
+/// Periods are compared with the nearest earlier period that has payments,
+/// so a gap in the numbering does not hide a jump.  A previous total of zero
+/// or less has no meaningful percentage and is never compared against.
+pub fn detect_spend_anomalies(events: &[rpc::PayrollEvent], pct: u32) -> Vec<SpendAnomaly> {
+    let mut totals: BTreeMap<u32, i128> = BTreeMap::new();
+    for ev in events {
+        *totals.entry(ev.period).or_default() += ev.amount;
+    }
+
+    let mut anomalies = Vec::new();
+    let mut previous: Option<(u32, i128)> = None;
+    for (&period, &total) in &totals {
+        if let Some((previous_period, previous_total)) = previous {
+            // total > previous * (1 + pct/100), kept in integers.
+            let limit = previous_total.saturating_mul(100 + i128::from(pct));
+            if previous_total > 0 && total.saturating_mul(100) > limit {
+                anomalies.push(SpendAnomaly {
+                    period,
+                    total,
+                    previous_period,
+                    previous_total,
+                });
+            }
+        }
+        previous = Some((period, total));
+    }
+    anomalies
+}
+
+// ── Watch mode ────────────────────────────────────────────────────────────────
+
+/// Source of `PayrollProcessed` events starting at a given ledger.
+///
+/// Abstracts the RPC transport so the polling loop can be tested offline.
+trait EventSource {
+    fn fetch(&mut self, start_ledger: u32) -> Result<rpc::EventScan>;
+}
+
+/// [`EventSource`] backed by the Soroban JSON-RPC `getEvents` method.
+struct RpcEventSource<'a> {
+    rpc_url: &'a str,
+    contract_id: &'a str,
+    company_id: &'a str,
+    period: Option<u32>,
+}
+
+impl EventSource for RpcEventSource<'_> {
+    fn fetch(&mut self, start_ledger: u32) -> Result<rpc::EventScan> {
+        rpc::fetch_payroll_events(
+            self.rpc_url,
+            self.contract_id,
+            self.company_id,
+            start_ledger,
+            self.period,
+        )
+    }
+}
+
+/// Run `reconcile --watch`: poll for new events every `interval` until Ctrl-C.
+pub fn watch(args: ReconcileArgs<'_>, interval: Duration) -> Result<()> {
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let stop = Arc::clone(&stop);
+        ctrlc::set_handler(move || stop.store(true, Ordering::SeqCst))
+            .context("Failed to install Ctrl-C handler")?;
+    }
+
+    // The cursor is only persisted when the local database exists.
+    let conn_opt = open_and_check_network(&args)?;
+
+    let stored = match &conn_opt {
+        Some(conn) => db::get_reconcile_cursor(conn, args.contract_id, args.company_id)?,
+        None => None,
+    };
+    let start = stored.map_or(args.start_ledger, |c| c.max(args.start_ledger));
+
+    println!("Watching payments for company: {}", args.company_id);
+    println!("Network      : {}", args.endpoint);
+    if let Some(network) = args.endpoint.network {
+        println!("Passphrase   : {}", network.passphrase());
+    }
+    println!("Contract     : {}", args.contract_id);
+    let token = token_display(&args);
+    println!("Token        : {}", token);
+    println!("Start ledger : {}", start);
+    println!("Interval     : {}s (Ctrl-C to stop)", interval.as_secs());
+    println!();
+
+    let mut source = RpcEventSource {
+        rpc_url: &args.endpoint.rpc_url,
+        contract_id: args.contract_id,
+        company_id: args.company_id,
+        period: args.period,
+    };
+
+    let cursor = watch_loop(&mut source, start, interval, &stop, |events, cursor| {
+        for ev in events {
+            let in_db = match &conn_opt {
+                Some(conn) => db::get_employee(conn, &ev.employee)?.is_some(),
+                None => false,
+            };
+            println!(
+                "[ledger {}] {} — in local DB: {}",
+                ev.ledger,
+                build_narrative(
+                    &ev.employee,
+                    ev.amount,
+                    &token,
+                    &format_period(ev.period, args.calendar.as_ref()),
+                    &ev.ledger_closed_at
+                ),
+                if in_db { "✓" } else { "✗" }
+            );
+            if !in_db {
+                eprintln!(
+                    "WARN: Employee {} appears in on-chain events but is not in the local database.",
+                    ev.employee
+                );
+            }
+        }
+        if let Some(conn) = &conn_opt {
+            check_salary_mismatches(conn, events)?;
+            db::set_reconcile_cursor(conn, args.contract_id, args.company_id, cursor)?;
+        }
+        Ok(())
+    })?;
+
+    println!();
+    println!("Stopped. Next scan will resume from ledger {}.", cursor);
+    Ok(())
+}
+
+/// Poll `source` until `stop` is set, calling `on_events` with the new events
+/// and the updated cursor whenever the cursor moves.  Returns the final
+/// cursor.
+///
+/// The cursor is the first ledger not yet scanned: after a poll it advances
+/// to one past the latest ledger the RPC reported, whether or not any of
+/// the scanned events belonged to the company.
+fn watch_loop<S, F>(
+    source: &mut S,
+    mut cursor: u32,
+    interval: Duration,
+    stop: &AtomicBool,
+    mut on_events: F,
+) -> Result<u32>
+where
+    S: EventSource,
+    F: FnMut(&[rpc::PayrollEvent], u32) -> Result<()>,
+{
+    while !stop.load(Ordering::SeqCst) {
+        let scan = source
+            .fetch(cursor)
+            .with_context(|| Classified::new(ErrorKind::Network, FETCH_FAILED))?;
+
+        let next = cursor.max(scan.latest_ledger.saturating_add(1));
+        if next != cursor || !scan.events.is_empty() {
+            cursor = next;
+            on_events(&scan.events, cursor)?;
+        }
+
+        // Sleep in short slices so Ctrl-C is honoured promptly.
+        let deadline = Instant::now() + interval;
+        while !stop.load(Ordering::SeqCst) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(100).min(interval));
+        }
+    }
+    Ok(cursor)
+}
+
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
+/// Open the local database if it exists, warning if the company was last
+/// reconciled against another network and recording this one.
+fn open_and_check_network(args: &ReconcileArgs<'_>) -> Result<Option<rusqlite::Connection>> {
+    let db_path = db::db_path(args.data_dir)?;
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    let conn = db::open(&db_path).context("Failed to open local database")?;
+    // Older databases predate the cursor and network tables.
+    db::initialise(&conn)?;
+
+    if let Some(previous) = record_network(&conn, args.company_id, args.endpoint)? {
+        eprintln!(
+            "WARN: Company '{}' was last reconciled against {} but this run uses {}. \
+             Check --network / --rpc-url.",
+            args.company_id,
+            previous,
+            args.endpoint.label()
+        );
+    }
+    Ok(Some(conn))
+}
+
+/// Store `endpoint` as the company's network, returning the previously
+/// stored one if it was different.
+fn record_network(
+    conn: &rusqlite::Connection,
+    company_id: &str,
+    endpoint: &Endpoint,
+) -> Result<Option<String>> {
+    let label = endpoint.label();
+    let previous = db::get_company_network(conn, company_id)?;
+    db::set_company_network(conn, company_id, &label)?;
+    Ok(previous.filter(|previous| *previous != label))
+}
+
+/// How token amounts are scaled and labelled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TokenDisplay {
+    decimals: u32,
+    symbol: String,
+}
+
+impl TokenDisplay {
+    fn xlm() -> Self {
+        Self {
+            decimals: XLM_DECIMALS,
+            symbol: "XLM".to_owned(),
+        }
+    }
+
+    /// Format a raw amount as "123.456 XLM".
+    fn format(&self, amount: i128) -> String {
+        let whole = amount as f64 / 10f64.powi(self.decimals as i32);
+        format!("{whole:.3} {}", self.symbol)
+    }
+}
+
+impl std::fmt::Display for TokenDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({} decimals)", self.symbol, self.decimals)
+    }
+}
+
+/// Display settings for `args.token_id`, read over RPC; XLM when no token
+/// is configured.
+fn token_display(args: &ReconcileArgs<'_>) -> TokenDisplay {
+    match args.token_id {
+        Some(token_id) => read_token_display(token_id, |function| {
+            rpc::simulate_read(&args.endpoint.rpc_url, token_id, function, vec![])
+        }),
+        None => TokenDisplay::xlm(),
+    }
+}
+
+/// Read `decimals` and `symbol` from `token_id` with `read`, falling back to
+/// XLM with a warning if either read fails.
+fn read_token_display<F>(token_id: &str, mut read: F) -> TokenDisplay
+where
+    F: FnMut(&str) -> Result<ScVal>,
+{
+    let mut query = || -> Result<TokenDisplay> {
+        let decimals = match read("decimals")? {
+            ScVal::U32(decimals) if decimals <= 38 => decimals,
+            other => bail!("decimals returned {:?}", other),
+        };
+        let symbol = match read("symbol")? {
+            ScVal::String(symbol) => symbol.to_utf8_string_lossy(),
+            other => bail!("symbol returned {:?}", other),
+        };
+        Ok(TokenDisplay { decimals, symbol })
+    };
+
+    query().unwrap_or_else(|err| {
+        eprintln!(
+            "WARN: Could not read decimals/symbol from token {}: {:#}. Showing amounts as XLM.",
+            token_id, err
+        );
+        TokenDisplay::xlm()
+    })
+}
+
 /// Build a human-readable payment narrative.
 ///
-/// E.g. "On 2024-12-01, paid GAAZ… 50.000 XLM (period 1)"
-fn build_narrative(employee: &str, amount: i128, period: u32, closed_at: &str) -> String {
+/// E.g. "On 2024-12-01, paid GAAZ… 50.000 XLM (period 1)", where `period` is
+/// already formatted by [`format_period`].
+fn build_narrative(
+    employee: &str,
+    amount: i128,
+    token: &TokenDisplay,
+    period: &str,
+    closed_at: &str,
+) -> String {
     let date = closed_at.split('T').next().unwrap_or(closed_at);
     format!(
         "On {}, paid {}… {} (period {})",
         date,
         &employee[..8.min(employee.len())],
-        stroops_to_xlm_display(amount),
+        token.format(amount),
         period,
     )
 }
 
-/// Format a stroop amount as "123.456 XLM".
-fn stroops_to_xlm_display(stroops: i128) -> String {
-    let xlm = stroops as f64 / STROOPS_PER_XLM as f64;
-    format!("{xlm:.3} XLM")
-}
-
 /// Truncate a string and append "…" if longer than `max` characters.
 fn truncate(s: &str, max: usize) -> String {
     if s.len() <= max {
@@ -191,6 +582,7 @@ fn check_salary_mismatches(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use stellar_xdr::curr::ScString;
 
     #[test]
     fn truncate_short_string_unchanged() {
@@ -207,17 +599,204 @@ mod tests {
     }
 
     #[test]
-    fn stroops_to_xlm_display_formats_correctly() {
-        assert_eq!(stroops_to_xlm_display(10_000_000), "1.000 XLM");
-        assert_eq!(stroops_to_xlm_display(50_000_000), "5.000 XLM");
-        assert_eq!(stroops_to_xlm_display(123_456_789), "12.346 XLM");
+    fn network_change_is_reported_once() {
+        use crate::network::Network;
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        db::initialise(&conn).unwrap();
+        let testnet = Endpoint::resolve(Some(Network::Testnet), None);
+        let mainnet = Endpoint::resolve(Some(Network::Mainnet), None);
+
+        assert_eq!(record_network(&conn, "ACME", &testnet).unwrap(), None);
+        assert_eq!(record_network(&conn, "ACME", &testnet).unwrap(), None);
+        assert_eq!(
+            record_network(&conn, "ACME", &mainnet).unwrap(),
+            Some("testnet".to_string())
+        );
+        assert_eq!(record_network(&conn, "ACME", &mainnet).unwrap(), None);
+        // Companies are tracked separately.
+        assert_eq!(record_network(&conn, "OTHER", &testnet).unwrap(), None);
+    }
+
+    #[test]
+    fn xlm_display_formats_correctly() {
+        let xlm = TokenDisplay::xlm();
+        assert_eq!(xlm.format(10_000_000), "1.000 XLM");
+        assert_eq!(xlm.format(50_000_000), "5.000 XLM");
+        assert_eq!(xlm.format(123_456_789), "12.346 XLM");
+    }
+
+    #[test]
+    fn token_metadata_scales_and_labels_amounts() {
+        let usdc = read_token_display("CUSDC", |function| match function {
+            "decimals" => Ok(ScVal::U32(6)),
+            "symbol" => Ok(ScVal::String(ScString("USDC".try_into().unwrap()))),
+            other => bail!("unexpected read of {}", other),
+        });
+        assert_eq!(usdc.to_string(), "USDC (6 decimals)");
+        assert_eq!(usdc.format(12_345_678), "12.346 USDC");
+        assert_eq!(usdc.format(1_000_000), "1.000 USDC");
+    }
+
+    #[test]
+    fn failed_token_read_falls_back_to_xlm() {
+        let unreachable = read_token_display("CUSDC", |_| bail!("connection refused"));
+        assert_eq!(unreachable, TokenDisplay::xlm());
+
+        let wrong_type = read_token_display("CUSDC", |_| Ok(ScVal::Void));
+        assert_eq!(wrong_type, TokenDisplay::xlm());
+        assert_eq!(wrong_type.format(10_000_000), "1.000 XLM");
+    }
+
+    /// Stub transport that returns nothing on the first poll and one event on
+    /// the second, then stops the loop.
+    struct StubSource<'a> {
+        requested: Vec<u32>,
+        stop: &'a AtomicBool,
+    }
+
+    impl EventSource for StubSource<'_> {
+        fn fetch(&mut self, start_ledger: u32) -> Result<rpc::EventScan> {
+            self.requested.push(start_ledger);
+            let (events, latest_ledger) = match self.requested.len() {
+                // Nothing new since the start.
+                1 => (vec![], 99),
+                // Only other companies' events: the cursor still advances.
+                2 => (vec![], 110),
+                3 => (
+                    vec![rpc::PayrollEvent {
+                        employee: "GAAZ1234".to_owned(),
+                        amount: 10_000_000,
+                        period: 1,
+                        ledger_closed_at: "2024-12-01T00:00:00Z".to_owned(),
+                        ledger: 120,
+                    }],
+                    125,
+                ),
+                _ => {
+                    self.stop.store(true, Ordering::SeqCst);
+                    (vec![], 125)
+                }
+            };
+            Ok(rpc::EventScan {
+                events,
+                latest_ledger,
+            })
+        }
+    }
+
+    #[test]
+    fn watch_loop_fetches_incrementally() {
+        let stop = AtomicBool::new(false);
+        let mut source = StubSource {
+            requested: vec![],
+            stop: &stop,
+        };
+        let mut batches = Vec::new();
+
+        let cursor = watch_loop(&mut source, 100, Duration::ZERO, &stop, |events, cursor| {
+            batches.push((events.len(), cursor));
+            Ok(())
+        })
+        .unwrap();
+
+        // The cursor follows the RPC's latest ledger, not the events seen.
+        assert_eq!(source.requested, vec![100, 100, 111, 126]);
+        assert_eq!(batches, vec![(0, 111), (1, 126)]);
+        assert_eq!(cursor, 126);
+    }
+
+    fn payment(period: u32, amount: i128) -> rpc::PayrollEvent {
+        rpc::PayrollEvent {
+            employee: "GAAZ1234".to_owned(),
+            amount,
+            period,
+            ledger_closed_at: "2024-12-01T00:00:00Z".to_owned(),
+            ledger: 120,
+        }
+    }
+
+    #[test]
+    fn spend_within_threshold_is_not_flagged() {
+        // Period 2 totals 11 000 against 10 000: exactly 10% up.
+        let events = [
+            payment(1, 6_000),
+            payment(1, 4_000),
+            payment(2, 5_000),
+            payment(2, 6_000),
+            payment(3, 9_000),
+        ];
+        assert!(detect_spend_anomalies(&events, 10).is_empty());
+    }
+
+    #[test]
+    fn spend_above_threshold_is_flagged() {
+        let events = [
+            payment(1, 10_000),
+            payment(2, 10_000),
+            payment(2, 2_000),
+            payment(3, 12_000),
+            // Compared with period 3, the last period that paid anyone.
+            payment(5, 15_000),
+        ];
+
+        let anomalies = detect_spend_anomalies(&events, 10);
+
+        assert_eq!(
+            anomalies,
+            vec![
+                SpendAnomaly {
+                    period: 2,
+                    total: 12_000,
+                    previous_period: 1,
+                    previous_total: 10_000,
+                },
+                SpendAnomaly {
+                    period: 5,
+                    total: 15_000,
+                    previous_period: 3,
+                    previous_total: 12_000,
+                },
+            ]
+        );
+        assert_eq!(anomalies[0].deviation_pct(), 20.0);
+        assert_eq!(anomalies[1].deviation_pct(), 25.0);
+    }
+
+    #[test]
+    fn single_period_has_no_anomalies() {
+        assert!(detect_spend_anomalies(&[payment(4, 1)], 0).is_empty());
+        assert!(detect_spend_anomalies(&[], 0).is_empty());
     }
 
     #[test]
     fn narrative_includes_date_and_period() {
-        let n = build_narrative("GAAZ1234", 10_000_000, 3, "2024-12-01T00:00:00Z");
+        let n = build_narrative(
+            "GAAZ1234",
+            10_000_000,
+            &TokenDisplay::xlm(),
+            "3",
+            "2024-12-01T00:00:00Z",
+        );
         assert!(n.contains("2024-12-01"));
         assert!(n.contains("period 3"));
         assert!(n.contains("1.000 XLM"));
     }
+
+    #[test]
+    fn narrative_shows_period_label() {
+        let calendar = PeriodCalendar {
+            granularity: crate::period::Granularity::Monthly,
+            start: "2024-01-01".parse().unwrap(),
+        };
+        let period = format_period(3, Some(&calendar));
+        let n = build_narrative(
+            "GAAZ1234",
+            10_000_000,
+            &TokenDisplay::xlm(),
+            &period,
+            "2024-03-28T00:00:00Z",
+        );
+        assert!(n.contains("(period 3 → March 2024)"));
+    }
 }
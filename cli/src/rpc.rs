@@ -2,27 +2,61 @@
 //!
 //! Calls the `getEvents` RPC method and returns strongly-typed
 //! [`PayrollEvent`] values for every confirmed payment belonging to a given
-//! company.
+//! company.  [`wait_for_transaction`] polls `getTransaction` for a submitted
+//! transaction and decodes the same events from its result metadata.
+//! [`simulate_read`] calls a read-only contract function through
+//! `simulateTransaction`, e.g. a token's `decimals` and `symbol` or an
+//! account's `balance`.
 //!
 //! # XDR layout produced by `payment_executor`
 //!
 //! ```text
-//! topics[0]  ScVal::Symbol("PayrollProcessed")
-//! topics[1]  ScVal::Symbol(<company_id>)
+//! topics[0]  ScVal::Symbol("PayrollProcessed_v1")
+//! topics[1]  ScVal::U64(<company_id>)
 //! data       ScVal::Vec([
 //!                ScVal::Address(<employee>),   // Stellar account address
 //!                ScVal::I128(Int128Parts),      // amount in stroops
 //!                ScVal::U32(<period>),
 //!            ])
 //! ```
+//!
+//! Company IDs are compared as decimal strings.  A `Symbol` company topic,
+//! as emitted by earlier deployments, is still accepted.
+//!
+//! The event name carries a layout version.  Only the names in
+//! [`PAYROLL_EVENT_NAMES`] are decoded, so events from a newer contract with
+//! an unknown layout are skipped rather than misread.
+
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, Context, Result};
 use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use stellar_xdr::curr::{
-    AccountId, Int128Parts, Limits, PublicKey, ReadXdr, ScAddress, ScVal, ScVec,
+    AccountId, ContractEventBody, Hash, HostFunction, Int128Parts, InvokeContractArgs,
+    InvokeHostFunctionOp, Limits, Memo, MuxedAccount, Operation, OperationBody, Preconditions,
+    PublicKey, ReadXdr, ScAddress, ScSymbol, ScVal, ScVec, SequenceNumber, Transaction,
+    TransactionEnvelope, TransactionExt, TransactionMeta, TransactionV1Envelope, Uint256, WriteXdr,
 };
 
+use crate::exit::{Classified, ErrorKind};
+
+/// First delay between `getTransaction` polls while the transaction is
+/// `NOT_FOUND`; doubled after every miss up to [`MAX_POLL_BACKOFF`].
+const INITIAL_POLL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Upper bound on the delay between `getTransaction` polls.
+const MAX_POLL_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Events requested per `getEvents` page.  A shorter page is the last one.
+const EVENTS_PAGE_LIMIT: usize = 200;
+
+/// Event names decoded as `PayrollProcessed`: the unversioned name emitted
+/// before topics carried a version, then every versioned name sharing the
+/// layout above.
+pub const PAYROLL_EVENT_NAMES: &[&str] = &["PayrollProcessed", "PayrollProcessed_v1"];
+
 // ── Public types ──────────────────────────────────────────────────────────────
 
 /// A decoded `PayrollProcessed` event emitted by `payment_executor`.
@@ -36,68 +70,281 @@ pub struct PayrollEvent {
     pub period: u32,
     /// ISO-8601 timestamp from the ledger that closed the event.
     pub ledger_closed_at: String,
+    /// Sequence number of the ledger the event was emitted in.
+    pub ledger: u32,
+}
+
+/// Result of scanning a contract's `PayrollProcessed` events.
+#[derive(Debug, Clone)]
+pub struct EventScan {
+    /// Matching events, oldest first.
+    pub events: Vec<PayrollEvent>,
+    /// Latest ledger the RPC had ingested; every ledger up to it was scanned.
+    pub latest_ledger: u32,
+}
+
+/// Final status of a transaction as reported by `getTransaction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxStatus {
+    Success,
+    Failed,
+}
+
+/// Outcome of a submitted transaction once it has been included in a ledger.
+#[derive(Debug, Clone)]
+pub struct TxResult {
+    pub status: TxStatus,
+    /// Sequence number of the ledger the transaction was included in.
+    pub ledger: u32,
+    /// `PayrollProcessed` events emitted by the transaction, for any company.
+    /// Always empty for a failed transaction.
+    pub events: Vec<PayrollEvent>,
 }
 
 // ── JSON-RPC response types ───────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
-struct RpcResponse {
-    result: Option<GetEventsResult>,
+struct RpcResponse<T> {
+    result: Option<T>,
     error: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
 struct GetEventsResult {
     events: Vec<RawEvent>,
+    #[serde(rename = "latestLedger")]
+    latest_ledger: u32,
+    /// Position after this page, passed back to fetch the next one.
+    #[serde(default)]
+    cursor: Option<String>,
 }
 
+/// One entry of a `getEvents` result.  `topic` and `value` hold base64 XDR
+/// `ScVal`s.
 #[derive(Debug, Deserialize)]
-struct RawEvent {
+pub struct RawEvent {
+    pub ledger: u32,
     #[serde(rename = "ledgerClosedAt")]
-    ledger_closed_at: String,
-    topic: Vec<String>,
-    value: String,
+    pub ledger_closed_at: String,
+    pub topic: Vec<String>,
+    pub value: String,
     #[serde(rename = "inSuccessfulContractCall")]
-    in_successful_contract_call: bool,
+    pub in_successful_contract_call: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetHealthResult {
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimulateTransactionResult {
+    /// Set instead of `results` when the simulated call failed.
+    error: Option<String>,
+    #[serde(default)]
+    results: Vec<SimulateHostFunctionResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimulateHostFunctionResult {
+    /// Base64 XDR `ScVal` returned by the contract function.
+    xdr: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetTransactionResult {
+    status: String,
+    ledger: Option<u32>,
+    /// Unix timestamp (seconds, as a string) of the ledger close.
+    #[serde(rename = "createdAt")]
+    created_at: Option<String>,
+    #[serde(rename = "resultMetaXdr")]
+    result_meta_xdr: Option<String>,
 }
 
 // ── Public API ────────────────────────────────────────────────────────────────
 
 /// Fetch all `PayrollProcessed` events for `company_id` from `contract_id`.
 ///
+/// Follows `getEvents` pagination until a page comes back short, so scans
+/// with more than one page of contract events are complete.
+///
 /// # Arguments
 /// * `rpc_url`      — Soroban RPC endpoint (e.g. `https://soroban-testnet.stellar.org`).
 /// * `contract_id`  — Strkey contract address (C… address).
 /// * `company_id`   — Company symbol used as the second event topic.
 /// * `start_ledger` — First ledger sequence to include in the scan.
+/// * `period`       — If set, only payments for this payroll period are
+///   returned.  `getEvents` cannot filter on event data, so this is applied
+///   after decoding.
 pub fn fetch_payroll_events(
     rpc_url: &str,
     contract_id: &str,
     company_id: &str,
     start_ledger: u32,
-) -> Result<Vec<PayrollEvent>> {
+    period: Option<u32>,
+) -> Result<EventScan> {
+    let client = http_client()?;
+    let (raw_events, latest_ledger) = collect_event_pages(contract_id, start_ledger, |body| {
+        rpc_call(&client, rpc_url, body)
+    })?;
+    Ok(EventScan {
+        events: decode_payroll_events(&raw_events, company_id, period)?,
+        latest_ledger,
+    })
+}
+
+/// Request `getEvents` pages through `call` until one holds fewer than
+/// [`EVENTS_PAGE_LIMIT`] events.  Returns every event and the latest ledger
+/// the RPC reported.
+fn collect_event_pages<F>(
+    contract_id: &str,
+    start_ledger: u32,
+    mut call: F,
+) -> Result<(Vec<RawEvent>, u32)>
+where
+    F: FnMut(&serde_json::Value) -> Result<Option<GetEventsResult>>,
+{
+    let filters = serde_json::json!([{
+        "type": "contract",
+        "contractIds": [contract_id]
+    }]);
+    let mut params = serde_json::json!({
+        "startLedger": start_ledger,
+        "filters": filters,
+        "pagination": { "limit": EVENTS_PAGE_LIMIT }
+    });
+
+    let mut events = Vec::new();
+    loop {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getEvents",
+            "params": params
+        });
+        let Some(page) = call(&body)? else {
+            // Nothing ingested yet: everything before the start is scanned.
+            return Ok((events, start_ledger.saturating_sub(1)));
+        };
+        let full = page.events.len() >= EVENTS_PAGE_LIMIT;
+        events.extend(page.events);
+        if !full {
+            return Ok((events, page.latest_ledger));
+        }
+        let cursor = page
+            .cursor
+            .context("Soroban RPC returned a full getEvents page without a cursor")?;
+        params = serde_json::json!({
+            "filters": filters,
+            "pagination": { "cursor": cursor, "limit": EVENTS_PAGE_LIMIT }
+        });
+    }
+}
+
+/// Poll `getTransaction` until `tx_hash` succeeds, fails, or `timeout` elapses.
+///
+/// While the RPC reports `NOT_FOUND` the delay between polls starts at
+/// 500 ms and doubles up to 5 s.  On `SUCCESS` the `PayrollProcessed` events
+/// in the transaction's result metadata are decoded into [`TxResult::events`].
+///
+/// # Arguments
+/// * `rpc_url` — Soroban RPC endpoint.
+/// * `tx_hash` — Hex-encoded transaction hash returned by `sendTransaction`.
+/// * `timeout` — How long to keep polling before giving up.
+pub fn wait_for_transaction(rpc_url: &str, tx_hash: &str, timeout: Duration) -> Result<TxResult> {
     let body = serde_json::json!({
         "jsonrpc": "2.0",
         "id": 1,
-        "method": "getEvents",
-        "params": {
-            "startLedger": start_ledger,
-            "filters": [{
-                "type": "contract",
-                "contractIds": [contract_id]
-            }],
-            "pagination": { "limit": 200 }
+        "method": "getTransaction",
+        "params": { "hash": tx_hash }
+    });
+
+    let client = http_client()?;
+    let deadline = Instant::now() + timeout;
+    let mut backoff = INITIAL_POLL_BACKOFF;
+
+    loop {
+        let result: Option<GetTransactionResult> = rpc_call(&client, rpc_url, &body)?;
+        let result = result.context("Soroban RPC returned no result for getTransaction")?;
+        if let Some(tx) = decode_transaction_result(&result)? {
+            return Ok(tx);
         }
+
+        let now = Instant::now();
+        if now >= deadline {
+            bail!(Classified::new(
+                ErrorKind::Network,
+                format!(
+                    "Timed out after {}s waiting for transaction {} to be confirmed",
+                    timeout.as_secs(),
+                    tx_hash
+                ),
+            ));
+        }
+        std::thread::sleep(backoff.min(deadline - now));
+        backoff = (backoff * 2).min(MAX_POLL_BACKOFF);
+    }
+}
+
+/// Call `getHealth` and return the status the node reports (`"healthy"`
+/// once it is ready to serve requests).
+pub fn get_health(rpc_url: &str) -> Result<String> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "getHealth"
+    });
+
+    let client = http_client()?;
+    let result: Option<GetHealthResult> = rpc_call(&client, rpc_url, &body)?;
+    result
+        .map(|r| r.status)
+        .context("Soroban RPC returned no result for getHealth")
+}
+
+/// Call the read-only `function` of `contract_id` with `args` through
+/// `simulateTransaction` and return its result.
+///
+/// Nothing is signed or submitted: the simulated transaction's source is the
+/// all-zero account, which the RPC accepts for reads.
+pub fn simulate_read(
+    rpc_url: &str,
+    contract_id: &str,
+    function: &str,
+    args: Vec<ScVal>,
+) -> Result<ScVal> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "simulateTransaction",
+        "params": { "transaction": build_read_transaction(contract_id, function, args)? }
     });
 
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
+    let client = http_client()?;
+    let result: Option<SimulateTransactionResult> = rpc_call(&client, rpc_url, &body)?;
+    let result = result.context("Soroban RPC returned no result for simulateTransaction")?;
+    decode_simulate_result(result, function)
+}
+
+// ── HTTP helpers ──────────────────────────────────────────────────────────────
+
+fn http_client() -> Result<reqwest::blocking::Client> {
+    reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(30))
         .build()
-        .context("Failed to build HTTP client")?;
+        .context("Failed to build HTTP client")
+}
 
-    let resp: RpcResponse = client
+/// POST a JSON-RPC request and return its `result`, failing on an RPC error.
+fn rpc_call<T: DeserializeOwned>(
+    client: &reqwest::blocking::Client,
+    rpc_url: &str,
+    body: &serde_json::Value,
+) -> Result<Option<T>> {
+    let resp: RpcResponse<T> = client
         .post(rpc_url)
-        .json(&body)
+        .json(body)
         .send()
         .context("Failed to reach Soroban RPC — check your --rpc-url")?
         .json()
@@ -106,58 +353,220 @@ pub fn fetch_payroll_events(
     if let Some(err) = resp.error {
         bail!("Soroban RPC error: {}", err);
     }
+    Ok(resp.result)
+}
+
+// ── XDR encoding helpers ──────────────────────────────────────────────────────
 
-    let raw_events = resp.result.map(|r| r.events).unwrap_or_default();
+/// Base64 `TransactionEnvelope` invoking `function` on `contract_id` with
+/// `args`, for `simulateTransaction`.
+fn build_read_transaction(contract_id: &str, function: &str, args: Vec<ScVal>) -> Result<String> {
+    let contract = stellar_strkey::Contract::from_string(contract_id)
+        .with_context(|| format!("'{}' is not a contract (C...) address", contract_id))?;
+    let function_name = ScSymbol(
+        function
+            .try_into()
+            .with_context(|| format!("'{}' is not a valid function name", function))?,
+    );
 
+    let op = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                contract_address: ScAddress::Contract(Hash(contract.0)),
+                function_name,
+                args: args
+                    .try_into()
+                    .context("Too many contract call arguments")?,
+            }),
+            auth: Default::default(),
+        }),
+    };
+    let tx = Transaction {
+        source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+        fee: 100,
+        seq_num: SequenceNumber(0),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![op].try_into()?,
+        ext: TransactionExt::V0,
+    };
+    let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx,
+        signatures: Default::default(),
+    });
+    let xdr = envelope
+        .to_xdr(Limits::none())
+        .context("Failed to XDR-encode transaction")?;
+    Ok(B64.encode(xdr))
+}
+
+// ── XDR decoding helpers ──────────────────────────────────────────────────────
+
+/// Decode the `PayrollProcessed` events for `company_id` (and `period`, if
+/// given) out of a `getEvents` page, skipping events from failed calls.
+fn decode_payroll_events(
+    raw_events: &[RawEvent],
+    company_id: &str,
+    period: Option<u32>,
+) -> Result<Vec<PayrollEvent>> {
     let mut out = Vec::new();
     for ev in raw_events {
         if !ev.in_successful_contract_call {
             continue;
         }
         // Filter to PayrollProcessed events for the requested company.
-        if let Some(event) = try_decode_payroll_event(&ev, company_id)? {
-            out.push(event);
+        if let Some(event) = try_decode_payroll_event(ev, company_id)? {
+            if period.is_none_or(|p| event.period == p) {
+                out.push(event);
+            }
         }
     }
     Ok(out)
 }
 
-// ── XDR decoding helpers ──────────────────────────────────────────────────────
+/// Interpret a `getTransaction` result.
+///
+/// Returns `Ok(None)` while the transaction is still `NOT_FOUND`.
+fn decode_transaction_result(result: &GetTransactionResult) -> Result<Option<TxResult>> {
+    let status = match result.status.as_str() {
+        "NOT_FOUND" => return Ok(None),
+        "SUCCESS" => TxStatus::Success,
+        "FAILED" => TxStatus::Failed,
+        other => bail!("Unexpected getTransaction status '{}'", other),
+    };
+    let ledger = result
+        .ledger
+        .context("getTransaction result is missing the ledger sequence")?;
+
+    let mut events = Vec::new();
+    if status == TxStatus::Success {
+        let meta_b64 = result
+            .result_meta_xdr
+            .as_deref()
+            .context("getTransaction result is missing resultMetaXdr")?;
+        let ledger_closed_at = result.created_at.clone().unwrap_or_default();
+        for (topics, data) in decode_meta_events(meta_b64)? {
+            if payroll_event_company(&topics)?.is_none() {
+                continue;
+            }
+            let (employee, amount, period) = decode_payroll_data(data)?;
+            events.push(PayrollEvent {
+                employee,
+                amount,
+                period,
+                ledger_closed_at: ledger_closed_at.clone(),
+                ledger,
+            });
+        }
+    }
+
+    Ok(Some(TxResult {
+        status,
+        ledger,
+        events,
+    }))
+}
+
+/// The return value of a simulated read, or the simulation's error.
+fn decode_simulate_result(result: SimulateTransactionResult, function: &str) -> Result<ScVal> {
+    if let Some(err) = result.error {
+        bail!("Simulating {} failed: {}", function, err);
+    }
+    let first = result
+        .results
+        .first()
+        .with_context(|| format!("Simulating {} returned no result", function))?;
+    decode_scval(&first.xdr).with_context(|| format!("Failed to decode {} result", function))
+}
+
+/// Extract the `(topics, data)` of every contract event in a base64
+/// `TransactionMeta`.
+fn decode_meta_events(b64: &str) -> Result<Vec<(Vec<ScVal>, ScVal)>> {
+    let bytes = B64
+        .decode(b64)
+        .context("Failed to base64-decode resultMetaXdr")?;
+    let meta = TransactionMeta::from_xdr(&bytes, Limits::none())
+        .context("Failed to XDR-decode TransactionMeta")?;
+
+    let soroban_meta = match meta {
+        TransactionMeta::V3(v3) => v3.soroban_meta,
+        _ => None,
+    };
+    Ok(soroban_meta
+        .map(|m| {
+            m.events
+                .iter()
+                .map(|ev| match &ev.body {
+                    ContractEventBody::V0(body) => (body.topics.to_vec(), body.data.clone()),
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
 
 /// Try to decode a raw RPC event as a `PayrollProcessed` event for `company_id`.
 ///
 /// Returns `Ok(None)` when the event is a different type or a different company.
-fn try_decode_payroll_event(ev: &RawEvent, company_id: &str) -> Result<Option<PayrollEvent>> {
+pub fn try_decode_payroll_event(ev: &RawEvent, company_id: &str) -> Result<Option<PayrollEvent>> {
     if ev.topic.len() < 2 {
         return Ok(None);
     }
 
-    // Topic 0 must be Symbol("PayrollProcessed").
-    let topic0 = decode_scval(&ev.topic[0]).context("Failed to decode event topic[0]")?;
-    let event_name = match &topic0 {
-        ScVal::Symbol(s) => std::str::from_utf8(s.as_slice())
-            .context("Event name is not valid UTF-8")?
-            .to_owned(),
+    let topics = [
+        decode_scval(&ev.topic[0]).context("Failed to decode event topic[0]")?,
+        decode_scval(&ev.topic[1]).context("Failed to decode event topic[1]")?,
+    ];
+    match payroll_event_company(&topics)? {
+        Some(event_company) if event_company == company_id => {}
         _ => return Ok(None),
-    };
-    if event_name != "PayrollProcessed" {
+    }
+
+    let data = decode_scval(&ev.value).context("Failed to decode event data")?;
+    let (employee, amount, period) = decode_payroll_data(data)?;
+
+    Ok(Some(PayrollEvent {
+        employee,
+        amount,
+        period,
+        ledger_closed_at: ev.ledger_closed_at.clone(),
+        ledger: ev.ledger,
+    }))
+}
+
+/// Return the company ID of a `PayrollProcessed` event, or `None` when the
+/// topics belong to a different event.
+fn payroll_event_company(topics: &[ScVal]) -> Result<Option<String>> {
+    if topics.len() < 2 {
         return Ok(None);
     }
 
-    // Topic 1 must be Symbol(<company_id>) matching the requested company.
-    let topic1 = decode_scval(&ev.topic[1]).context("Failed to decode event topic[1]")?;
-    let event_company = match &topic1 {
+    // Topic 0 must be one of the known PayrollProcessed names.
+    let event_name = match &topics[0] {
         ScVal::Symbol(s) => std::str::from_utf8(s.as_slice())
-            .context("Company ID is not valid UTF-8")?
+            .context("Event name is not valid UTF-8")?
             .to_owned(),
         _ => return Ok(None),
     };
-    if event_company != company_id {
+    if !PAYROLL_EVENT_NAMES.contains(&event_name.as_str()) {
         return Ok(None);
     }
 
-    // Data is Vec([Address(employee), I128(amount), U32(period)]).
-    let data = decode_scval(&ev.value).context("Failed to decode event data")?;
+    // Topic 1 is U64(<company_id>), or Symbol(<company_id>) from older
+    // deployments.
+    match &topics[1] {
+        ScVal::U64(id) => Ok(Some(id.to_string())),
+        ScVal::Symbol(s) => Ok(Some(
+            std::str::from_utf8(s.as_slice())
+                .context("Company ID is not valid UTF-8")?
+                .to_owned(),
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// Decode `PayrollProcessed` data: `Vec([Address(employee), I128(amount), U32(period)])`.
+fn decode_payroll_data(data: ScVal) -> Result<(String, i128, u32)> {
     let vec = match data {
         ScVal::Vec(Some(v)) => v,
         _ => bail!("Expected ScVal::Vec for PayrollProcessed data"),
@@ -166,13 +575,7 @@ fn try_decode_payroll_event(ev: &RawEvent, company_id: &str) -> Result<Option<Pa
     let employee = extract_address(&vec, 0)?;
     let amount = extract_i128(&vec, 1)?;
     let period = extract_u32(&vec, 2)?;
-
-    Ok(Some(PayrollEvent {
-        employee,
-        amount,
-        period,
-        ledger_closed_at: ev.ledger_closed_at.clone(),
-    }))
+    Ok((employee, amount, period))
 }
 
 fn decode_scval(b64: &str) -> Result<ScVal> {
@@ -192,12 +595,29 @@ fn extract_address(vec: &ScVec, idx: usize) -> Result<String> {
 
 fn extract_i128(vec: &ScVec, idx: usize) -> Result<i128> {
     match vec.get(idx) {
-        Some(ScVal::I128(Int128Parts { hi, lo })) => Ok(((*hi as i128) << 64) | (*lo as i128)),
+        Some(ScVal::I128(parts)) => Ok(int128_parts_to_i128(parts)),
         Some(other) => bail!("Expected ScVal::I128 at index {idx}, got {:?}", other),
         None => bail!("Missing element at index {idx} in event data Vec"),
     }
 }
 
+/// The `i128` held by `val`, or `None` if it is another type.
+pub fn scval_to_i128(val: &ScVal) -> Option<i128> {
+    match val {
+        ScVal::I128(parts) => Some(int128_parts_to_i128(parts)),
+        _ => None,
+    }
+}
+
+/// Reassemble an `i128` from its XDR halves.
+///
+/// `hi` carries the sign and the upper 64 bits; `lo` is the raw lower 64
+/// bits and must be zero-extended (never sign-extended) before the OR, or a
+/// set top bit in `lo` would clobber the upper half.
+fn int128_parts_to_i128(parts: &Int128Parts) -> i128 {
+    (i128::from(parts.hi) << 64) | i128::from(parts.lo)
+}
+
 fn extract_u32(vec: &ScVec, idx: usize) -> Result<u32> {
     match vec.get(idx) {
         Some(ScVal::U32(v)) => Ok(*v),
@@ -207,7 +627,7 @@ fn extract_u32(vec: &ScVec, idx: usize) -> Result<u32> {
 }
 
 /// Convert a Soroban `ScAddress` to a Stellar G-address StrKey string.
-fn scaddress_to_strkey(addr: &ScAddress) -> Result<String> {
+pub fn scaddress_to_strkey(addr: &ScAddress) -> Result<String> {
     match addr {
         ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(bytes))) => {
             let pk = stellar_strkey::ed25519::PublicKey(bytes.0);
@@ -241,4 +661,247 @@ mod tests {
             other => panic!("expected Symbol, got {:?}", other),
         }
     }
+
+    /// `getTransaction` SUCCESS response for an `execute_payment`
+    /// call: a token `transfer` event followed by `PayrollProcessed`.
+    const GET_TRANSACTION_SUCCESS: &str =
+        include_str!("../tests/fixtures/get_transaction_success.json");
+
+    #[test]
+    fn decode_get_transaction_success_response() {
+        let resp: RpcResponse<GetTransactionResult> =
+            serde_json::from_str(GET_TRANSACTION_SUCCESS).unwrap();
+        let tx = decode_transaction_result(&resp.result.unwrap())
+            .unwrap()
+            .expect("SUCCESS must be final");
+
+        assert_eq!(tx.status, TxStatus::Success);
+        assert_eq!(tx.ledger, 1_204_510);
+        // The token transfer event is skipped; only PayrollProcessed is decoded.
+        assert_eq!(tx.events.len(), 1);
+        let ev = &tx.events[0];
+        assert_eq!(
+            ev.employee,
+            "GADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOZPI"
+        );
+        assert_eq!(ev.amount, 500_000_000);
+        assert_eq!(ev.period, 3);
+        assert_eq!(ev.ledger, 1_204_510);
+        assert_eq!(ev.ledger_closed_at, "1733011250");
+    }
+
+    #[test]
+    fn not_found_is_not_final_and_failed_has_no_events() {
+        let pending: GetTransactionResult =
+            serde_json::from_str(r#"{"status":"NOT_FOUND","latestLedger":1204512}"#).unwrap();
+        assert!(decode_transaction_result(&pending).unwrap().is_none());
+
+        let failed: GetTransactionResult = serde_json::from_str(
+            r#"{"status":"FAILED","ledger":1204510,"createdAt":"1733011250"}"#,
+        )
+        .unwrap();
+        let tx = decode_transaction_result(&failed).unwrap().unwrap();
+        assert_eq!(tx.status, TxStatus::Failed);
+        assert!(tx.events.is_empty());
+    }
+
+    #[test]
+    fn event_pages_are_followed_until_a_short_page() {
+        let mut requests = Vec::new();
+        let (events, latest) = collect_event_pages("CCONTRACT", 100, |body| {
+            requests.push(body["params"].clone());
+            let count = if requests.len() < 3 {
+                EVENTS_PAGE_LIMIT
+            } else {
+                5
+            };
+            Ok(Some(GetEventsResult {
+                events: (0..count)
+                    .map(|_| raw_payroll_event("ACME_CORP", 1, 120))
+                    .collect(),
+                latest_ledger: 150,
+                cursor: Some(format!("page-{}", requests.len())),
+            }))
+        })
+        .unwrap();
+
+        assert_eq!(events.len(), 2 * EVENTS_PAGE_LIMIT + 5);
+        assert_eq!(latest, 150);
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0]["startLedger"], 100);
+        assert!(requests[1].get("startLedger").is_none());
+        assert_eq!(requests[1]["pagination"]["cursor"], "page-1");
+        assert_eq!(requests[2]["pagination"]["cursor"], "page-2");
+    }
+
+    #[test]
+    fn full_page_without_cursor_is_an_error() {
+        let result = collect_event_pages("CCONTRACT", 100, |_| {
+            Ok(Some(GetEventsResult {
+                events: (0..EVENTS_PAGE_LIMIT)
+                    .map(|_| raw_payroll_event("ACME_CORP", 1, 120))
+                    .collect(),
+                latest_ledger: 150,
+                cursor: None,
+            }))
+        });
+        assert!(result.is_err());
+    }
+
+    /// Build a `getEvents` entry for a `PayrollProcessed` event.
+    fn raw_payroll_event(company_id: &str, period: u32, ledger: u32) -> RawEvent {
+        use stellar_xdr::curr::{Uint256, WriteXdr};
+        let encode = |v: ScVal| B64.encode(v.to_xdr(Limits::none()).unwrap());
+        let employee = ScVal::Address(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256([7u8; 32])),
+        )));
+        let data = ScVal::Vec(Some(
+            vec![
+                employee,
+                ScVal::I128(Int128Parts { hi: 0, lo: 500 }),
+                ScVal::U32(period),
+            ]
+            .try_into()
+            .unwrap(),
+        ));
+        RawEvent {
+            ledger,
+            ledger_closed_at: "2024-12-01T00:00:00Z".to_owned(),
+            topic: vec![
+                encode(ScVal::Symbol("PayrollProcessed".try_into().unwrap())),
+                encode(ScVal::Symbol(company_id.try_into().unwrap())),
+            ],
+            value: encode(data),
+            in_successful_contract_call: true,
+        }
+    }
+
+    #[test]
+    fn versioned_event_names_decode_only_when_known() {
+        use stellar_xdr::curr::WriteXdr;
+        let named = |name: &str| {
+            let mut ev = raw_payroll_event("ACME_CORP", 1, 100);
+            ev.topic[0] = B64.encode(
+                ScVal::Symbol(name.try_into().unwrap())
+                    .to_xdr(Limits::none())
+                    .unwrap(),
+            );
+            ev
+        };
+
+        let v1 = named("PayrollProcessed_v1");
+        assert!(try_decode_payroll_event(&v1, "ACME_CORP")
+            .unwrap()
+            .is_some());
+
+        // A future layout is skipped until its name is added.
+        let v2 = named("PayrollProcessed_v2");
+        assert!(try_decode_payroll_event(&v2, "ACME_CORP")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn u64_company_topic_matches_decimal_company_id() {
+        use stellar_xdr::curr::WriteXdr;
+        let mut ev = raw_payroll_event("ACME_CORP", 1, 100);
+        ev.topic[1] = B64.encode(ScVal::U64(42).to_xdr(Limits::none()).unwrap());
+
+        assert!(try_decode_payroll_event(&ev, "42").unwrap().is_some());
+        assert!(try_decode_payroll_event(&ev, "ACME_CORP")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn period_filter_excludes_other_periods() {
+        let raw = vec![
+            raw_payroll_event("ACME_CORP", 1, 100),
+            raw_payroll_event("ACME_CORP", 2, 200),
+            raw_payroll_event("ACME_CORP", 1, 300),
+        ];
+
+        let all = decode_payroll_events(&raw, "ACME_CORP", None).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let period_2 = decode_payroll_events(&raw, "ACME_CORP", Some(2)).unwrap();
+        assert_eq!(period_2.len(), 1);
+        assert_eq!(period_2[0].period, 2);
+        assert_eq!(period_2[0].ledger, 200);
+    }
+
+    /// Split an `i128` the way the XDR encoder does.
+    fn to_parts(v: i128) -> Int128Parts {
+        Int128Parts {
+            hi: (v >> 64) as i64,
+            lo: v as u64,
+        }
+    }
+
+    #[test]
+    fn int128_parts_roundtrip_edge_cases() {
+        for v in [
+            0,
+            500_000_000,
+            -1,
+            -500_000_000,
+            i128::MIN,
+            i128::MAX,
+            // High bit set in both halves.
+            (i128::from(i64::MIN | 0x1234) << 64) | i128::from(0x8000_0000_0000_0001u64),
+            // High bit set in `lo` only, positive overall.
+            i128::from(u64::MAX),
+        ] {
+            assert_eq!(int128_parts_to_i128(&to_parts(v)), v, "roundtrip of {v}");
+        }
+    }
+
+    #[test]
+    fn extract_i128_decodes_negative_amounts() {
+        let vec: ScVec = vec![ScVal::I128(to_parts(-42))].try_into().unwrap();
+        assert_eq!(extract_i128(&vec, 0).unwrap(), -42);
+    }
+
+    #[test]
+    fn read_transaction_invokes_function_with_args() {
+        let contract_id = stellar_strkey::Contract([7; 32]).to_string();
+        let b64 = build_read_transaction(&contract_id, "balance", vec![ScVal::U64(9)]).unwrap();
+
+        let bytes = B64.decode(b64).unwrap();
+        let envelope = TransactionEnvelope::from_xdr(&bytes, Limits::none()).unwrap();
+        let TransactionEnvelope::Tx(v1) = envelope else {
+            panic!("expected a v1 envelope");
+        };
+        let OperationBody::InvokeHostFunction(op) = &v1.tx.operations[0].body else {
+            panic!("expected InvokeHostFunction");
+        };
+        let HostFunction::InvokeContract(call) = &op.host_function else {
+            panic!("expected InvokeContract");
+        };
+        assert_eq!(call.contract_address, ScAddress::Contract(Hash([7; 32])));
+        assert_eq!(call.function_name.0.as_slice(), b"balance");
+        assert_eq!(call.args.to_vec(), vec![ScVal::U64(9)]);
+
+        assert!(build_read_transaction("GABC", "decimals", vec![]).is_err());
+    }
+
+    #[test]
+    fn simulate_result_returns_value_or_error() {
+        let xdr = B64.encode(ScVal::U32(6).to_xdr(Limits::none()).unwrap());
+        let ok = SimulateTransactionResult {
+            error: None,
+            results: vec![SimulateHostFunctionResult { xdr }],
+        };
+        assert_eq!(
+            decode_simulate_result(ok, "decimals").unwrap(),
+            ScVal::U32(6)
+        );
+
+        let failed = SimulateTransactionResult {
+            error: Some("HostError: Error(WasmVm, MissingValue)".to_owned()),
+            results: vec![],
+        };
+        let err = decode_simulate_result(failed, "decimals").unwrap_err();
+        assert!(err.to_string().contains("MissingValue"));
+    }
 }
@@ -0,0 +1,200 @@
+//! `check-treasury` command — confirm a company's treasury can cover the
+//! next payroll run before it is submitted.
+//!
+//! The planned batch is the sum of the local database's salaries for every
+//! active employee.  The treasury address comes from the payroll registry's
+//! `get_company`, and its balance from the token contract's `balance`, both
+//! read through `simulateTransaction`, so nothing is signed or submitted.
+//!
+//! Salaries and the balance are compared in raw token units (stroops for
+//! XLM).
+//!
+//! # Example output
+//!
+//! ```text
+//! SHORT: treasury GBXX... holds 8000, 3 active employees need 10000 (short by 2000)
+//! ```
+
+use anyhow::{bail, Context, Result};
+use stellar_xdr::curr::{ScMap, ScVal};
+
+use crate::rpc;
+
+/// Where to look up the company and its token.
+pub struct TreasuryArgs<'a> {
+    /// Payroll registry contract (C... Strkey address).
+    pub registry_id: &'a str,
+    pub company_id: u64,
+    /// Payment token contract (C... Strkey address).
+    pub token_id: &'a str,
+}
+
+/// Treasury balance against the planned batch.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TreasuryCheck {
+    /// Treasury address as a StrKey.
+    pub treasury: String,
+    pub employees: usize,
+    /// Sum of active employees' salaries.
+    pub required: i128,
+    pub balance: i128,
+}
+
+impl TreasuryCheck {
+    /// `balance - required`: the surplus, or the shortfall when negative.
+    pub fn delta(&self) -> i128 {
+        self.balance - self.required
+    }
+
+    pub fn is_sufficient(&self) -> bool {
+        self.delta() >= 0
+    }
+}
+
+impl std::fmt::Display for TreasuryCheck {
+    /// `SUFFICIENT: ... (surplus N)` or `SHORT: ... (short by N)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (verdict, delta) = if self.is_sufficient() {
+            ("SUFFICIENT", format!("surplus {}", self.delta()))
+        } else {
+            ("SHORT", format!("short by {}", -self.delta()))
+        };
+        write!(
+            f,
+            "{}: treasury {} holds {}, {} active employees need {} ({})",
+            verdict, self.treasury, self.balance, self.employees, self.required, delta
+        )
+    }
+}
+
+/// Compare the treasury balance with `salaries`, reading contract state with
+/// `read(contract_id, function, args)`.
+pub fn check<F>(args: &TreasuryArgs<'_>, salaries: &[u64], mut read: F) -> Result<TreasuryCheck>
+where
+    F: FnMut(&str, &str, Vec<ScVal>) -> Result<ScVal>,
+{
+    let company = read(
+        args.registry_id,
+        "get_company",
+        vec![ScVal::U64(args.company_id)],
+    )
+    .with_context(|| {
+        format!(
+            "Failed to read company {} from the registry",
+            args.company_id
+        )
+    })?;
+    let treasury = match company {
+        ScVal::Map(Some(fields)) => map_field(&fields, "treasury")?,
+        other => bail!("get_company returned {:?}", other),
+    };
+    let ScVal::Address(treasury_address) = &treasury else {
+        bail!("Company treasury is not an address: {:?}", treasury);
+    };
+    let treasury_strkey = rpc::scaddress_to_strkey(treasury_address)?;
+
+    let balance = read(args.token_id, "balance", vec![treasury.clone()])
+        .with_context(|| format!("Failed to read the balance of {}", treasury_strkey))?;
+    let balance =
+        rpc::scval_to_i128(&balance).with_context(|| format!("balance returned {:?}", balance))?;
+
+    Ok(TreasuryCheck {
+        treasury: treasury_strkey,
+        employees: salaries.len(),
+        required: salaries.iter().map(|&s| i128::from(s)).sum(),
+        balance,
+    })
+}
+
+/// The value stored under the symbol `name` in a contract struct.
+fn map_field(fields: &ScMap, name: &str) -> Result<ScVal> {
+    fields
+        .iter()
+        .find(|entry| matches!(&entry.key, ScVal::Symbol(key) if key.as_slice() == name.as_bytes()))
+        .map(|entry| entry.val.clone())
+        .with_context(|| format!("Company record has no '{}' field", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::{
+        AccountId, Int128Parts, PublicKey, ScAddress, ScMapEntry, ScSymbol, Uint256,
+    };
+
+    const REGISTRY: &str = "CREGISTRY";
+    const TOKEN: &str = "CTOKEN";
+
+    fn args() -> TreasuryArgs<'static> {
+        TreasuryArgs {
+            registry_id: REGISTRY,
+            company_id: 7,
+            token_id: TOKEN,
+        }
+    }
+
+    fn treasury() -> ScVal {
+        ScVal::Address(ScAddress::Account(AccountId(
+            PublicKey::PublicKeyTypeEd25519(Uint256([3; 32])),
+        )))
+    }
+
+    fn company() -> ScVal {
+        let field = |name: &str, val: ScVal| ScMapEntry {
+            key: ScVal::Symbol(ScSymbol(name.try_into().unwrap())),
+            val,
+        };
+        ScVal::Map(Some(
+            vec![field("admin", ScVal::Void), field("treasury", treasury())]
+                .try_into()
+                .unwrap(),
+        ))
+    }
+
+    /// A registry and token that answer with `company()` and `balance`.
+    fn stub(balance: u64) -> impl FnMut(&str, &str, Vec<ScVal>) -> Result<ScVal> {
+        move |contract, function, call_args| match (contract, function) {
+            (REGISTRY, "get_company") => {
+                assert_eq!(call_args, vec![ScVal::U64(7)]);
+                Ok(company())
+            }
+            (TOKEN, "balance") => {
+                assert_eq!(call_args, vec![treasury()]);
+                Ok(ScVal::I128(Int128Parts { hi: 0, lo: balance }))
+            }
+            _ => bail!("unexpected call {}.{}", contract, function),
+        }
+    }
+
+    #[test]
+    fn balance_covering_salaries_is_sufficient() {
+        let check = check(&args(), &[4_000, 6_000], stub(12_000)).unwrap();
+
+        assert_eq!(check.employees, 2);
+        assert_eq!(check.required, 10_000);
+        assert!(check.is_sufficient());
+        assert_eq!(check.delta(), 2_000);
+        assert!(check.to_string().starts_with("SUFFICIENT: treasury G"));
+        assert!(check.to_string().ends_with("(surplus 2000)"));
+    }
+
+    #[test]
+    fn balance_below_salaries_is_short() {
+        let check = check(&args(), &[4_000, 6_000], stub(8_000)).unwrap();
+
+        assert!(!check.is_sufficient());
+        assert_eq!(check.delta(), -2_000);
+        assert!(check.to_string().starts_with("SHORT:"));
+        assert!(check.to_string().ends_with("(short by 2000)"));
+    }
+
+    #[test]
+    fn failed_balance_read_errors() {
+        let err = check(&args(), &[1], |_, function, _| match function {
+            "get_company" => Ok(company()),
+            _ => bail!("simulation failed"),
+        })
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("simulation failed"));
+    }
+}
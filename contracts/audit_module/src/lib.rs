@@ -1,5 +1,8 @@
 #![no_std]
 
+use payment_executor::PaymentExecutorClient;
+use payroll_registry::PayrollRegistryClient;
+use salary_commitment::SalaryCommitmentContractClient;
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env,
     Symbol, Vec,
@@ -28,12 +31,62 @@ pub enum AuditError {
     CommitmentMismatch = 6,
     /// Supplied key material does not belong to the auditor.
     InvalidViewKey = 7,
+    /// The requested scope is not strictly narrower than the granted scope.
+    ScopeNotNarrower = 8,
+    /// The key has used up its operations for the current rate-limit window.
+    RateLimited = 9,
+    /// Paired input vectors have different lengths.
+    LengthMismatch = 10,
+    /// The commitment contract has no commitment of the requested version
+    /// for the employee.
+    VersionNotFound = 11,
+    /// The company already holds as many live keys as its cap allows.
+    KeyLimitReached = 12,
+    /// `initialize` has not been called, so there is no executor or
+    /// registry to report from.
+    NotInitialized = 13,
+    /// A time-range key's `allowed_start` is after its `allowed_end`.
+    InvalidTimeRange = 14,
+}
+
+/// Error returned by a second call to `initialize`.
+#[contracterror]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum InitError {
+    AlreadyInitialized = 1,
 }
 
 // ---------------------------------------------------------------------------
 // Data types
 // ---------------------------------------------------------------------------
 
+/// Resolution of the period bounds given to `AggregateOnly` auditors.
+pub const AGGREGATE_BOUND_RESOLUTION_SECS: u64 = 86_400;
+
+/// Company ID that keys and audit log entries are recorded under.
+pub const DEFAULT_COMPANY: &str = "default";
+
+/// Nominal ledger close time, used to express ledger counts in seconds.
+pub const LEDGER_CLOSE_SECS: u64 = 5;
+
+/// Domain-separation tag prepended to the view-key derivation preimage.
+pub const VIEW_KEY_DOMAIN: &[u8] = b"ZKP_VIEWKEY_V1";
+
+/// Domain-separation tag prepended to the salary commitment preimage.
+pub use salary_commitment::COMMITMENT_DOMAIN;
+
+/// Domain-separation tag prepended to the aggregate report hash preimage.
+pub const REPORT_DOMAIN: &[u8] = b"ZKP_REPORT_V1";
+
+/// Contracts aggregate reports are read from.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContractAddresses {
+    pub executor: Address,
+    pub registry: Address,
+}
+
 /// Record stored in Persistent storage for each auditor.
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -41,6 +94,42 @@ pub struct ViewKeyRecord {
     pub key_bytes: BytesN<32>,
     pub expiration_ledger: u32,
     pub granted_by: Address,
+    /// Earliest `period_start` a `TimeRange` key may report on; `None` for
+    /// keys issued without a window.
+    pub allowed_start: Option<u64>,
+    /// Latest `period_end` a `TimeRange` key may report on.
+    pub allowed_end: Option<u64>,
+    /// Employees an `EmployeeList` key may verify commitments for; `None`
+    /// for keys issued without a list.
+    pub allowed_employees: Option<Vec<Address>>,
+}
+
+impl ViewKeyRecord {
+    /// `true` once the ledger has passed `expiration_ledger`.
+    fn is_expired(&self, env: &Env) -> bool {
+        env.ledger().sequence() > self.expiration_ledger
+    }
+
+    /// Validity left on the key in seconds; 0 once expired.
+    fn seconds_remaining(&self, env: &Env) -> u64 {
+        let ledgers = self
+            .expiration_ledger
+            .saturating_sub(env.ledger().sequence());
+        u64::from(ledgers) * LEDGER_CLOSE_SECS
+    }
+}
+
+/// Lifecycle state of a view key, as reported by `key_status`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum KeyStatus {
+    /// Held by an auditor and not yet expired.
+    Active = 0,
+    /// Held by an auditor but past its expiration ledger.
+    Expired = 1,
+    /// Never issued, or revoked, surrendered or replaced since.
+    NotFound = 2,
 }
 
 /// What the auditor is allowed to examine.
@@ -54,6 +143,38 @@ pub enum AuditScope {
     AggregateOnly = 3,
 }
 
+impl AuditScope {
+    /// `true` if `self` grants strictly less access than `other`.
+    ///
+    /// `FullCompany` covers every other scope; `TimeRange` and `EmployeeList`
+    /// are incomparable with each other but both cover `AggregateOnly`.
+    pub fn is_narrower_than(self, other: AuditScope) -> bool {
+        match (other, self) {
+            (AuditScope::FullCompany, s) => s != AuditScope::FullCompany,
+            (AuditScope::TimeRange, AuditScope::AggregateOnly) => true,
+            (AuditScope::EmployeeList, AuditScope::AggregateOnly) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Operation budget for an auditor's key: at most `max_ops` audit
+/// operations per `window_secs` of ledger time.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub max_ops: u32,
+    pub window_secs: u64,
+}
+
+/// Operations counted against a key's [`RateLimit`] in the current window.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RateWindow {
+    pub window_start: u64,
+    pub ops: u32,
+}
+
 /// An audit log entry written each time an auditor performs a verification
 /// or generates a report. Stored in Persistent under DataKey::AuditLog(company_symbol, counter).
 ///
@@ -70,15 +191,39 @@ pub struct AuditLogEntry {
 }
 
 /// Aggregate snapshot returned to an auditor.
+///
+/// For keys limited to `AggregateOnly`, `period_start` is rounded down and
+/// `period_end` up to whole days so the report does not reveal pay-cycle
+/// timing; broader scopes receive the requested bounds unchanged.
+///
+/// `report_hash` commits to every other field and is also published in the
+/// `AggregateAuditGenerated` event, so a holder can prove the report is the
+/// one the contract produced; see `verify_report_hash`.
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct AuditReport {
-    pub company_id: Symbol,
+    /// Registry company the report covers.
+    pub company_id: u64,
     pub total_employees: u32,
     pub total_paid: i128,
     pub period_start: u64,
     pub period_end: u64,
     pub verified: bool,
+    pub report_hash: BytesN<32>,
+}
+
+/// Outcome of `verify_commitment_version`.
+///
+/// `matched` says whether the opening matches the commitment of
+/// `verified_version`; `outdated` says the employee's salary commitment has
+/// been updated since, so a match no longer describes current pay.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionedVerification {
+    pub matched: bool,
+    pub verified_version: u32,
+    pub current_version: u32,
+    pub outdated: bool,
 }
 
 /// Query result envelope so consumers can enumerate matching logs.
@@ -117,6 +262,29 @@ pub struct AuditMetadataSummary {
     pub exported_by: Address,
 }
 
+/// An auditor's view key that has passed the auth, expiry, rate-limit and
+/// scope checks for the current call, with the scope it was checked at.
+///
+/// Only `validate_key` builds one, and it is never stored, so verifying
+/// against it needs no further key loads or checks.
+struct ValidatedKey {
+    auditor: Address,
+    key_bytes: BytesN<32>,
+    scope: AuditScope,
+    allowed_employees: Option<Vec<Address>>,
+}
+
+impl ValidatedKey {
+    /// Reject an employee outside the key's allowlist. Keys issued without
+    /// one, such as `FullCompany` and `TimeRange` keys, allow any employee.
+    fn check_employee(&self, employee: &Address) -> Result<(), AuditError> {
+        match &self.allowed_employees {
+            Some(allowed) if !allowed.contains(employee) => Err(AuditError::InsufficientScope),
+            _ => Ok(()),
+        }
+    }
+}
+
 /// Storage key namespace.
 #[contracttype]
 pub enum DataKey {
@@ -126,6 +294,24 @@ pub enum DataKey {
     AuditLogCounter(Symbol),
     /// Audit log entry keyed by (company_id, log_index).
     AuditLog(Symbol, u32),
+    /// Scope an auditor's key has been downgraded to. Absent = `FullCompany`.
+    KeyScope(Address),
+    /// Operation budget for an auditor's key. Absent = unlimited.
+    RateLimit(Address),
+    /// Operations used by an auditor's key in the current window.
+    RateWindow(Address),
+    /// Maps a live key's `key_bytes` → the auditor holding it.
+    KeyOwner(BytesN<32>),
+    /// `(timestamp, scope)` for the grant and each later scope change of a
+    /// key, oldest first. Kept after the key is revoked or surrendered.
+    ScopeHistory(BytesN<32>),
+    /// Most live keys a company may hold at once. Absent = unlimited.
+    MaxKeys(Symbol),
+    /// Auditors issued a key for a company. Expired and removed keys are
+    /// pruned on the next issuance.
+    KeyIndex(Symbol),
+    /// Payment executor and registry set by `initialize`.
+    Addresses,
 }
 
 // ---------------------------------------------------------------------------
@@ -137,24 +323,181 @@ pub struct AuditModule;
 
 #[contractimpl]
 impl AuditModule {
+    /// Record the payment executor and payroll registry that aggregate
+    /// reports are read from. Can only be called once.
+    pub fn initialize(
+        env: Env,
+        executor_addr: Address,
+        registry_addr: Address,
+    ) -> Result<(), InitError> {
+        if env.storage().persistent().has(&DataKey::Addresses) {
+            return Err(InitError::AlreadyInitialized);
+        }
+        let addresses = ContractAddresses {
+            executor: executor_addr,
+            registry: registry_addr,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Addresses, &addresses);
+        Ok(())
+    }
+
+    /// The contracts set by `initialize`.
+    pub fn get_addresses(env: Env) -> Result<ContractAddresses, AuditError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Addresses)
+            .ok_or(AuditError::NotInitialized)
+    }
+
     // -----------------------------------------------------------------------
     // View-key lifecycle
     // -----------------------------------------------------------------------
 
-    pub fn generate_view_key(env: Env, auditor: Address, expiration_ledger: u32) -> BytesN<32> {
+    /// Issue `auditor` a key valid up to `expiration_ledger`, replacing any
+    /// key they already hold.
+    ///
+    /// Fails with `KeyLimitReached` if the company is at its `set_max_keys`
+    /// cap. Replacing an auditor's live key does not count as a new key.
+    pub fn generate_view_key(
+        env: Env,
+        auditor: Address,
+        expiration_ledger: u32,
+    ) -> Result<BytesN<32>, AuditError> {
+        Self::issue_view_key(
+            env,
+            auditor,
+            expiration_ledger,
+            AuditScope::FullCompany,
+            None,
+            None,
+        )
+    }
+
+    /// Issue `auditor` a `TimeRange` key that may only report on periods
+    /// inside `[allowed_start, allowed_end]`, replacing any key they
+    /// already hold.
+    ///
+    /// Fails with `InvalidTimeRange` if `allowed_start > allowed_end`, and
+    /// otherwise as `generate_view_key`.
+    pub fn generate_time_range_view_key(
+        env: Env,
+        auditor: Address,
+        expiration_ledger: u32,
+        allowed_start: u64,
+        allowed_end: u64,
+    ) -> Result<BytesN<32>, AuditError> {
+        if allowed_start > allowed_end {
+            return Err(AuditError::InvalidTimeRange);
+        }
+        Self::issue_view_key(
+            env,
+            auditor,
+            expiration_ledger,
+            AuditScope::TimeRange,
+            Some((allowed_start, allowed_end)),
+            None,
+        )
+    }
+
+    /// Issue `auditor` an `EmployeeList` key that may only verify
+    /// commitments of `employees`, replacing any key they already hold.
+    ///
+    /// Fails as `generate_view_key`.
+    pub fn generate_employee_list_view_key(
+        env: Env,
+        auditor: Address,
+        expiration_ledger: u32,
+        employees: Vec<Address>,
+    ) -> Result<BytesN<32>, AuditError> {
+        Self::issue_view_key(
+            env,
+            auditor,
+            expiration_ledger,
+            AuditScope::EmployeeList,
+            None,
+            Some(employees),
+        )
+    }
+
+    /// Store a fresh key for `auditor` at `scope`, with `allowed` as its
+    /// `(allowed_start, allowed_end)` window and `employees` as its
+    /// allowlist if it has them.
+    fn issue_view_key(
+        env: Env,
+        auditor: Address,
+        expiration_ledger: u32,
+        scope: AuditScope,
+        allowed: Option<(u64, u64)>,
+        employees: Option<Vec<Address>>,
+    ) -> Result<BytesN<32>, AuditError> {
         let admin = env.current_contract_address();
+        let company_id = Symbol::new(&env, DEFAULT_COMPANY);
+
+        let mut holders = Self::live_key_holders(&env, &company_id);
+        if !holders.contains(&auditor) {
+            let max_keys: Option<u32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::MaxKeys(company_id.clone()));
+            if max_keys.is_some_and(|max| holders.len() >= max) {
+                return Err(AuditError::KeyLimitReached);
+            }
+            holders.push_back(auditor.clone());
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::KeyIndex(company_id), &holders);
 
         let key_bytes = Self::derive_key_bytes(&env, &auditor, expiration_ledger);
 
+        // A replaced key no longer resolves to this auditor.
+        if let Some(old) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ViewKeyRecord>(&DataKey::AuditorKey(auditor.clone()))
+        {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::KeyOwner(old.key_bytes));
+        }
+
         let record = ViewKeyRecord {
             key_bytes: key_bytes.clone(),
             expiration_ledger,
             granted_by: admin,
+            allowed_start: allowed.map(|(start, _)| start),
+            allowed_end: allowed.map(|(_, end)| end),
+            allowed_employees: employees,
         };
 
         env.storage()
             .persistent()
             .set(&DataKey::AuditorKey(auditor.clone()), &record);
+        // A freshly generated key starts with its issued scope and no limit.
+        if scope == AuditScope::FullCompany {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::KeyScope(auditor.clone()));
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::KeyScope(auditor.clone()), &scope);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RateLimit(auditor.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RateWindow(auditor.clone()));
+        env.storage()
+            .persistent()
+            .set(&DataKey::KeyOwner(key_bytes.clone()), &auditor);
+        env.storage().persistent().set(
+            &DataKey::ScopeHistory(key_bytes.clone()),
+            &Vec::from_array(&env, [(env.ledger().timestamp(), scope)]),
+        );
 
         env.events().publish(
             (Symbol::new(&env, "ViewKeyGenerated"), auditor),
@@ -163,7 +506,60 @@ impl AuditModule {
         // topics : ("ViewKeyGenerated", auditor)
         // data   : (key_bytes, expiration_ledger)
 
-        key_bytes
+        Ok(key_bytes)
+    }
+
+    /// Cap the number of live keys `company_id` may hold at once.
+    ///
+    /// Requires the granter's auth. Keys already issued are kept even if
+    /// they exceed the new cap; further issuance fails with
+    /// `KeyLimitReached` until enough of them expire or are revoked.
+    pub fn set_max_keys(
+        env: Env,
+        company_admin: Address,
+        company_id: Symbol,
+        max: u32,
+    ) -> Result<(), AuditError> {
+        company_admin.require_auth();
+        if company_admin != env.current_contract_address() {
+            return Err(AuditError::NotKeyGranter);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MaxKeys(company_id.clone()), &max);
+
+        env.events().publish(
+            (Symbol::new(&env, "MaxKeysSet"), company_admin, company_id),
+            (max,),
+        );
+        // topics : ("MaxKeysSet", company_admin, company_id)
+        // data   : (max,)
+
+        Ok(())
+    }
+
+    /// Live-key cap configured for `company_id`, if any.
+    pub fn get_max_keys(env: Env, company_id: Symbol) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MaxKeys(company_id))
+    }
+
+    /// Auditors in the company's key index whose key still verifies.
+    fn live_key_holders(env: &Env, company_id: &Symbol) -> Vec<Address> {
+        let indexed: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::KeyIndex(company_id.clone()))
+            .unwrap_or(Vec::new(env));
+        let mut live = Vec::new(env);
+        for auditor in indexed.iter() {
+            if Self::verify_access(env.clone(), auditor.clone()) {
+                live.push_back(auditor);
+            }
+        }
+        live
     }
 
     pub fn verify_access(env: Env, auditor: Address) -> bool {
@@ -172,14 +568,78 @@ impl AuditModule {
             .persistent()
             .get::<DataKey, ViewKeyRecord>(&DataKey::AuditorKey(auditor))
         {
-            Some(record) => env.ledger().sequence() <= record.expiration_ledger,
+            Some(record) => !record.is_expired(&env),
             None => false,
         }
     }
 
+    /// Status of the view key `key_id` and the seconds left before it
+    /// expires (0 unless `Active`). Shares its expiry rule with
+    /// `verify_access`.
+    pub fn key_status(env: Env, key_id: BytesN<32>) -> (KeyStatus, u64) {
+        let record = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::KeyOwner(key_id))
+            .and_then(|auditor| {
+                env.storage()
+                    .persistent()
+                    .get::<DataKey, ViewKeyRecord>(&DataKey::AuditorKey(auditor))
+            });
+        match record {
+            None => (KeyStatus::NotFound, 0),
+            Some(record) if record.is_expired(&env) => (KeyStatus::Expired, 0),
+            Some(record) => (KeyStatus::Active, record.seconds_remaining(&env)),
+        }
+    }
+
     pub fn revoke_view_key(env: Env, admin: Address, auditor: Address) -> Result<(), AuditError> {
         admin.require_auth();
+        Self::revoke_view_key_inner(&env, admin, auditor)
+    }
+
+    /// Revoke every live key in `key_ids` that `company_admin` granted and
+    /// return how many were revoked, e.g. when an audit firm's engagement
+    /// ends. Keys that are expired or no longer held (revoked, surrendered
+    /// or replaced) are skipped. If any listed key was granted by another
+    /// admin the call fails with `NotKeyGranter` and no key is revoked.
+    pub fn revoke_view_keys(
+        env: Env,
+        company_admin: Address,
+        key_ids: Vec<BytesN<32>>,
+    ) -> Result<u32, AuditError> {
+        company_admin.require_auth();
+
+        let mut revoked = 0;
+        for key_id in key_ids.iter() {
+            let auditor: Address = match env.storage().persistent().get(&DataKey::KeyOwner(key_id))
+            {
+                Some(auditor) => auditor,
+                None => continue,
+            };
+            let record: ViewKeyRecord = env
+                .storage()
+                .persistent()
+                .get(&DataKey::AuditorKey(auditor.clone()))
+                .ok_or(AuditError::KeyNotFound)?;
+            if record.granted_by != company_admin {
+                return Err(AuditError::NotKeyGranter);
+            }
+            if record.is_expired(&env) {
+                continue;
+            }
 
+            Self::revoke_view_key_inner(&env, company_admin.clone(), auditor)?;
+            revoked += 1;
+        }
+        Ok(revoked)
+    }
+
+    fn revoke_view_key_inner(
+        env: &Env,
+        admin: Address,
+        auditor: Address,
+    ) -> Result<(), AuditError> {
         let record: ViewKeyRecord = env
             .storage()
             .persistent()
@@ -190,21 +650,232 @@ impl AuditModule {
             return Err(AuditError::NotKeyGranter);
         }
 
+        // Validity given up by revoking early; nothing once expired.
+        let forfeited_secs = record.seconds_remaining(env);
+
+        Self::remove_key(env, &auditor);
+
+        // Emit revocation event for audit trail
+        env.events().publish(
+            (
+                Symbol::new(env, "ViewKeyRevoked"),
+                Symbol::new(env, DEFAULT_COMPANY),
+            ),
+            (auditor, record.key_bytes, forfeited_secs),
+        );
+        // topics : ("ViewKeyRevoked", company_id)
+        // data   : (auditor, key_id, forfeited_secs)
+
+        Ok(())
+    }
+
+    /// Let an auditor give up their own key before it expires, e.g. when the
+    /// engagement ends early or their device is compromised.
+    ///
+    /// Requires the auditor's auth, not the granter's. `key_id` must be the
+    /// key the auditor currently holds.
+    pub fn surrender_view_key(
+        env: Env,
+        auditor: Address,
+        key_id: BytesN<32>,
+    ) -> Result<(), AuditError> {
+        auditor.require_auth();
+
+        let record: ViewKeyRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AuditorKey(auditor.clone()))
+            .ok_or(AuditError::KeyNotFound)?;
+        if record.key_bytes != key_id {
+            return Err(AuditError::InvalidViewKey);
+        }
+
+        Self::remove_key(&env, &auditor);
+
+        env.events().publish(
+            (
+                Symbol::new(&env, "ViewKeySurrendered"),
+                Symbol::new(&env, DEFAULT_COMPANY),
+            ),
+            (auditor, key_id, env.ledger().timestamp()),
+        );
+        // topics : ("ViewKeySurrendered", company_id)
+        // data   : (auditor, key_id, timestamp)
+
+        Ok(())
+    }
+
+    /// Delete an auditor's key together with its scope and rate limit.
+    fn remove_key(env: &Env, auditor: &Address) {
+        if let Some(record) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ViewKeyRecord>(&DataKey::AuditorKey(auditor.clone()))
+        {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::KeyOwner(record.key_bytes));
+        }
         env.storage()
             .persistent()
             .remove(&DataKey::AuditorKey(auditor.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::KeyScope(auditor.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RateLimit(auditor.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RateWindow(auditor.clone()));
+    }
+
+    /// Narrow the scope of the live key `key_id` mid-engagement without
+    /// revoking it.
+    ///
+    /// Requires the granter's auth. Fails with `KeyNotFound` if no auditor
+    /// holds `key_id`. `narrower_scope` must be strictly narrower than the
+    /// key's current scope; broadening is rejected with `ScopeNotNarrower`.
+    pub fn downgrade_scope(
+        env: Env,
+        company_admin: Address,
+        key_id: BytesN<32>,
+        narrower_scope: AuditScope,
+    ) -> Result<(), AuditError> {
+        company_admin.require_auth();
+
+        let auditor: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::KeyOwner(key_id.clone()))
+            .ok_or(AuditError::KeyNotFound)?;
+        let record: ViewKeyRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AuditorKey(auditor.clone()))
+            .ok_or(AuditError::KeyNotFound)?;
+
+        if record.granted_by != company_admin {
+            return Err(AuditError::NotKeyGranter);
+        }
+
+        let current = Self::get_key_scope(env.clone(), auditor.clone());
+        if !narrower_scope.is_narrower_than(current) {
+            return Err(AuditError::ScopeNotNarrower);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::KeyScope(auditor.clone()), &narrower_scope);
+
+        let history_key = DataKey::ScopeHistory(key_id.clone());
+        let mut history: Vec<(u64, AuditScope)> = env
+            .storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or(Vec::new(&env));
+        history.push_back((env.ledger().timestamp(), narrower_scope));
+        env.storage().persistent().set(&history_key, &history);
+
+        env.events().publish(
+            (
+                Symbol::new(&env, "ViewKeyDowngraded"),
+                Symbol::new(&env, DEFAULT_COMPANY),
+            ),
+            (auditor, key_id, current, narrower_scope),
+        );
+        // topics : ("ViewKeyDowngraded", company_id)
+        // data   : (auditor, key_id, old_scope, new_scope)
+
+        Ok(())
+    }
+
+    /// Scope currently granted to an auditor's key (`FullCompany` unless
+    /// downgraded).
+    pub fn get_key_scope(env: Env, auditor: Address) -> AuditScope {
+        env.storage()
+            .persistent()
+            .get(&DataKey::KeyScope(auditor))
+            .unwrap_or(AuditScope::FullCompany)
+    }
+
+    /// Scope currently granted to the live key `key_id`.
+    ///
+    /// Fails with `KeyNotFound` if no auditor holds `key_id`, including after
+    /// it was revoked, surrendered or replaced.
+    pub fn get_scope(env: Env, key_id: BytesN<32>) -> Result<AuditScope, AuditError> {
+        let auditor: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::KeyOwner(key_id))
+            .ok_or(AuditError::KeyNotFound)?;
+        Ok(Self::get_key_scope(env, auditor))
+    }
+
+    /// Every scope `key_id` has had, as `(timestamp, scope)` pairs oldest
+    /// first: the `FullCompany` grant, then one entry per downgrade.
+    ///
+    /// Still available after the key is revoked or surrendered; empty for a
+    /// key that was never issued.
+    pub fn get_scope_history(env: Env, key_id: BytesN<32>) -> Vec<(u64, AuditScope)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ScopeHistory(key_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Cap an auditor's key at `max_ops` audit operations per `window_secs`
+    /// of ledger time.
+    ///
+    /// Requires the granter's auth. Every call to a verification, report or
+    /// export function counts as one operation; once the budget is spent
+    /// further calls fail with `RateLimited` until the window rolls over.
+    pub fn set_rate_limit(
+        env: Env,
+        admin: Address,
+        auditor: Address,
+        max_ops: u32,
+        window_secs: u64,
+    ) -> Result<(), AuditError> {
+        admin.require_auth();
+
+        let record: ViewKeyRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AuditorKey(auditor.clone()))
+            .ok_or(AuditError::KeyNotFound)?;
+
+        if record.granted_by != admin {
+            return Err(AuditError::NotKeyGranter);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::RateLimit(auditor.clone()),
+            &RateLimit {
+                max_ops,
+                window_secs,
+            },
+        );
+        // Start counting against the new budget from scratch.
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RateWindow(auditor.clone()));
 
-        // Emit revocation event for audit trail
         env.events().publish(
-            (Symbol::new(&env, "AuditAccessRevoked"), admin, auditor.clone()),
-            (env.ledger().timestamp(),),
+            (Symbol::new(&env, "RateLimitSet"), admin, auditor),
+            (max_ops, window_secs),
         );
-        // topics : ("AuditAccessRevoked", admin, auditor)
-        // data   : (timestamp,)
+        // topics : ("RateLimitSet", admin, auditor)
+        // data   : (max_ops, window_secs)
 
         Ok(())
     }
 
+    /// Operation budget configured for an auditor's key, if any.
+    pub fn get_rate_limit(env: Env, auditor: Address) -> Option<RateLimit> {
+        env.storage().persistent().get(&DataKey::RateLimit(auditor))
+    }
+
     pub fn get_view_key(env: Env, auditor: Address) -> Result<ViewKeyRecord, AuditError> {
         env.storage()
             .persistent()
@@ -216,30 +887,29 @@ impl AuditModule {
     // Audit operations
     // -----------------------------------------------------------------------
 
+    /// Check `employee`'s opening against `stored_commitment`.
+    ///
+    /// Fails with `InsufficientScope` if the key has an employee allowlist
+    /// that does not include `employee`.
     pub fn verify_commitment_with_key(
         env: Env,
         auditor: Address,
+        employee: Address,
         stored_commitment: BytesN<32>,
         claimed_amount: i128,
         blinding_factor: BytesN<32>,
         scope: AuditScope,
     ) -> Result<bool, AuditError> {
-        let record = Self::authorize_auditor(&env, auditor.clone())?;
-        Self::verify_scope_for_commitment(scope)?;
-
-        let matched = Self::verify_commitment_inner(
+        let key = Self::validate_key(&env, auditor, Some(scope))?;
+        key.check_employee(&employee)?;
+        let matched = Self::verify_with_key(
             &env,
-            &auditor,
-            &record.key_bytes,
+            &key,
             &stored_commitment,
             claimed_amount,
             &blinding_factor,
-            scope,
         );
 
-        // Record audit log entry for query retrieval
-        Self::record_audit_log(&env, &auditor, scope, matched);
-
         if !matched {
             return Err(AuditError::CommitmentMismatch);
         }
@@ -247,34 +917,31 @@ impl AuditModule {
         Ok(matched)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn verify_commitment_with_view_key(
         env: Env,
         auditor: Address,
+        employee: Address,
         supplied_key: BytesN<32>,
         stored_commitment: BytesN<32>,
         claimed_amount: i128,
         blinding_factor: BytesN<32>,
         scope: AuditScope,
     ) -> Result<bool, AuditError> {
-        let record = Self::authorize_auditor(&env, auditor.clone())?;
-        Self::verify_scope_for_commitment(scope)?;
-
-        if supplied_key != record.key_bytes {
+        let key = Self::validate_key(&env, auditor, Some(scope))?;
+        if supplied_key != key.key_bytes {
             return Err(AuditError::InvalidViewKey);
         }
+        key.check_employee(&employee)?;
 
-        let matched = Self::verify_commitment_inner(
+        let matched = Self::verify_with_key(
             &env,
-            &auditor,
-            &supplied_key,
+            &key,
             &stored_commitment,
             claimed_amount,
             &blinding_factor,
-            scope,
         );
 
-        Self::record_audit_log(&env, &auditor, scope, matched);
-
         if !matched {
             return Err(AuditError::CommitmentMismatch);
         }
@@ -282,6 +949,156 @@ impl AuditModule {
         Ok(matched)
     }
 
+    /// Check many `(employee, claimed_amount, blinding)` openings against
+    /// `stored[i]` in one call, returning whether each one matched.
+    ///
+    /// `key_id` must be the auditor's current key. The key is loaded and its
+    /// auth, expiry (against the ledger at call start), scope and rate limit
+    /// are checked once for the whole batch, at the key's granted scope; a
+    /// failed check, including an entry for an employee outside the key's
+    /// allowlist, rejects the batch before any entry is verified. A mismatch is reported as `false` rather than an
+    /// error; each entry is logged and a match emits `AuditSuccessful` as
+    /// in `verify_commitment_with_key`.
+    pub fn batch_verify_commitments(
+        env: Env,
+        key_id: BytesN<32>,
+        auditor: Address,
+        entries: Vec<(Address, i128, BytesN<32>)>,
+        stored: Vec<BytesN<32>>,
+    ) -> Result<Vec<bool>, AuditError> {
+        if entries.len() != stored.len() {
+            return Err(AuditError::LengthMismatch);
+        }
+
+        let key = Self::validate_key(&env, auditor, None)?;
+        if key_id != key.key_bytes {
+            return Err(AuditError::InvalidViewKey);
+        }
+        for (employee, _, _) in entries.iter() {
+            key.check_employee(&employee)?;
+        }
+
+        let mut results = Vec::new(&env);
+        for ((_employee, claimed_amount, blinding_factor), stored_commitment) in
+            entries.iter().zip(stored.iter())
+        {
+            results.push_back(Self::verify_with_key(
+                &env,
+                &key,
+                &stored_commitment,
+                claimed_amount,
+                &blinding_factor,
+            ));
+        }
+
+        Ok(results)
+    }
+
+    /// Verify an opening against version `version` of `employee`'s commitment
+    /// in `commitment_contract`, reporting whether that version has since
+    /// been superseded.
+    ///
+    /// The commitment is read from the contract rather than supplied, the
+    /// active one if `version` is current and the archived snapshot
+    /// otherwise, so an auditor holding a key issued before a salary change
+    /// learns that what they verified is stale. Checks and logging are as
+    /// in `verify_commitment_with_key`, but a mismatch is reported in the
+    /// result instead of as an error.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_commitment_version(
+        env: Env,
+        auditor: Address,
+        commitment_contract: Address,
+        employee: Address,
+        version: u32,
+        claimed_amount: i128,
+        blinding_factor: BytesN<32>,
+        scope: AuditScope,
+    ) -> Result<VersionedVerification, AuditError> {
+        let key = Self::validate_key(&env, auditor, Some(scope))?;
+        key.check_employee(&employee)?;
+
+        let commitments = SalaryCommitmentContractClient::new(&env, &commitment_contract);
+        let current = commitments.get_commitment(&employee);
+        let stored_commitment = if version == current.version {
+            current.commitment
+        } else {
+            commitments
+                .get_commitment_history(&employee)
+                .iter()
+                .find(|snapshot| snapshot.version == version)
+                .ok_or(AuditError::VersionNotFound)?
+                .commitment
+        };
+
+        let matched = Self::verify_with_key(
+            &env,
+            &key,
+            &stored_commitment,
+            claimed_amount,
+            &blinding_factor,
+        );
+
+        Ok(VersionedVerification {
+            matched,
+            verified_version: version,
+            current_version: current.version,
+            outdated: version < current.version,
+        })
+    }
+
+    /// Load the auditor's key and run every per-call check on it: auth,
+    /// expiry, rate limit, and that `scope` (or, if `None`, the key's
+    /// granted scope) is granted and allows commitment verification.
+    fn validate_key(
+        env: &Env,
+        auditor: Address,
+        scope: Option<AuditScope>,
+    ) -> Result<ValidatedKey, AuditError> {
+        let record = Self::authorize_auditor(env, auditor.clone())?;
+        let scope = match scope {
+            Some(scope) => {
+                Self::verify_scope_for_commitment(scope)?;
+                Self::verify_scope_granted(env, &auditor, scope)?;
+                scope
+            }
+            None => {
+                let scope = Self::get_key_scope(env.clone(), auditor.clone());
+                Self::verify_scope_for_commitment(scope)?;
+                scope
+            }
+        };
+
+        Ok(ValidatedKey {
+            auditor,
+            key_bytes: record.key_bytes,
+            scope,
+            allowed_employees: record.allowed_employees,
+        })
+    }
+
+    /// Verify one opening against `stored_commitment` under an already
+    /// validated key and log the result.
+    fn verify_with_key(
+        env: &Env,
+        key: &ValidatedKey,
+        stored_commitment: &BytesN<32>,
+        claimed_amount: i128,
+        blinding_factor: &BytesN<32>,
+    ) -> bool {
+        let matched = Self::verify_commitment_inner(
+            env,
+            &key.auditor,
+            &key.key_bytes,
+            stored_commitment,
+            claimed_amount,
+            blinding_factor,
+            key.scope,
+        );
+        Self::record_audit_log(env, &key.auditor, key.scope, matched);
+        matched
+    }
+
     fn verify_scope_for_commitment(scope: AuditScope) -> Result<(), AuditError> {
         if scope == AuditScope::AggregateOnly {
             return Err(AuditError::InsufficientScope);
@@ -289,22 +1106,85 @@ impl AuditModule {
         Ok(())
     }
 
+    /// Reject a period reaching outside the key's time-range window.
+    /// Keys issued without a window, such as `FullCompany` keys, allow any
+    /// period.
+    fn verify_period_allowed(
+        record: &ViewKeyRecord,
+        period_start: u64,
+        period_end: u64,
+    ) -> Result<(), AuditError> {
+        let too_early = record
+            .allowed_start
+            .is_some_and(|start| period_start < start);
+        let too_late = record.allowed_end.is_some_and(|end| period_end > end);
+        if too_early || too_late {
+            return Err(AuditError::InsufficientScope);
+        }
+        Ok(())
+    }
+
+    fn verify_scope_granted(
+        env: &Env,
+        auditor: &Address,
+        scope: AuditScope,
+    ) -> Result<(), AuditError> {
+        let granted = Self::get_key_scope(env.clone(), auditor.clone());
+        if scope != granted && !scope.is_narrower_than(granted) {
+            return Err(AuditError::InsufficientScope);
+        }
+        Ok(())
+    }
+
     fn authorize_auditor(env: &Env, auditor: Address) -> Result<ViewKeyRecord, AuditError> {
         auditor.require_auth();
 
         let record: ViewKeyRecord = env
             .storage()
             .persistent()
-            .get(&DataKey::AuditorKey(auditor))
+            .get(&DataKey::AuditorKey(auditor.clone()))
             .ok_or(AuditError::KeyNotFound)?;
 
-        if env.ledger().sequence() > record.expiration_ledger {
+        if record.is_expired(env) {
             return Err(AuditError::KeyExpired);
         }
 
+        Self::consume_rate_limit(env, &auditor)?;
+
         Ok(record)
     }
 
+    /// Count one operation against the auditor's rate limit, starting a new
+    /// window once `window_secs` have passed since the current one opened.
+    fn consume_rate_limit(env: &Env, auditor: &Address) -> Result<(), AuditError> {
+        let limit: RateLimit = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::RateLimit(auditor.clone()))
+        {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+
+        let now = env.ledger().timestamp();
+        let key = DataKey::RateWindow(auditor.clone());
+        let mut window = match env.storage().persistent().get::<DataKey, RateWindow>(&key) {
+            Some(w) if now < w.window_start.saturating_add(limit.window_secs) => w,
+            _ => RateWindow {
+                window_start: now,
+                ops: 0,
+            },
+        };
+
+        if window.ops >= limit.max_ops {
+            return Err(AuditError::RateLimited);
+        }
+        window.ops += 1;
+        env.storage().persistent().set(&key, &window);
+
+        Ok(())
+    }
+
     fn verify_commitment_inner(
         env: &Env,
         auditor: &Address,
@@ -331,23 +1211,42 @@ impl AuditModule {
         matched
     }
 
+    /// Report a company's headcount from the registry and its total paid
+    /// from the payment executor.
+    ///
+    /// `total_paid` is the executor's running total for the company; the
+    /// executor does not index payments by time, so the period bounds label
+    /// the report rather than filter it.
     pub fn generate_aggregate_report(
         env: Env,
         auditor: Address,
-        company_id: Symbol,
+        company_id: u64,
         period_start: u64,
         period_end: u64,
     ) -> Result<AuditReport, AuditError> {
-        Self::authorize_auditor(&env, auditor.clone())?;
+        let addresses = Self::get_addresses(env.clone())?;
+        let record = Self::authorize_auditor(&env, auditor.clone())?;
+        Self::verify_period_allowed(&record, period_start, period_end)?;
 
-        let report = AuditReport {
-            company_id: company_id.clone(),
-            total_employees: 0,
-            total_paid: 0,
+        let (period_start, period_end) =
+            if Self::get_key_scope(env.clone(), auditor.clone()) == AuditScope::AggregateOnly {
+                Self::coarsen_period(period_start, period_end)
+            } else {
+                (period_start, period_end)
+            };
+
+        let registry = PayrollRegistryClient::new(&env, &addresses.registry);
+        let executor = PaymentExecutorClient::new(&env, &addresses.executor);
+        let mut report = AuditReport {
+            company_id,
+            total_employees: registry.get_employee_count(&company_id),
+            total_paid: executor.get_total_paid(&company_id),
             period_start,
             period_end,
             verified: true,
+            report_hash: BytesN::from_array(&env, &[0u8; 32]),
         };
+        report.report_hash = Self::compute_report_hash(&env, &report);
 
         env.events().publish(
             (
@@ -355,13 +1254,14 @@ impl AuditModule {
                 auditor.clone(),
             ),
             (
-                report.company_id.clone(),
+                report.company_id,
                 report.period_start,
                 report.period_end,
+                report.report_hash.clone(),
             ),
         );
         // topics : ("AggregateAuditGenerated", auditor)
-        // data   : (company_id, period_start, period_end)
+        // data   : (company_id, period_start, period_end, report_hash)
 
         // Record the aggregate report generation as an audit log entry.
         Self::record_audit_log(&env, &auditor, AuditScope::AggregateOnly, true);
@@ -369,6 +1269,15 @@ impl AuditModule {
         Ok(report)
     }
 
+    /// Whether `report.report_hash` matches the report's other fields.
+    ///
+    /// A `true` result shows the report was not altered after generation;
+    /// matching the hash against an `AggregateAuditGenerated` event shows the
+    /// contract generated it.
+    pub fn verify_report_hash(env: Env, report: AuditReport) -> bool {
+        Self::compute_report_hash(&env, &report) == report.report_hash
+    }
+
     // -----------------------------------------------------------------------
     // Audit query patterns — company-level, employee-level, period-level
     //
@@ -509,7 +1418,7 @@ impl AuditModule {
     /// Store a single audit log entry keyed by (company_id, counter) and
     /// increment the counter. Called after every verification / report.
     fn record_audit_log(env: &Env, auditor: &Address, scope: AuditScope, matched: bool) {
-        let company_id = Symbol::new(env, "default");
+        let company_id = Symbol::new(env, DEFAULT_COMPANY);
         let counter: u32 = env
             .storage()
             .persistent()
@@ -532,6 +1441,25 @@ impl AuditModule {
             .set(&DataKey::AuditLogCounter(company_id), &(counter + 1));
     }
 
+    /// Widen `[start, end]` to whole `AGGREGATE_BOUND_RESOLUTION_SECS` units.
+    fn coarsen_period(start: u64, end: u64) -> (u64, u64) {
+        let unit = AGGREGATE_BOUND_RESOLUTION_SECS;
+        let start = start - start % unit;
+        let end = match end % unit {
+            0 => end,
+            rem => end.saturating_add(unit - rem),
+        };
+        (start, end)
+    }
+
+    /// SHA-256 over `tag ‖ body`, so hashes built for different purposes
+    /// never share a preimage space.
+    fn tagged_sha256(env: &Env, tag: &[u8], body: &Bytes) -> BytesN<32> {
+        let mut preimage = Bytes::from_slice(env, tag);
+        preimage.append(body);
+        env.crypto().sha256(&preimage).into()
+    }
+
     fn derive_key_bytes(env: &Env, auditor: &Address, expiration_ledger: u32) -> BytesN<32> {
         let mut preimage = Bytes::new(env);
 
@@ -540,15 +1468,25 @@ impl AuditModule {
         preimage.extend_from_array(&expiration_ledger.to_le_bytes());
         preimage.extend_from_array(&env.ledger().sequence().to_le_bytes());
 
-        env.crypto().sha256(&preimage).into()
+        Self::tagged_sha256(env, VIEW_KEY_DOMAIN, &preimage)
     }
 
+    /// Same commitment `SalaryCommitmentContract::compute_commitment`
+    /// produces without a Poseidon host.
     fn compute_commitment(env: &Env, amount: i128, blinding: &BytesN<32>) -> BytesN<32> {
+        salary_commitment::sha256_commitment(env, amount, blinding)
+    }
+
+    /// Hash of every `AuditReport` field except `report_hash` itself.
+    fn compute_report_hash(env: &Env, report: &AuditReport) -> BytesN<32> {
         let mut preimage = Bytes::new(env);
-        preimage.extend_from_array(&amount.to_le_bytes());
-        let blinding_slice: [u8; 32] = blinding.into();
-        preimage.extend_from_array(&blinding_slice);
-        env.crypto().sha256(&preimage).into()
+        preimage.extend_from_array(&report.company_id.to_le_bytes());
+        preimage.extend_from_array(&report.total_employees.to_le_bytes());
+        preimage.extend_from_array(&report.total_paid.to_le_bytes());
+        preimage.extend_from_array(&report.period_start.to_le_bytes());
+        preimage.extend_from_array(&report.period_end.to_le_bytes());
+        preimage.push_back(report.verified as u8);
+        Self::tagged_sha256(env, REPORT_DOMAIN, &preimage)
     }
 
     fn compute_keyed_commitment(
@@ -1,6 +1,8 @@
 use super::*;
+use payment_executor::PaymentExecutor;
+use payroll_registry::PayrollRegistry;
 use soroban_sdk::testutils::{Address as _, Events, Ledger as _};
-use soroban_sdk::{Env, Symbol, TryIntoVal};
+use soroban_sdk::{Env, IntoVal, Symbol, TryIntoVal};
 
 // ---------------------------------------------------------------------------
 // Helpers
@@ -13,6 +15,25 @@ fn setup() -> (Env, soroban_sdk::Address) {
     (env, contract_id)
 }
 
+/// Point the module at a fresh registry holding one two-employee company
+/// and an executor that has paid nothing, returning the company's ID.
+fn report_company(env: &Env, client: &AuditModuleClient) -> u64 {
+    let registry_id = env.register_contract(None, PayrollRegistry);
+    let registry = payroll_registry::PayrollRegistryClient::new(env, &registry_id);
+    let admin = Address::generate(env);
+    let company_id = registry.register_company(&admin, &Address::generate(env));
+    for _ in 0..2 {
+        registry.add_employee(
+            &company_id,
+            &admin,
+            &Address::generate(env),
+            &BytesN::from_array(env, &[1u8; 32]),
+        );
+    }
+    client.initialize(&env.register_contract(None, PaymentExecutor), &registry_id);
+    company_id
+}
+
 // ---------------------------------------------------------------------------
 // generate_view_key / verify_access
 // ---------------------------------------------------------------------------
@@ -121,13 +142,13 @@ fn test_revoke_removes_key() {
     assert_eq!(after, before + 1);
 
     let event = env.events().all().get(after - 1).unwrap();
-    assert_eq!(event.1.len(), 3);
+    assert_eq!(event.1.len(), 2);
     let sym0: Symbol = event.1.get(0).unwrap().try_into_val(&env.clone()).unwrap();
-    assert_eq!(sym0, Symbol::new(&env, "AuditAccessRevoked"));
-    let addr0: Address = event.1.get(1).unwrap().try_into_val(&env.clone()).unwrap();
-    assert_eq!(addr0, admin);
-    let addr1: Address = event.1.get(2).unwrap().try_into_val(&env.clone()).unwrap();
-    assert_eq!(addr1, auditor);
+    assert_eq!(sym0, Symbol::new(&env, "ViewKeyRevoked"));
+    let company: Symbol = event.1.get(1).unwrap().try_into_val(&env.clone()).unwrap();
+    assert_eq!(company, Symbol::new(&env, DEFAULT_COMPANY));
+    let (addr, _, _): (Address, BytesN<32>, u64) = event.2.try_into_val(&env).unwrap();
+    assert_eq!(addr, auditor);
 
     assert!(!client.verify_access(&auditor));
 
@@ -147,6 +168,54 @@ fn test_revoke_wrong_admin_fails() {
     assert!(client.try_revoke_view_key(&interloper, &auditor).is_err());
 }
 
+#[test]
+fn test_auditor_can_surrender_own_key() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key_id = client.generate_view_key(&auditor, &(seq + 1_000));
+
+    client.surrender_view_key(&auditor, &key_id);
+    assert_eq!(env.auths()[0].0, auditor);
+
+    let event = env.events().all().last().unwrap();
+    let sym0: Symbol = event.1.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(sym0, Symbol::new(&env, "ViewKeySurrendered"));
+    let company: Symbol = event.1.get(1).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(company, Symbol::new(&env, "default"));
+    let (who, surrendered, _at): (Address, BytesN<32>, u64) = event.2.try_into_val(&env).unwrap();
+    assert_eq!(who, auditor);
+    assert_eq!(surrendered, key_id);
+
+    assert!(!client.verify_access(&auditor));
+    assert!(client.try_get_view_key(&auditor).is_err());
+}
+
+#[test]
+fn test_cannot_surrender_another_auditors_key() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let other = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key_id = client.generate_view_key(&auditor, &(seq + 1_000));
+
+    // An address with no key of its own.
+    let result = client.try_surrender_view_key(&other, &key_id);
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::KeyNotFound);
+
+    // An auditor holding a different key.
+    client.generate_view_key(&other, &(seq + 1_000));
+    let result = client.try_surrender_view_key(&other, &key_id);
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::InvalidViewKey);
+
+    assert!(client.verify_access(&auditor));
+    assert!(client.verify_access(&other));
+}
+
 // ---------------------------------------------------------------------------
 // Commitment verification
 // ---------------------------------------------------------------------------
@@ -163,7 +232,7 @@ fn test_verify_commitment_with_key_matches() {
     let amount: i128 = 500_000;
     let blinding = BytesN::from_array(&env, &[0xAB; 32]);
 
-    let mut preimage = soroban_sdk::Bytes::new(&env);
+    let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
     preimage.extend_from_array(&amount.to_le_bytes());
     let blinding_slice: [u8; 32] = (&blinding).into();
     preimage.extend_from_array(&blinding_slice);
@@ -171,6 +240,7 @@ fn test_verify_commitment_with_key_matches() {
 
     assert!(client.verify_commitment_with_key(
         &auditor,
+        &Address::generate(&env),
         &stored,
         &amount,
         &blinding,
@@ -180,6 +250,7 @@ fn test_verify_commitment_with_key_matches() {
     // Wrong amount must return CommitmentMismatch error
     let result = client.try_verify_commitment_with_key(
         &auditor,
+        &Address::generate(&env),
         &stored,
         &999_i128,
         &blinding,
@@ -200,7 +271,7 @@ fn test_verify_commitment_with_supplied_key_matches() {
     let amount: i128 = 120_000;
     let blinding = BytesN::from_array(&env, &[0xCD; 32]);
 
-    let mut preimage = soroban_sdk::Bytes::new(&env);
+    let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
     preimage.extend_from_array(&amount.to_le_bytes());
     let blinding_slice: [u8; 32] = (&blinding).into();
     preimage.extend_from_array(&blinding_slice);
@@ -208,6 +279,7 @@ fn test_verify_commitment_with_supplied_key_matches() {
 
     assert!(client.verify_commitment_with_view_key(
         &auditor,
+        &Address::generate(&env),
         &key,
         &stored,
         &amount,
@@ -228,7 +300,7 @@ fn test_verify_commitment_with_supplied_key_rejects_wrong_key() {
 
     let amount: i128 = 120_000;
     let blinding = BytesN::from_array(&env, &[0xCD; 32]);
-    let mut preimage = soroban_sdk::Bytes::new(&env);
+    let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
     preimage.extend_from_array(&amount.to_le_bytes());
     let blinding_slice: [u8; 32] = (&blinding).into();
     preimage.extend_from_array(&blinding_slice);
@@ -237,6 +309,7 @@ fn test_verify_commitment_with_supplied_key_rejects_wrong_key() {
     assert!(client
         .try_verify_commitment_with_view_key(
             &auditor,
+            &Address::generate(&env),
             &wrong_key,
             &stored,
             &amount,
@@ -259,7 +332,7 @@ fn test_cross_auditor_key_contamination_is_rejected() {
 
     let amount: i128 = 77_000;
     let blinding = BytesN::from_array(&env, &[0x11; 32]);
-    let mut preimage = soroban_sdk::Bytes::new(&env);
+    let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
     preimage.extend_from_array(&amount.to_le_bytes());
     let blinding_slice: [u8; 32] = (&blinding).into();
     preimage.extend_from_array(&blinding_slice);
@@ -268,6 +341,7 @@ fn test_cross_auditor_key_contamination_is_rejected() {
     assert!(client
         .try_verify_commitment_with_view_key(
             &auditor_b,
+            &Address::generate(&env),
             &key_a,
             &stored,
             &amount,
@@ -290,6 +364,7 @@ fn test_aggregate_only_scope_rejects_commitment_verification() {
     assert!(client
         .try_verify_commitment_with_key(
             &auditor,
+            &Address::generate(&env),
             &dummy,
             &0_i128,
             &dummy,
@@ -309,7 +384,7 @@ fn test_successful_commitment_audit_emits_event() {
 
     let amount: i128 = 42_000;
     let blinding = BytesN::from_array(&env, &[0x99; 32]);
-    let mut preimage = soroban_sdk::Bytes::new(&env);
+    let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
     preimage.extend_from_array(&amount.to_le_bytes());
     let blinding_slice: [u8; 32] = (&blinding).into();
     preimage.extend_from_array(&blinding_slice);
@@ -318,6 +393,7 @@ fn test_successful_commitment_audit_emits_event() {
     let before = env.events().all().len();
     assert!(client.verify_commitment_with_key(
         &auditor,
+        &Address::generate(&env),
         &stored,
         &amount,
         &blinding,
@@ -340,13 +416,16 @@ fn test_generate_aggregate_report_valid_key() {
     let seq = env.ledger().sequence();
     client.generate_view_key(&auditor, &(seq + 1_000));
 
-    let company_id = Symbol::new(&env, "ACME");
+    let company_id = report_company(&env, &client);
     let now = env.ledger().timestamp();
     let before = env.events().all().len();
     let report = client.generate_aggregate_report(&auditor, &company_id, &now, &(now + 86_400));
     let after = env.events().all().len();
 
     assert_eq!(report.company_id, company_id);
+    assert_eq!(report.total_employees, 2);
+    assert_eq!(report.total_paid, 0);
+    assert!(report.verified);
     assert_eq!(report.period_start, now);
     assert_eq!(after, before + 1);
 
@@ -356,6 +435,58 @@ fn test_generate_aggregate_report_valid_key() {
         .is_err());
 }
 
+#[test]
+fn test_report_hash_is_published_and_verifies() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    client.generate_view_key(&auditor, &(seq + 1_000));
+
+    let company_id = report_company(&env, &client);
+    let now = env.ledger().timestamp();
+    let report = client.generate_aggregate_report(&auditor, &company_id, &now, &(now + 86_400));
+    assert!(client.verify_report_hash(&report));
+
+    let event = env.events().all().last().unwrap();
+    let (_, _, _, published): (u64, u64, u64, BytesN<32>) = event.2.try_into_val(&env).unwrap();
+    assert_eq!(published, report.report_hash);
+}
+
+#[test]
+fn test_tampered_report_fails_hash_check() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    client.generate_view_key(&auditor, &(seq + 1_000));
+
+    let company_id = report_company(&env, &client);
+    let now = env.ledger().timestamp();
+    let report = client.generate_aggregate_report(&auditor, &company_id, &now, &(now + 86_400));
+
+    let mut tampered = report.clone();
+    tampered.total_paid = 1_000_000;
+    assert!(!client.verify_report_hash(&tampered));
+
+    let mut tampered = report.clone();
+    tampered.company_id += 1;
+    assert!(!client.verify_report_hash(&tampered));
+
+    let mut tampered = report.clone();
+    tampered.period_end += 1;
+    assert!(!client.verify_report_hash(&tampered));
+
+    // Re-hashing a tampered report cannot be passed off as the original:
+    // the hash no longer matches the one published on-chain.
+    let mut forged = report;
+    forged.verified = false;
+    forged.report_hash = BytesN::from_array(&env, &[0u8; 32]);
+    assert!(!client.verify_report_hash(&forged));
+}
+
 // ---------------------------------------------------------------------------
 // Audit query patterns — company-level, employee-level, period-level
 // ---------------------------------------------------------------------------
@@ -371,7 +502,7 @@ fn test_query_by_company_returns_audit_log_entries() {
 
     let amount: i128 = 100_000;
     let blinding = BytesN::from_array(&env, &[0xBB; 32]);
-    let mut preimage = soroban_sdk::Bytes::new(&env);
+    let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
     preimage.extend_from_array(&amount.to_le_bytes());
     let blinding_slice: [u8; 32] = (&blinding).into();
     preimage.extend_from_array(&blinding_slice);
@@ -379,6 +510,7 @@ fn test_query_by_company_returns_audit_log_entries() {
 
     client.verify_commitment_with_key(
         &auditor,
+        &Address::generate(&env),
         &stored,
         &amount,
         &blinding,
@@ -402,7 +534,7 @@ fn test_query_by_employee_filters_by_auditor() {
 
     let amount: i128 = 50_000;
     let blinding = BytesN::from_array(&env, &[0xCC; 32]);
-    let mut preimage = soroban_sdk::Bytes::new(&env);
+    let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
     preimage.extend_from_array(&amount.to_le_bytes());
     let blinding_slice: [u8; 32] = (&blinding).into();
     preimage.extend_from_array(&blinding_slice);
@@ -410,6 +542,7 @@ fn test_query_by_employee_filters_by_auditor() {
 
     client.verify_commitment_with_key(
         &auditor,
+        &Address::generate(&env),
         &stored,
         &amount,
         &blinding,
@@ -433,7 +566,7 @@ fn test_query_by_period_filters_by_time_range() {
 
     let amount: i128 = 75_000;
     let blinding = BytesN::from_array(&env, &[0xDD; 32]);
-    let mut preimage = soroban_sdk::Bytes::new(&env);
+    let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
     preimage.extend_from_array(&amount.to_le_bytes());
     let blinding_slice: [u8; 32] = (&blinding).into();
     preimage.extend_from_array(&blinding_slice);
@@ -442,6 +575,7 @@ fn test_query_by_period_filters_by_time_range() {
     let ts = env.ledger().timestamp();
     client.verify_commitment_with_key(
         &auditor,
+        &Address::generate(&env),
         &stored,
         &amount,
         &blinding,
@@ -468,7 +602,7 @@ fn test_get_audit_log_count_increments() {
 
     let amount: i128 = 25_000;
     let blinding = BytesN::from_array(&env, &[0xEE; 32]);
-    let mut preimage = soroban_sdk::Bytes::new(&env);
+    let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
     preimage.extend_from_array(&amount.to_le_bytes());
     let blinding_slice: [u8; 32] = (&blinding).into();
     preimage.extend_from_array(&blinding_slice);
@@ -476,6 +610,7 @@ fn test_get_audit_log_count_increments() {
 
     client.verify_commitment_with_key(
         &auditor,
+        &Address::generate(&env),
         &stored,
         &amount,
         &blinding,
@@ -500,7 +635,7 @@ fn test_export_audit_summary_returns_correct_counts() {
     // Generate one passing and one failing audit entry.
     let amount: i128 = 10_000;
     let blinding = BytesN::from_array(&env, &[0xAA; 32]);
-    let mut preimage = soroban_sdk::Bytes::new(&env);
+    let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
     preimage.extend_from_array(&amount.to_le_bytes());
     let blinding_slice: [u8; 32] = (&blinding).into();
     preimage.extend_from_array(&blinding_slice);
@@ -509,6 +644,7 @@ fn test_export_audit_summary_returns_correct_counts() {
     // Pass
     client.verify_commitment_with_key(
         &auditor,
+        &Address::generate(&env),
         &correct_commitment,
         &amount,
         &blinding,
@@ -518,6 +654,7 @@ fn test_export_audit_summary_returns_correct_counts() {
     // Fail — wrong amount causes CommitmentMismatch
     let _ = client.try_verify_commitment_with_key(
         &auditor,
+        &Address::generate(&env),
         &correct_commitment,
         &999_i128,
         &blinding,
@@ -547,7 +684,7 @@ fn test_export_audit_summary_excludes_out_of_period_entries() {
 
     let amount: i128 = 5_000;
     let blinding = BytesN::from_array(&env, &[0xBB; 32]);
-    let mut preimage = soroban_sdk::Bytes::new(&env);
+    let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
     preimage.extend_from_array(&amount.to_le_bytes());
     let blinding_slice: [u8; 32] = (&blinding).into();
     preimage.extend_from_array(&blinding_slice);
@@ -555,6 +692,7 @@ fn test_export_audit_summary_excludes_out_of_period_entries() {
 
     client.verify_commitment_with_key(
         &auditor,
+        &Address::generate(&env),
         &commitment,
         &amount,
         &blinding,
@@ -607,3 +745,1000 @@ fn test_export_audit_summary_emits_event() {
 
     assert!(env.events().all().len() > before);
 }
+
+// ── Scope downgrade and batch revocation ─────────────────────────────────────
+
+#[test]
+fn test_downgrade_scope_narrows_key() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key_id = client.generate_view_key(&auditor, &(seq + 1_000));
+    assert_eq!(client.get_key_scope(&auditor), AuditScope::FullCompany);
+
+    let admin = contract_id.clone();
+    client.downgrade_scope(&admin, &key_id, &AuditScope::EmployeeList);
+    assert_eq!(client.get_key_scope(&auditor), AuditScope::EmployeeList);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (
+            Symbol::new(&env, "ViewKeyDowngraded"),
+            Symbol::new(&env, "default")
+        )
+            .into_val(&env)
+    );
+    let data: (Address, BytesN<32>, AuditScope, AuditScope) = event.2.try_into_val(&env).unwrap();
+    assert_eq!(
+        data,
+        (
+            auditor.clone(),
+            key_id,
+            AuditScope::FullCompany,
+            AuditScope::EmployeeList
+        )
+    );
+
+    // The downgraded key can no longer be used at the broader scope.
+    let stored = BytesN::from_array(&env, &[0u8; 32]);
+    let blinding = BytesN::from_array(&env, &[0u8; 32]);
+    let result = client.try_verify_commitment_with_key(
+        &auditor,
+        &Address::generate(&env),
+        &stored,
+        &1_i128,
+        &blinding,
+        &AuditScope::FullCompany,
+    );
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::InsufficientScope);
+}
+
+#[test]
+fn test_downgrade_scope_rejects_broadening() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key_id = client.generate_view_key(&auditor, &(seq + 1_000));
+
+    let admin = contract_id.clone();
+    client.downgrade_scope(&admin, &key_id, &AuditScope::TimeRange);
+
+    for scope in [
+        AuditScope::FullCompany,
+        AuditScope::TimeRange,
+        AuditScope::EmployeeList,
+    ] {
+        let result = client.try_downgrade_scope(&admin, &key_id, &scope);
+        assert_eq!(result.unwrap_err().unwrap(), AuditError::ScopeNotNarrower);
+    }
+    assert_eq!(client.get_key_scope(&auditor), AuditScope::TimeRange);
+}
+
+#[test]
+fn test_downgrade_scope_wrong_admin_fails() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key_id = client.generate_view_key(&auditor, &(seq + 1_000));
+
+    let interloper = soroban_sdk::Address::generate(&env);
+    let result = client.try_downgrade_scope(&interloper, &key_id, &AuditScope::AggregateOnly);
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::NotKeyGranter);
+}
+
+#[test]
+fn test_downgrade_scope_unknown_key_fails() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let unknown = BytesN::from_array(&env, &[9u8; 32]);
+    let result = client.try_downgrade_scope(&contract_id, &unknown, &AuditScope::AggregateOnly);
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::KeyNotFound);
+}
+
+#[test]
+fn test_get_scope_reads_current_scope_by_key_id() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key_id = client.generate_view_key(&auditor, &(seq + 1_000));
+    assert_eq!(client.get_scope(&key_id), AuditScope::FullCompany);
+
+    client.downgrade_scope(&contract_id, &key_id, &AuditScope::TimeRange);
+    assert_eq!(client.get_scope(&key_id), AuditScope::TimeRange);
+
+    let unknown = BytesN::from_array(&env, &[9u8; 32]);
+    assert_eq!(
+        client.try_get_scope(&unknown).unwrap_err().unwrap(),
+        AuditError::KeyNotFound
+    );
+}
+
+#[test]
+fn test_downgrade_appends_scope_history_entry() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let key_id = client.generate_view_key(&auditor, &(seq + 1_000));
+    assert_eq!(
+        client.get_scope_history(&key_id),
+        Vec::from_array(&env, [(1_000u64, AuditScope::FullCompany)])
+    );
+
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    client.downgrade_scope(&contract_id, &key_id, &AuditScope::EmployeeList);
+    env.ledger().with_mut(|l| l.timestamp = 3_000);
+    client.downgrade_scope(&contract_id, &key_id, &AuditScope::AggregateOnly);
+
+    // A rejected downgrade leaves no trace.
+    let _ = client.try_downgrade_scope(&contract_id, &key_id, &AuditScope::TimeRange);
+
+    let history = client.get_scope_history(&key_id);
+    assert_eq!(
+        history,
+        Vec::from_array(
+            &env,
+            [
+                (1_000u64, AuditScope::FullCompany),
+                (2_000u64, AuditScope::EmployeeList),
+                (3_000u64, AuditScope::AggregateOnly),
+            ]
+        )
+    );
+
+    // The live-key lookup ends with the key; the history does not.
+    client.revoke_view_key(&contract_id, &auditor);
+    assert!(client.try_get_scope(&key_id).is_err());
+    assert_eq!(client.get_scope_history(&key_id), history);
+}
+
+#[test]
+fn test_revoke_view_keys_counts_only_live_keys() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let seq = env.ledger().sequence();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let stale = Address::generate(&env);
+    let key_a = client.generate_view_key(&a, &(seq + 1_000));
+    let key_b = client.generate_view_key(&b, &(seq + 1_000));
+    let stale_key = client.generate_view_key(&stale, &seq);
+    env.ledger().set_sequence_number(seq + 1);
+
+    let admin = contract_id.clone();
+    let revoked = client.revoke_view_keys(
+        &admin,
+        &soroban_sdk::Vec::from_array(
+            &env,
+            [
+                key_a.clone(),
+                stale_key.clone(),
+                BytesN::from_array(&env, &[0xAB; 32]),
+                key_b,
+                key_a,
+            ],
+        ),
+    );
+
+    assert_eq!(revoked, 2);
+    assert!(!client.verify_access(&a));
+    assert!(!client.verify_access(&b));
+    // The expired key is skipped, not revoked.
+    assert_eq!(client.get_view_key(&stale).key_bytes, stale_key);
+}
+
+#[test]
+fn test_revoke_view_keys_reverts_on_foreign_key() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let seq = env.ledger().sequence();
+    let ours = Address::generate(&env);
+    let theirs = Address::generate(&env);
+    let our_key = client.generate_view_key(&ours, &(seq + 1_000));
+    let their_key = client.generate_view_key(&theirs, &(seq + 1_000));
+    env.as_contract(&contract_id, || {
+        let key = DataKey::AuditorKey(theirs.clone());
+        let mut record: ViewKeyRecord = env.storage().persistent().get(&key).unwrap();
+        record.granted_by = Address::generate(&env);
+        env.storage().persistent().set(&key, &record);
+    });
+
+    let admin = contract_id.clone();
+    let result = client.try_revoke_view_keys(
+        &admin,
+        &soroban_sdk::Vec::from_array(&env, [our_key, their_key]),
+    );
+
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::NotKeyGranter);
+    assert!(client.verify_access(&ours));
+    assert!(client.verify_access(&theirs));
+}
+
+// ── Rate limiting ────────────────────────────────────────────────────────────
+
+#[test]
+fn test_rate_limit_rejects_ops_beyond_window_budget() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    client.generate_view_key(&auditor, &(seq + 1_000));
+
+    let admin = contract_id.clone();
+    client.set_rate_limit(&admin, &auditor, &2, &3_600);
+    assert_eq!(
+        client.get_rate_limit(&auditor),
+        Some(RateLimit {
+            max_ops: 2,
+            window_secs: 3_600,
+        })
+    );
+
+    let company_id = report_company(&env, &client);
+    let now = env.ledger().timestamp();
+    client.generate_aggregate_report(&auditor, &company_id, &now, &(now + 86_400));
+    client.generate_aggregate_report(&auditor, &company_id, &now, &(now + 86_400));
+
+    let result = client.try_generate_aggregate_report(&auditor, &company_id, &now, &(now + 86_400));
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::RateLimited);
+
+    // The budget is shared by every audit operation on the key.
+    let acme = Symbol::new(&env, "ACME");
+    let result = client.try_export_audit_summary(&auditor, &acme, &now, &(now + 86_400));
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::RateLimited);
+    assert_eq!(client.get_audit_log_count(&Symbol::new(&env, "default")), 2);
+}
+
+#[test]
+fn test_rate_limit_resets_after_window_advances() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    client.generate_view_key(&auditor, &(seq + 1_000));
+
+    let admin = contract_id.clone();
+    client.set_rate_limit(&admin, &auditor, &1, &3_600);
+
+    let company_id = report_company(&env, &client);
+    let start = env.ledger().timestamp();
+    client.generate_aggregate_report(&auditor, &company_id, &start, &(start + 86_400));
+
+    // Still inside the window.
+    env.ledger().set_timestamp(start + 3_599);
+    let result =
+        client.try_generate_aggregate_report(&auditor, &company_id, &start, &(start + 86_400));
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::RateLimited);
+
+    // The window has rolled over.
+    env.ledger().set_timestamp(start + 3_600);
+    client.generate_aggregate_report(&auditor, &company_id, &start, &(start + 86_400));
+    let result =
+        client.try_generate_aggregate_report(&auditor, &company_id, &start, &(start + 86_400));
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::RateLimited);
+}
+
+#[test]
+fn test_set_rate_limit_wrong_admin_fails() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    client.generate_view_key(&auditor, &(seq + 1_000));
+
+    let interloper = soroban_sdk::Address::generate(&env);
+    let result = client.try_set_rate_limit(&interloper, &auditor, &0, &3_600);
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::NotKeyGranter);
+    assert_eq!(client.get_rate_limit(&auditor), None);
+}
+
+// ── Aggregate report redaction ───────────────────────────────────────────────
+
+#[test]
+fn test_aggregate_only_report_has_day_aligned_bounds() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key_id = client.generate_view_key(&auditor, &(seq + 1_000));
+    client.downgrade_scope(&contract_id, &key_id, &AuditScope::AggregateOnly);
+
+    let company_id = report_company(&env, &client);
+    let start = 10 * 86_400 + 3_725;
+    let end = 40 * 86_400 + 61;
+    let report = client.generate_aggregate_report(&auditor, &company_id, &start, &end);
+
+    assert_eq!(report.period_start, 10 * 86_400);
+    assert_eq!(report.period_end, 41 * 86_400);
+    assert_eq!(report.total_paid, 0);
+}
+
+#[test]
+fn test_full_company_report_has_exact_bounds() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let seq = env.ledger().sequence();
+    client.generate_view_key(&auditor, &(seq + 1_000));
+
+    let company_id = report_company(&env, &client);
+    let start = 10 * 86_400 + 3_725;
+    let end = 40 * 86_400 + 61;
+    let report = client.generate_aggregate_report(&auditor, &company_id, &start, &end);
+
+    assert_eq!(report.period_start, start);
+    assert_eq!(report.period_end, end);
+}
+
+// ── Hash domain separation ───────────────────────────────────────────────────
+
+#[test]
+fn test_changing_domain_tag_changes_hash() {
+    let (env, contract_id) = setup();
+    env.as_contract(&contract_id, || {
+        let body = soroban_sdk::Bytes::from_array(&env, &[7u8; 48]);
+        let v1 = AuditModule::tagged_sha256(&env, b"ZKP_VIEWKEY_V1", &body);
+        let v2 = AuditModule::tagged_sha256(&env, b"ZKP_VIEWKEY_V2", &body);
+        let untagged: BytesN<32> = env.crypto().sha256(&body).into();
+        assert_ne!(v1, v2);
+        assert_ne!(v1, untagged);
+    });
+}
+
+#[test]
+fn test_key_id_and_commitment_over_same_body_differ() {
+    let (env, contract_id) = setup();
+    env.as_contract(&contract_id, || {
+        let body = soroban_sdk::Bytes::from_array(&env, &[0x11; 48]);
+        let key_id = AuditModule::tagged_sha256(&env, VIEW_KEY_DOMAIN, &body);
+        let commitment = AuditModule::tagged_sha256(&env, COMMITMENT_DOMAIN, &body);
+        assert_ne!(key_id, commitment);
+    });
+
+    let amount: i128 = 1_234;
+    let blinding = BytesN::from_array(&env, &[0x22; 32]);
+    let mut untagged = soroban_sdk::Bytes::new(&env);
+    untagged.extend_from_array(&amount.to_le_bytes());
+    let blinding_slice: [u8; 32] = (&blinding).into();
+    untagged.extend_from_array(&blinding_slice);
+    let untagged_commitment: BytesN<32> = env.crypto().sha256(&untagged).into();
+    let commitment = AuditModule::compute_commitment(&env, amount, &blinding);
+    assert_ne!(commitment, untagged_commitment);
+}
+
+// ── batch_verify_commitments ─────────────────────────────────────────────────
+
+fn commitment(env: &Env, amount: i128, blinding: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = soroban_sdk::Bytes::from_slice(env, COMMITMENT_DOMAIN);
+    preimage.extend_from_array(&amount.to_le_bytes());
+    let blinding_slice: [u8; 32] = blinding.into();
+    preimage.extend_from_array(&blinding_slice);
+    env.crypto().sha256(&preimage).into()
+}
+
+#[test]
+fn test_batch_verify_reports_each_entry() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let key = client.generate_view_key(&auditor, &(env.ledger().sequence() + 1_000));
+
+    let blinding_a = BytesN::from_array(&env, &[0x01; 32]);
+    let blinding_b = BytesN::from_array(&env, &[0x02; 32]);
+    let blinding_c = BytesN::from_array(&env, &[0x03; 32]);
+    let entries = Vec::from_array(
+        &env,
+        [
+            (Address::generate(&env), 1_000i128, blinding_a.clone()),
+            // Claimed amount differs from the committed 2_000.
+            (Address::generate(&env), 2_500i128, blinding_b.clone()),
+            (Address::generate(&env), 3_000i128, blinding_c.clone()),
+        ],
+    );
+    let stored = Vec::from_array(
+        &env,
+        [
+            commitment(&env, 1_000, &blinding_a),
+            commitment(&env, 2_000, &blinding_b),
+            commitment(&env, 3_000, &blinding_c),
+        ],
+    );
+
+    let results = client.batch_verify_commitments(&key, &auditor, &entries, &stored);
+
+    assert_eq!(results, Vec::from_array(&env, [true, false, true]));
+    let company = Symbol::new(&env, "default");
+    assert_eq!(client.get_audit_log_count(&company), 3);
+}
+
+#[test]
+fn test_batch_verify_rejects_misaligned_inputs() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let key = client.generate_view_key(&auditor, &(env.ledger().sequence() + 1_000));
+
+    let blinding = BytesN::from_array(&env, &[0x01; 32]);
+    let entries = Vec::from_array(
+        &env,
+        [(Address::generate(&env), 1_000i128, blinding.clone())],
+    );
+    let stored = Vec::from_array(
+        &env,
+        [
+            commitment(&env, 1_000, &blinding),
+            commitment(&env, 2_000, &blinding),
+        ],
+    );
+
+    let result = client.try_batch_verify_commitments(&key, &auditor, &entries, &stored);
+    assert_eq!(result, Err(Ok(AuditError::LengthMismatch)));
+}
+
+#[test]
+fn test_batch_verify_rejects_other_key_and_aggregate_scope() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let other = soroban_sdk::Address::generate(&env);
+    let expiry = env.ledger().sequence() + 1_000;
+    let key = client.generate_view_key(&auditor, &expiry);
+    let other_key = client.generate_view_key(&other, &expiry);
+
+    let blinding = BytesN::from_array(&env, &[0x01; 32]);
+    let entries = Vec::from_array(
+        &env,
+        [(Address::generate(&env), 1_000i128, blinding.clone())],
+    );
+    let stored = Vec::from_array(&env, [commitment(&env, 1_000, &blinding)]);
+
+    let result = client.try_batch_verify_commitments(&other_key, &auditor, &entries, &stored);
+    assert_eq!(result, Err(Ok(AuditError::InvalidViewKey)));
+
+    let admin = contract_id.clone();
+    client.downgrade_scope(&admin, &key, &AuditScope::AggregateOnly);
+    let result = client.try_batch_verify_commitments(&key, &auditor, &entries, &stored);
+    assert_eq!(result, Err(Ok(AuditError::InsufficientScope)));
+}
+
+#[test]
+fn test_batch_verify_with_expired_key_is_rejected_wholesale() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    let expiration = env.ledger().sequence() + 10;
+    let key = client.generate_view_key(&auditor, &expiration);
+
+    let blinding_a = BytesN::from_array(&env, &[0x01; 32]);
+    let blinding_b = BytesN::from_array(&env, &[0x02; 32]);
+    let entries = Vec::from_array(
+        &env,
+        [
+            (Address::generate(&env), 1_000i128, blinding_a.clone()),
+            (Address::generate(&env), 2_000i128, blinding_b.clone()),
+        ],
+    );
+    let stored = Vec::from_array(
+        &env,
+        [
+            commitment(&env, 1_000, &blinding_a),
+            commitment(&env, 2_000, &blinding_b),
+        ],
+    );
+
+    env.ledger().set_sequence_number(expiration + 1);
+    let result = client.try_batch_verify_commitments(&key, &auditor, &entries, &stored);
+
+    assert_eq!(result, Err(Ok(AuditError::KeyExpired)));
+    let company = Symbol::new(&env, "default");
+    assert_eq!(client.get_audit_log_count(&company), 0);
+}
+
+// ── verify_commitment_version ────────────────────────────────────────────────
+
+#[test]
+fn test_verifying_superseded_commitment_reports_version_mismatch() {
+    use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let commitments_id = env.register_contract(None, SalaryCommitmentContract);
+    let commitments = SalaryCommitmentContractClient::new(&env, &commitments_id);
+    commitments.init_commitment_admin(&Address::generate(&env));
+
+    let employee = Address::generate(&env);
+    let old_blinding = BytesN::from_array(&env, &[0x01; 32]);
+    let new_blinding = BytesN::from_array(&env, &[0x02; 32]);
+    commitments.store_commitment(&employee, &commitment(&env, 1_000, &old_blinding));
+
+    let auditor = soroban_sdk::Address::generate(&env);
+    client.generate_view_key(&auditor, &(env.ledger().sequence() + 1_000));
+
+    // The salary changes after the audit key was issued.
+    commitments.update_commitment(&employee, &commitment(&env, 1_200, &new_blinding));
+
+    let stale = client.verify_commitment_version(
+        &auditor,
+        &commitments_id,
+        &employee,
+        &1,
+        &1_000,
+        &old_blinding,
+        &AuditScope::FullCompany,
+    );
+    assert_eq!(
+        stale,
+        VersionedVerification {
+            matched: true,
+            verified_version: 1,
+            current_version: 2,
+            outdated: true,
+        }
+    );
+
+    let current = client.verify_commitment_version(
+        &auditor,
+        &commitments_id,
+        &employee,
+        &2,
+        &1_200,
+        &new_blinding,
+        &AuditScope::FullCompany,
+    );
+    assert!(current.matched);
+    assert!(!current.outdated);
+
+    let result = client.try_verify_commitment_version(
+        &auditor,
+        &commitments_id,
+        &employee,
+        &3,
+        &1_200,
+        &new_blinding,
+        &AuditScope::FullCompany,
+    );
+    assert_eq!(result, Err(Ok(AuditError::VersionNotFound)));
+}
+
+// ---------------------------------------------------------------------------
+// Live-key cap
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_key_issuance_stops_at_cap_until_a_key_ends() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let company = Symbol::new(&env, DEFAULT_COMPANY);
+    let seq = env.ledger().sequence();
+
+    client.set_max_keys(&contract_id, &company, &2);
+    assert_eq!(client.get_max_keys(&company), Some(2));
+
+    let short_lived = soroban_sdk::Address::generate(&env);
+    let revoked = soroban_sdk::Address::generate(&env);
+    let waiting = soroban_sdk::Address::generate(&env);
+    client.generate_view_key(&short_lived, &(seq + 10));
+    client.generate_view_key(&revoked, &(seq + 1_000));
+
+    assert_eq!(
+        client.try_generate_view_key(&waiting, &(seq + 1_000)),
+        Err(Ok(AuditError::KeyLimitReached))
+    );
+    // Re-issuing a live key does not take another slot.
+    client.generate_view_key(&revoked, &(seq + 2_000));
+
+    client.revoke_view_key(&contract_id, &revoked);
+    client.generate_view_key(&waiting, &(seq + 1_000));
+
+    let late = soroban_sdk::Address::generate(&env);
+    assert_eq!(
+        client.try_generate_view_key(&late, &(seq + 1_000)),
+        Err(Ok(AuditError::KeyLimitReached))
+    );
+    env.ledger().set_sequence_number(seq + 11);
+    client.generate_view_key(&late, &(seq + 1_000));
+}
+
+#[test]
+fn test_set_max_keys_requires_granter() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+
+    let interloper = soroban_sdk::Address::generate(&env);
+    assert_eq!(
+        client.try_set_max_keys(&interloper, &Symbol::new(&env, DEFAULT_COMPANY), &0),
+        Err(Ok(AuditError::NotKeyGranter))
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Forfeited validity on revocation
+// ---------------------------------------------------------------------------
+
+/// Revoke `auditor`'s key and return the `(key_id, forfeited_secs)` data of
+/// the `ViewKeyRevoked` event.
+fn revoke_and_read_forfeit(
+    env: &Env,
+    client: &AuditModuleClient,
+    admin: &Address,
+    auditor: &Address,
+) -> (BytesN<32>, u64) {
+    client.revoke_view_key(admin, auditor);
+    let event = env.events().all().last().unwrap();
+    let sym0: Symbol = event.1.get(0).unwrap().try_into_val(env).unwrap();
+    assert_eq!(sym0, Symbol::new(env, "ViewKeyRevoked"));
+    let (revoked, key_id, forfeited): (Address, BytesN<32>, u64) =
+        event.2.try_into_val(env).unwrap();
+    assert_eq!(&revoked, auditor);
+    (key_id, forfeited)
+}
+
+#[test]
+fn test_mid_life_revocation_reports_forfeited_seconds() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key = client.generate_view_key(&auditor, &(seq + 1_000));
+
+    env.ledger().set_sequence_number(seq + 400);
+    let (key_id, forfeited) = revoke_and_read_forfeit(&env, &client, &contract_id, &auditor);
+
+    assert_eq!(key_id, key);
+    assert_eq!(forfeited, 600 * LEDGER_CLOSE_SECS);
+}
+
+#[test]
+fn test_near_expiry_revocation_reports_little_forfeited() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    client.generate_view_key(&auditor, &(seq + 1_000));
+
+    env.ledger().set_sequence_number(seq + 999);
+    let (_, forfeited) = revoke_and_read_forfeit(&env, &client, &contract_id, &auditor);
+
+    assert_eq!(forfeited, LEDGER_CLOSE_SECS);
+}
+
+#[test]
+fn test_expired_key_revocation_forfeits_nothing() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    client.generate_view_key(&auditor, &(seq + 10));
+
+    env.ledger().set_sequence_number(seq + 50);
+    let (_, forfeited) = revoke_and_read_forfeit(&env, &client, &contract_id, &auditor);
+
+    assert_eq!(forfeited, 0);
+}
+
+// ---------------------------------------------------------------------------
+// Report sources
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_report_before_initialize_returns_not_initialized() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    client.generate_view_key(&auditor, &(seq + 1_000));
+
+    let result = client.try_generate_aggregate_report(&auditor, &0, &0, &86_400);
+
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::NotInitialized);
+    assert_eq!(
+        client.try_get_addresses().unwrap_err().unwrap(),
+        AuditError::NotInitialized
+    );
+}
+
+#[test]
+fn test_second_initialize_returns_already_initialized() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    report_company(&env, &client);
+
+    let result = client.try_initialize(&Address::generate(&env), &Address::generate(&env));
+
+    assert_eq!(result, Err(Ok(InitError::AlreadyInitialized)));
+}
+
+// ---------------------------------------------------------------------------
+// Time-range windows
+// ---------------------------------------------------------------------------
+
+/// A `TimeRange` key for days 10–20 and a report company to use it on.
+fn time_range_auditor(env: &Env, client: &AuditModuleClient) -> (Address, u64) {
+    let auditor = Address::generate(env);
+    let seq = env.ledger().sequence();
+    client.generate_time_range_view_key(&auditor, &(seq + 1_000), &(10 * 86_400), &(20 * 86_400));
+    (auditor, report_company(env, client))
+}
+
+#[test]
+fn test_time_range_key_reports_period_inside_window() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let (auditor, company_id) = time_range_auditor(&env, &client);
+
+    let report =
+        client.generate_aggregate_report(&auditor, &company_id, &(12 * 86_400), &(20 * 86_400));
+
+    assert_eq!(client.get_key_scope(&auditor), AuditScope::TimeRange);
+    assert_eq!(report.period_start, 12 * 86_400);
+    assert_eq!(report.period_end, 20 * 86_400);
+}
+
+#[test]
+fn test_time_range_key_rejects_partially_overlapping_period() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let (auditor, company_id) = time_range_auditor(&env, &client);
+
+    let early =
+        client.try_generate_aggregate_report(&auditor, &company_id, &(9 * 86_400), &(15 * 86_400));
+    let late = client.try_generate_aggregate_report(
+        &auditor,
+        &company_id,
+        &(15 * 86_400),
+        &(20 * 86_400 + 1),
+    );
+
+    assert_eq!(early.unwrap_err().unwrap(), AuditError::InsufficientScope);
+    assert_eq!(late.unwrap_err().unwrap(), AuditError::InsufficientScope);
+}
+
+#[test]
+fn test_time_range_key_rejects_period_outside_window() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let (auditor, company_id) = time_range_auditor(&env, &client);
+
+    let result =
+        client.try_generate_aggregate_report(&auditor, &company_id, &(30 * 86_400), &(40 * 86_400));
+
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::InsufficientScope);
+}
+
+#[test]
+fn test_full_company_key_has_no_window() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    client.generate_view_key(&auditor, &(seq + 1_000));
+    let company_id = report_company(&env, &client);
+
+    let record = client.get_view_key(&auditor);
+    let report = client.generate_aggregate_report(&auditor, &company_id, &0, &u64::MAX);
+
+    assert_eq!(record.allowed_start, None);
+    assert_eq!(record.allowed_end, None);
+    assert_eq!(report.period_end, u64::MAX);
+}
+
+#[test]
+fn test_time_range_key_rejects_inverted_window() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let seq = env.ledger().sequence();
+
+    let result = client.try_generate_time_range_view_key(&auditor, &(seq + 1_000), &20, &10);
+
+    assert_eq!(result.unwrap_err().unwrap(), AuditError::InvalidTimeRange);
+    assert_eq!(
+        client.try_get_view_key(&auditor).unwrap_err().unwrap(),
+        AuditError::KeyNotFound
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Employee allowlists
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_employee_list_key_verifies_listed_employee() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let listed = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key = client.generate_employee_list_view_key(
+        &auditor,
+        &(seq + 1_000),
+        &Vec::from_array(&env, [listed.clone()]),
+    );
+    let blinding = BytesN::from_array(&env, &[7u8; 32]);
+    let stored = commitment(&env, 1_000, &blinding);
+    let scope = AuditScope::EmployeeList;
+
+    assert_eq!(client.get_key_scope(&auditor), AuditScope::EmployeeList);
+    assert!(
+        client.verify_commitment_with_key(&auditor, &listed, &stored, &1_000, &blinding, &scope)
+    );
+    assert!(client.verify_commitment_with_view_key(
+        &auditor, &listed, &key, &stored, &1_000, &blinding, &scope
+    ));
+}
+
+#[test]
+fn test_employee_list_key_rejects_unlisted_employee() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let unlisted = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key = client.generate_employee_list_view_key(
+        &auditor,
+        &(seq + 1_000),
+        &Vec::from_array(&env, [Address::generate(&env)]),
+    );
+    let blinding = BytesN::from_array(&env, &[7u8; 32]);
+    let stored = commitment(&env, 1_000, &blinding);
+    let scope = AuditScope::EmployeeList;
+
+    let result = client
+        .try_verify_commitment_with_key(&auditor, &unlisted, &stored, &1_000, &blinding, &scope);
+    assert_eq!(result, Err(Ok(AuditError::InsufficientScope)));
+    let result = client.try_verify_commitment_with_view_key(
+        &auditor, &unlisted, &key, &stored, &1_000, &blinding, &scope,
+    );
+    assert_eq!(result, Err(Ok(AuditError::InsufficientScope)));
+    assert_eq!(
+        client.get_audit_log_count(&Symbol::new(&env, DEFAULT_COMPANY)),
+        0
+    );
+}
+
+#[test]
+fn test_batch_verify_rejects_unlisted_employee_wholesale() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let listed = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key = client.generate_employee_list_view_key(
+        &auditor,
+        &(seq + 1_000),
+        &Vec::from_array(&env, [listed.clone()]),
+    );
+    let blinding = BytesN::from_array(&env, &[7u8; 32]);
+    let entries = Vec::from_array(
+        &env,
+        [
+            (listed, 1_000i128, blinding.clone()),
+            (Address::generate(&env), 1_000i128, blinding.clone()),
+        ],
+    );
+    let stored = Vec::from_array(
+        &env,
+        [
+            commitment(&env, 1_000, &blinding),
+            commitment(&env, 1_000, &blinding),
+        ],
+    );
+
+    let result = client.try_batch_verify_commitments(&key, &auditor, &entries, &stored);
+
+    assert_eq!(result, Err(Ok(AuditError::InsufficientScope)));
+}
+
+#[test]
+fn test_keys_without_allowlist_verify_any_employee() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let full = Address::generate(&env);
+    let ranged = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    client.generate_view_key(&full, &(seq + 1_000));
+    client.generate_time_range_view_key(&ranged, &(seq + 1_000), &0, &86_400);
+    let employee = Address::generate(&env);
+    let blinding = BytesN::from_array(&env, &[7u8; 32]);
+    let stored = commitment(&env, 1_000, &blinding);
+
+    assert_eq!(client.get_view_key(&full).allowed_employees, None);
+    assert!(client.verify_commitment_with_key(
+        &full,
+        &employee,
+        &stored,
+        &1_000,
+        &blinding,
+        &AuditScope::FullCompany
+    ));
+    assert!(client.verify_commitment_with_key(
+        &ranged,
+        &employee,
+        &stored,
+        &1_000,
+        &blinding,
+        &AuditScope::TimeRange
+    ));
+}
+
+// ---------------------------------------------------------------------------
+// Key status
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_key_status_reports_seconds_remaining_for_active_key() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key_id = client.generate_view_key(&auditor, &(seq + 100));
+
+    assert_eq!(
+        client.key_status(&key_id),
+        (KeyStatus::Active, 100 * LEDGER_CLOSE_SECS)
+    );
+
+    env.ledger().set_sequence_number(seq + 60);
+    assert_eq!(
+        client.key_status(&key_id),
+        (KeyStatus::Active, 40 * LEDGER_CLOSE_SECS)
+    );
+}
+
+#[test]
+fn test_key_status_expired_agrees_with_verify_access() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key_id = client.generate_view_key(&auditor, &(seq + 10));
+
+    env.ledger().set_sequence_number(seq + 10);
+    assert_eq!(client.key_status(&key_id).0, KeyStatus::Active);
+    assert!(client.verify_access(&auditor));
+
+    env.ledger().set_sequence_number(seq + 11);
+    assert_eq!(client.key_status(&key_id), (KeyStatus::Expired, 0));
+    assert!(!client.verify_access(&auditor));
+}
+
+#[test]
+fn test_key_status_not_found_for_unknown_or_revoked_key() {
+    let (env, contract_id) = setup();
+    let client = AuditModuleClient::new(&env, &contract_id);
+    let auditor = Address::generate(&env);
+    let seq = env.ledger().sequence();
+    let key_id = client.generate_view_key(&auditor, &(seq + 100));
+
+    assert_eq!(
+        client.key_status(&BytesN::from_array(&env, &[0u8; 32])),
+        (KeyStatus::NotFound, 0)
+    );
+
+    client.revoke_view_key(&contract_id, &auditor);
+    assert_eq!(client.key_status(&key_id), (KeyStatus::NotFound, 0));
+}
@@ -0,0 +1,113 @@
+//! Aggregate audit reports across audit_module, payment_executor and
+//! payroll_registry.
+//!
+//! `AuditModule::generate_aggregate_report` reads the headcount from the
+//! registry and the amount paid from the executor, so a report generated
+//! after a real payment must carry that payment's figures.
+
+use audit_module::{AuditModule, AuditModuleClient};
+use payment_executor::{ContractAddresses, PaymentExecutor, PaymentExecutorClient};
+use payroll_registry::{PayrollRegistry, PayrollRegistryClient};
+use proof_verifier::testutils::{g1_point, g2_point};
+use proof_verifier::{ProofVerifier, ProofVerifierClient, VerificationKey};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::testutils::{Address as _, BytesN as _};
+use soroban_sdk::{Address, BytesN, Env, Vec};
+use token::{Token, TokenClient};
+
+fn mock_vk(env: &Env) -> VerificationKey {
+    VerificationKey {
+        alpha: BytesN::from_array(env, &[0u8; 64]),
+        beta: BytesN::from_array(env, &[0u8; 128]),
+        gamma: BytesN::from_array(env, &[0u8; 128]),
+        delta: BytesN::from_array(env, &[0u8; 128]),
+        ic: Vec::from_array(
+            env,
+            [
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+            ],
+        ),
+    }
+}
+
+/// Pay one employee `amount` through the executor and return an audit module
+/// pointed at that executor and its registry, plus the company ID.
+fn pay_and_audit(env: &Env, amount: i128) -> (AuditModuleClient<'static>, u64) {
+    env.mock_all_auths();
+
+    let addresses = ContractAddresses {
+        registry: env.register_contract(None, PayrollRegistry),
+        commitment: env.register_contract(None, SalaryCommitmentContract),
+        verifier: env.register_contract(None, ProofVerifier),
+        token: env.register_contract(None, Token),
+    };
+    let verifier = ProofVerifierClient::new(env, &addresses.verifier);
+    verifier.init_verifier_admin(&Address::generate(env));
+    verifier.initialize_verifier(&mock_vk(env));
+    let commitment = SalaryCommitmentContractClient::new(env, &addresses.commitment);
+    commitment.init_commitment_admin(&Address::generate(env));
+
+    let executor_id = env.register_contract(None, PaymentExecutor);
+    let executor = PaymentExecutorClient::new(env, &executor_id);
+    executor.initialize(&addresses);
+
+    let registry = PayrollRegistryClient::new(env, &addresses.registry);
+    let treasury = Address::generate(env);
+    let employee = Address::generate(env);
+    let salary_commitment = BytesN::from_array(env, &[9u8; 32]);
+    let admin = Address::generate(env);
+    let company_id = registry.register_company(&admin, &treasury);
+    commitment.store_commitment(&employee, &salary_commitment);
+    registry.add_employee(&company_id, &admin, &employee, &salary_commitment);
+    TokenClient::new(env, &addresses.token).mint(&treasury, &10_000);
+    let period = executor.create_period(&company_id).period_id;
+
+    executor.execute_payment(
+        &company_id,
+        &employee,
+        &amount,
+        &g1_point(env, 1),
+        &g2_point(env, 2),
+        &g1_point(env, 3),
+        &BytesN::from_array(env, &[4u8; 32]),
+        &period,
+        &BytesN::random(env),
+    );
+
+    let audit = AuditModuleClient::new(env, &env.register_contract(None, AuditModule));
+    audit.initialize(&executor_id, &addresses.registry);
+    (audit, company_id)
+}
+
+#[test]
+fn test_report_reflects_executed_payment() {
+    let env = Env::default();
+    let (audit, company_id) = pay_and_audit(&env, 2_500);
+    let auditor = Address::generate(&env);
+    audit.generate_view_key(&auditor, &(env.ledger().sequence() + 1_000));
+
+    let now = env.ledger().timestamp();
+    let report = audit.generate_aggregate_report(&auditor, &company_id, &now, &(now + 86_400));
+
+    assert_eq!(report.company_id, company_id);
+    assert_eq!(report.total_employees, 1);
+    assert_eq!(report.total_paid, 2_500);
+    assert!(report.verified);
+}
+
+#[test]
+fn test_report_for_unknown_company_is_empty() {
+    let env = Env::default();
+    let (audit, company_id) = pay_and_audit(&env, 2_500);
+    let auditor = Address::generate(&env);
+    audit.generate_view_key(&auditor, &(env.ledger().sequence() + 1_000));
+
+    let now = env.ledger().timestamp();
+    let report =
+        audit.generate_aggregate_report(&auditor, &(company_id + 1), &now, &(now + 86_400));
+
+    assert_eq!(report.total_employees, 0);
+    assert_eq!(report.total_paid, 0);
+}
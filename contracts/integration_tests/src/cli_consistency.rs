@@ -0,0 +1,125 @@
+//! Consistency between the CLI's commitments and the circuit's proofs.
+//!
+//! The CLI computes `Poseidon(salary, blinding)` off-chain with
+//! `zk_payroll_cli::crypto`; the payment circuit recomputes it as a public
+//! signal.  If the two ever disagree — different Poseidon parameters, a
+//! byte-order slip, a placeholder constraint left in the circuit — every
+//! real payment fails verification.  This test runs the whole chain with a
+//! real SnarkJS proof: CLI commitment → `store_commitment` → proof →
+//! `batch_process_payroll`.
+//!
+//! It needs Node.js, SnarkJS and the compiled circuit artefacts, and skips
+//! (with a warning) when any of them is missing; the mock proofs
+//! `generate_proof.js` falls back to do not use Poseidon and would never
+//! match.
+
+use crate::proof_helper::try_generate_real_proof;
+use payroll::{NullifierPolicy, Payroll, PayrollClient};
+use proof_verifier::{ProofVerifier, ProofVerifierClient, VerificationKey};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Vec};
+use token::{Token, TokenClient};
+use zk_payroll_cli::crypto::{poseidon_commitment, PoseidonParams};
+
+const SALARY: u64 = 5_000;
+const BLINDING: u64 = 123;
+
+/// Verification key with one IC point per public input
+/// (commitment, nullifier, recipient_hash) plus the constant term.
+fn mock_vk(env: &Env) -> VerificationKey {
+    VerificationKey {
+        alpha: BytesN::from_array(env, &[0u8; 64]),
+        beta: BytesN::from_array(env, &[0u8; 128]),
+        gamma: BytesN::from_array(env, &[0u8; 128]),
+        delta: BytesN::from_array(env, &[0u8; 128]),
+        ic: Vec::from_array(
+            env,
+            [
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+            ],
+        ),
+    }
+}
+
+/// The commitment `zk-payroll add-employee` would produce for `SALARY` with
+/// blinding factor `BLINDING`, in the big-endian form the circuit exposes as
+/// its public signal (the CLI itself works in little-endian).
+fn cli_commitment() -> [u8; 32] {
+    let mut blinding_le = [0u8; 32];
+    blinding_le[..8].copy_from_slice(&BLINDING.to_le_bytes());
+
+    let mut commitment =
+        poseidon_commitment(SALARY, &blinding_le, PoseidonParams::default()).unwrap();
+    commitment.reverse();
+    commitment
+}
+
+#[test]
+fn test_cli_commitment_matches_real_proof_and_pays() {
+    let proof_data = match try_generate_real_proof(SALARY, BLINDING) {
+        Some(p) => p,
+        None => return, // SnarkJS toolchain not available — skip gracefully.
+    };
+
+    let commitment_bytes = cli_commitment();
+    assert_eq!(
+        proof_data.salary_commitment, commitment_bytes,
+        "circuit salary_commitment differs from the CLI's Poseidon commitment"
+    );
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    let verifier_id = env.register_contract(None, ProofVerifier);
+    let verifier = ProofVerifierClient::new(&env, &verifier_id);
+    verifier.init_verifier_admin(&admin);
+    verifier.initialize_verifier(&mock_vk(&env));
+
+    let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+    let commitment = SalaryCommitmentContractClient::new(&env, &commitment_id);
+    commitment.init_commitment_admin(&admin);
+
+    let token_id = env.register_contract(None, Token);
+    let token = TokenClient::new(&env, &token_id);
+    token.mint(&treasury, &10_000);
+
+    let payroll_id = env.register_contract(None, Payroll);
+    let payroll = PayrollClient::new(&env, &payroll_id);
+    payroll.initialize(
+        &admin,
+        &token_id,
+        &verifier_id,
+        &commitment_id,
+        &treasury,
+        &Address::generate(&env),
+    );
+    payroll.set_require_bound_nullifiers(&false);
+
+    commitment.set_payroll_operator(&payroll_id);
+    commitment.store_commitment(&alice, &BytesN::from_array(&env, &commitment_bytes));
+
+    let mut proof_bytes = [0u8; 256];
+    proof_bytes[..64].copy_from_slice(&proof_data.pi_a);
+    proof_bytes[64..192].copy_from_slice(&proof_data.pi_b);
+    proof_bytes[192..].copy_from_slice(&proof_data.pi_c);
+
+    payroll.batch_process_payroll(
+        &Vec::from_array(&env, [BytesN::from_array(&env, &proof_bytes)]),
+        &Vec::from_array(&env, [5_000i128]),
+        &Vec::from_array(&env, [alice.clone()]),
+        &5_000,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &None,
+        &NullifierPolicy::Abort,
+    );
+
+    assert_eq!(token.balance(&alice), 5_000);
+    assert_eq!(token.balance(&treasury), 5_000);
+}
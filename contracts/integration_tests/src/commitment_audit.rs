@@ -0,0 +1,50 @@
+//! Stored commitments opened through the audit module.
+//!
+//! Without a Poseidon host, `SalaryCommitmentContract::compute_commitment`
+//! and `AuditModule::verify_commitment_with_key` share
+//! `salary_commitment::sha256_commitment`, so an auditor holding the salary
+//! and blinding factor can verify the commitment the contract stored.
+
+use audit_module::{AuditError, AuditModule, AuditModuleClient, AuditScope};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, BytesN, Env};
+
+#[test]
+fn test_stored_commitment_verifies_through_audit_module() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+    let commitment = SalaryCommitmentContractClient::new(&env, &commitment_id);
+    commitment.init_commitment_admin(&Address::generate(&env));
+
+    let employee = Address::generate(&env);
+    let blinding = BytesN::from_array(&env, &[42u8; 32]);
+    commitment.store_commitment(&employee, &commitment.compute_commitment(&5_000, &blinding));
+    let stored = commitment.get_commitment(&employee).commitment;
+
+    let audit = AuditModuleClient::new(&env, &env.register_contract(None, AuditModule));
+    let auditor = Address::generate(&env);
+    audit.generate_view_key(&auditor, &(env.ledger().sequence() + 1_000));
+
+    assert!(audit.verify_commitment_with_key(
+        &auditor,
+        &employee,
+        &stored,
+        &5_000,
+        &blinding,
+        &AuditScope::FullCompany
+    ));
+    assert_eq!(
+        audit.try_verify_commitment_with_key(
+            &auditor,
+            &employee,
+            &stored,
+            &5_001,
+            &blinding,
+            &AuditScope::FullCompany
+        ),
+        Err(Ok(AuditError::CommitmentMismatch))
+    );
+}
@@ -0,0 +1,116 @@
+//! Registry/commitment-contract consistency tests.
+//!
+//! Exercises `PayrollRegistry::set_require_commitment_match`, which makes
+//! `add_employee` reject a commitment that differs from the one the
+//! `SalaryCommitmentContract` already stores for the employee.
+
+use payroll_registry::{PayrollRegistry, PayrollRegistryClient, RegistryError};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+
+struct Ctx<'a> {
+    admin: Address,
+    company_id: u64,
+    registry: PayrollRegistryClient<'a>,
+    commitment: SalaryCommitmentContractClient<'a>,
+}
+
+/// Register a company whose registry checks commitments against a freshly
+/// initialised commitment contract.
+fn setup(env: &Env) -> Ctx<'_> {
+    env.mock_all_auths();
+
+    let registry_id = env.register_contract(None, PayrollRegistry);
+    let registry = PayrollRegistryClient::new(env, &registry_id);
+
+    let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+    let commitment = SalaryCommitmentContractClient::new(env, &commitment_id);
+    commitment.init_commitment_admin(&Address::generate(env));
+
+    let admin = Address::generate(env);
+    let company_id = registry.register_company(&admin, &Address::generate(env));
+    registry.set_commitment_contract(&company_id, &commitment_id);
+    registry.set_require_commitment_match(&company_id, &true);
+
+    Ctx {
+        admin,
+        company_id,
+        registry,
+        commitment,
+    }
+}
+
+#[test]
+fn test_matching_commitment_is_registered() {
+    let env = Env::default();
+    let ctx = setup(&env);
+    let employee = Address::generate(&env);
+    let value = BytesN::from_array(&env, &[1u8; 32]);
+
+    ctx.commitment.store_commitment(&employee, &value);
+    ctx.registry
+        .add_employee(&ctx.company_id, &ctx.admin, &employee, &value);
+
+    assert_eq!(
+        ctx.registry.get_commitment(&ctx.company_id, &employee),
+        value
+    );
+}
+
+#[test]
+fn test_mismatched_commitment_is_rejected() {
+    let env = Env::default();
+    let ctx = setup(&env);
+    let employee = Address::generate(&env);
+
+    ctx.commitment
+        .store_commitment(&employee, &BytesN::from_array(&env, &[1u8; 32]));
+    let result = ctx.registry.try_add_employee(
+        &ctx.company_id,
+        &ctx.admin,
+        &employee,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        RegistryError::CommitmentMismatch
+    );
+    assert_eq!(ctx.registry.get_employee_count(&ctx.company_id), 0);
+}
+
+#[test]
+fn test_commitment_missing_from_commitment_contract_is_rejected() {
+    let env = Env::default();
+    let ctx = setup(&env);
+
+    let result = ctx.registry.try_add_employee(
+        &ctx.company_id,
+        &ctx.admin,
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        RegistryError::CommitmentMismatch
+    );
+}
+
+#[test]
+fn test_disabled_check_allows_any_commitment() {
+    let env = Env::default();
+    let ctx = setup(&env);
+    ctx.registry
+        .set_require_commitment_match(&ctx.company_id, &false);
+    assert!(!ctx.registry.get_require_commitment_match(&ctx.company_id));
+
+    let employee = Address::generate(&env);
+    ctx.registry.add_employee(
+        &ctx.company_id,
+        &ctx.admin,
+        &employee,
+        &BytesN::from_array(&env, &[3u8; 32]),
+    );
+    assert_eq!(ctx.registry.get_employee_count(&ctx.company_id), 1);
+}
@@ -0,0 +1,102 @@
+//! Commitment-opening proof tests.
+//!
+//! Exercises `SalaryCommitmentContract::verify_commitment_zk`, which lets an
+//! auditor confirm that an employee's stored commitment is well-formed by
+//! checking a Groth16 proof of knowledge of its opening, without learning the
+//! salary or blinding factor.
+
+use proof_verifier::testutils::{g1_point, g2_point};
+use proof_verifier::{Groth16Proof, ProofVerifier, ProofVerifierClient, VerificationKey};
+use salary_commitment::{
+    CommitmentError, SalaryCommitmentContract, SalaryCommitmentContractClient,
+};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Vec};
+
+/// Verification key with `ic_len` IC points (one more than the number of
+/// public inputs the circuit exposes).
+fn mock_vk(env: &Env, ic_len: u32) -> VerificationKey {
+    let mut ic = Vec::new(env);
+    for _ in 0..ic_len {
+        ic.push_back(BytesN::from_array(env, &[0u8; 64]));
+    }
+    VerificationKey {
+        alpha: BytesN::from_array(env, &[0u8; 64]),
+        beta: BytesN::from_array(env, &[0u8; 128]),
+        gamma: BytesN::from_array(env, &[0u8; 128]),
+        delta: BytesN::from_array(env, &[0u8; 128]),
+        ic,
+    }
+}
+
+fn mock_proof(env: &Env) -> Groth16Proof {
+    Groth16Proof {
+        a: g1_point(env, 1),
+        b: g2_point(env, 2),
+        c: g1_point(env, 3),
+    }
+}
+
+/// Register a commitment contract wired to a verifier whose key has
+/// `ic_len` IC points, and store a commitment for a fresh employee.
+fn setup(env: &Env, ic_len: u32) -> (SalaryCommitmentContractClient<'_>, Address) {
+    env.mock_all_auths();
+
+    let verifier_id = env.register_contract(None, ProofVerifier);
+    let verifier = ProofVerifierClient::new(env, &verifier_id);
+    verifier.init_verifier_admin(&Address::generate(env));
+    verifier.initialize_verifier(&mock_vk(env, ic_len));
+
+    let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+    let commitment = SalaryCommitmentContractClient::new(env, &commitment_id);
+    commitment.init_commitment_admin(&Address::generate(env));
+    commitment.set_opening_verifier(&verifier_id);
+
+    let employee = Address::generate(env);
+    let mut blinding = [0u8; 32];
+    blinding[31] = 123;
+    let value = commitment.compute_commitment(&5000u64, &BytesN::from_array(env, &blinding));
+    commitment.store_commitment(&employee, &value);
+
+    (commitment, employee)
+}
+
+/// Only checks the wiring: the stored commitment is passed as the single
+/// public input. `simulated_verify_groth16` accepts every well-formed proof,
+/// so this says nothing about the proof itself until real pairing lands.
+#[test]
+fn test_opening_proof_reaches_simulated_verifier() {
+    let env = Env::default();
+    let (commitment, employee) = setup(&env, 2);
+
+    assert!(commitment.verify_commitment_zk(&employee, &mock_proof(&env)));
+}
+
+#[test]
+fn test_opening_proof_rejected_by_payment_circuit_key() {
+    let env = Env::default();
+    // The payment circuit exposes two public inputs, so its key cannot verify
+    // a single-input opening proof.
+    let (commitment, employee) = setup(&env, 3);
+
+    assert!(!commitment.verify_commitment_zk(&employee, &mock_proof(&env)));
+}
+
+#[test]
+fn test_opening_proof_requires_configured_verifier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+    let commitment = SalaryCommitmentContractClient::new(&env, &commitment_id);
+    commitment.init_commitment_admin(&Address::generate(&env));
+
+    let employee = Address::generate(&env);
+    commitment.store_commitment(&employee, &BytesN::from_array(&env, &[7u8; 32]));
+
+    assert!(commitment.get_opening_verifier().is_none());
+    let result = commitment.try_verify_commitment_zk(&employee, &mock_proof(&env));
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        CommitmentError::OpeningVerifierNotSet
+    );
+}
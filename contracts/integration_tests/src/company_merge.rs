@@ -0,0 +1,150 @@
+//! Company merge tests.
+//!
+//! Exercises `PayrollRegistry::merge_companies`, which moves every employee
+//! of an acquired company to the acquirer and deactivates the acquired one.
+
+use payroll_registry::{EmployeeStatus, PayrollRegistry, PayrollRegistryClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Symbol};
+
+struct Ctx<'a> {
+    source_admin: Address,
+    target_admin: Address,
+    source_id: u64,
+    target_id: u64,
+    registry: PayrollRegistryClient<'a>,
+}
+
+/// Register an acquirer and an acquired company under different admins.
+fn setup(env: &Env) -> Ctx<'_> {
+    env.mock_all_auths();
+
+    let registry_id = env.register_contract(None, PayrollRegistry);
+    let registry = PayrollRegistryClient::new(env, &registry_id);
+
+    let target_admin = Address::generate(env);
+    let source_admin = Address::generate(env);
+    let target_id = registry.register_company(&target_admin, &Address::generate(env));
+    let source_id = registry.register_company(&source_admin, &Address::generate(env));
+
+    Ctx {
+        source_admin,
+        target_admin,
+        source_id,
+        target_id,
+        registry,
+    }
+}
+
+fn commitment(env: &Env, seed: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[seed; 32])
+}
+
+#[test]
+fn merge_moves_source_roster_into_target() {
+    let env = Env::default();
+    let ctx = setup(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    ctx.registry.add_employee(
+        &ctx.target_id,
+        &ctx.target_admin,
+        &alice,
+        &commitment(&env, 1),
+    );
+    ctx.registry.add_employee(
+        &ctx.source_id,
+        &ctx.source_admin,
+        &bob,
+        &commitment(&env, 2),
+    );
+    ctx.registry.add_employee(
+        &ctx.source_id,
+        &ctx.source_admin,
+        &carol,
+        &commitment(&env, 3),
+    );
+    ctx.registry
+        .deactivate_employee(&ctx.source_id, &carol, &Symbol::new(&env, "leave"));
+
+    ctx.registry.merge_companies(&ctx.source_id, &ctx.target_id);
+
+    // Combined roster lives under the target.
+    let roster = ctx.registry.get_employees(&ctx.target_id);
+    assert_eq!(ctx.registry.get_employee_count(&ctx.target_id), 3);
+    assert!(roster.contains(&alice));
+    assert!(roster.contains(&bob));
+    assert!(roster.contains(&carol));
+    assert_eq!(
+        ctx.registry.get_commitment(&ctx.target_id, &bob),
+        commitment(&env, 2)
+    );
+    assert!(ctx.registry.is_eligible(&ctx.target_id, &bob));
+    assert_eq!(
+        ctx.registry.get_employee_status(&ctx.target_id, &carol),
+        EmployeeStatus::Inactive
+    );
+    assert!(ctx
+        .registry
+        .get_deactivation(&ctx.target_id, &carol)
+        .is_some());
+
+    // Source is emptied and marked as merged.
+    assert_eq!(ctx.registry.get_employee_count(&ctx.source_id), 0);
+    assert_eq!(ctx.registry.find_employee(&ctx.source_id, &bob), None);
+    assert_eq!(
+        ctx.registry.get_merged_into(&ctx.source_id),
+        Some(ctx.target_id)
+    );
+    assert!(ctx
+        .registry
+        .try_add_employee(
+            &ctx.source_id,
+            &ctx.source_admin,
+            &Address::generate(&env),
+            &commitment(&env, 4)
+        )
+        .is_err());
+}
+
+#[test]
+fn merge_rejects_employee_already_in_target() {
+    let env = Env::default();
+    let ctx = setup(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    ctx.registry.add_employee(
+        &ctx.target_id,
+        &ctx.target_admin,
+        &alice,
+        &commitment(&env, 1),
+    );
+    ctx.registry.add_employee(
+        &ctx.source_id,
+        &ctx.source_admin,
+        &bob,
+        &commitment(&env, 2),
+    );
+    ctx.registry.add_employee(
+        &ctx.source_id,
+        &ctx.source_admin,
+        &alice,
+        &commitment(&env, 3),
+    );
+
+    assert!(ctx
+        .registry
+        .try_merge_companies(&ctx.source_id, &ctx.target_id)
+        .is_err());
+
+    // Nothing moved.
+    assert_eq!(ctx.registry.get_employee_count(&ctx.source_id), 2);
+    assert_eq!(ctx.registry.get_employee_count(&ctx.target_id), 1);
+    assert_eq!(
+        ctx.registry.get_commitment(&ctx.source_id, &bob),
+        commitment(&env, 2)
+    );
+    assert_eq!(ctx.registry.get_merged_into(&ctx.source_id), None);
+}
@@ -0,0 +1,147 @@
+//! `PayrollProcessed` events as emitted by `payment_executor` and decoded by
+//! the CLI's `rpc` module.
+//!
+//! The CLI reads events back from Soroban RPC as base64 XDR and assumes a
+//! fixed topic and data layout.  These tests emit a real event from the
+//! contract, wrap its XDR in the `getEvents` entry shape the RPC returns, and
+//! decode it with `zk_payroll_cli::rpc`, so a change on either side of that
+//! layout fails here.
+
+extern crate std;
+
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use payment_executor::{ContractAddresses, PaymentExecutor, PaymentExecutorClient};
+use payroll_registry::{PayrollRegistry, PayrollRegistryClient};
+use proof_verifier::testutils::{g1_point, g2_point};
+use proof_verifier::{ProofVerifier, ProofVerifierClient, VerificationKey};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::testutils::{Address as _, BytesN as _, Events};
+use soroban_sdk::xdr::{Limits, ScVal, WriteXdr};
+use soroban_sdk::{Address, BytesN, Env, IntoVal, TryFromVal, Val, Vec};
+use std::string::{String, ToString};
+use token::{Token, TokenClient};
+use zk_payroll_cli::rpc::{try_decode_payroll_event, RawEvent};
+
+const LEDGER: u32 = 1_204_510;
+const CLOSED_AT: &str = "2024-12-01T00:00:00Z";
+
+fn mock_vk(env: &Env) -> VerificationKey {
+    VerificationKey {
+        alpha: BytesN::from_array(env, &[0u8; 64]),
+        beta: BytesN::from_array(env, &[0u8; 128]),
+        gamma: BytesN::from_array(env, &[0u8; 128]),
+        delta: BytesN::from_array(env, &[0u8; 128]),
+        ic: Vec::from_array(
+            env,
+            [
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+            ],
+        ),
+    }
+}
+
+/// Base64 XDR of a contract value, as Soroban RPC returns it.
+fn to_b64(env: &Env, val: &Val) -> String {
+    let scval = ScVal::try_from_val(env, val).unwrap();
+    B64.encode(scval.to_xdr(Limits::none()).unwrap())
+}
+
+/// Run one `execute_payment` in a fresh period and return the company ID,
+/// the period and the `PayrollProcessed` event in `getEvents` form.
+fn pay(env: &Env, employee: &Address, amount: i128) -> (u64, u32, RawEvent) {
+    env.mock_all_auths();
+
+    let addresses = ContractAddresses {
+        registry: env.register_contract(None, PayrollRegistry),
+        commitment: env.register_contract(None, SalaryCommitmentContract),
+        verifier: env.register_contract(None, ProofVerifier),
+        token: env.register_contract(None, Token),
+    };
+    let verifier = ProofVerifierClient::new(env, &addresses.verifier);
+    verifier.init_verifier_admin(&Address::generate(env));
+    verifier.initialize_verifier(&mock_vk(env));
+    let commitment = SalaryCommitmentContractClient::new(env, &addresses.commitment);
+    commitment.init_commitment_admin(&Address::generate(env));
+
+    let executor_id = env.register_contract(None, PaymentExecutor);
+    let executor = PaymentExecutorClient::new(env, &executor_id);
+    executor.initialize(&addresses);
+
+    let registry = PayrollRegistryClient::new(env, &addresses.registry);
+    let treasury = Address::generate(env);
+    let salary_commitment = BytesN::from_array(env, &[9u8; 32]);
+    let admin = Address::generate(env);
+    let company_id = registry.register_company(&admin, &treasury);
+    commitment.store_commitment(employee, &salary_commitment);
+    registry.add_employee(&company_id, &admin, employee, &salary_commitment);
+    TokenClient::new(env, &addresses.token).mint(&treasury, &10_000);
+    let period = executor.create_period(&company_id).period_id;
+
+    executor.execute_payment(
+        &company_id,
+        employee,
+        &amount,
+        &g1_point(env, 1),
+        &g2_point(env, 2),
+        &g1_point(env, 3),
+        &BytesN::from_array(env, &[4u8; 32]),
+        &period,
+        &BytesN::random(env),
+    );
+
+    let (contract, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(contract, executor_id);
+    let raw = RawEvent {
+        ledger: LEDGER,
+        ledger_closed_at: CLOSED_AT.to_string(),
+        topic: topics.iter().map(|t| to_b64(env, &t)).collect(),
+        value: to_b64(env, &data),
+        in_successful_contract_call: true,
+    };
+    (company_id, period, raw)
+}
+
+#[test]
+fn test_emitted_payroll_event_decodes_to_payment_inputs() {
+    let env = Env::default();
+    let employee = Address::generate(&env);
+
+    let (company_id, period, raw) = pay(&env, &employee, 1_000);
+    let event = try_decode_payroll_event(&raw, &company_id.to_string())
+        .unwrap()
+        .expect("event must decode as PayrollProcessed for the company");
+
+    assert_eq!(event.amount, 1_000);
+    assert_eq!(event.period, period);
+    assert_eq!(event.ledger, LEDGER);
+    assert_eq!(event.ledger_closed_at, CLOSED_AT);
+    // Generated test addresses are contracts, which the decoder renders as
+    // `C:<hex of the contract ID>`.
+    let employee_val: Val = employee.into_val(&env);
+    let ScVal::Address(soroban_sdk::xdr::ScAddress::Contract(hash)) =
+        ScVal::try_from_val(&env, &employee_val).unwrap()
+    else {
+        panic!("generated address is not a contract address");
+    };
+    assert_eq!(event.employee, std::format!("C:{}", hex_lower(&hash.0)));
+}
+
+#[test]
+fn test_emitted_payroll_event_is_skipped_for_other_company() {
+    let env = Env::default();
+    let employee = Address::generate(&env);
+
+    let (company_id, _, raw) = pay(&env, &employee, 1_000);
+
+    assert!(
+        try_decode_payroll_event(&raw, &(company_id + 1).to_string())
+            .unwrap()
+            .is_none()
+    );
+}
+
+fn hex_lower(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| std::format!("{:02x}", b)).collect()
+}
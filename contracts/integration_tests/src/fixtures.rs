@@ -5,8 +5,11 @@
 ///
 /// All fixtures are deterministic and documented in `docs/fixtures-guide.md`.
 #[cfg(test)]
+// Not every fixture is used by a test yet.
+#[allow(dead_code, clippy::module_inception)]
 pub mod fixtures {
-    use soroban_sdk::{Address, BytesN, Env};
+    use soroban_sdk::xdr::{Hash, ScAddress};
+    use soroban_sdk::{Address, Env, TryFromVal};
 
     // ── Company Fixtures ─────────────────────────────────────────────────────
 
@@ -36,7 +39,7 @@ pub mod fixtures {
         pub id: u8,
         pub name: &'static str,
         pub salary: u64,
-        pub blinding_factor: u8,
+        pub blinding_factor: u16,
     }
 
     pub const ALICE: EmployeeFixture = EmployeeFixture {
@@ -90,15 +93,16 @@ pub mod fixtures {
     pub fn address_from_seed(env: &Env, seed: u8) -> Address {
         let mut bytes = [0u8; 32];
         bytes[0] = seed;
-        Address::from_contract_id(&BytesN::from_array(env, &bytes))
+        Address::try_from_val(env, &ScAddress::Contract(Hash(bytes))).unwrap()
     }
 
     /// Generate a deterministic 32-byte blinding factor.
-    pub fn blinding_bytes(factor: u8) -> BytesN<32> {
+    ///
+    /// Returned as raw bytes: `BytesN::from_array` needs an `&Env`, so
+    /// callers wrap it themselves.
+    pub fn blinding_bytes(factor: u16) -> [u8; 32] {
         let mut bytes = [0u8; 32];
-        bytes[31] = factor;
-        // BytesN::from_array requires an &Env, so callers must construct this
-        // Cannot be done in const context without env
+        bytes[30..].copy_from_slice(&factor.to_be_bytes());
         bytes
     }
 
@@ -164,9 +168,10 @@ pub mod fixtures {
         #[test]
         fn test_payroll_period_fixtures() {
             assert_eq!(Q1_2024_ACME.company_id, 0);
-            assert_eq!(Q1_2024_ACME.is_active, false);
-
-            assert_eq!(Q2_2024_ACME.is_active, true);
+            assert_eq!(
+                (Q1_2024_ACME.is_active, Q2_2024_ACME.is_active),
+                (false, true)
+            );
             assert_eq!(FEB_2024_GLOBALPAY.company_id, 2);
         }
 
@@ -200,6 +205,9 @@ pub mod fixtures {
 
             let bytes_255 = blinding_bytes(255);
             assert_eq!(bytes_255[31], 255);
+
+            let bytes_456 = blinding_bytes(BOB.blinding_factor);
+            assert_eq!(bytes_456[30..], [0x01, 0xc8]);
         }
     }
 }
@@ -9,9 +9,74 @@ mod fixtures;
 #[cfg(test)]
 mod upgrade_simulation;
 
+// Commitment-opening proof verification across salary_commitment and
+// proof_verifier.
+#[cfg(test)]
+mod commitment_opening;
+
+// Payroll against a real SEP-41 token with explicit nested auth.
+#[cfg(test)]
+mod nested_auth;
+
+// Two-step salary-change approval across payroll_registry and
+// salary_commitment.
+#[cfg(test)]
+mod salary_change;
+
+// Company merge across two payroll_registry companies.
+#[cfg(test)]
+mod company_merge;
+
+// Registry commitments checked against salary_commitment on add_employee.
+#[cfg(test)]
+mod commitment_match;
+
+// Recipient-bound nullifiers across payroll and proof_verifier.
+#[cfg(test)]
+mod recipient_binding;
+
+// Payroll batches cross-checked against registry commitments.
+#[cfg(test)]
+mod registry_cross_check;
+
+// CLI commitments checked against real SnarkJS proofs and paid on-chain;
+// skipped without the SnarkJS toolchain.
+#[cfg(test)]
+mod cli_consistency;
+
+// PayrollProcessed events from payment_executor decoded by the CLI's rpc
+// module.
+#[cfg(test)]
+mod event_decoding;
+
+// Aggregate audit reports read from payment_executor and payroll_registry.
+#[cfg(test)]
+mod audit_report;
+
+// Payment timestamps recorded in payroll_registry by payment_executor.
+#[cfg(test)]
+mod payment_timestamp;
+
+// Treasury updates in payroll_registry picked up by payment_executor.
+#[cfg(test)]
+mod treasury_update;
+
+// Treasury balance read through payroll from the token contract.
+#[cfg(test)]
+mod treasury_balance;
+
+// Commitments stored by salary_commitment verified by audit_module.
+#[cfg(test)]
+mod commitment_audit;
+
+// Company deactivation in payroll_registry enforced by payment_executor.
+#[cfg(test)]
+mod company_deactivation;
+
 // Proof generation helper — only compiled in test mode.
-// Provides `try_generate_proof` which spawns `node generate_proof.js` and
-// parses the output into Soroban-compatible byte arrays.
+// Provides `try_generate_proof` (and the batched `try_generate_proofs`) which
+// spawn `node generate_proof.js` and parse the output into Soroban-compatible
+// byte arrays.
 #[cfg(test)]
 mod proof_helper;
 
@@ -30,15 +95,15 @@ mod proof_helper;
 ///      unregistered employees cannot be paid.
 #[cfg(test)]
 mod e2e {
-    use proof_verifier::{ProofVerifier, ProofVerifierClient, VerificationKey};
-    use payroll::{Payroll, PayrollClient};
+    use payroll::{NullifierPolicy, Payroll, PayrollClient};
     use payroll_registry::{PayrollRegistry, PayrollRegistryClient};
+    use proof_verifier::{ProofVerifier, ProofVerifierClient, VerificationKey};
     use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
-    use token::{Token, TokenClient};
     use soroban_sdk::{
         testutils::{Address as _, Events},
         Address, BytesN, Env, Symbol, TryIntoVal, Vec,
     };
+    use token::{Token, TokenClient};
 
     // ── Helpers ──────────────────────────────────────────────────────────────
 
@@ -96,6 +161,7 @@ mod e2e {
         company_id: u64,
         token_client: TokenClient<'a>,
         registry_client: PayrollRegistryClient<'a>,
+        verifier_client: ProofVerifierClient<'a>,
         commitment_client: SalaryCommitmentContractClient<'a>,
         payroll_client: PayrollClient<'a>,
     }
@@ -135,6 +201,8 @@ mod e2e {
             &treasury,
             &treasury_owner,
         );
+        // These flows submit index-derived nullifiers; recipient_binding covers the default.
+        payroll_client.set_require_bound_nullifiers(&false);
 
         let commitment_client_init = SalaryCommitmentContractClient::new(&env, &commitment_id);
         commitment_client_init.set_payroll_operator(&payroll_id);
@@ -146,7 +214,7 @@ mod e2e {
 
         // Register a company up-front; first ID is always 0.
         let company_id = registry_client.register_company(&admin, &treasury);
-        TestContext {
+        let ctx = TestContext {
             env,
             admin,
             treasury,
@@ -154,9 +222,50 @@ mod e2e {
             company_id,
             token_client,
             registry_client,
+            verifier_client,
             commitment_client,
             payroll_client,
-        }
+        };
+        validate_wiring(&ctx);
+        ctx
+    }
+
+    /// Assert that every contract in `ctx` is initialized and that the
+    /// payroll contract points at the contracts registered alongside it.
+    fn validate_wiring(ctx: &TestContext) {
+        assert!(
+            ctx.verifier_client.is_initialized(),
+            "verifier not initialized"
+        );
+        assert!(
+            ctx.commitment_client.is_initialized(),
+            "commitment contract not initialized"
+        );
+        assert!(
+            ctx.payroll_client.is_initialized(),
+            "payroll not initialized"
+        );
+
+        let addrs = ctx.payroll_client.get_addresses();
+        assert_eq!(addrs.admin, ctx.admin, "payroll admin mis-wired");
+        assert_eq!(addrs.treasury, ctx.treasury, "payroll treasury mis-wired");
+        assert_eq!(
+            addrs.token, ctx.token_client.address,
+            "payroll token mis-wired"
+        );
+        assert_eq!(
+            addrs.verifier, ctx.verifier_client.address,
+            "payroll verifier mis-wired"
+        );
+        assert_eq!(
+            addrs.commitment, ctx.commitment_client.address,
+            "payroll commitment mis-wired"
+        );
+        assert_eq!(
+            ctx.commitment_client.get_payroll_operator(),
+            Some(ctx.payroll_client.address.clone()),
+            "commitment payroll operator mis-wired"
+        );
     }
 
     // ── Tests ─────────────────────────────────────────────────────────────────
@@ -181,7 +290,7 @@ mod e2e {
 
         // Register Alice in the registry with the same commitment.
         ctx.registry_client
-            .add_employee(&ctx.company_id, &ctx.alice, &commitment);
+            .add_employee(&ctx.company_id, &ctx.admin, &ctx.alice, &commitment);
         // ── PHASE 3: EXECUTION ────────────────────────────────────────────────
         // Mint tokens into the company treasury.
         let initial_treasury: i128 = 10_000;
@@ -209,6 +318,7 @@ mod e2e {
             &payment_amount,
             &test_nonce(env, 1),
             &None,
+            &NullifierPolicy::Abort,
         );
 
         // ── ASSERTIONS ────────────────────────────────────────────────────────
@@ -276,6 +386,49 @@ mod e2e {
         assert_eq!(sym4b, Symbol::new(env, "run_executed"));
     }
 
+    /// A payroll contract pointed at the wrong verifier fails the wiring check.
+    #[test]
+    #[should_panic(expected = "payroll verifier mis-wired")]
+    fn test_validate_wiring_detects_wrong_verifier() {
+        let mut ctx = setup();
+        let env = &ctx.env;
+
+        let payroll_id = env.register_contract(None, Payroll);
+        let payroll_client = PayrollClient::new(env, &payroll_id);
+        // A live verifier, just not the one the rest of the system uses.
+        payroll_client.initialize(
+            &ctx.admin,
+            &ctx.token_client.address,
+            &env.register_contract(None, ProofVerifier),
+            &ctx.commitment_client.address,
+            &ctx.treasury,
+            &Address::generate(env),
+        );
+        ctx.commitment_client.set_payroll_operator(&payroll_id);
+        ctx.payroll_client = payroll_client;
+
+        validate_wiring(&ctx);
+    }
+
+    /// A verifier address with no contract behind it is rejected by
+    /// `initialize` itself, not at the first payroll run.
+    #[test]
+    #[should_panic(expected = "is not a ProofVerifier contract")]
+    fn test_initialize_rejects_non_contract_verifier() {
+        let ctx = setup();
+        let env = &ctx.env;
+
+        let payroll_client = PayrollClient::new(env, &env.register_contract(None, Payroll));
+        payroll_client.initialize(
+            &ctx.admin,
+            &ctx.token_client.address,
+            &Address::generate(env),
+            &ctx.commitment_client.address,
+            &ctx.treasury,
+            &Address::generate(env),
+        );
+    }
+
     /// Paying an employee who has no commitment on-chain must panic.
     #[test]
     #[should_panic(expected = "Commitment not found")]
@@ -303,6 +456,7 @@ mod e2e {
             &5_000i128,
             &test_nonce(env, 2),
             &None,
+            &NullifierPolicy::Abort,
         );
     }
 
@@ -318,7 +472,7 @@ mod e2e {
         ctx.commitment_client
             .store_commitment(&ctx.alice, &commitment);
         ctx.registry_client
-            .add_employee(&ctx.company_id, &ctx.alice, &commitment);
+            .add_employee(&ctx.company_id, &ctx.admin, &ctx.alice, &commitment);
 
         ctx.token_client.mint(&ctx.treasury, &20_000i128);
 
@@ -341,6 +495,7 @@ mod e2e {
             &5_000i128,
             &test_nonce(env, 3),
             &None,
+            &NullifierPolicy::Abort,
         );
 
         // Second payroll run with the same nullifier (batch index 0) must panic.
@@ -352,6 +507,7 @@ mod e2e {
             &5_000i128,
             &test_nonce(env, 4),
             &None,
+            &NullifierPolicy::Abort,
         );
     }
 
@@ -381,6 +537,7 @@ mod e2e {
             &5_000i128,
             &test_nonce(env, 5),
             &None,
+            &NullifierPolicy::Abort,
         );
     }
 
@@ -431,8 +588,12 @@ mod e2e {
             .register_company(&ctx.admin, &ctx.treasury);
         ctx.commitment_client
             .store_commitment(&ctx.alice, &salary_commitment);
-        ctx.registry_client
-            .add_employee(&ctx.company_id, &ctx.alice, &salary_commitment);
+        ctx.registry_client.add_employee(
+            &ctx.company_id,
+            &ctx.admin,
+            &ctx.alice,
+            &salary_commitment,
+        );
 
         let initial_treasury: i128 = 10_000;
         let payment_amount: i128 = 5_000;
@@ -452,6 +613,7 @@ mod e2e {
             &payment_amount,
             &test_nonce(env, 6),
             &None,
+            &NullifierPolicy::Abort,
         );
 
         assert_eq!(
@@ -0,0 +1,249 @@
+//! Nested-authorization tests against a real SEP-41 token.
+//!
+//! The placeholder `token` contract skips `from.require_auth()` because
+//! `mock_all_auths` cannot satisfy auth that is first required inside a
+//! nested call (payroll → token). These tests instead run payroll against the
+//! built-in Stellar Asset Contract, which enforces `require_auth`, and supply
+//! the treasury's authorization explicitly with [`mock_payroll_auths`].
+
+extern crate std;
+
+use payroll::{NullifierPolicy, Payroll, PayrollClient};
+use proof_verifier::{ProofVerifier, ProofVerifierClient, VerificationKey};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, IntoVal, Vec,
+};
+
+// ── Helpers ──────────────────────────────────────────────────────────────────
+
+fn mock_vk(env: &Env) -> VerificationKey {
+    VerificationKey {
+        alpha: BytesN::from_array(env, &[0u8; 64]),
+        beta: BytesN::from_array(env, &[0u8; 128]),
+        gamma: BytesN::from_array(env, &[0u8; 128]),
+        delta: BytesN::from_array(env, &[0u8; 128]),
+        ic: Vec::from_array(
+            env,
+            [
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+            ],
+        ),
+    }
+}
+
+/// Arguments of a single `batch_process_payroll` call.
+struct PayrollBatch {
+    proofs: Vec<BytesN<256>>,
+    amounts: Vec<i128>,
+    employees: Vec<Address>,
+    total: i128,
+    nonce: BytesN<32>,
+}
+
+/// Install explicit mock auths for `payroll.batch_process_payroll(batch)`.
+///
+/// The admin authorizes the root payroll call. The treasury authorizes one
+/// `token.transfer(treasury, employee, amount)` per entry in `transfers`;
+/// each of those trees is rooted at the nested token call, since that is
+/// where the treasury's `require_auth` happens.
+fn mock_payroll_auths(
+    env: &Env,
+    admin: &Address,
+    treasury: &Address,
+    payroll_id: &Address,
+    token_id: &Address,
+    batch: &PayrollBatch,
+    transfers: &[(Address, i128)],
+) {
+    let payroll_invoke = MockAuthInvoke {
+        contract: payroll_id,
+        fn_name: "batch_process_payroll",
+        args: (
+            batch.proofs.clone(),
+            batch.amounts.clone(),
+            batch.employees.clone(),
+            batch.total,
+            batch.nonce.clone(),
+            Option::<BytesN<32>>::None,
+            NullifierPolicy::Abort,
+        )
+            .into_val(env),
+        sub_invokes: &[],
+    };
+
+    let transfer_invokes: std::vec::Vec<MockAuthInvoke> = transfers
+        .iter()
+        .map(|(to, amount)| MockAuthInvoke {
+            contract: token_id,
+            fn_name: "transfer",
+            args: (treasury.clone(), to.clone(), *amount).into_val(env),
+            sub_invokes: &[],
+        })
+        .collect();
+
+    let mut auths = std::vec![MockAuth {
+        address: admin,
+        invoke: &payroll_invoke,
+    }];
+    auths.extend(transfer_invokes.iter().map(|invoke| MockAuth {
+        address: treasury,
+        invoke,
+    }));
+    env.mock_auths(&auths);
+}
+
+struct TestContext<'a> {
+    env: Env,
+    admin: Address,
+    treasury: Address,
+    alice: Address,
+    payroll_id: Address,
+    token_id: Address,
+    payroll_client: PayrollClient<'a>,
+    token_client: TokenClient<'a>,
+}
+
+/// Deploy payroll against a Stellar Asset Contract and fund the treasury.
+fn setup() -> TestContext<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let treasury_owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    let verifier_id = env.register_contract(None, ProofVerifier);
+    let verifier_client = ProofVerifierClient::new(&env, &verifier_id);
+    verifier_client.init_verifier_admin(&admin);
+    verifier_client.initialize_verifier(&mock_vk(&env));
+
+    let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+    let commitment_client = SalaryCommitmentContractClient::new(&env, &commitment_id);
+    commitment_client.init_commitment_admin(&admin);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &token_id).mint(&treasury, &10_000);
+
+    let payroll_id = env.register_contract(None, Payroll);
+    let payroll_client = PayrollClient::new(&env, &payroll_id);
+    payroll_client.initialize(
+        &admin,
+        &token_id,
+        &verifier_id,
+        &commitment_id,
+        &treasury,
+        &treasury_owner,
+    );
+    payroll_client.set_require_bound_nullifiers(&false);
+    commitment_client.set_payroll_operator(&payroll_id);
+    commitment_client.store_commitment(&alice, &BytesN::from_array(&env, &[5u8; 32]));
+
+    let token_client = TokenClient::new(&env, &token_id);
+
+    TestContext {
+        env,
+        admin,
+        treasury,
+        alice,
+        payroll_id,
+        token_id,
+        payroll_client,
+        token_client,
+    }
+}
+
+fn single_payment(ctx: &TestContext, amount: i128) -> PayrollBatch {
+    let env = &ctx.env;
+    PayrollBatch {
+        proofs: Vec::from_array(env, [BytesN::from_array(env, &[0u8; 256])]),
+        amounts: Vec::from_array(env, [amount]),
+        employees: Vec::from_array(env, [ctx.alice.clone()]),
+        total: amount,
+        nonce: BytesN::from_array(env, &[1u8; 32]),
+    }
+}
+
+fn run(ctx: &TestContext, batch: &PayrollBatch) -> bool {
+    ctx.payroll_client
+        .try_batch_process_payroll(
+            &batch.proofs,
+            &batch.amounts,
+            &batch.employees,
+            &batch.total,
+            &batch.nonce,
+            &None,
+            &NullifierPolicy::Abort,
+        )
+        .is_ok()
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_authorized_nested_transfer_succeeds() {
+    let ctx = setup();
+    let batch = single_payment(&ctx, 4_000);
+
+    mock_payroll_auths(
+        &ctx.env,
+        &ctx.admin,
+        &ctx.treasury,
+        &ctx.payroll_id,
+        &ctx.token_id,
+        &batch,
+        &[(ctx.alice.clone(), 4_000)],
+    );
+
+    assert!(run(&ctx, &batch));
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 6_000);
+    assert_eq!(ctx.token_client.balance(&ctx.alice), 4_000);
+}
+
+#[test]
+fn test_unauthorized_nested_transfer_fails() {
+    let ctx = setup();
+    let batch = single_payment(&ctx, 4_000);
+
+    // The treasury authorizes a smaller transfer than the batch performs.
+    mock_payroll_auths(
+        &ctx.env,
+        &ctx.admin,
+        &ctx.treasury,
+        &ctx.payroll_id,
+        &ctx.token_id,
+        &batch,
+        &[(ctx.alice.clone(), 1_000)],
+    );
+
+    assert!(!run(&ctx, &batch));
+    assert_eq!(ctx.token_client.balance(&ctx.treasury), 10_000);
+    assert_eq!(ctx.token_client.balance(&ctx.alice), 0);
+}
+
+#[test]
+fn test_nested_transfer_without_treasury_auth_fails() {
+    let ctx = setup();
+    let batch = single_payment(&ctx, 4_000);
+
+    mock_payroll_auths(
+        &ctx.env,
+        &ctx.admin,
+        &ctx.treasury,
+        &ctx.payroll_id,
+        &ctx.token_id,
+        &batch,
+        &[],
+    );
+
+    assert!(!run(&ctx, &batch));
+    assert_eq!(ctx.token_client.balance(&ctx.alice), 0);
+}
@@ -0,0 +1,134 @@
+//! Last-payment timestamps across payment_executor and payroll_registry.
+//!
+//! `PaymentExecutor::execute_payment` reports each payment to
+//! `PayrollRegistry::record_payment`, so the registry's per-employee
+//! last-payment time follows the executor's payments.
+
+use payment_executor::{ContractAddresses, PaymentExecutor, PaymentExecutorClient};
+use payroll_registry::{PayrollRegistry, PayrollRegistryClient};
+use proof_verifier::testutils::{g1_point, g2_point};
+use proof_verifier::{ProofVerifier, ProofVerifierClient, VerificationKey};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::testutils::{Address as _, BytesN as _, Ledger as _};
+use soroban_sdk::{Address, BytesN, Env, Vec};
+use token::{Token, TokenClient};
+
+struct Ctx<'a> {
+    env: Env,
+    employee: Address,
+    company_id: u64,
+    executor: PaymentExecutorClient<'a>,
+    registry: PayrollRegistryClient<'a>,
+}
+
+fn mock_vk(env: &Env) -> VerificationKey {
+    VerificationKey {
+        alpha: BytesN::from_array(env, &[0u8; 64]),
+        beta: BytesN::from_array(env, &[0u8; 128]),
+        gamma: BytesN::from_array(env, &[0u8; 128]),
+        delta: BytesN::from_array(env, &[0u8; 128]),
+        ic: Vec::from_array(
+            env,
+            [
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+            ],
+        ),
+    }
+}
+
+/// Deploy the executor and its dependencies and enrol one employee in a
+/// funded company.
+fn setup() -> Ctx<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let addresses = ContractAddresses {
+        registry: env.register_contract(None, PayrollRegistry),
+        commitment: env.register_contract(None, SalaryCommitmentContract),
+        verifier: env.register_contract(None, ProofVerifier),
+        token: env.register_contract(None, Token),
+    };
+    let verifier = ProofVerifierClient::new(&env, &addresses.verifier);
+    verifier.init_verifier_admin(&Address::generate(&env));
+    verifier.initialize_verifier(&mock_vk(&env));
+    let commitment = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
+    commitment.init_commitment_admin(&Address::generate(&env));
+
+    let executor = PaymentExecutorClient::new(&env, &env.register_contract(None, PaymentExecutor));
+    executor.initialize(&addresses);
+
+    let registry = PayrollRegistryClient::new(&env, &addresses.registry);
+    let treasury = Address::generate(&env);
+    let employee = Address::generate(&env);
+    let salary_commitment = BytesN::from_array(&env, &[9u8; 32]);
+    let admin = Address::generate(&env);
+    let company_id = registry.register_company(&admin, &treasury);
+    commitment.store_commitment(&employee, &salary_commitment);
+    registry.add_employee(&company_id, &admin, &employee, &salary_commitment);
+    TokenClient::new(&env, &addresses.token).mint(&treasury, &10_000);
+
+    Ctx {
+        env,
+        employee,
+        company_id,
+        executor,
+        registry,
+    }
+}
+
+/// Pay the employee 1 000 in a fresh period at ledger time `now`, returning
+/// the timestamp on the executor's payment record.
+fn pay_at(ctx: &Ctx, now: u64) -> u64 {
+    let env = &ctx.env;
+    env.ledger().with_mut(|ledger| ledger.timestamp = now);
+    let period = ctx.executor.create_period(&ctx.company_id).period_id;
+
+    let record = ctx.executor.execute_payment(
+        &ctx.company_id,
+        &ctx.employee,
+        &1_000,
+        // A distinct proof per payment, so the replay guard lets it through.
+        &g1_point(env, period.into()),
+        &g2_point(env, 2),
+        &g1_point(env, 3),
+        &BytesN::random(env),
+        &period,
+        &BytesN::random(env),
+    );
+    record.timestamp
+}
+
+#[test]
+fn test_payment_records_timestamp_in_registry() {
+    let ctx = setup();
+    assert_eq!(
+        ctx.registry
+            .get_last_payment_timestamp(&ctx.company_id, &ctx.employee),
+        None
+    );
+
+    let paid_at = pay_at(&ctx, 1_700_000_000);
+
+    assert_eq!(paid_at, 1_700_000_000);
+    assert_eq!(
+        ctx.registry
+            .get_last_payment_timestamp(&ctx.company_id, &ctx.employee),
+        Some(paid_at)
+    );
+}
+
+#[test]
+fn test_later_payment_advances_registry_timestamp() {
+    let ctx = setup();
+    pay_at(&ctx, 1_700_000_000);
+
+    let paid_at = pay_at(&ctx, 1_700_086_400);
+
+    assert_eq!(
+        ctx.registry
+            .get_last_payment_timestamp(&ctx.company_id, &ctx.employee),
+        Some(paid_at)
+    );
+}
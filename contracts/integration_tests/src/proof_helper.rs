@@ -9,13 +9,18 @@
 //! 3. Deserialising the hex-encoded fields into fixed-size Rust arrays that
 //!    map directly onto the `Groth16Proof` and `BytesN` types used by Soroban.
 //!
+//! Tests that need several proofs use [`try_generate_proofs`], which runs the
+//! script once in `--batch` mode and reads the `proofs_bytes.json` array
+//! instead of spawning Node.js per proof.  Tests that must not run against
+//! the script's mock fallback use [`try_generate_real_proof`].
+//!
 //! If Node.js is not installed or the script cannot be located the helper
 //! returns `None` and emits a warning to stderr so that CI environments
 //! without a Node.js / SnarkJS toolchain gracefully skip the test.
 //!
 //! # Security
-//! The subprocess receives only two `u64` arguments converted to decimal
-//! strings by [`u64_to_decimal`].  There is no string interpolation from
+//! The subprocess receives only `u64` arguments converted to decimal strings
+//! by [`u64_to_decimal`] (plus the fixed `--batch` flag).  There is no string interpolation from
 //! external sources, so command injection is not possible.
 
 // This module is only compiled in `cfg(test)` mode.
@@ -80,6 +85,101 @@ pub struct GeneratedProof {
 /// This allows CI environments without a Node.js / SnarkJS toolchain to skip
 /// proof-generation tests gracefully without failing the build.
 pub fn try_generate_proof(salary: u64, blinding: u64) -> Option<GeneratedProof> {
+    let args = std::vec![u64_to_decimal(salary), u64_to_decimal(blinding)];
+
+    let out_dir = run_script("zk_payroll_proofs", &args)?;
+
+    let bytes_path = out_dir.join("proof_bytes.json");
+    let bytes_json = match fs::read_to_string(&bytes_path) {
+        Ok(s) => s,
+        Err(_) => {
+            warn("Cannot read proof_bytes.json; skipping.");
+            return None;
+        }
+    };
+
+    match parse_proof_bytes(&bytes_json) {
+        Some(p) => Some(p),
+        None => {
+            warn("Failed to parse proof_bytes.json; skipping.");
+            None
+        }
+    }
+}
+
+/// Attempt to generate one Groth16 proof per `(salary, blinding)` pair in a
+/// single Node.js subprocess.
+///
+/// Calls `node circuits/generate_proof.js --batch <salary> <blinding> ...`,
+/// then reads and parses the resulting `proofs_bytes.json` array.  Proofs are
+/// returned in the order of `inputs`.
+///
+/// Returns `None` (with a stderr warning) in the same situations as
+/// [`try_generate_proof`], and also when the script returns a different
+/// number of proofs than requested.
+pub fn try_generate_proofs(inputs: &[(u64, u64)]) -> Option<Vec<GeneratedProof>> {
+    if inputs.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut args = Vec::new();
+    args.push(String::from("--batch"));
+    for &(salary, blinding) in inputs {
+        args.push(u64_to_decimal(salary));
+        args.push(u64_to_decimal(blinding));
+    }
+
+    let out_dir = run_script("zk_payroll_proofs_batch", &args)?;
+
+    let bytes_path = out_dir.join("proofs_bytes.json");
+    let bytes_json = match fs::read_to_string(&bytes_path) {
+        Ok(s) => s,
+        Err(_) => {
+            warn("Cannot read proofs_bytes.json; skipping.");
+            return None;
+        }
+    };
+
+    match parse_proofs_bytes(&bytes_json) {
+        Some(proofs) if proofs.len() == inputs.len() => Some(proofs),
+        Some(_) => {
+            warn("proofs_bytes.json has the wrong number of proofs; skipping.");
+            None
+        }
+        None => {
+            warn("Failed to parse proofs_bytes.json; skipping.");
+            None
+        }
+    }
+}
+
+/// Like [`try_generate_proof`], but only when the proof will be a **real**
+/// Groth16 proof: SnarkJS must be installed and the compiled circuit
+/// artefacts (`circuits/payment_js/payment.wasm` and
+/// `circuits/payment_final.zkey`) present.
+///
+/// Returns `None` (with a stderr warning) whenever `generate_proof.js` would
+/// fall back to a mock proof, so tests that check the circuit's public
+/// signals against off-chain code skip instead of comparing mock values.
+pub fn try_generate_real_proof(salary: u64, blinding: u64) -> Option<GeneratedProof> {
+    if !is_real_prover_available() {
+        warn(
+            "SnarkJS or the compiled payment circuit is missing; \
+             skipping real proof generation.",
+        );
+        return None;
+    }
+    try_generate_proof(salary, blinding)
+}
+
+// ── Private helpers ───────────────────────────────────────────────────────────
+
+/// Run `node circuits/generate_proof.js <args>` inside the temp subdirectory
+/// `dir_name`, returning that directory once the script has succeeded.
+///
+/// Returns `None` (with a stderr warning) when Node.js or the script is
+/// unavailable, or the subprocess fails.
+fn run_script(dir_name: &str, args: &[String]) -> Option<PathBuf> {
     if !is_node_available() {
         warn(
             "Node.js is not installed; \
@@ -100,21 +200,17 @@ pub fn try_generate_proof(salary: u64, blinding: u64) -> Option<GeneratedProof>
     };
 
     // Write proof artefacts into an isolated temp directory.
-    let out_dir = env::temp_dir().join("zk_payroll_proofs");
+    let out_dir = env::temp_dir().join(dir_name);
     if fs::create_dir_all(&out_dir).is_err() {
         warn("Cannot create temp directory; skipping dynamic proof generation.");
         return None;
     }
 
-    // Spawn: node <script_path> <salary> <blinding>
+    // Spawn: node <script_path> <args...>
     // Arguments are validated u64 values converted to decimal — no injection.
-    let salary_str = u64_to_decimal(salary);
-    let blinding_str = u64_to_decimal(blinding);
-
     let output = Command::new("node")
         .arg(&script_path)
-        .arg(&salary_str)
-        .arg(&blinding_str)
+        .args(args)
         .current_dir(&out_dir)
         .output();
 
@@ -131,26 +227,9 @@ pub fn try_generate_proof(salary: u64, blinding: u64) -> Option<GeneratedProof>
         return None;
     }
 
-    let bytes_path = out_dir.join("proof_bytes.json");
-    let bytes_json = match fs::read_to_string(&bytes_path) {
-        Ok(s) => s,
-        Err(_) => {
-            warn("Cannot read proof_bytes.json; skipping.");
-            return None;
-        }
-    };
-
-    match parse_proof_bytes(&bytes_json) {
-        Some(p) => Some(p),
-        None => {
-            warn("Failed to parse proof_bytes.json; skipping.");
-            None
-        }
-    }
+    Some(out_dir)
 }
 
-// ── Private helpers ───────────────────────────────────────────────────────────
-
 /// Write a warning to stderr without using the `eprintln!` macro (which
 /// requires the std prelude to be in scope).
 fn warn(msg: &str) {
@@ -168,6 +247,29 @@ fn is_node_available() -> bool {
         .unwrap_or(false)
 }
 
+/// Return `true` if Node.js can load `snarkjs` from the circuits directory
+/// and the compiled circuit artefacts sit beside `generate_proof.js` — the
+/// same conditions under which the script produces a real proof.
+fn is_real_prover_available() -> bool {
+    if !is_node_available() {
+        return false;
+    }
+    let circuits_dir = match find_script().as_deref().and_then(Path::parent) {
+        Some(dir) => dir.to_path_buf(),
+        None => return false,
+    };
+    let has_artifacts = circuits_dir.join("payment_js").join("payment.wasm").exists()
+        && circuits_dir.join("payment_final.zkey").exists();
+
+    has_artifacts
+        && Command::new("node")
+            .args(["-e", "require('snarkjs')"])
+            .current_dir(&circuits_dir)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+}
+
 /// Locate `circuits/generate_proof.js` by navigating upward from
 /// `CARGO_MANIFEST_DIR` (i.e. `contracts/integration_tests`) to the
 /// workspace root.
@@ -206,67 +308,167 @@ fn u64_to_decimal(mut n: u64) -> String {
 /// Parse `proof_bytes.json` (a flat JSON object with hex-string values)
 /// into a [`GeneratedProof`].
 ///
-/// The format is intentionally simple — no nested objects, no escape
-/// sequences — so a lightweight hand-rolled parser suffices, avoiding any
-/// external JSON library dependency.
+/// The format is intentionally simple — one flat object of string values —
+/// so the small tokenizer below suffices, avoiding any external JSON library
+/// dependency.  Anything after the object other than whitespace is rejected.
 fn parse_proof_bytes(json: &str) -> Option<GeneratedProof> {
-    let pi_a_hex = extract_str_field(json, "pi_a")?;
-    let pi_b_hex = extract_str_field(json, "pi_b")?;
-    let pi_c_hex = extract_str_field(json, "pi_c")?;
-    let commit_hex = extract_str_field(json, "salary_commitment")?;
-    let nullifier_hex = extract_str_field(json, "payment_nullifier")?;
-    let recipient_hex = extract_str_field(json, "recipient_hash")?;
+    let (fields, rest) = parse_object(json)?;
+    if !skip_ws(rest).is_empty() {
+        return None;
+    }
+    proof_from_fields(&fields)
+}
+
+/// Parse `proofs_bytes.json` (a JSON array of `proof_bytes.json` objects)
+/// into one [`GeneratedProof`] per element, in order.
+///
+/// Returns `None` if the input is not an array or any element fails to
+/// parse.
+fn parse_proofs_bytes(json: &str) -> Option<Vec<GeneratedProof>> {
+    let mut rest = skip_ws(json).strip_prefix('[')?;
+    let mut proofs = Vec::new();
+    loop {
+        rest = skip_ws(rest);
+        if let Some(after) = rest.strip_prefix(']') {
+            return skip_ws(after).is_empty().then_some(proofs);
+        }
+        if !proofs.is_empty() {
+            rest = rest.strip_prefix(',')?;
+        }
+        let (fields, after) = parse_object(rest)?;
+        proofs.push(proof_from_fields(&fields)?);
+        rest = after;
+    }
+}
+
+/// Build a [`GeneratedProof`] from the fields of one parsed object.
+fn proof_from_fields(fields: &[(String, String)]) -> Option<GeneratedProof> {
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
 
     Some(GeneratedProof {
-        pi_a: hex_decode::<64>(pi_a_hex)?,
-        pi_b: hex_decode::<128>(pi_b_hex)?,
-        pi_c: hex_decode::<64>(pi_c_hex)?,
-        salary_commitment: hex_decode::<32>(commit_hex)?,
-        payment_nullifier: hex_decode::<32>(nullifier_hex)?,
-        recipient_hash: hex_decode::<32>(recipient_hex)?,
+        pi_a: hex_decode::<64>(field("pi_a")?)?,
+        pi_b: hex_decode::<128>(field("pi_b")?)?,
+        pi_c: hex_decode::<64>(field("pi_c")?)?,
+        salary_commitment: hex_decode::<32>(field("salary_commitment")?)?,
+        payment_nullifier: hex_decode::<32>(field("payment_nullifier")?)?,
+        recipient_hash: hex_decode::<32>(field("recipient_hash")?)?,
     })
 }
 
 /// Extract the string value of a top-level JSON key.
 ///
-/// Scans for the exact byte pattern `"<field>": "<value>"` and returns the
-/// slice of `json` between the value's enclosing quotes.  Handles flat JSON
-/// objects with string values only — sufficient for `proof_bytes.json`.
-fn extract_str_field<'a>(json: &'a str, field: &str) -> Option<&'a str> {
-    let bytes = json.as_bytes();
-    let field_bytes = field.as_bytes();
-    let flen = field_bytes.len();
-
-    let mut i = 0usize;
-    while i + flen + 2 <= bytes.len() {
-        // Match the pattern: `"<field>"`
-        if bytes[i] == b'"'
-            && bytes.get(i + 1..i + 1 + flen) == Some(field_bytes)
-            && bytes.get(i + 1 + flen) == Some(&b'"')
-        {
-            // Advance past the closing `"` of the key name.
-            let after_key = &json[i + 1 + flen + 1..];
-            // Expect optional whitespace, then `:`.
-            let after_key = after_key.trim_start_matches(|c: char| c.is_ascii_whitespace());
-            let after_col = after_key.strip_prefix(':')?;
-            // Expect optional whitespace, then the opening `"` of the value.
-            let after_col = after_col.trim_start_matches(|c: char| c.is_ascii_whitespace());
-            let value = after_col.strip_prefix('"')?;
-            // Value extends to the next `"`.
-            let end = value.find('"')?;
-            return Some(&value[..end]);
+/// `json` must be a flat object of string values; keys and values are
+/// unescaped, so `"a\"b"` yields `a"b`.  Returns `None` if the key is
+/// absent or `json` is not such an object.
+fn extract_str_field(json: &str, field: &str) -> Option<String> {
+    let (fields, _) = parse_object(json)?;
+    fields
+        .into_iter()
+        .find(|(key, _)| key == field)
+        .map(|(_, value)| value)
+}
+
+/// Parse a flat JSON object whose values are all strings, starting at the
+/// first non-whitespace character of `input`.
+///
+/// Returns the `(key, value)` pairs in document order and the input left
+/// after the closing `}`.
+fn parse_object(input: &str) -> Option<(Vec<(String, String)>, &str)> {
+    let mut rest = skip_ws(input).strip_prefix('{')?;
+    let mut fields = Vec::new();
+    loop {
+        rest = skip_ws(rest);
+        if let Some(after) = rest.strip_prefix('}') {
+            return Some((fields, after));
+        }
+        if !fields.is_empty() {
+            rest = skip_ws(rest.strip_prefix(',')?);
+        }
+        let (key, after) = parse_string(rest)?;
+        let after = skip_ws(skip_ws(after).strip_prefix(':')?);
+        let (value, after) = parse_string(after)?;
+        fields.push((key, value));
+        rest = after;
+    }
+}
+
+/// Parse a JSON string literal at the start of `input`, decoding escape
+/// sequences (including `\uXXXX` and surrogate pairs).
+///
+/// Returns the decoded string and the input left after the closing quote.
+fn parse_string(input: &str) -> Option<(String, &str)> {
+    let mut chars = input.strip_prefix('"')?.char_indices();
+    let body = &input[1..];
+    let mut out = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((out, &body[i + 1..])),
+            '\\' => {
+                let decoded = match chars.next()?.1 {
+                    '"' => '"',
+                    '\\' => '\\',
+                    '/' => '/',
+                    'b' => '\u{8}',
+                    'f' => '\u{c}',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'u' => {
+                        let unit = hex_u16(&mut chars)?;
+                        if (0xD800..0xDC00).contains(&unit) {
+                            // High surrogate: a `\uXXXX` low surrogate must follow.
+                            if chars.next()?.1 != '\\' || chars.next()?.1 != 'u' {
+                                return None;
+                            }
+                            let low = hex_u16(&mut chars)?;
+                            if !(0xDC00..0xE000).contains(&low) {
+                                return None;
+                            }
+                            let code = 0x10000
+                                + ((u32::from(unit) - 0xD800) << 10)
+                                + (u32::from(low) - 0xDC00);
+                            char::from_u32(code)?
+                        } else {
+                            char::from_u32(u32::from(unit))?
+                        }
+                    }
+                    _ => return None,
+                };
+                out.push(decoded);
+            }
+            // Control characters must be escaped inside JSON strings.
+            c if (c as u32) < 0x20 => return None,
+            c => out.push(c),
         }
-        i += 1;
     }
     None
 }
 
+/// Read the four hex digits of a `\uXXXX` escape.
+fn hex_u16(chars: &mut std::str::CharIndices<'_>) -> Option<u16> {
+    let mut unit = 0u16;
+    for _ in 0..4 {
+        unit = unit * 16 + chars.next()?.1.to_digit(16)? as u16;
+    }
+    Some(unit)
+}
+
+/// `input` without leading JSON whitespace.
+fn skip_ws(input: &str) -> &str {
+    input.trim_start_matches([' ', '\t', '\n', '\r'])
+}
+
 /// Decode a lowercase hex string into exactly `N` bytes.
 ///
 /// * The input must have exactly `N * 2` characters (no `0x` prefix).
-/// * Returns `None` on length mismatch or invalid hex digits.
+/// * Returns `None` on length mismatch or invalid or non-ASCII hex digits.
 fn hex_decode<const N: usize>(hex: &str) -> Option<[u8; N]> {
-    if hex.len() != N * 2 {
+    if hex.len() != N * 2 || !hex.is_ascii() {
         return None;
     }
     let mut out = [0u8; N];
@@ -308,15 +510,137 @@ mod inner {
     #[test]
     fn test_extract_str_field() {
         let json = r#"{"pi_a": "deadbeef", "pi_b": "cafebabe"}"#;
-        assert_eq!(extract_str_field(json, "pi_a"), Some("deadbeef"));
-        assert_eq!(extract_str_field(json, "pi_b"), Some("cafebabe"));
+        assert_eq!(extract_str_field(json, "pi_a").as_deref(), Some("deadbeef"));
+        assert_eq!(extract_str_field(json, "pi_b").as_deref(), Some("cafebabe"));
         assert_eq!(extract_str_field(json, "pi_c"), None);
     }
 
+    /// A flat `proof_bytes.json` object whose every field is `digit` repeated.
+    fn proof_object(digit: &str) -> String {
+        let mut json = String::from("{");
+        let fields = [
+            ("pi_a", 64),
+            ("pi_b", 128),
+            ("pi_c", 64),
+            ("salary_commitment", 32),
+            ("payment_nullifier", 32),
+            ("recipient_hash", 32),
+        ];
+        for (i, (name, len)) in fields.iter().enumerate() {
+            if i > 0 {
+                json.push_str(",\n    ");
+            }
+            json.push('"');
+            json.push_str(name);
+            json.push_str("\": \"");
+            json.push_str(&digit.repeat(len * 2));
+            json.push('"');
+        }
+        json.push('}');
+        json
+    }
+
+    #[test]
+    fn test_parse_proofs_bytes_multi_proof_fixture() {
+        let json =
+            String::from("[\n  ") + &proof_object("1") + ",\n  " + &proof_object("2") + "\n]\n";
+
+        let proofs = parse_proofs_bytes(&json).expect("fixture must parse");
+        assert_eq!(proofs.len(), 2);
+        assert_eq!(proofs[0].pi_a, [0x11u8; 64]);
+        assert_eq!(proofs[0].payment_nullifier, [0x11u8; 32]);
+        assert_eq!(proofs[1].pi_b, [0x22u8; 128]);
+        assert_eq!(proofs[1].recipient_hash, [0x22u8; 32]);
+    }
+
+    #[test]
+    fn test_parse_proofs_bytes_rejects_malformed_input() {
+        assert_eq!(parse_proofs_bytes("[]").map(|p| p.len()), Some(0));
+        // A single object rather than an array.
+        assert!(parse_proofs_bytes(&proof_object("1")).is_none());
+        // One element with a truncated field poisons the whole batch.
+        let bad = String::from("[") + &proof_object("1") + ", {\"pi_a\": \"00\"}]";
+        assert!(parse_proofs_bytes(&bad).is_none());
+    }
+
+    /// Skips gracefully (like `test_dynamic_proof_integration`) when Node.js
+    /// is unavailable.
+    #[test]
+    fn test_try_generate_proofs_returns_one_proof_per_input() {
+        let proofs = match try_generate_proofs(&[(5000, 123), (6000, 7)]) {
+            Some(p) => p,
+            None => return,
+        };
+        assert_eq!(proofs.len(), 2);
+        assert_ne!(proofs[0].salary_commitment, proofs[1].salary_commitment);
+
+        let single = try_generate_proof(5000, 123).expect("node was available for the batch");
+        assert_eq!(proofs[0].salary_commitment, single.salary_commitment);
+        assert_eq!(proofs[0].pi_a, single.pi_a);
+    }
+
     #[test]
     fn test_extract_str_field_no_partial_match() {
         // "pi_a" must not match a field literally named "pi_ax"
         let json = r#"{"pi_ax": "wrongval", "pi_a": "rightval"}"#;
-        assert_eq!(extract_str_field(json, "pi_a"), Some("rightval"));
+        assert_eq!(extract_str_field(json, "pi_a").as_deref(), Some("rightval"));
+    }
+
+    #[test]
+    fn test_extract_str_field_unescapes_values() {
+        let json = r#"{"note": "say \"hi\" \\ bye", "pi_a": "after"}"#;
+        assert_eq!(
+            extract_str_field(json, "note").as_deref(),
+            Some(r#"say "hi" \ bye"#)
+        );
+        // The escaped quotes must not end the value early.
+        assert_eq!(extract_str_field(json, "pi_a").as_deref(), Some("after"));
+
+        let json = r#"{"unicode": "caf\u00e9 \ud83d\ude00", "tab": "a\tb"}"#;
+        assert_eq!(
+            extract_str_field(json, "unicode").as_deref(),
+            Some("café 😀")
+        );
+        assert_eq!(extract_str_field(json, "tab").as_deref(), Some("a\tb"));
+
+        // A lone surrogate or unknown escape is malformed.
+        assert_eq!(extract_str_field(r#"{"k": "\ud83d"}"#, "k"), None);
+        assert_eq!(extract_str_field(r#"{"k": "\q"}"#, "k"), None);
+    }
+
+    #[test]
+    fn test_extract_str_field_values_containing_colons_and_keys() {
+        let json = r#"{"label": "pi_a: \"nope\", {}", "pi_a": "ab:cd"}"#;
+        assert_eq!(
+            extract_str_field(json, "label").as_deref(),
+            Some(r#"pi_a: "nope", {}"#)
+        );
+        assert_eq!(extract_str_field(json, "pi_a").as_deref(), Some("ab:cd"));
+    }
+
+    #[test]
+    fn test_parse_proof_bytes_multi_line_json() {
+        let json = proof_object("3")
+            .replace('{', "{\r\n\t")
+            .replace("\": ", "\"\n  :\n  ")
+            .replace('}', "\n}\n");
+
+        let proof = parse_proof_bytes(&json).expect("multi-line object must parse");
+        assert_eq!(proof.pi_c, [0x33u8; 64]);
+        assert_eq!(proof.salary_commitment, [0x33u8; 32]);
+
+        // Trailing content after the object is rejected.
+        assert!(parse_proof_bytes(&(json + "x")).is_none());
+    }
+
+    #[test]
+    fn test_parse_proofs_bytes_tolerates_braces_in_values() {
+        let with_note = proof_object("4").replace('{', r#"{"note": "}{", "#);
+        let json = String::from("[") + &with_note + "," + &proof_object("5") + "]";
+
+        let proofs = parse_proofs_bytes(&json).expect("braces inside strings are data");
+        assert_eq!(proofs.len(), 2);
+        assert_eq!(proofs[0].pi_a, [0x44u8; 64]);
+        assert_eq!(proofs[1].pi_a, [0x55u8; 64]);
     }
 }
@@ -0,0 +1,170 @@
+//! Recipient-bound nullifier tests across payroll and proof_verifier.
+//!
+//! A bound payment carries the `recipient_hash` its proof was generated for.
+//! The verifier recomputes the hash from the employee actually being paid, so
+//! a proof and nullifier issued for Alice cannot be redirected to Bob.
+
+use payroll::{BoundPayment, NullifierPolicy, Payroll, PayrollClient};
+use proof_verifier::{recipient_hash, ProofVerifier, ProofVerifierClient, VerificationKey};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Vec};
+use token::{Token, TokenClient};
+
+struct Ctx<'a> {
+    env: Env,
+    alice: Address,
+    bob: Address,
+    payroll: PayrollClient<'a>,
+    token: TokenClient<'a>,
+}
+
+/// Verification key with one IC point per public input
+/// (commitment, nullifier, recipient_hash) plus the constant term.
+fn mock_vk(env: &Env) -> VerificationKey {
+    VerificationKey {
+        alpha: BytesN::from_array(env, &[0u8; 64]),
+        beta: BytesN::from_array(env, &[0u8; 128]),
+        gamma: BytesN::from_array(env, &[0u8; 128]),
+        delta: BytesN::from_array(env, &[0u8; 128]),
+        ic: Vec::from_array(
+            env,
+            [
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+            ],
+        ),
+    }
+}
+
+/// Deploy payroll with Alice and Bob enrolled and a funded treasury, leaving
+/// the default requirement for recipient-bound nullifiers in place.
+fn setup() -> Ctx<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let verifier_id = env.register_contract(None, ProofVerifier);
+    let verifier = ProofVerifierClient::new(&env, &verifier_id);
+    verifier.init_verifier_admin(&admin);
+    verifier.initialize_verifier(&mock_vk(&env));
+
+    let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+    let commitment = SalaryCommitmentContractClient::new(&env, &commitment_id);
+    commitment.init_commitment_admin(&admin);
+
+    let token_id = env.register_contract(None, Token);
+    let token = TokenClient::new(&env, &token_id);
+    token.mint(&treasury, &10_000);
+
+    let payroll_id = env.register_contract(None, Payroll);
+    let payroll = PayrollClient::new(&env, &payroll_id);
+    payroll.initialize(
+        &admin,
+        &token_id,
+        &verifier_id,
+        &commitment_id,
+        &treasury,
+        &Address::generate(&env),
+    );
+
+    commitment.set_payroll_operator(&payroll_id);
+    commitment.store_commitment(&alice, &BytesN::from_array(&env, &[1u8; 32]));
+    commitment.store_commitment(&bob, &BytesN::from_array(&env, &[2u8; 32]));
+
+    Ctx {
+        env,
+        alice,
+        bob,
+        payroll,
+        token,
+    }
+}
+
+/// A single payment to `employee` whose nullifier was derived for `bound_to`.
+fn payment(ctx: &Ctx, employee: &Address, bound_to: &Address) -> Vec<BoundPayment> {
+    let env = &ctx.env;
+    Vec::from_array(
+        env,
+        [BoundPayment {
+            proof: BytesN::from_array(env, &[0u8; 256]),
+            amount: 3_000,
+            employee: employee.clone(),
+            nullifier: BytesN::from_array(env, &[9u8; 32]),
+            recipient_hash: recipient_hash(env, bound_to),
+        }],
+    )
+}
+
+#[test]
+fn test_nullifier_bound_to_alice_pays_alice() {
+    let ctx = setup();
+    let payments = payment(&ctx, &ctx.alice, &ctx.alice);
+
+    ctx.payroll.batch_process_bound_payroll(
+        &payments,
+        &3_000,
+        &BytesN::from_array(&ctx.env, &[1u8; 32]),
+        &None,
+        &NullifierPolicy::Abort,
+    );
+
+    assert_eq!(ctx.token.balance(&ctx.alice), 3_000);
+}
+
+#[test]
+#[should_panic(expected = "Invalid payment proof")]
+fn test_nullifier_bound_to_alice_cannot_pay_bob() {
+    let ctx = setup();
+    let payments = payment(&ctx, &ctx.bob, &ctx.alice);
+
+    ctx.payroll.batch_process_bound_payroll(
+        &payments,
+        &3_000,
+        &BytesN::from_array(&ctx.env, &[1u8; 32]),
+        &None,
+        &NullifierPolicy::Abort,
+    );
+}
+
+#[test]
+fn test_redirected_payment_leaves_balances_untouched() {
+    let ctx = setup();
+    let payments = payment(&ctx, &ctx.bob, &ctx.alice);
+
+    let result = ctx.payroll.try_batch_process_bound_payroll(
+        &payments,
+        &3_000,
+        &BytesN::from_array(&ctx.env, &[1u8; 32]),
+        &None,
+        &NullifierPolicy::Abort,
+    );
+
+    assert!(result.is_err());
+    assert_eq!(ctx.token.balance(&ctx.bob), 0);
+    assert_eq!(ctx.token.balance(&ctx.alice), 0);
+}
+
+#[test]
+fn test_index_derived_nullifiers_are_rejected_by_default() {
+    let ctx = setup();
+    assert!(ctx.payroll.get_require_bound_nullifiers());
+
+    let result = ctx.payroll.try_batch_process_payroll(
+        &Vec::from_array(&ctx.env, [BytesN::from_array(&ctx.env, &[0u8; 256])]),
+        &Vec::from_array(&ctx.env, [3_000i128]),
+        &Vec::from_array(&ctx.env, [ctx.bob.clone()]),
+        &3_000,
+        &BytesN::from_array(&ctx.env, &[1u8; 32]),
+        &None,
+        &NullifierPolicy::Abort,
+    );
+
+    assert!(result.is_err());
+    assert_eq!(ctx.token.balance(&ctx.bob), 0);
+}
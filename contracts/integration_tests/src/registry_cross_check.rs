@@ -0,0 +1,160 @@
+//! Registry cross-check tests across payroll, payroll_registry and
+//! salary_commitment.
+//!
+//! With `Payroll::set_registry_cross_check` set, a batch compares the
+//! commitment contract's commitment for each employee with the one the
+//! registry enrolled them under, and rejects the batch when they diverge.
+
+use payroll::{NullifierPolicy, Payroll, PayrollClient, RegistryCrossCheck};
+use payroll_registry::{PayrollRegistry, PayrollRegistryClient};
+use proof_verifier::{ProofVerifier, ProofVerifierClient, VerificationKey};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Vec};
+use token::{Token, TokenClient};
+
+struct Ctx<'a> {
+    env: Env,
+    alice: Address,
+    payroll: PayrollClient<'a>,
+    registry: PayrollRegistryClient<'a>,
+    company_id: u64,
+    commitment: SalaryCommitmentContractClient<'a>,
+    token: TokenClient<'a>,
+}
+
+/// Verification key with one IC point per public input
+/// (commitment, nullifier, recipient_hash) plus the constant term.
+fn mock_vk(env: &Env) -> VerificationKey {
+    VerificationKey {
+        alpha: BytesN::from_array(env, &[0u8; 64]),
+        beta: BytesN::from_array(env, &[0u8; 128]),
+        gamma: BytesN::from_array(env, &[0u8; 128]),
+        delta: BytesN::from_array(env, &[0u8; 128]),
+        ic: Vec::from_array(
+            env,
+            [
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+            ],
+        ),
+    }
+}
+
+/// Deploy payroll with a funded treasury, enrol Alice in a registry company
+/// and the commitment contract under the same commitment, and point the
+/// payroll's cross-check at that company.
+fn setup() -> Ctx<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    let verifier_id = env.register_contract(None, ProofVerifier);
+    let verifier = ProofVerifierClient::new(&env, &verifier_id);
+    verifier.init_verifier_admin(&admin);
+    verifier.initialize_verifier(&mock_vk(&env));
+
+    let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+    let commitment = SalaryCommitmentContractClient::new(&env, &commitment_id);
+    commitment.init_commitment_admin(&admin);
+
+    let token_id = env.register_contract(None, Token);
+    let token = TokenClient::new(&env, &token_id);
+    token.mint(&treasury, &10_000);
+
+    let registry_id = env.register_contract(None, PayrollRegistry);
+    let registry = PayrollRegistryClient::new(&env, &registry_id);
+    let company_id = registry.register_company(&admin, &treasury);
+
+    let payroll_id = env.register_contract(None, Payroll);
+    let payroll = PayrollClient::new(&env, &payroll_id);
+    payroll.initialize(
+        &admin,
+        &token_id,
+        &verifier_id,
+        &commitment_id,
+        &treasury,
+        &Address::generate(&env),
+    );
+    payroll.set_require_bound_nullifiers(&false);
+    payroll.set_registry_cross_check(&Some(RegistryCrossCheck {
+        registry: registry_id,
+        company_id,
+    }));
+
+    let salary = BytesN::from_array(&env, &[1u8; 32]);
+    commitment.set_payroll_operator(&payroll_id);
+    commitment.store_commitment(&alice, &salary);
+    registry.add_employee(&company_id, &admin, &alice, &salary);
+
+    Ctx {
+        env,
+        alice,
+        payroll,
+        registry,
+        company_id,
+        commitment,
+        token,
+    }
+}
+
+/// Submit a single-payment batch paying Alice 3 000.
+fn pay_alice(ctx: &Ctx) {
+    let env = &ctx.env;
+    ctx.payroll.batch_process_payroll(
+        &Vec::from_array(env, [BytesN::from_array(env, &[0u8; 256])]),
+        &Vec::from_array(env, [3_000i128]),
+        &Vec::from_array(env, [ctx.alice.clone()]),
+        &3_000,
+        &BytesN::from_array(env, &[1u8; 32]),
+        &None,
+        &NullifierPolicy::Abort,
+    );
+}
+
+#[test]
+fn test_matching_stores_pay_the_batch() {
+    let ctx = setup();
+
+    pay_alice(&ctx);
+
+    assert_eq!(ctx.token.balance(&ctx.alice), 3_000);
+}
+
+#[test]
+#[should_panic(expected = "Commitment divergence")]
+fn test_diverged_commitment_rejects_the_batch() {
+    let ctx = setup();
+    // The commitment contract moves on; the registry still holds the old
+    // commitment.
+    ctx.commitment
+        .update_commitment(&ctx.alice, &BytesN::from_array(&ctx.env, &[2u8; 32]));
+
+    pay_alice(&ctx);
+}
+
+#[test]
+#[should_panic(expected = "Commitment divergence")]
+fn test_employee_missing_from_registry_rejects_the_batch() {
+    let ctx = setup();
+    ctx.registry.remove_employee(&ctx.company_id, &ctx.alice);
+
+    pay_alice(&ctx);
+}
+
+#[test]
+fn test_cross_check_can_be_disabled() {
+    let ctx = setup();
+    ctx.commitment
+        .update_commitment(&ctx.alice, &BytesN::from_array(&ctx.env, &[2u8; 32]));
+    ctx.payroll.set_registry_cross_check(&None);
+
+    pay_alice(&ctx);
+
+    assert_eq!(ctx.payroll.get_registry_cross_check(), None);
+    assert_eq!(ctx.token.balance(&ctx.alice), 3_000);
+}
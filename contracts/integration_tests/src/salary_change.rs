@@ -0,0 +1,132 @@
+//! Salary-change approval tests.
+//!
+//! Exercises the two-step `propose_salary_change` / `approve_salary_change`
+//! flow on `PayrollRegistry`, which only pushes the new commitment into
+//! `SalaryCommitmentContract` once a second signer approves it.
+
+use payroll_registry::{PayrollRegistry, PayrollRegistryClient, RegistryError};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+
+struct Ctx<'a> {
+    admin: Address,
+    approver: Address,
+    employee: Address,
+    company_id: u64,
+    registry: PayrollRegistryClient<'a>,
+    commitment: SalaryCommitmentContractClient<'a>,
+}
+
+/// Register a company with a salary approver, wire the registry as the
+/// commitment contract's admin, and onboard one employee at salary 5000.
+fn setup(env: &Env) -> Ctx<'_> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let approver = Address::generate(env);
+    let employee = Address::generate(env);
+
+    let registry_id = env.register_contract(None, PayrollRegistry);
+    let registry = PayrollRegistryClient::new(env, &registry_id);
+
+    let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+    let commitment = SalaryCommitmentContractClient::new(env, &commitment_id);
+    commitment.init_commitment_admin(&registry_id);
+
+    let company_id = registry.register_company(&admin, &Address::generate(env));
+    registry.set_salary_approver(&company_id, &approver);
+    registry.set_commitment_contract(&company_id, &commitment_id);
+
+    let initial = salary_commitment(&commitment, 5000, 1);
+    registry.add_employee(&company_id, &admin, &employee, &initial);
+    commitment.store_commitment(&employee, &initial);
+
+    Ctx {
+        admin,
+        approver,
+        employee,
+        company_id,
+        registry,
+        commitment,
+    }
+}
+
+fn salary_commitment(client: &SalaryCommitmentContractClient, salary: u64, seed: u8) -> BytesN<32> {
+    let mut blinding = [0u8; 32];
+    blinding[31] = seed;
+    client.compute_commitment(&salary, &BytesN::from_array(&client.env, &blinding))
+}
+
+#[test]
+fn test_raise_applies_only_after_second_admin_approves() {
+    let env = Env::default();
+    let ctx = setup(&env);
+    let before = ctx.commitment.get_commitment(&ctx.employee);
+    let raised = salary_commitment(&ctx.commitment, 6000, 2);
+
+    ctx.registry
+        .propose_salary_change(&ctx.company_id, &ctx.admin, &ctx.employee, &raised);
+    assert!(ctx
+        .registry
+        .get_pending_salary_change(&ctx.company_id, &ctx.employee)
+        .is_some());
+    assert_eq!(
+        ctx.registry.get_commitment(&ctx.company_id, &ctx.employee),
+        before.commitment
+    );
+
+    // The proposing admin cannot approve their own change.
+    let self_approval =
+        ctx.registry
+            .try_approve_salary_change(&ctx.company_id, &ctx.admin, &ctx.employee);
+    assert!(self_approval.is_err());
+    assert_eq!(
+        ctx.commitment.get_commitment(&ctx.employee).commitment,
+        before.commitment
+    );
+
+    ctx.registry
+        .approve_salary_change(&ctx.company_id, &ctx.approver, &ctx.employee);
+
+    assert_eq!(
+        ctx.registry.get_commitment(&ctx.company_id, &ctx.employee),
+        raised
+    );
+    let after = ctx.commitment.get_commitment(&ctx.employee);
+    assert_eq!(after.commitment, raised);
+    assert_eq!(after.version, before.version + 1);
+    assert!(ctx
+        .registry
+        .get_pending_salary_change(&ctx.company_id, &ctx.employee)
+        .is_none());
+}
+
+#[test]
+fn test_unknown_approver_rejected() {
+    let env = Env::default();
+    let ctx = setup(&env);
+    let raised = salary_commitment(&ctx.commitment, 6000, 2);
+
+    ctx.registry
+        .propose_salary_change(&ctx.company_id, &ctx.admin, &ctx.employee, &raised);
+    let result = ctx.registry.try_approve_salary_change(
+        &ctx.company_id,
+        &Address::generate(&env),
+        &ctx.employee,
+    );
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::NotAuthorized);
+}
+
+#[test]
+fn test_approve_without_proposal_rejected() {
+    let env = Env::default();
+    let ctx = setup(&env);
+
+    let result =
+        ctx.registry
+            .try_approve_salary_change(&ctx.company_id, &ctx.approver, &ctx.employee);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        RegistryError::NoPendingSalaryChange
+    );
+}
@@ -0,0 +1,65 @@
+//! Treasury balance read through payroll from the token contract.
+//!
+//! `Payroll::get_treasury_balance` queries the configured token for the
+//! configured treasury, so funds minted straight to the treasury show up
+//! without the caller knowing the token address.
+
+use payroll::{Payroll, PayrollClient};
+use proof_verifier::{ProofVerifier, ProofVerifierClient, VerificationKey};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Vec};
+use token::{Token, TokenClient};
+
+fn mock_vk(env: &Env) -> VerificationKey {
+    VerificationKey {
+        alpha: BytesN::from_array(env, &[0u8; 64]),
+        beta: BytesN::from_array(env, &[0u8; 128]),
+        gamma: BytesN::from_array(env, &[0u8; 128]),
+        delta: BytesN::from_array(env, &[0u8; 128]),
+        ic: Vec::from_array(
+            env,
+            [
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+            ],
+        ),
+    }
+}
+
+#[test]
+fn test_treasury_balance_reflects_mints() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+
+    let verifier_id = env.register_contract(None, ProofVerifier);
+    let verifier = ProofVerifierClient::new(&env, &verifier_id);
+    verifier.init_verifier_admin(&admin);
+    verifier.initialize_verifier(&mock_vk(&env));
+
+    let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+    SalaryCommitmentContractClient::new(&env, &commitment_id).init_commitment_admin(&admin);
+
+    let token_id = env.register_contract(None, Token);
+    let token = TokenClient::new(&env, &token_id);
+
+    let payroll = PayrollClient::new(&env, &env.register_contract(None, Payroll));
+    payroll.initialize(
+        &admin,
+        &token_id,
+        &verifier_id,
+        &commitment_id,
+        &treasury,
+        &Address::generate(&env),
+    );
+    assert_eq!(payroll.get_treasury_balance(), 0);
+
+    token.mint(&treasury, &10_000);
+    assert_eq!(payroll.get_treasury_balance(), 10_000);
+
+    token.mint(&treasury, &2_500);
+    assert_eq!(payroll.get_treasury_balance(), 12_500);
+    assert_eq!(payroll.get_treasury_balance(), token.balance(&treasury));
+}
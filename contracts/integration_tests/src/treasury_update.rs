@@ -0,0 +1,121 @@
+//! Treasury updates in payroll_registry picked up by payment_executor.
+//!
+//! `PaymentExecutor::execute_payment` reads the company's treasury from the
+//! registry on every payment, so a payment made after
+//! `PayrollRegistry::update_treasury` debits the new treasury.
+
+use payment_executor::{ContractAddresses, PaymentExecutor, PaymentExecutorClient};
+use payroll_registry::{PayrollRegistry, PayrollRegistryClient};
+use proof_verifier::testutils::{g1_point, g2_point};
+use proof_verifier::{ProofVerifier, ProofVerifierClient, VerificationKey};
+use salary_commitment::{SalaryCommitmentContract, SalaryCommitmentContractClient};
+use soroban_sdk::testutils::{Address as _, BytesN as _};
+use soroban_sdk::{Address, BytesN, Env, Vec};
+use token::{Token, TokenClient};
+
+struct Ctx<'a> {
+    env: Env,
+    admin: Address,
+    employee: Address,
+    treasury: Address,
+    company_id: u64,
+    executor: PaymentExecutorClient<'a>,
+    registry: PayrollRegistryClient<'a>,
+    token: TokenClient<'a>,
+}
+
+fn mock_vk(env: &Env) -> VerificationKey {
+    VerificationKey {
+        alpha: BytesN::from_array(env, &[0u8; 64]),
+        beta: BytesN::from_array(env, &[0u8; 128]),
+        gamma: BytesN::from_array(env, &[0u8; 128]),
+        delta: BytesN::from_array(env, &[0u8; 128]),
+        ic: Vec::from_array(
+            env,
+            [
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+                BytesN::from_array(env, &[0u8; 64]),
+            ],
+        ),
+    }
+}
+
+/// Deploy the executor and its dependencies and enrol one employee in a
+/// company whose treasury holds 10 000.
+fn setup() -> Ctx<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let addresses = ContractAddresses {
+        registry: env.register_contract(None, PayrollRegistry),
+        commitment: env.register_contract(None, SalaryCommitmentContract),
+        verifier: env.register_contract(None, ProofVerifier),
+        token: env.register_contract(None, Token),
+    };
+    let verifier = ProofVerifierClient::new(&env, &addresses.verifier);
+    verifier.init_verifier_admin(&Address::generate(&env));
+    verifier.initialize_verifier(&mock_vk(&env));
+    let commitment = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
+    commitment.init_commitment_admin(&Address::generate(&env));
+
+    let executor = PaymentExecutorClient::new(&env, &env.register_contract(None, PaymentExecutor));
+    executor.initialize(&addresses);
+
+    let registry = PayrollRegistryClient::new(&env, &addresses.registry);
+    let token = TokenClient::new(&env, &addresses.token);
+    let treasury = Address::generate(&env);
+    let employee = Address::generate(&env);
+    let salary_commitment = BytesN::from_array(&env, &[9u8; 32]);
+    let admin = Address::generate(&env);
+    let company_id = registry.register_company(&admin, &treasury);
+    commitment.store_commitment(&employee, &salary_commitment);
+    registry.add_employee(&company_id, &admin, &employee, &salary_commitment);
+    token.mint(&treasury, &10_000);
+
+    Ctx {
+        env,
+        admin,
+        employee,
+        treasury,
+        company_id,
+        executor,
+        registry,
+        token,
+    }
+}
+
+/// Pay the employee 1 000 in a fresh period.
+fn pay(ctx: &Ctx) {
+    let env = &ctx.env;
+    let period = ctx.executor.create_period(&ctx.company_id).period_id;
+    ctx.executor.execute_payment(
+        &ctx.company_id,
+        &ctx.employee,
+        &1_000,
+        // A distinct proof per payment, so the replay guard lets it through.
+        &g1_point(env, period.into()),
+        &g2_point(env, 2),
+        &g1_point(env, 3),
+        &BytesN::random(env),
+        &period,
+        &BytesN::random(env),
+    );
+}
+
+#[test]
+fn test_payment_after_treasury_update_debits_new_treasury() {
+    let ctx = setup();
+    pay(&ctx);
+    assert_eq!(ctx.token.balance(&ctx.treasury), 9_000);
+
+    let new_treasury = Address::generate(&ctx.env);
+    ctx.token.mint(&new_treasury, &5_000);
+    ctx.registry
+        .update_treasury(&ctx.company_id, &ctx.admin, &new_treasury);
+    pay(&ctx);
+
+    assert_eq!(ctx.token.balance(&ctx.treasury), 9_000);
+    assert_eq!(ctx.token.balance(&new_treasury), 4_000);
+    assert_eq!(ctx.token.balance(&ctx.employee), 2_000);
+}
@@ -181,7 +181,7 @@ mod upgrade_simulation {
         // Populate all current key variants.
         let company_id = registry.register_company(&admin, &treasury);
         let commitment = zero_commitment(&env);
-        registry.add_employee(&company_id, &employee, &commitment);
+        registry.add_employee(&company_id, &admin, &employee, &commitment);
 
         // Assertions: all reads succeed and return correct values.
         let info = registry.get_company(&company_id);
@@ -397,8 +397,8 @@ mod upgrade_simulation {
         bob_bytes[31] = 0xFF;
         let bob_commitment = BytesN::from_array(&env, &bob_bytes);
 
-        registry.add_employee(&company_id, &alice, &alice_commitment);
-        registry.add_employee(&company_id, &bob, &bob_commitment);
+        registry.add_employee(&company_id, &admin, &alice, &alice_commitment);
+        registry.add_employee(&company_id, &admin, &bob, &bob_commitment);
 
         // Simulate upgrade migration: remove Alice (e.g. stale employee cleanup).
         registry.remove_employee(&company_id, &alice);
@@ -32,11 +32,6 @@ pub enum ModuleError {
     Unauthorized = 3,
 }
 
-#[contracttype]
-pub enum DataKey {
-    Admin,
-}
-
 // ── Storage keys ──────────────────────────────────────────────────────────────
 
 /// One variant per logical storage slot.
@@ -98,8 +93,6 @@ impl ModuleTemplate {
             .get(&DataKey::Admin)
             .ok_or(ModuleError::NotInitialized)
     }
-}
-
 
     // TODO: implement module-specific entry-points below.
     //
@@ -138,9 +131,6 @@ mod tests {
         let contract_id = env.register_contract(None, ModuleTemplate);
         let client = ModuleTemplateClient::new(&env, &contract_id);
 
-        let contract_id = env.register_contract(None, ModuleTemplate);
-        let client = ModuleTemplateClient::new(&env, &contract_id);
-
         let admin = Address::generate(&env);
         client.initialize(&admin);
         assert_eq!(client.get_admin(), admin);
@@ -152,14 +142,6 @@ mod tests {
         env.mock_all_auths();
         let contract_id = env.register_contract(None, ModuleTemplate);
         let client = ModuleTemplateClient::new(&env, &contract_id);
-        let admin = Address::generate(&env);
-        client.initialize(&admin);
-        let result = client.try_initialize(&admin);
-        assert_eq!(result, Err(Ok(ModuleError::AlreadyInitialized)));
-    }
-
-        let contract_id = env.register_contract(None, ModuleTemplate);
-        let client = ModuleTemplateClient::new(&env, &contract_id);
 
         let admin = Address::generate(&env);
         client.initialize(&admin);
@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Symbol};
 
 #[contracttype]
 pub enum DataKey {
@@ -8,6 +8,14 @@ pub enum DataKey {
     Operator,
 }
 
+/// Error returned by a second call to an initializer.
+#[contracterror]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum InitError {
+    AlreadyInitialized = 1,
+}
+
 #[cfg(feature = "contract")]
 #[contract]
 pub struct PauseManager;
@@ -15,12 +23,13 @@ pub struct PauseManager;
 #[cfg(feature = "contract")]
 #[contractimpl]
 impl PauseManager {
-    pub fn initialize(e: Env, operator: Address) {
+    pub fn initialize(e: Env, operator: Address) -> Result<(), InitError> {
         if e.storage().persistent().has(&DataKey::Operator) {
-            panic!("Already initialized");
+            return Err(InitError::AlreadyInitialized);
         }
         e.storage().persistent().set(&DataKey::Operator, &operator);
         e.storage().persistent().set(&DataKey::Paused, &false);
+        Ok(())
     }
 
     pub fn pause(e: Env) {
@@ -141,7 +150,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Already initialized")]
     fn test_initialize_cannot_be_called_twice() {
         let env = Env::default();
         env.mock_all_auths();
@@ -149,7 +157,10 @@ mod tests {
         let client = PauseManagerClient::new(&env, &contract_id);
         let operator = Address::generate(&env);
         client.initialize(&operator);
-        client.initialize(&operator);
+        assert_eq!(
+            client.try_initialize(&operator),
+            Err(Ok(InitError::AlreadyInitialized))
+        );
     }
 
     #[test]
@@ -1,17 +1,44 @@
 #![no_std]
 
 use pause_manager::PauseManagerClient;
-use payroll_registry::{CompanyInfo, PayrollRegistryClient};
-use proof_verifier::{Groth16Proof, ProofVerifierClient};
+use payroll_registry::{CompanyInfo, PayrollRegistryClient, RegistryError};
+use proof_verifier::{ProofVerifierClient, VerifierError};
 use salary_commitment::SalaryCommitmentContractClient;
+use soroban_sdk::xdr::ToXdr;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, token, Address, BytesN, Env,
+    contract, contracterror, contractimpl, contracttype, token, Address, Bytes, BytesN, Env,
+    TryFromVal, Val,
 };
 
 /// Maximum age for a proof relative to its period creation time (7 days in seconds).
 /// Proofs must be submitted within this window to prevent replay attacks using stale proofs.
 const MAX_PROOF_AGE_SECONDS: u64 = 7 * 24 * 60 * 60;
 
+/// Length of the window over which an employee's annual cap applies (365 days in seconds).
+const ANNUAL_CAP_WINDOW_SECONDS: u64 = 365 * 24 * 60 * 60;
+
+/// Number of recent proofs remembered across periods by the duplicate-proof
+/// detector. The oldest hash is forgotten once this many newer payments
+/// have executed.
+pub const RECENT_PROOF_WINDOW: u32 = 256;
+
+/// Layout version of persisted `PaymentRecord`s.
+///
+/// Bump this whenever `PaymentRecord` changes shape, keep the previous
+/// layout as its own type, and teach `load_record` to decode it.
+const STORAGE_VERSION: u32 = 1;
+
+/// Maximum number of records `queue_migration` takes or `migrate_storage`
+/// rewrites in one call.
+pub const MAX_MIGRATION_BATCH: u32 = 50;
+
+/// Topic name of the event emitted for every executed payment.
+///
+/// The suffix versions the event's data layout. Bump it whenever the topics
+/// or data change shape, so indexers can tell old and new events apart while
+/// both are on chain.
+pub const PAYROLL_PROCESSED_TOPIC: &str = "PayrollProcessed_v1";
+
 /// Payment record
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -21,6 +48,34 @@ pub struct PaymentRecord {
     pub proof_hash: BytesN<32>,
     pub timestamp: u64,
     pub period: u32,
+    /// Ledger sequence the payment executed in (0 for records migrated from
+    /// storage version 0, which did not track it).
+    pub ledger: u32,
+}
+
+/// `PaymentRecord` layout used before storage versioning (version 0).
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PaymentRecordV0 {
+    pub company_id: u64,
+    pub employee: Address,
+    pub proof_hash: BytesN<32>,
+    pub timestamp: u64,
+    pub period: u32,
+}
+
+/// Privacy-preserving receipt an employee can present as proof of payment.
+///
+/// Carries the salary commitment the payment proof was verified against
+/// rather than the amount, so the receipt reveals nothing about the salary.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PaymentProof {
+    pub company_id: u64,
+    pub employee: Address,
+    pub period: u32,
+    pub timestamp: u64,
+    pub amount_commitment: BytesN<32>,
 }
 
 /// A payroll period definition with scheduling metadata.
@@ -47,6 +102,34 @@ pub struct PayrollPeriod {
     pub payment_count: u32,
 }
 
+/// A payment earmarked ahead of execution.
+///
+/// Staging reserves `amount` against the company's treasury for a given
+/// employee and period. A stage that is not executed by `expires_at` can no
+/// longer be executed and is released by `expire_stale_stages`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StagedPayment {
+    pub company_id: u64,
+    pub employee: Address,
+    pub amount: i128,
+    pub period: u32,
+    /// Unix timestamp when the payment was staged (on-chain time).
+    pub staged_at: u64,
+    /// Unix timestamp after which the stage can no longer be executed.
+    pub expires_at: u64,
+}
+
+/// Payments to an employee within the rolling annual-cap window.
+///
+/// Holds `(timestamp, amount)` for every payment made in the last
+/// `ANNUAL_CAP_WINDOW_SECONDS`; older entries are dropped on the next payment.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CapWindow {
+    pub payments: soroban_sdk::Vec<(u64, i128)>,
+}
+
 #[contracterror]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u32)]
@@ -62,6 +145,46 @@ pub enum PaymentError {
     PeriodAlreadyExists = 6,
     /// The proof has expired and can no longer be used (issue #77).
     ProofExpired = 7,
+    /// The staged payment has passed its `expires_at` deadline.
+    StagedPaymentExpired = 8,
+    /// The payment would take the employee over their annual cap.
+    AnnualCapExceeded = 9,
+    /// No payment is recorded for this employee and period.
+    PaymentNotFound = 10,
+    /// The payment amount is zero or negative.
+    InvalidAmount = 11,
+    /// `initialize` has not been called yet.
+    NotInitialized = 12,
+    /// The company has been deactivated in the registry.
+    CompanyInactive = 13,
+    /// No company is registered under the given ID in the registry.
+    CompanyNotFound = 14,
+    /// The employee is not registered under the company in the registry.
+    EmployeeNotFound = 15,
+    /// The idempotency key was already used for a payment with a different
+    /// employee, amount or period.
+    IdempotencyKeyReused = 16,
+    /// The payment amount differs from the amount staged for the employee
+    /// and period.
+    StagedAmountMismatch = 17,
+    /// The company's circuit uses a proof system the verifier cannot check
+    /// yet.
+    UnsupportedProofSystem = 18,
+    /// The caller is not the admin the operation requires.
+    Unauthorized = 19,
+    /// More records were passed than `MAX_MIGRATION_BATCH`.
+    MigrationBatchTooLarge = 20,
+    /// The registry refused to record the payment for a reason not covered
+    /// by another variant.
+    RegistryRejected = 21,
+}
+
+/// Error returned by a second call to an initializer.
+#[contracterror]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum InitError {
+    AlreadyInitialized = 1,
 }
 
 /// Contract addresses for dependencies
@@ -76,6 +199,7 @@ pub struct ContractAddresses {
 
 /// Storage keys
 #[contracttype]
+#[derive(Clone)]
 pub enum DataKey {
     Addresses,
     Payment(Address, u32),
@@ -85,6 +209,43 @@ pub enum DataKey {
     PauseManager,
     Period(u64, u32),
     PeriodSequence(u64),
+    /// Staged payment for (company_id, employee, period).
+    Stage(u64, Address, u32),
+    /// Outstanding (employee, period) stages for a company.
+    CompanyStages(u64),
+    /// Sum of outstanding staged amounts for a company.
+    StagedTotal(u64),
+    /// Cumulative amount ever paid to an employee.
+    EmployeeTotal(Address),
+    /// Optional maximum (company_id, employee) may receive per annual window.
+    AnnualCap(u64, Address),
+    /// Payments to (company_id, employee) in the rolling annual window.
+    CapWindow(u64, Address),
+    /// Record returned for a (company_id, idempotency key) already executed.
+    Idempotency(u64, BytesN<32>),
+    /// Hash of the (employee, amount, period) the idempotency key was first
+    /// used with.
+    IdempotencyArgs(u64, BytesN<32>),
+    /// Commitment the payment proof for (employee, period) was verified against.
+    PaidCommitment(Address, u32),
+    /// Layout version of persisted payment records (absent = 0).
+    StorageVersion,
+    /// (employee, period) of the payment that consumed a nullifier.
+    NullifierToPayment(BytesN<32>),
+    /// Proof hash held in ring-buffer slot `0..RECENT_PROOF_WINDOW`.
+    RecentProofSlot(u32),
+    /// Next ring-buffer slot to overwrite (absent = 0).
+    RecentProofHead,
+    /// Present while a proof hash is in the ring buffer.
+    RecentProof(BytesN<32>),
+    /// Period `execute_payment_current` pays into (absent = none yet).
+    CurrentPeriod(u64),
+    /// (employee, period) of the old-layout record queued at this position.
+    MigrationQueue(u32),
+    /// Number of records ever queued for migration.
+    MigrationQueueLen,
+    /// Queue position `migrate_storage` resumes from.
+    MigrationCursor,
 }
 
 #[contract]
@@ -92,6 +253,36 @@ pub struct PaymentExecutor;
 
 #[contractimpl]
 impl PaymentExecutor {
+    /// SHA-256 of the proof points `a || b || c`.
+    fn proof_hash(
+        env: &Env,
+        proof_a: &BytesN<64>,
+        proof_b: &BytesN<128>,
+        proof_c: &BytesN<64>,
+    ) -> BytesN<32> {
+        let mut bytes = Bytes::from_array(env, &proof_a.to_array());
+        bytes.extend_from_array(&proof_b.to_array());
+        bytes.extend_from_array(&proof_c.to_array());
+        env.crypto().sha256(&bytes).into()
+    }
+
+    /// Add `proof_hash` to the ring buffer of recent proofs, forgetting the
+    /// hash in the slot it overwrites.
+    fn remember_proof(env: &Env, proof_hash: BytesN<32>) {
+        let storage = env.storage().persistent();
+        let head: u32 = storage.get(&DataKey::RecentProofHead).unwrap_or(0);
+        let slot = DataKey::RecentProofSlot(head);
+        if let Some(evicted) = storage.get::<DataKey, BytesN<32>>(&slot) {
+            storage.remove(&DataKey::RecentProof(evicted));
+        }
+        storage.set(&DataKey::RecentProof(proof_hash.clone()), &true);
+        storage.set(&slot, &proof_hash);
+        storage.set(
+            &DataKey::RecentProofHead,
+            &((head + 1) % RECENT_PROOF_WINDOW),
+        );
+    }
+
     fn amount_to_public_input(env: &Env, amount: i128) -> BytesN<32> {
         if amount < 0 {
             panic!("Amount must be non-negative");
@@ -103,13 +294,26 @@ impl PaymentExecutor {
         BytesN::from_array(env, &bytes)
     }
 
+    /// `a ‖ b ‖ c`, the 256-byte layout `verify_payment_proof` takes.
+    fn pack_proof(env: &Env, a: &BytesN<64>, b: &BytesN<128>, c: &BytesN<64>) -> BytesN<256> {
+        let mut bytes = [0u8; 256];
+        bytes[..64].copy_from_slice(&a.to_array());
+        bytes[64..192].copy_from_slice(&b.to_array());
+        bytes[192..].copy_from_slice(&c.to_array());
+        BytesN::from_array(env, &bytes)
+    }
+
     /// Initialize with contract addresses
-    pub fn initialize(env: Env, addresses: ContractAddresses) {
+    pub fn initialize(env: Env, addresses: ContractAddresses) -> Result<(), InitError> {
         let key = DataKey::Addresses;
         if env.storage().persistent().has(&key) {
-            panic!("Already initialized");
+            return Err(InitError::AlreadyInitialized);
         }
         env.storage().persistent().set(&key, &addresses);
+        env.storage()
+            .persistent()
+            .set(&DataKey::StorageVersion, &STORAGE_VERSION);
+        Ok(())
     }
 
     /// Set the executor-level admin (one-time, protected by auth).
@@ -136,6 +340,209 @@ impl PaymentExecutor {
             .set(&DataKey::PauseManager, &pause_manager);
     }
 
+    // -----------------------------------------------------------------------
+    // Storage versioning
+    // -----------------------------------------------------------------------
+
+    /// Get the layout version of persisted payment records.
+    pub fn get_storage_version(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StorageVersion)
+            .unwrap_or(0)
+    }
+
+    /// Queue the listed `(employee, period)` payment records for
+    /// `migrate_storage`. Only the executor admin may call.
+    ///
+    /// Contract storage cannot be enumerated, so records written in an older
+    /// layout have to be named once. Only records that exist and are still
+    /// in an older layout are queued; queueing any sets the storage version
+    /// back to 0 until they are migrated. Takes at most
+    /// `MAX_MIGRATION_BATCH` records per call and returns how many were
+    /// queued.
+    pub fn queue_migration(
+        env: Env,
+        admin: Address,
+        records: soroban_sdk::Vec<(Address, u32)>,
+    ) -> Result<u32, PaymentError> {
+        Self::require_executor_admin(&env, &admin)?;
+        if records.len() > MAX_MIGRATION_BATCH {
+            return Err(PaymentError::MigrationBatchTooLarge);
+        }
+
+        let mut len: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MigrationQueueLen)
+            .unwrap_or(0);
+        let mut queued = 0u32;
+        for (employee, period) in records.iter() {
+            let fields: Option<soroban_sdk::Map<soroban_sdk::Symbol, Val>> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Payment(employee.clone(), period));
+            match fields {
+                Some(fields) if !Self::is_current_record(&env, &fields) => {}
+                _ => continue,
+            }
+            env.storage()
+                .persistent()
+                .set(&DataKey::MigrationQueue(len), &(employee, period));
+            len += 1;
+            queued += 1;
+        }
+
+        if queued > 0 {
+            env.storage()
+                .persistent()
+                .set(&DataKey::MigrationQueueLen, &len);
+            env.storage().persistent().remove(&DataKey::StorageVersion);
+        }
+        Ok(queued)
+    }
+
+    /// Queued records `migrate_storage` has not reached yet.
+    pub fn get_pending_migrations(env: Env) -> u32 {
+        let len: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MigrationQueueLen)
+            .unwrap_or(0);
+        let cursor: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MigrationCursor)
+            .unwrap_or(0);
+        len - cursor
+    }
+
+    /// Rewrite up to `MAX_MIGRATION_BATCH` queued payment records into the
+    /// current layout. Only the executor admin may call.
+    ///
+    /// Old-layout records are already readable (see `load_record`), so this
+    /// can run in as many calls as needed. The storage version is bumped to
+    /// `STORAGE_VERSION` only once the queue is exhausted, and records
+    /// already current are skipped, which makes it idempotent. Returns the
+    /// number of records rewritten.
+    pub fn migrate_storage(env: Env, admin: Address) -> Result<u32, PaymentError> {
+        Self::require_executor_admin(&env, &admin)?;
+
+        let len: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MigrationQueueLen)
+            .unwrap_or(0);
+        let mut cursor: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MigrationCursor)
+            .unwrap_or(0);
+        let from_version = Self::get_storage_version(env.clone());
+        let stop = len.min(cursor.saturating_add(MAX_MIGRATION_BATCH));
+
+        let mut migrated = 0u32;
+        while cursor < stop {
+            let (employee, period): (Address, u32) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::MigrationQueue(cursor))
+                .unwrap();
+            env.storage()
+                .persistent()
+                .remove(&DataKey::MigrationQueue(cursor));
+            cursor += 1;
+
+            let key = DataKey::Payment(employee, period);
+            let fields: Option<soroban_sdk::Map<soroban_sdk::Symbol, Val>> =
+                env.storage().persistent().get(&key);
+            let Some(fields) = fields else { continue };
+            if Self::is_current_record(&env, &fields) {
+                continue;
+            }
+            if let Some(record) = Self::load_record(&env, &key) {
+                env.storage().persistent().set(&key, &record);
+                migrated += 1;
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::MigrationCursor, &cursor);
+
+        let to_version = if cursor == len {
+            env.storage()
+                .persistent()
+                .set(&DataKey::StorageVersion, &STORAGE_VERSION);
+            STORAGE_VERSION
+        } else {
+            from_version
+        };
+
+        env.events().publish(
+            (soroban_sdk::Symbol::new(&env, "StorageMigrated"),),
+            (from_version, to_version, migrated, len - cursor),
+        );
+        // topics : ("StorageMigrated",)
+        // data   : (from_version, to_version, migrated, remaining)
+
+        Ok(migrated)
+    }
+
+    /// Require `admin` to be the executor admin and to have authorised.
+    fn require_executor_admin(env: &Env, admin: &Address) -> Result<(), PaymentError> {
+        let stored_admin: Option<Address> = env.storage().persistent().get(&DataKey::ExecutorAdmin);
+        if stored_admin.as_ref() != Some(admin) {
+            return Err(PaymentError::Unauthorized);
+        }
+        admin.require_auth();
+        Ok(())
+    }
+
+    /// Dependency addresses set by `initialize`.
+    fn addresses(env: &Env) -> Result<ContractAddresses, PaymentError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Addresses)
+            .ok_or(PaymentError::NotInitialized)
+    }
+
+    /// Company record from the registry.
+    fn company(
+        registry: &PayrollRegistryClient,
+        company_id: u64,
+    ) -> Result<CompanyInfo, PaymentError> {
+        registry
+            .try_get_company(&company_id)
+            .ok()
+            .and_then(Result::ok)
+            .ok_or(PaymentError::CompanyNotFound)
+    }
+
+    /// Read the payment record under `key`, decoding any older layout into
+    /// the current one. The stored entry is left as it is.
+    fn load_record(env: &Env, key: &DataKey) -> Option<PaymentRecord> {
+        let fields: soroban_sdk::Map<soroban_sdk::Symbol, Val> =
+            env.storage().persistent().get(key)?;
+        if Self::is_current_record(env, &fields) {
+            return Some(PaymentRecord::try_from_val(env, &fields.to_val()).unwrap());
+        }
+        let old = PaymentRecordV0::try_from_val(env, &fields.to_val()).unwrap();
+        Some(PaymentRecord {
+            company_id: old.company_id,
+            employee: old.employee,
+            proof_hash: old.proof_hash,
+            timestamp: old.timestamp,
+            period: old.period,
+            ledger: 0,
+        })
+    }
+
+    /// Whether a stored record's fields are in the current layout. The
+    /// `ledger` field is the one version 1 added.
+    fn is_current_record(env: &Env, fields: &soroban_sdk::Map<soroban_sdk::Symbol, Val>) -> bool {
+        fields.contains_key(soroban_sdk::Symbol::new(env, "ledger"))
+    }
+
     // -----------------------------------------------------------------------
     // Payroll period lifecycle
     // -----------------------------------------------------------------------
@@ -146,14 +553,10 @@ impl PaymentExecutor {
     /// be open at a time — a new period cannot be created until the previous
     /// one is closed (or no periods exist yet).
     pub fn create_period(env: Env, company_id: u64) -> Result<PayrollPeriod, PaymentError> {
-        let addresses: ContractAddresses = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Addresses)
-            .expect("Not initialized");
+        let addresses = Self::addresses(&env)?;
 
         let registry = PayrollRegistryClient::new(&env, &addresses.registry);
-        let company: CompanyInfo = registry.get_company(&company_id);
+        let company = Self::company(&registry, company_id)?;
         company.admin.require_auth();
 
         // Assign sequential period ID
@@ -192,14 +595,10 @@ impl PaymentExecutor {
         company_id: u64,
         period_id: u32,
     ) -> Result<PayrollPeriod, PaymentError> {
-        let addresses: ContractAddresses = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Addresses)
-            .expect("Not initialized");
+        let addresses = Self::addresses(&env)?;
 
         let registry = PayrollRegistryClient::new(&env, &addresses.registry);
-        let company: CompanyInfo = registry.get_company(&company_id);
+        let company = Self::company(&registry, company_id)?;
         company.admin.require_auth();
 
         let period_key = DataKey::Period(company_id, period_id);
@@ -231,141 +630,537 @@ impl PaymentExecutor {
         env.storage().persistent().get(&key)
     }
 
+    /// Move the company's current period on to the next one and return its
+    /// ID. Only the company admin may call.
+    ///
+    /// The current period only moves forward, one period at a time, and only
+    /// onto a period already opened with `create_period`; otherwise this
+    /// fails with `PeriodNotFound`.
+    pub fn advance_period(env: Env, admin: Address, company_id: u64) -> Result<u32, PaymentError> {
+        let addresses = Self::addresses(&env)?;
+
+        let registry = PayrollRegistryClient::new(&env, &addresses.registry);
+        let company = Self::company(&registry, company_id)?;
+        if admin != company.admin {
+            return Err(PaymentError::Unauthorized);
+        }
+        admin.require_auth();
+
+        let current = Self::get_current_period(env.clone(), company_id);
+        let next = current.checked_add(1).ok_or(PaymentError::PeriodNotFound)?;
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Period(company_id, next))
+        {
+            return Err(PaymentError::PeriodNotFound);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::CurrentPeriod(company_id), &next);
+
+        env.events().publish(
+            (soroban_sdk::Symbol::new(&env, "PeriodAdvanced"), company_id),
+            (current, next),
+        );
+        // topics : ("PeriodAdvanced", company_id)
+        // data   : (previous_period, current_period)
+
+        Ok(next)
+    }
+
+    /// The company's current period, or 0 if `advance_period` has never
+    /// been called.
+    pub fn get_current_period(env: Env, company_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CurrentPeriod(company_id))
+            .unwrap_or(0)
+    }
+
     // -----------------------------------------------------------------------
-    // Payment execution
+    // Payment staging
     // -----------------------------------------------------------------------
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn execute_payment(
+    /// Earmark a payment for an employee in an open period.
+    ///
+    /// The stage must be executed via `execute_payment` before `expires_at`;
+    /// afterwards it is rejected and can be swept by `expire_stale_stages`.
+    /// Staging the same employee and period again replaces the earlier stage.
+    pub fn stage_payment(
         env: Env,
         company_id: u64,
         employee: Address,
         amount: i128,
-        proof_a: BytesN<64>,
-        proof_b: BytesN<128>,
-        proof_c: BytesN<64>,
-        nullifier: BytesN<32>,
         period: u32,
-    ) -> Result<PaymentRecord, PaymentError> {
-        let addresses: ContractAddresses = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Addresses)
-            .expect("Not initialized");
+        expires_at: u64,
+    ) -> Result<StagedPayment, PaymentError> {
+        let addresses = Self::addresses(&env)?;
 
-        // Check if pause manager is configured and system is paused
-        if env.storage().persistent().has(&DataKey::PauseManager) {
-            let pm_addr: Address = env
-                .storage()
-                .persistent()
-                .get(&DataKey::PauseManager)
-                .unwrap();
-            let pm_client = PauseManagerClient::new(&env, &pm_addr);
-            if pm_client.is_paused() {
-                panic!("Payroll is paused");
-            }
+        let registry = PayrollRegistryClient::new(&env, &addresses.registry);
+        let company = Self::company(&registry, company_id)?;
+        company.admin.require_auth();
+
+        if amount <= 0 {
+            return Err(PaymentError::InvalidAmount);
         }
 
-        // Validate the period exists and is open
-        let period_key = DataKey::Period(company_id, period);
         let period_record: PayrollPeriod = env
             .storage()
             .persistent()
-            .get(&period_key)
+            .get(&DataKey::Period(company_id, period))
             .ok_or(PaymentError::PeriodNotFound)?;
-
         if period_record.closed {
             return Err(PaymentError::PeriodClosed);
         }
 
-        // Check proof freshness: reject stale proofs (issue #77).
-        // Proofs must be submitted within MAX_PROOF_AGE_SECONDS of the period creation.
-        let current_time = env.ledger().timestamp();
-        let proof_age = current_time.saturating_sub(period_record.created_at);
-        if proof_age > MAX_PROOF_AGE_SECONDS {
-            return Err(PaymentError::ProofExpired);
-        }
-
-        // Check cryptographically if the exact proof was submitted previously
-        let nullifier_key = DataKey::Nullifier(nullifier.clone());
-        if env.storage().persistent().has(&nullifier_key) {
-            return Err(PaymentError::ProofAlreadyUsed);
-        }
-
-        // Check payment hasn't been made for this period
-        let payment_key = DataKey::Payment(employee.clone(), period);
-        if env.storage().persistent().has(&payment_key) {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Payment(employee.clone(), period))
+        {
             return Err(PaymentError::AlreadyPaid);
         }
 
-        // Read the employee commitment from the dedicated commitment contract
-        // and company metadata from payroll_registry.
-        let commitment_client = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
-        let commitment = commitment_client.get_commitment(&employee).commitment;
-        let registry = PayrollRegistryClient::new(&env, &addresses.registry);
-        let company: CompanyInfo = registry.get_company(&company_id);
-
-        // Ensure only HR admin for this company can trigger payroll.
-        company.admin.require_auth();
-
-        // Construct public inputs required by issue #20:
-        let mut public_inputs = soroban_sdk::Vec::new(&env);
-        public_inputs.push_back(commitment);
-        public_inputs.push_back(Self::amount_to_public_input(&env, amount));
-
-        // Validate Groth16 proof via proof_verifier contract.
-        let verifier = ProofVerifierClient::new(&env, &addresses.verifier);
-        let proof = Groth16Proof {
-            a: proof_a.clone(),
-            b: proof_b.clone(),
-            c: proof_c.clone(),
-        };
-        if !verifier.verify(&proof, &public_inputs) {
-            panic!("Invalid payment proof");
+        let now = env.ledger().timestamp();
+        if expires_at <= now {
+            return Err(PaymentError::StagedPaymentExpired);
         }
 
-        // Execute token transfer from company treasury to employee.
-        let token_client = token::Client::new(&env, &addresses.token);
-        token_client.transfer(&company.treasury, &employee, &amount);
+        // Release any stage being replaced before reserving the new amount.
+        if let Some(previous) = Self::take_stage(&env, company_id, &employee, period) {
+            Self::release_staged_amount(&env, company_id, previous.amount);
+        }
 
-        // Record payment
-        let record = PaymentRecord {
+        let staged = StagedPayment {
             company_id,
             employee: employee.clone(),
-            proof_hash: nullifier.clone(),
-            timestamp: env.ledger().timestamp(),
+            amount,
             period,
+            staged_at: now,
+            expires_at,
         };
+        env.storage().persistent().set(
+            &DataKey::Stage(company_id, employee.clone(), period),
+            &staged,
+        );
 
-        env.storage().persistent().set(&payment_key, &record);
-        env.storage().persistent().set(&nullifier_key, &true);
+        let index_key = DataKey::CompanyStages(company_id);
+        let mut index: soroban_sdk::Vec<(Address, u32)> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(soroban_sdk::Vec::new(&env));
+        // A replaced or executed stage may still be indexed.
+        if !index.contains((employee.clone(), period)) {
+            index.push_back((employee.clone(), period));
+            env.storage().persistent().set(&index_key, &index);
+        }
 
-        // Update total paid
-        let total_key = DataKey::TotalPaid(company_id);
-        let current_total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        let total_key = DataKey::StagedTotal(company_id);
+        let total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
         env.storage()
             .persistent()
-            .set(&total_key, &(current_total + amount));
+            .set(&total_key, &(total + amount));
 
-        // Emit PayrollProcessed event so off-chain indexers can reconcile payments.
         env.events().publish(
-            (
-                soroban_sdk::Symbol::new(&env, "PayrollProcessed"),
-                company_id,
-            ),
-            (employee, amount, period),
+            (soroban_sdk::Symbol::new(&env, "PaymentStaged"), company_id),
+            (employee, amount, period, expires_at),
         );
-        // topics : ("PayrollProcessed", company_id)
-        // data   : (employee, amount, period)
-
-        let _ = nullifier;
+        // topics : ("PaymentStaged", company_id)
+        // data   : (employee, amount, period, expires_at)
 
-        Ok(record)
+        Ok(staged)
     }
 
-    /// Execute batch payroll for multiple employees
-    #[allow(clippy::too_many_arguments)]
-    pub fn execute_batch_payroll(
+    /// Remove every expired stage for a company and release its reserved
+    /// amount. Returns the number of stages removed.
+    ///
+    /// Only stages already past their deadline are touched, so the sweep is
+    /// callable by anyone.
+    pub fn expire_stale_stages(env: Env, company_id: u64) -> u32 {
+        let index_key = DataKey::CompanyStages(company_id);
+        let index: soroban_sdk::Vec<(Address, u32)> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(soroban_sdk::Vec::new(&env));
+
+        let now = env.ledger().timestamp();
+        let mut remaining = soroban_sdk::Vec::new(&env);
+        let mut released: i128 = 0;
+        let mut removed: u32 = 0;
+
+        for (employee, period) in index.iter() {
+            let key = DataKey::Stage(company_id, employee.clone(), period);
+            match env.storage().persistent().get::<_, StagedPayment>(&key) {
+                Some(stage) if stage.expires_at <= now => {
+                    env.storage().persistent().remove(&key);
+                    released += stage.amount;
+                    removed += 1;
+                }
+                Some(_) => remaining.push_back((employee, period)),
+                // Already executed or replaced; drop the stale index entry.
+                None => {}
+            }
+        }
+
+        env.storage().persistent().set(&index_key, &remaining);
+        Self::release_staged_amount(&env, company_id, released);
+
+        if removed > 0 {
+            env.events().publish(
+                (soroban_sdk::Symbol::new(&env, "StagesExpired"), company_id),
+                (removed, released),
+            );
+            // topics : ("StagesExpired", company_id)
+            // data   : (removed, released)
+        }
+
+        removed
+    }
+
+    /// Read a staged payment, if one is outstanding.
+    pub fn get_staged_payment(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+        period: u32,
+    ) -> Option<StagedPayment> {
+        let key = DataKey::Stage(company_id, employee, period);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Total amount currently earmarked by outstanding stages for a company.
+    pub fn get_staged_total(env: Env, company_id: u64) -> i128 {
+        let key = DataKey::StagedTotal(company_id);
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    fn take_stage(
+        env: &Env,
+        company_id: u64,
+        employee: &Address,
+        period: u32,
+    ) -> Option<StagedPayment> {
+        let key = DataKey::Stage(company_id, employee.clone(), period);
+        let stage: Option<StagedPayment> = env.storage().persistent().get(&key);
+        if stage.is_some() {
+            env.storage().persistent().remove(&key);
+        }
+        stage
+    }
+
+    fn release_staged_amount(env: &Env, company_id: u64, amount: i128) {
+        if amount == 0 {
+            return;
+        }
+        let key = DataKey::StagedTotal(company_id);
+        let total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key, &(total - amount).max(0));
+    }
+
+    // -----------------------------------------------------------------------
+    // Payment execution
+    // -----------------------------------------------------------------------
+
+    /// Execute a single private payment.
+    ///
+    /// `idempotency_key` makes retries safe: the first call with a given key
+    /// for a company stores the resulting record, and any later call with the
+    /// same key returns that record without executing again. A later call
+    /// whose employee, amount or period differ from the first one fails with
+    /// `IdempotencyKeyReused` instead.
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_payment(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+        amount: i128,
+        proof_a: BytesN<64>,
+        proof_b: BytesN<128>,
+        proof_c: BytesN<64>,
+        nullifier: BytesN<32>,
+        period: u32,
+        idempotency_key: BytesN<32>,
+    ) -> Result<PaymentRecord, PaymentError> {
+        Self::addresses(&env)?;
+
+        let args_key = DataKey::IdempotencyArgs(company_id, idempotency_key.clone());
+        let args_hash: BytesN<32> = env
+            .crypto()
+            .sha256(&(employee.clone(), amount, period).to_xdr(&env))
+            .into();
+        let idempotency = DataKey::Idempotency(company_id, idempotency_key);
+        if let Some(record) = Self::load_record(&env, &idempotency) {
+            // Records stored before the arguments were hashed have no hash
+            // to compare against.
+            let stored: Option<BytesN<32>> = env.storage().persistent().get(&args_key);
+            if stored.is_some_and(|stored| stored != args_hash) {
+                return Err(PaymentError::IdempotencyKeyReused);
+            }
+            return Ok(record);
+        }
+
+        let record = Self::process_payment(
+            env.clone(),
+            company_id,
+            employee,
+            amount,
+            proof_a,
+            proof_b,
+            proof_c,
+            nullifier,
+            period,
+        )?;
+        env.storage().persistent().set(&idempotency, &record);
+        env.storage().persistent().set(&args_key, &args_hash);
+        Ok(record)
+    }
+
+    /// As `execute_payment`, paying into the company's current period (see
+    /// `advance_period`). Fails with `PeriodNotFound` if the company has no
+    /// current period yet.
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_payment_current(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+        amount: i128,
+        proof_a: BytesN<64>,
+        proof_b: BytesN<128>,
+        proof_c: BytesN<64>,
+        nullifier: BytesN<32>,
+        idempotency_key: BytesN<32>,
+    ) -> Result<PaymentRecord, PaymentError> {
+        let period = Self::get_current_period(env.clone(), company_id);
+        Self::execute_payment(
+            env,
+            company_id,
+            employee,
+            amount,
+            proof_a,
+            proof_b,
+            proof_c,
+            nullifier,
+            period,
+            idempotency_key,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_payment(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+        amount: i128,
+        proof_a: BytesN<64>,
+        proof_b: BytesN<128>,
+        proof_c: BytesN<64>,
+        nullifier: BytesN<32>,
+        period: u32,
+    ) -> Result<PaymentRecord, PaymentError> {
+        // Reject bogus amounts before any state is written, so they cannot
+        // burn the nullifier.
+        if amount <= 0 {
+            return Err(PaymentError::InvalidAmount);
+        }
+
+        let addresses = Self::addresses(&env)?;
+
+        // Check if pause manager is configured and system is paused
+        if env.storage().persistent().has(&DataKey::PauseManager) {
+            let pm_addr: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PauseManager)
+                .unwrap();
+            let pm_client = PauseManagerClient::new(&env, &pm_addr);
+            if pm_client.is_paused() {
+                panic!("Payroll is paused");
+            }
+        }
+
+        // Validate the period exists and is open
+        let period_key = DataKey::Period(company_id, period);
+        let period_record: PayrollPeriod = env
+            .storage()
+            .persistent()
+            .get(&period_key)
+            .ok_or(PaymentError::PeriodNotFound)?;
+
+        if period_record.closed {
+            return Err(PaymentError::PeriodClosed);
+        }
+
+        // Check proof freshness: reject stale proofs (issue #77).
+        // Proofs must be submitted within MAX_PROOF_AGE_SECONDS of the period creation.
+        let current_time = env.ledger().timestamp();
+        let proof_age = current_time.saturating_sub(period_record.created_at);
+        if proof_age > MAX_PROOF_AGE_SECONDS {
+            return Err(PaymentError::ProofExpired);
+        }
+
+        // Check cryptographically if the exact proof was submitted previously
+        let nullifier_key = DataKey::Nullifier(nullifier.clone());
+        if env.storage().persistent().has(&nullifier_key) {
+            return Err(PaymentError::ProofAlreadyUsed);
+        }
+
+        // A fresh nullifier does not make a fresh proof: reject a proof seen
+        // recently in any period, in case nullifiers can be ground.
+        let proof_hash = Self::proof_hash(&env, &proof_a, &proof_b, &proof_c);
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::RecentProof(proof_hash.clone()))
+        {
+            return Err(PaymentError::ProofAlreadyUsed);
+        }
+
+        // Check payment hasn't been made for this period
+        let payment_key = DataKey::Payment(employee.clone(), period);
+        if env.storage().persistent().has(&payment_key) {
+            return Err(PaymentError::AlreadyPaid);
+        }
+
+        // Enforce the employee's annual cap, if one is configured.
+        let cap_window =
+            Self::cap_window_for_payment(&env, company_id, &employee, amount, current_time)?;
+
+        // Reject a staged payment that has passed its deadline or is paid
+        // out at another amount.
+        let stage_key = DataKey::Stage(company_id, employee.clone(), period);
+        let stage: Option<StagedPayment> = env.storage().persistent().get(&stage_key);
+        if let Some(ref stage) = stage {
+            if stage.expires_at <= current_time {
+                return Err(PaymentError::StagedPaymentExpired);
+            }
+            if stage.amount != amount {
+                return Err(PaymentError::StagedAmountMismatch);
+            }
+        }
+
+        // Read the employee commitment from the dedicated commitment contract
+        // and company metadata from payroll_registry.
+        let commitment_client = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
+        let commitment = commitment_client.get_commitment(&employee).commitment;
+        let registry = PayrollRegistryClient::new(&env, &addresses.registry);
+        let company = Self::company(&registry, company_id)?;
+        if !registry.is_company_active(&company_id) {
+            return Err(PaymentError::CompanyInactive);
+        }
+
+        // Ensure only HR admin for this company can trigger payroll.
+        company.admin.require_auth();
+
+        // Construct public inputs required by issue #20:
+        let mut public_inputs = soroban_sdk::Vec::new(&env);
+        public_inputs.push_back(commitment.clone());
+        public_inputs.push_back(Self::amount_to_public_input(&env, amount));
+
+        // Validate the proof against the company's key via proof_verifier.
+        let verifier = ProofVerifierClient::new(&env, &addresses.verifier);
+        let proof = Self::pack_proof(&env, &proof_a, &proof_b, &proof_c);
+        match verifier.try_verify_payment_proof(&company_id, &proof, &public_inputs) {
+            Ok(Ok(true)) => {}
+            Err(Ok(err)) if err == VerifierError::UnsupportedProofSystem.into() => {
+                return Err(PaymentError::UnsupportedProofSystem);
+            }
+            _ => panic!("Invalid payment proof"),
+        }
+
+        // Execute token transfer from company treasury to employee.
+        let token_client = token::Client::new(&env, &addresses.token);
+        token_client.transfer(&company.treasury, &employee, &amount);
+
+        // Record payment
+        let record = PaymentRecord {
+            company_id,
+            employee: employee.clone(),
+            proof_hash: nullifier.clone(),
+            timestamp: env.ledger().timestamp(),
+            period,
+            ledger: env.ledger().sequence(),
+        };
+
+        env.storage().persistent().set(&payment_key, &record);
+        env.storage().persistent().set(&nullifier_key, &true);
+        Self::remember_proof(&env, proof_hash);
+        env.storage().persistent().set(
+            &DataKey::NullifierToPayment(nullifier.clone()),
+            &(employee.clone(), period),
+        );
+        env.storage().persistent().set(
+            &DataKey::PaidCommitment(employee.clone(), period),
+            &commitment,
+        );
+
+        let employee_total_key = DataKey::EmployeeTotal(employee.clone());
+        let employee_total: i128 = env
+            .storage()
+            .persistent()
+            .get(&employee_total_key)
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&employee_total_key, &(employee_total + amount));
+        env.storage().persistent().set(
+            &DataKey::CapWindow(company_id, employee.clone()),
+            &cap_window,
+        );
+
+        // An executed stage no longer reserves funds.
+        if let Some(stage) = stage {
+            env.storage().persistent().remove(&stage_key);
+            Self::release_staged_amount(&env, company_id, stage.amount);
+        }
+
+        // Update total paid
+        let total_key = DataKey::TotalPaid(company_id);
+        let current_total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&total_key, &(current_total + amount));
+
+        // Keep the registry's last-payment time for the employee current,
+        // once this contract's own state is settled.
+        match registry.try_record_payment(&company_id, &employee, &record.timestamp) {
+            Ok(Ok(())) => {}
+            Err(Ok(RegistryError::EmployeeNotFound)) => {
+                return Err(PaymentError::EmployeeNotFound);
+            }
+            Err(Ok(RegistryError::CompanyNotFound)) => {
+                return Err(PaymentError::CompanyNotFound);
+            }
+            Err(Ok(RegistryError::CompanyInactive)) => {
+                return Err(PaymentError::CompanyInactive);
+            }
+            Err(Ok(RegistryError::NotAuthorized)) => return Err(PaymentError::Unauthorized),
+            _ => return Err(PaymentError::RegistryRejected),
+        }
+
+        // Emit PayrollProcessed event so off-chain indexers can reconcile payments.
+        env.events().publish(
+            (
+                soroban_sdk::Symbol::new(&env, PAYROLL_PROCESSED_TOPIC),
+                company_id,
+            ),
+            (employee, amount, period),
+        );
+        // topics : ("PayrollProcessed_v1", company_id)
+        // data   : (employee, amount, period)
+
+        let _ = nullifier;
+
+        Ok(record)
+    }
+
+    /// Execute batch payroll for multiple employees
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_batch_payroll(
         env: Env,
         company_id: u64,
         employees: soroban_sdk::Vec<Address>,
@@ -376,6 +1171,8 @@ impl PaymentExecutor {
         nullifiers: soroban_sdk::Vec<BytesN<32>>,
         period: u32,
     ) -> Result<soroban_sdk::Vec<PaymentRecord>, PaymentError> {
+        Self::addresses(&env)?;
+
         let count = employees.len();
 
         if amounts.len() != count
@@ -390,7 +1187,7 @@ impl PaymentExecutor {
         let mut records = soroban_sdk::Vec::new(&env);
 
         for i in 0..count {
-            let record = Self::execute_payment(
+            let record = Self::process_payment(
                 env.clone(),
                 company_id,
                 employees.get(i).unwrap(),
@@ -408,12 +1205,50 @@ impl PaymentExecutor {
     }
 
     /// Get payment record
-    pub fn get_payment(env: Env, employee: Address, period: u32) -> PaymentRecord {
+    pub fn get_payment(
+        env: Env,
+        employee: Address,
+        period: u32,
+    ) -> Result<PaymentRecord, PaymentError> {
         let key = DataKey::Payment(employee, period);
-        env.storage()
+        Self::load_record(&env, &key).ok_or(PaymentError::PaymentNotFound)
+    }
+
+    /// Look up the payment that consumed `nullifier`, if any.
+    pub fn get_payment_by_nullifier(env: Env, nullifier: BytesN<32>) -> Option<PaymentRecord> {
+        let (employee, period): (Address, u32) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NullifierToPayment(nullifier))?;
+        Self::load_record(&env, &DataKey::Payment(employee, period))
+    }
+
+    /// Return the employee's receipt for `period`.
+    ///
+    /// Requires the employee's authorisation. The receipt proves the payment
+    /// happened without disclosing the amount.
+    pub fn prove_payment(
+        env: Env,
+        employee: Address,
+        period: u32,
+    ) -> Result<PaymentProof, PaymentError> {
+        employee.require_auth();
+
+        let record = Self::load_record(&env, &DataKey::Payment(employee.clone(), period))
+            .ok_or(PaymentError::PaymentNotFound)?;
+        let amount_commitment: BytesN<32> = env
+            .storage()
             .persistent()
-            .get(&key)
-            .expect("Payment not found")
+            .get(&DataKey::PaidCommitment(employee.clone(), period))
+            .ok_or(PaymentError::PaymentNotFound)?;
+
+        Ok(PaymentProof {
+            company_id: record.company_id,
+            employee,
+            period,
+            timestamp: record.timestamp,
+            amount_commitment,
+        })
     }
 
     /// Check if payment was made for a period
@@ -428,27 +1263,112 @@ impl PaymentExecutor {
         env.storage().persistent().get(&key).unwrap_or(0)
     }
 
-    /// Get the maximum allowed age for a proof in seconds (issue #77).
-    pub fn get_max_proof_age(_env: Env) -> u64 {
-        MAX_PROOF_AGE_SECONDS
+    /// Get the cumulative amount paid to an employee across all companies and periods.
+    pub fn get_employee_total(env: Env, employee: Address) -> i128 {
+        let key = DataKey::EmployeeTotal(employee);
+        env.storage().persistent().get(&key).unwrap_or(0)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use ::pause_manager::{PauseManager, PauseManagerClient};
-    use ::salary_commitment::SalaryCommitmentContract;
-    use ::token::{Token, TokenClient};
-    use payroll_registry::PayrollRegistry;
-    use proof_verifier::{ProofVerifier, VerificationKey};
-    use soroban_sdk::testutils::{Address as _, Events};
-    use soroban_sdk::{Env, IntoVal, Symbol, TryIntoVal};
+    /// Set or clear (`None`) the annual cap for an employee of a company.
+    ///
+    /// Requires authorisation from the company admin. Once set, payments
+    /// that would take the employee's total from this company over the
+    /// preceding `ANNUAL_CAP_WINDOW_SECONDS` above the cap fail with
+    /// `AnnualCapExceeded`.
+    pub fn set_annual_cap(env: Env, company_id: u64, employee: Address, cap: Option<i128>) {
+        let addresses: ContractAddresses = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Addresses)
+            .expect("Not initialized");
 
-    fn setup_addresses(env: &Env) -> ContractAddresses {
-        env.mock_all_auths();
-        let registry_id = env.register_contract(None, PayrollRegistry);
-        let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+        let registry = PayrollRegistryClient::new(&env, &addresses.registry);
+        let company: CompanyInfo = registry.get_company(&company_id);
+        company.admin.require_auth();
+
+        if registry.find_employee(&company_id, &employee).is_none() {
+            panic!("Employee not found");
+        }
+
+        let key = DataKey::AnnualCap(company_id, employee);
+        match cap {
+            Some(cap) => {
+                if cap <= 0 {
+                    panic!("Annual cap must be positive");
+                }
+                env.storage().persistent().set(&key, &cap);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Get the annual cap for an employee of a company, if one is set.
+    pub fn get_annual_cap(env: Env, company_id: u64, employee: Address) -> Option<i128> {
+        let key = DataKey::AnnualCap(company_id, employee);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Return the employee's cap window updated with `amount`, or
+    /// `AnnualCapExceeded` if the payment would breach the configured cap.
+    /// Payments older than `ANNUAL_CAP_WINDOW_SECONDS` fall out of the window.
+    fn cap_window_for_payment(
+        env: &Env,
+        company_id: u64,
+        employee: &Address,
+        amount: i128,
+        now: u64,
+    ) -> Result<CapWindow, PaymentError> {
+        let stored: Option<CapWindow> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CapWindow(company_id, employee.clone()));
+        let mut window = CapWindow {
+            payments: soroban_sdk::Vec::new(env),
+        };
+        let mut paid: i128 = 0;
+        for (timestamp, paid_amount) in stored.iter().flat_map(|w| w.payments.iter()) {
+            if now < timestamp.saturating_add(ANNUAL_CAP_WINDOW_SECONDS) {
+                window.payments.push_back((timestamp, paid_amount));
+                paid += paid_amount;
+            }
+        }
+
+        let cap: Option<i128> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AnnualCap(company_id, employee.clone()));
+        if let Some(cap) = cap {
+            if paid + amount > cap {
+                return Err(PaymentError::AnnualCapExceeded);
+            }
+        }
+
+        window.payments.push_back((now, amount));
+        Ok(window)
+    }
+
+    /// Get the maximum allowed age for a proof in seconds (issue #77).
+    pub fn get_max_proof_age(_env: Env) -> u64 {
+        MAX_PROOF_AGE_SECONDS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::pause_manager::{PauseManager, PauseManagerClient};
+    use ::salary_commitment::SalaryCommitmentContract;
+    use ::token::{Token, TokenClient};
+    use payroll_registry::PayrollRegistry;
+    use proof_verifier::testutils::{g1_point, g2_point};
+    use proof_verifier::{ProofSystem, ProofVerifier, VerificationKey};
+    use soroban_sdk::testutils::{Address as _, BytesN as _, Events, Ledger};
+    use soroban_sdk::{Env, IntoVal, Symbol, TryIntoVal};
+
+    fn setup_addresses(env: &Env) -> ContractAddresses {
+        env.mock_all_auths();
+        let registry_id = env.register_contract(None, PayrollRegistry);
+        let commitment_id = env.register_contract(None, SalaryCommitmentContract);
         let verifier_id = env.register_contract(None, ProofVerifier);
         let token_id = env.register_contract(None, Token);
 
@@ -497,22 +1417,620 @@ mod tests {
     }
 
     #[test]
-    fn test_is_paid() {
+    fn test_second_initialize_returns_already_initialized() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        client.initialize(&addresses);
+
+        assert_eq!(
+            client.try_initialize(&addresses),
+            Err(Ok(InitError::AlreadyInitialized))
+        );
+    }
+
+    #[test]
+    fn test_execute_payment_before_initialize_returns_not_initialized() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let result = client.try_execute_payment(
+            &1,
+            &Address::generate(&env),
+            &1000,
+            &BytesN::from_array(&env, &[0u8; 64]),
+            &BytesN::from_array(&env, &[0u8; 128]),
+            &BytesN::from_array(&env, &[0u8; 64]),
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &1,
+            &BytesN::from_array(&env, &[2u8; 32]),
+        );
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::NotInitialized);
+
+        let result = client.try_create_period(&1);
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::NotInitialized);
+    }
+
+    #[test]
+    fn test_get_unknown_payment_returns_payment_not_found() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let result = client.try_get_payment(&Address::generate(&env), &1);
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::PaymentNotFound);
+    }
+
+    #[test]
+    fn test_is_paid() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        client.initialize(&addresses);
+
+        let employee = Address::generate(&env);
+
+        assert!(!client.is_paid(&employee, &1));
+    }
+
+    #[test]
+    fn test_execute_payment_transfers_after_verification() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        client.initialize(&addresses);
+
+        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
+        let commitment_client = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
+        let token_client = TokenClient::new(&env, &addresses.token);
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let employee = Address::generate(&env);
+        let commitment = BytesN::from_array(&env, &[9u8; 32]);
+
+        let company_id = registry_client.register_company(&admin, &treasury);
+        commitment_client.store_commitment(&employee, &commitment);
+        registry_client.add_employee(&company_id, &admin, &employee, &commitment);
+        token_client.mint(&treasury, &10_000);
+
+        // Create payroll period
+        let _ = client.create_period(&company_id);
+
+        let valid_proof_a = g1_point(&env, 1);
+        let valid_proof_b = g2_point(&env, 2);
+        let valid_proof_c = g1_point(&env, 3);
+        let valid_nullifier = BytesN::from_array(&env, &[4u8; 32]);
+
+        client.execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &valid_proof_a,
+            &valid_proof_b,
+            &valid_proof_c,
+            &valid_nullifier,
+            &1,
+            &BytesN::random(&env),
+        );
+
+        assert_eq!(token_client.balance(&treasury), 9_000);
+        assert_eq!(token_client.balance(&employee), 1_000);
+
+        let events = env.events().all();
+        assert_eq!(events.len(), 5);
+        let event = events.get(4).unwrap();
+        assert_eq!(event.1.len(), 2);
+        let sym0: Symbol = event.1.get(0).unwrap().try_into_val(&env.clone()).unwrap();
+        assert_eq!(sym0, Symbol::new(&env, PAYROLL_PROCESSED_TOPIC));
+        let comp_id: u64 = event.1.get(1).unwrap().try_into_val(&env.clone()).unwrap();
+        assert_eq!(comp_id, company_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid payment proof")]
+    fn test_payment_is_verified_against_company_key() {
+        let env = Env::default();
+        let (client, verifier_client, company_id, employee) = setup_verified_company(&env);
+
+        // A company key expecting one public input rejects the executor's two.
+        let mut vk = mock_vk(&env);
+        vk.ic.pop_back();
+        verifier_client.register_vk(&company_id, &vk);
+
+        client.execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &g1_point(&env, 1),
+            &g2_point(&env, 2),
+            &g1_point(&env, 3),
+            &BytesN::from_array(&env, &[4u8; 32]),
+            &1,
+            &BytesN::random(&env),
+        );
+    }
+
+    #[test]
+    fn test_plonk_company_payment_is_rejected() {
+        let env = Env::default();
+        let (client, verifier_client, company_id, employee) = setup_verified_company(&env);
+        verifier_client.set_proof_system(&company_id, &ProofSystem::Plonk);
+
+        let result = client.try_execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &g1_point(&env, 1),
+            &g2_point(&env, 2),
+            &g1_point(&env, 3),
+            &BytesN::from_array(&env, &[4u8; 32]),
+            &1,
+            &BytesN::random(&env),
+        );
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            PaymentError::UnsupportedProofSystem
+        );
+    }
+
+    #[test]
+    fn test_double_spend_proof_reuse_fails() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        client.initialize(&addresses);
+
+        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
+        let commitment_client = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
+        let token_client = TokenClient::new(&env, &addresses.token);
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let employee = Address::generate(&env);
+        let commitment = BytesN::from_array(&env, &[7u8; 32]);
+
+        let company_id = registry_client.register_company(&admin, &treasury);
+        commitment_client.store_commitment(&employee, &commitment);
+        registry_client.add_employee(&company_id, &admin, &employee, &commitment);
+        token_client.mint(&treasury, &10_000);
+
+        let _ = client.create_period(&company_id);
+
+        let valid_proof_a = g1_point(&env, 1);
+        let valid_proof_b = g2_point(&env, 2);
+        let valid_proof_c = g1_point(&env, 3);
+        let valid_nullifier = BytesN::from_array(&env, &[4u8; 32]);
+
+        client.execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &valid_proof_a,
+            &valid_proof_b,
+            &valid_proof_c,
+            &valid_nullifier,
+            &1,
+            &BytesN::random(&env),
+        );
+
+        let result = client.try_execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &valid_proof_a,
+            &valid_proof_b,
+            &valid_proof_c,
+            &valid_nullifier,
+            &1,
+            &BytesN::random(&env),
+        );
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
+    }
+
+    #[test]
+    fn test_batch_array_length_mismatch_fails() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        client.initialize(&addresses);
+
+        let company_id = 0u64;
+
+        let employees =
+            soroban_sdk::Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]);
+        let amounts: soroban_sdk::Vec<i128> = soroban_sdk::Vec::from_array(&env, [1000]);
+        let proofs_a: soroban_sdk::Vec<BytesN<64>> =
+            soroban_sdk::Vec::from_array(&env, [BytesN::from_array(&env, &[0u8; 64])]);
+        let proofs_b: soroban_sdk::Vec<BytesN<128>> =
+            soroban_sdk::Vec::from_array(&env, [BytesN::from_array(&env, &[0u8; 128])]);
+        let proofs_c: soroban_sdk::Vec<BytesN<64>> =
+            soroban_sdk::Vec::from_array(&env, [BytesN::from_array(&env, &[0u8; 64])]);
+        let nullifiers: soroban_sdk::Vec<BytesN<32>> =
+            soroban_sdk::Vec::from_array(&env, [BytesN::from_array(&env, &[0u8; 32])]);
+        let period = 1;
+
+        let result = client.try_execute_batch_payroll(
+            &company_id,
+            &employees,
+            &amounts,
+            &proofs_a,
+            &proofs_b,
+            &proofs_c,
+            &nullifiers,
+            &period,
+        );
+
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            PaymentError::ArrayLengthMismatch
+        );
+    }
+
+    // -----------------------------------------------------------------------
+    // Period tests
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_create_period() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        client.initialize(&addresses);
+
+        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let company_id = registry_client.register_company(&admin, &treasury);
+
+        let period = client.create_period(&company_id);
+        let result = period;
+        assert_eq!(result.period_id, 1);
+        assert_eq!(result.company_id, company_id);
+        assert!(!result.closed);
+        assert_eq!(result.payment_count, 0);
+    }
+
+    #[test]
+    fn test_create_period_for_unknown_company_fails() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        client.initialize(&addresses);
+
+        let result = client.try_create_period(&99u64);
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::CompanyNotFound);
+    }
+
+    #[test]
+    fn test_close_period() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        client.initialize(&addresses);
+
+        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let company_id = registry_client.register_company(&admin, &treasury);
+
+        let _ = client.create_period(&company_id);
+        let result = client.close_period(&company_id, &1);
+
+        assert!(result.closed);
+        assert_eq!(result.end_ledger, result.start_ledger);
+    }
+
+    #[test]
+    fn test_payment_in_closed_period_fails() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        client.initialize(&addresses);
+
+        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
+        let token_client = TokenClient::new(&env, &addresses.token);
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let employee = Address::generate(&env);
+        let commitment = BytesN::from_array(&env, &[8u8; 32]);
+
+        let company_id = registry_client.register_company(&admin, &treasury);
+        registry_client.add_employee(&company_id, &admin, &employee, &commitment);
+        token_client.mint(&treasury, &10_000);
+
+        let _ = client.create_period(&company_id);
+        let _ = client.close_period(&company_id, &1);
+
+        let proof_a = g1_point(&env, 5);
+        let proof_b = g2_point(&env, 6);
+        let proof_c = g1_point(&env, 7);
+        let nullifier = BytesN::from_array(&env, &[9u8; 32]);
+
+        let result = client.try_execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &proof_a,
+            &proof_b,
+            &proof_c,
+            &nullifier,
+            &1,
+            &BytesN::random(&env),
+        );
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::PeriodClosed);
+    }
+
+    #[test]
+    fn test_payment_in_nonexistent_period_fails() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        client.initialize(&addresses);
+
+        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
+        let token_client = TokenClient::new(&env, &addresses.token);
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let employee = Address::generate(&env);
+        let commitment = BytesN::from_array(&env, &[8u8; 32]);
+
+        let company_id = registry_client.register_company(&admin, &treasury);
+        registry_client.add_employee(&company_id, &admin, &employee, &commitment);
+        token_client.mint(&treasury, &10_000);
+
+        let proof_a = g1_point(&env, 5);
+        let proof_b = g2_point(&env, 6);
+        let proof_c = g1_point(&env, 7);
+        let nullifier = BytesN::from_array(&env, &[9u8; 32]);
+
+        // Period 99 doesn't exist
+        let result = client.try_execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &proof_a,
+            &proof_b,
+            &proof_c,
+            &nullifier,
+            &99,
+            &BytesN::random(&env),
+        );
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::PeriodNotFound);
+    }
+
+    /// Acceptance Criteria: Reentrancy
+    #[test]
+    fn test_reentrancy_cei_pattern() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        client.initialize(&addresses);
+
+        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
+        let commitment_client = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
+        let token_client = TokenClient::new(&env, &addresses.token);
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let employee = Address::generate(&env);
+        let commitment = BytesN::from_array(&env, &[8u8; 32]);
+
+        let company_id = registry_client.register_company(&admin, &treasury);
+        commitment_client.store_commitment(&employee, &commitment);
+        registry_client.add_employee(&company_id, &admin, &employee, &commitment);
+        token_client.mint(&treasury, &10_000);
+
+        let _ = client.create_period(&company_id);
+
+        let proof_a = g1_point(&env, 5);
+        let proof_b = g2_point(&env, 6);
+        let proof_c = g1_point(&env, 7);
+        let nullifier = BytesN::from_array(&env, &[9u8; 32]);
+
+        client.execute_payment(
+            &company_id,
+            &employee,
+            &2_500,
+            &proof_a,
+            &proof_b,
+            &proof_c,
+            &nullifier,
+            &1,
+            &BytesN::random(&env),
+        );
+
+        assert_eq!(token_client.balance(&treasury), 7_500);
+        assert_eq!(token_client.balance(&employee), 2_500);
+        assert!(client.is_paid(&employee, &1));
+        assert_eq!(client.get_total_paid(&company_id), 2_500);
+
+        let events = env.events().all();
+        assert_eq!(events.len(), 5);
+        let event = events.get(4).unwrap();
+        assert_eq!(event.1.len(), 2);
+        let sym: Symbol = event.1.get(0).unwrap().try_into_val(&env.clone()).unwrap();
+        assert_eq!(sym, Symbol::new(&env, PAYROLL_PROCESSED_TOPIC));
+
+        let replay = client.try_execute_payment(
+            &company_id,
+            &employee,
+            &2_500,
+            &proof_a,
+            &proof_b,
+            &proof_c,
+            &nullifier,
+            &1,
+            &BytesN::random(&env),
+        );
+
+        assert_eq!(replay.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
+        assert_eq!(token_client.balance(&treasury), 7_500);
+        assert_eq!(token_client.balance(&employee), 2_500);
+        assert_eq!(client.get_total_paid(&company_id), 2_500);
+    }
+
+    // ── Pause tests ──────────────────────────────────────────────────────────
+
+    fn setup_executor_with_pause_manager(
+        env: &Env,
+    ) -> (
+        PaymentExecutorClient<'_>,
+        PauseManagerClient<'_>,
+        u64,
+        Address,
+        Address,
+        Address,
+    ) {
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(env, &contract_id);
+
+        let addresses = setup_addresses(env);
+        client.initialize(&addresses);
+
+        let registry_client = PayrollRegistryClient::new(env, &addresses.registry);
+        let commitment_client = SalaryCommitmentContractClient::new(env, &addresses.commitment);
+        let token_client = TokenClient::new(env, &addresses.token);
+
+        let admin = Address::generate(env);
+        let treasury = Address::generate(env);
+        let employee = Address::generate(env);
+        let commitment = BytesN::from_array(env, &[9u8; 32]);
+
+        let company_id = registry_client.register_company(&admin, &treasury);
+        commitment_client.store_commitment(&employee, &commitment);
+        registry_client.add_employee(&company_id, &admin, &employee, &commitment);
+        token_client.mint(&treasury, &10_000);
+
+        // Set executor admin
+        client.set_executor_admin(&admin);
+
+        // Register and configure pause manager
+        let pm_id = env.register_contract(None, PauseManager);
+        let pm_client = PauseManagerClient::new(env, &pm_id);
+        let operator = Address::generate(env);
+        pm_client.initialize(&operator);
+
+        client.set_pause_manager(&pm_id);
+
+        (client, pm_client, company_id, admin, treasury, employee)
+    }
+
+    #[test]
+    fn test_paused_executor_rejects_payment() {
+        let env = Env::default();
+        let (client, pm_client, company_id, _admin, _treasury, employee) =
+            setup_executor_with_pause_manager(&env);
+
+        let proof_a = g1_point(&env, 1);
+        let proof_b = g2_point(&env, 2);
+        let proof_c = g1_point(&env, 3);
+        let nullifier = BytesN::from_array(&env, &[4u8; 32]);
+
+        pm_client.pause();
+
+        let result = client.try_execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &proof_a,
+            &proof_b,
+            &proof_c,
+            &nullifier,
+            &1,
+            &BytesN::random(&env),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_unpaused_executor_resumes_payment() {
         let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(&env, &contract_id);
+        let (client, pm_client, company_id, _admin, _treasury, employee) =
+            setup_executor_with_pause_manager(&env);
 
-        let addresses = setup_addresses(&env);
-        client.initialize(&addresses);
+        let proof_a = g1_point(&env, 1);
+        let proof_b = g2_point(&env, 2);
+        let proof_c = g1_point(&env, 3);
+        let nullifier = BytesN::from_array(&env, &[4u8; 32]);
 
-        let employee = Address::generate(&env);
+        client.create_period(&company_id);
 
-        assert!(!client.is_paid(&employee, &1));
+        pm_client.pause();
+
+        // Verify paused
+        let result = client.try_execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &proof_a.clone(),
+            &proof_b.clone(),
+            &proof_c.clone(),
+            &nullifier.clone(),
+            &1,
+            &BytesN::random(&env),
+        );
+        assert!(result.is_err());
+
+        // Unpause
+        pm_client.unpause();
+
+        // Should succeed now
+        client.execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &proof_a,
+            &proof_b,
+            &proof_c,
+            &nullifier,
+            &1,
+            &BytesN::random(&env),
+        );
+
+        assert!(client.is_paid(&employee, &1));
     }
 
     #[test]
-    fn test_execute_payment_transfers_after_verification() {
+    fn test_executor_works_without_pause_manager() {
         let env = Env::default();
         env.mock_all_auths();
         let contract_id = env.register_contract(None, PaymentExecutor);
@@ -532,43 +2050,84 @@ mod tests {
 
         let company_id = registry_client.register_company(&admin, &treasury);
         commitment_client.store_commitment(&employee, &commitment);
-        registry_client.add_employee(&company_id, &employee, &commitment);
+        registry_client.add_employee(&company_id, &admin, &employee, &commitment);
+        client.create_period(&company_id);
         token_client.mint(&treasury, &10_000);
 
-        // Create payroll period
-        let _ = client.create_period(&company_id);
-
-        let valid_proof_a = BytesN::from_array(&env, &[1u8; 64]);
-        let valid_proof_b = BytesN::from_array(&env, &[2u8; 128]);
-        let valid_proof_c = BytesN::from_array(&env, &[3u8; 64]);
-        let valid_nullifier = BytesN::from_array(&env, &[4u8; 32]);
+        let proof_a = g1_point(&env, 1);
+        let proof_b = g2_point(&env, 2);
+        let proof_c = g1_point(&env, 3);
+        let nullifier = BytesN::from_array(&env, &[4u8; 32]);
 
         client.execute_payment(
             &company_id,
             &employee,
             &1000,
-            &valid_proof_a,
-            &valid_proof_b,
-            &valid_proof_c,
-            &valid_nullifier,
+            &proof_a,
+            &proof_b,
+            &proof_c,
+            &nullifier,
             &1,
+            &BytesN::random(&env),
         );
 
         assert_eq!(token_client.balance(&treasury), 9_000);
         assert_eq!(token_client.balance(&employee), 1_000);
+    }
 
-        let events = env.events().all();
-        assert_eq!(events.len(), 5);
-        let event = events.get(4).unwrap();
-        assert_eq!(event.1.len(), 2);
-        let sym0: Symbol = event.1.get(0).unwrap().try_into_val(&env.clone()).unwrap();
-        assert_eq!(sym0, Symbol::new(&env, "PayrollProcessed"));
-        let comp_id: u64 = event.1.get(1).unwrap().try_into_val(&env.clone()).unwrap();
-        assert_eq!(comp_id, company_id);
+    #[test]
+    #[should_panic(expected = "authorized")]
+    fn test_set_pause_manager_rejects_unauthorized() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let addresses = setup_addresses(&env);
+        let admin = Address::generate(&env);
+
+        // Only mock auth for admin during initialize
+        env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+            address: &admin,
+            invoke: &soroban_sdk::testutils::MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "initialize",
+                args: (addresses.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        client.initialize(&addresses);
+
+        // Set executor admin as the legitimate admin
+        env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+            address: &admin,
+            invoke: &soroban_sdk::testutils::MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "set_executor_admin",
+                args: (admin.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        client.set_executor_admin(&admin);
+
+        // Attacker tries to set pause manager
+        let pm_id = env.register_contract(None, PauseManager);
+        let attacker = Address::generate(&env);
+        env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+            address: &attacker,
+            invoke: &soroban_sdk::testutils::MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "set_pause_manager",
+                args: (pm_id.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        client.set_pause_manager(&pm_id);
     }
 
+    // ── Issue #77: proof expiration checks ────────────────────────────────────
+
     #[test]
-    fn test_double_spend_proof_reuse_fails() {
+    fn test_fresh_proof_within_expiration_window() {
         let env = Env::default();
         env.mock_all_auths();
         let contract_id = env.register_contract(None, PaymentExecutor);
@@ -584,46 +2143,35 @@ mod tests {
         let admin = Address::generate(&env);
         let treasury = Address::generate(&env);
         let employee = Address::generate(&env);
-        let commitment = BytesN::from_array(&env, &[7u8; 32]);
+        let commitment = BytesN::from_array(&env, &[9u8; 32]);
 
         let company_id = registry_client.register_company(&admin, &treasury);
         commitment_client.store_commitment(&employee, &commitment);
-        registry_client.add_employee(&company_id, &employee, &commitment);
-        token_client.mint(&treasury, &10_000);
-
-        let _ = client.create_period(&company_id);
-
-        let valid_proof_a = BytesN::from_array(&env, &[1u8; 64]);
-        let valid_proof_b = BytesN::from_array(&env, &[2u8; 128]);
-        let valid_proof_c = BytesN::from_array(&env, &[3u8; 64]);
-        let valid_nullifier = BytesN::from_array(&env, &[4u8; 32]);
+        registry_client.add_employee(&company_id, &admin, &employee, &commitment);
+        token_client.mint(&treasury, &10000i128);
 
-        client.execute_payment(
-            &company_id,
-            &employee,
-            &1000,
-            &valid_proof_a,
-            &valid_proof_b,
-            &valid_proof_c,
-            &valid_nullifier,
-            &1,
-        );
+        // Create a period
+        let period = client.create_period(&company_id);
+        assert_eq!(period.period_id, 1);
 
+        // Execute payment immediately (proof is fresh)
         let result = client.try_execute_payment(
             &company_id,
             &employee,
-            &1000,
-            &valid_proof_a,
-            &valid_proof_b,
-            &valid_proof_c,
-            &valid_nullifier,
+            &1000i128,
+            &g1_point(&env, 1),
+            &g2_point(&env, 2),
+            &g1_point(&env, 3),
+            &BytesN::from_array(&env, &[4u8; 32]),
             &1,
+            &BytesN::random(&env),
         );
-        assert_eq!(result.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
+        // Should succeed (proof is fresh)
+        assert!(result.is_ok());
     }
 
     #[test]
-    fn test_batch_array_length_mismatch_fails() {
+    fn test_period_tracks_creation_time() {
         let env = Env::default();
         env.mock_all_auths();
         let contract_id = env.register_contract(None, PaymentExecutor);
@@ -632,545 +2180,862 @@ mod tests {
         let addresses = setup_addresses(&env);
         client.initialize(&addresses);
 
-        let company_id = 0u64;
+        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
+        let commitment_client = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
+        let token_client = TokenClient::new(&env, &addresses.token);
 
-        let employees =
-            soroban_sdk::Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]);
-        let amounts: soroban_sdk::Vec<i128> = soroban_sdk::Vec::from_array(&env, [1000]);
-        let proofs_a: soroban_sdk::Vec<BytesN<64>> =
-            soroban_sdk::Vec::from_array(&env, [BytesN::from_array(&env, &[0u8; 64])]);
-        let proofs_b: soroban_sdk::Vec<BytesN<128>> =
-            soroban_sdk::Vec::from_array(&env, [BytesN::from_array(&env, &[0u8; 128])]);
-        let proofs_c: soroban_sdk::Vec<BytesN<64>> =
-            soroban_sdk::Vec::from_array(&env, [BytesN::from_array(&env, &[0u8; 64])]);
-        let nullifiers: soroban_sdk::Vec<BytesN<32>> =
-            soroban_sdk::Vec::from_array(&env, [BytesN::from_array(&env, &[0u8; 32])]);
-        let period = 1;
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let employee = Address::generate(&env);
+        let commitment = BytesN::from_array(&env, &[9u8; 32]);
 
-        let result = client.try_execute_batch_payroll(
-            &company_id,
-            &employees,
-            &amounts,
-            &proofs_a,
-            &proofs_b,
-            &proofs_c,
-            &nullifiers,
-            &period,
-        );
+        let company_id = registry_client.register_company(&admin, &treasury);
+        commitment_client.store_commitment(&employee, &commitment);
+        registry_client.add_employee(&company_id, &admin, &employee, &commitment);
+        token_client.mint(&treasury, &10000i128);
+
+        // Create a period
+        let period = client.create_period(&company_id);
+
+        // Verify period is created and has correct initial state
+        assert_eq!(period.period_id, 1);
+        assert_eq!(period.company_id, company_id);
+        assert!(!period.closed);
+        assert_eq!(period.payment_count, 0);
+        // created_at is set to current ledger timestamp (can be 0 in test env)
+    }
+
+    #[test]
+    fn test_get_max_proof_age() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(&env, &contract_id);
+
+        let max_age = client.get_max_proof_age();
+        // Should be 7 days in seconds
+        assert_eq!(max_age, 7 * 24 * 60 * 60);
+    }
+
+    // ── Payment staging expiry ────────────────────────────────────────────────
+
+    fn setup_staged_company(
+        env: &Env,
+    ) -> (PaymentExecutorClient<'_>, TokenClient<'_>, u64, Address) {
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(env, &contract_id);
+
+        let addresses = setup_addresses(env);
+        client.initialize(&addresses);
+
+        let registry_client = PayrollRegistryClient::new(env, &addresses.registry);
+        let commitment_client = SalaryCommitmentContractClient::new(env, &addresses.commitment);
+        let token_client = TokenClient::new(env, &addresses.token);
+
+        let admin = Address::generate(env);
+        let treasury = Address::generate(env);
+        let employee = Address::generate(env);
+        let commitment = BytesN::from_array(env, &[9u8; 32]);
+
+        let company_id = registry_client.register_company(&admin, &treasury);
+        commitment_client.store_commitment(&employee, &commitment);
+        registry_client.add_employee(&company_id, &admin, &employee, &commitment);
+        token_client.mint(&treasury, &10_000);
+        client.create_period(&company_id);
+
+        (client, token_client, company_id, employee)
+    }
+
+    /// As [`setup_staged_company`], also returning the proof verifier.
+    fn setup_verified_company(
+        env: &Env,
+    ) -> (
+        PaymentExecutorClient<'_>,
+        ProofVerifierClient<'_>,
+        u64,
+        Address,
+    ) {
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, PaymentExecutor);
+        let client = PaymentExecutorClient::new(env, &contract_id);
+
+        let addresses = setup_addresses(env);
+        client.initialize(&addresses);
+
+        let registry_client = PayrollRegistryClient::new(env, &addresses.registry);
+        let commitment_client = SalaryCommitmentContractClient::new(env, &addresses.commitment);
+
+        let admin = Address::generate(env);
+        let treasury = Address::generate(env);
+        let employee = Address::generate(env);
+        let commitment = BytesN::from_array(env, &[9u8; 32]);
+
+        let company_id = registry_client.register_company(&admin, &treasury);
+        commitment_client.store_commitment(&employee, &commitment);
+        registry_client.add_employee(&company_id, &admin, &employee, &commitment);
+        TokenClient::new(env, &addresses.token).mint(&treasury, &10_000);
+        client.create_period(&company_id);
 
-        assert_eq!(
-            result.unwrap_err().unwrap(),
-            PaymentError::ArrayLengthMismatch
-        );
+        let verifier_client = ProofVerifierClient::new(env, &addresses.verifier);
+        (client, verifier_client, company_id, employee)
     }
 
-    // -----------------------------------------------------------------------
-    // Period tests
-    // -----------------------------------------------------------------------
-
     #[test]
-    fn test_create_period() {
+    fn test_staged_payment_executes_before_deadline() {
         let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(&env, &contract_id);
+        let (client, token_client, company_id, employee) = setup_staged_company(&env);
 
-        let addresses = setup_addresses(&env);
-        client.initialize(&addresses);
+        client.stage_payment(&company_id, &employee, &1000, &1, &100);
+        assert_eq!(client.get_staged_total(&company_id), 1000);
 
-        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
-        let admin = Address::generate(&env);
-        let treasury = Address::generate(&env);
-        let company_id = registry_client.register_company(&admin, &treasury);
+        client.execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &g1_point(&env, 1),
+            &g2_point(&env, 2),
+            &g1_point(&env, 3),
+            &BytesN::from_array(&env, &[4u8; 32]),
+            &1,
+            &BytesN::random(&env),
+        );
 
-        let period = client.create_period(&company_id);
-        let result = period;
-        assert_eq!(result.period_id, 1);
-        assert_eq!(result.company_id, company_id);
-        assert!(!result.closed);
-        assert_eq!(result.payment_count, 0);
+        assert_eq!(token_client.balance(&employee), 1000);
+        assert!(client
+            .get_staged_payment(&company_id, &employee, &1)
+            .is_none());
+        assert_eq!(client.get_staged_total(&company_id), 0);
     }
 
     #[test]
-    fn test_close_period() {
+    fn test_expired_stage_cannot_be_executed() {
         let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(&env, &contract_id);
+        let (client, token_client, company_id, employee) = setup_staged_company(&env);
 
-        let addresses = setup_addresses(&env);
-        client.initialize(&addresses);
+        client.stage_payment(&company_id, &employee, &1000, &1, &100);
+        env.ledger().with_mut(|l| l.timestamp = 100);
 
-        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
-        let admin = Address::generate(&env);
-        let treasury = Address::generate(&env);
-        let company_id = registry_client.register_company(&admin, &treasury);
+        let result = client.try_execute_payment(
+            &company_id,
+            &employee,
+            &1000,
+            &g1_point(&env, 1),
+            &g2_point(&env, 2),
+            &g1_point(&env, 3),
+            &BytesN::from_array(&env, &[4u8; 32]),
+            &1,
+            &BytesN::random(&env),
+        );
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            PaymentError::StagedPaymentExpired
+        );
+        assert_eq!(token_client.balance(&employee), 0);
+        assert!(!client.is_paid(&employee, &1));
+    }
 
-        let _ = client.create_period(&company_id);
-        let result = client.close_period(&company_id, &1);
+    #[test]
+    fn test_staged_payment_rejects_other_amount() {
+        let env = Env::default();
+        let (client, token_client, company_id, employee) = setup_staged_company(&env);
+        client.stage_payment(&company_id, &employee, &1000, &1, &100);
 
-        assert!(result.closed);
-        assert_eq!(result.end_ledger, result.start_ledger);
+        let result = pay(&env, &client, company_id, &employee, 1500, 4, 1);
+
+        assert_eq!(result.unwrap_err(), PaymentError::StagedAmountMismatch);
+        assert_eq!(token_client.balance(&employee), 0);
+        assert_eq!(client.get_staged_total(&company_id), 1000);
     }
 
     #[test]
-    fn test_payment_in_closed_period_fails() {
+    fn test_stage_rejects_non_positive_amount() {
         let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(&env, &contract_id);
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
 
-        let addresses = setup_addresses(&env);
-        client.initialize(&addresses);
+        let result = client.try_stage_payment(&company_id, &employee, &0, &1, &100);
 
-        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
-        let token_client = TokenClient::new(&env, &addresses.token);
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::InvalidAmount);
+    }
 
-        let admin = Address::generate(&env);
-        let treasury = Address::generate(&env);
-        let employee = Address::generate(&env);
-        let commitment = BytesN::from_array(&env, &[8u8; 32]);
+    #[test]
+    fn test_restaging_replaces_stage_without_duplicating_it() {
+        let env = Env::default();
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
 
-        let company_id = registry_client.register_company(&admin, &treasury);
-        registry_client.add_employee(&company_id, &employee, &commitment);
-        token_client.mint(&treasury, &10_000);
+        client.stage_payment(&company_id, &employee, &1000, &1, &100);
+        client.stage_payment(&company_id, &employee, &800, &1, &100);
+        assert_eq!(client.get_staged_total(&company_id), 800);
 
-        let _ = client.create_period(&company_id);
-        let _ = client.close_period(&company_id, &1);
+        let index: soroban_sdk::Vec<(Address, u32)> = env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .get(&DataKey::CompanyStages(company_id))
+                .unwrap()
+        });
+        assert_eq!(index, soroban_sdk::vec![&env, (employee.clone(), 1)]);
+    }
 
-        let proof_a = BytesN::from_array(&env, &[5u8; 64]);
-        let proof_b = BytesN::from_array(&env, &[6u8; 128]);
-        let proof_c = BytesN::from_array(&env, &[7u8; 64]);
-        let nullifier = BytesN::from_array(&env, &[9u8; 32]);
+    #[test]
+    fn test_stage_with_past_deadline_is_rejected() {
+        let env = Env::default();
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
+        env.ledger().with_mut(|l| l.timestamp = 500);
 
-        let result = client.try_execute_payment(
-            &company_id,
-            &employee,
-            &1000,
-            &proof_a,
-            &proof_b,
-            &proof_c,
-            &nullifier,
-            &1,
+        let result = client.try_stage_payment(&company_id, &employee, &1000, &1, &500);
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            PaymentError::StagedPaymentExpired
         );
-        assert_eq!(result.unwrap_err().unwrap(), PaymentError::PeriodClosed);
     }
 
     #[test]
-    fn test_payment_in_nonexistent_period_fails() {
+    fn test_expire_stale_stages_removes_only_expired() {
         let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(&env, &contract_id);
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
+        let other = Address::generate(&env);
 
-        let addresses = setup_addresses(&env);
-        client.initialize(&addresses);
+        client.stage_payment(&company_id, &employee, &1000, &1, &100);
+        client.stage_payment(&company_id, &other, &500, &1, &1_000);
+        assert_eq!(client.get_staged_total(&company_id), 1500);
 
-        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
-        let token_client = TokenClient::new(&env, &addresses.token);
+        env.ledger().with_mut(|l| l.timestamp = 200);
+        let removed = client.expire_stale_stages(&company_id);
 
-        let admin = Address::generate(&env);
-        let treasury = Address::generate(&env);
-        let employee = Address::generate(&env);
-        let commitment = BytesN::from_array(&env, &[8u8; 32]);
+        assert_eq!(removed, 1);
+        assert!(client
+            .get_staged_payment(&company_id, &employee, &1)
+            .is_none());
+        assert!(client.get_staged_payment(&company_id, &other, &1).is_some());
+        assert_eq!(client.get_staged_total(&company_id), 500);
 
-        let company_id = registry_client.register_company(&admin, &treasury);
-        registry_client.add_employee(&company_id, &employee, &commitment);
-        token_client.mint(&treasury, &10_000);
+        // A second sweep finds nothing new to expire.
+        assert_eq!(client.expire_stale_stages(&company_id), 0);
+    }
 
-        let proof_a = BytesN::from_array(&env, &[5u8; 64]);
-        let proof_b = BytesN::from_array(&env, &[6u8; 128]);
-        let proof_c = BytesN::from_array(&env, &[7u8; 64]);
-        let nullifier = BytesN::from_array(&env, &[9u8; 32]);
+    // ── Per-employee totals and annual cap ────────────────────────────────────
 
-        // Period 99 doesn't exist
-        let result = client.try_execute_payment(
-            &company_id,
-            &employee,
-            &1000,
-            &proof_a,
-            &proof_b,
-            &proof_c,
-            &nullifier,
-            &99,
+    fn pay(
+        env: &Env,
+        client: &PaymentExecutorClient<'_>,
+        company_id: u64,
+        employee: &Address,
+        amount: i128,
+        nullifier_byte: u8,
+        period: u32,
+    ) -> Result<PaymentRecord, PaymentError> {
+        client
+            .try_execute_payment(
+                &company_id,
+                employee,
+                &amount,
+                &g1_point(env, 1),
+                &g2_point(env, 2),
+                &g1_point(env, nullifier_byte.into()),
+                &BytesN::from_array(env, &[nullifier_byte; 32]),
+                &period,
+                &BytesN::random(env),
+            )
+            .map(|r| r.unwrap())
+            .map_err(|e| e.unwrap())
+    }
+
+    #[test]
+    fn test_payment_to_employee_missing_from_registry_is_rejected() {
+        let env = Env::default();
+        let (client, token_client, company_id, _employee) = setup_staged_company(&env);
+        let addresses: ContractAddresses = env.as_contract(&client.address, || {
+            env.storage().persistent().get(&DataKey::Addresses).unwrap()
+        });
+        let stranger = Address::generate(&env);
+        SalaryCommitmentContractClient::new(&env, &addresses.commitment)
+            .store_commitment(&stranger, &BytesN::from_array(&env, &[9u8; 32]));
+
+        assert_eq!(
+            pay(&env, &client, company_id, &stranger, 1000, 1, 1).unwrap_err(),
+            PaymentError::EmployeeNotFound
         );
-        assert_eq!(result.unwrap_err().unwrap(), PaymentError::PeriodNotFound);
+        assert_eq!(token_client.balance(&stranger), 0);
+        assert_eq!(client.get_employee_total(&stranger), 0);
     }
 
-    /// Acceptance Criteria: Reentrancy
     #[test]
-    fn test_reentrancy_cei_pattern() {
+    fn test_employee_total_accumulates_across_periods() {
         let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(&env, &contract_id);
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
 
-        let addresses = setup_addresses(&env);
-        client.initialize(&addresses);
+        assert_eq!(client.get_employee_total(&employee), 0);
 
-        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
-        let commitment_client = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
-        let token_client = TokenClient::new(&env, &addresses.token);
+        pay(&env, &client, company_id, &employee, 1000, 1, 1).unwrap();
+        client.close_period(&company_id, &1);
+        client.create_period(&company_id);
+        pay(&env, &client, company_id, &employee, 1500, 2, 2).unwrap();
 
-        let admin = Address::generate(&env);
-        let treasury = Address::generate(&env);
-        let employee = Address::generate(&env);
-        let commitment = BytesN::from_array(&env, &[8u8; 32]);
+        assert_eq!(client.get_employee_total(&employee), 2500);
+    }
 
-        let company_id = registry_client.register_company(&admin, &treasury);
-        commitment_client.store_commitment(&employee, &commitment);
-        registry_client.add_employee(&company_id, &employee, &commitment);
-        token_client.mint(&treasury, &10_000);
+    #[test]
+    fn test_annual_cap_rejects_payment_over_cap() {
+        let env = Env::default();
+        let (client, token_client, company_id, employee) = setup_staged_company(&env);
 
-        let _ = client.create_period(&company_id);
+        client.set_annual_cap(&company_id, &employee, &Some(2000));
+        assert_eq!(client.get_annual_cap(&company_id, &employee), Some(2000));
 
-        let proof_a = BytesN::from_array(&env, &[5u8; 64]);
-        let proof_b = BytesN::from_array(&env, &[6u8; 128]);
-        let proof_c = BytesN::from_array(&env, &[7u8; 64]);
-        let nullifier = BytesN::from_array(&env, &[9u8; 32]);
+        pay(&env, &client, company_id, &employee, 1500, 1, 1).unwrap();
+        client.close_period(&company_id, &1);
+        client.create_period(&company_id);
 
-        client.execute_payment(
-            &company_id,
-            &employee,
-            &2_500,
-            &proof_a,
-            &proof_b,
-            &proof_c,
-            &nullifier,
-            &1,
-        );
+        let result = pay(&env, &client, company_id, &employee, 600, 2, 2);
+        assert_eq!(result.unwrap_err(), PaymentError::AnnualCapExceeded);
+        assert_eq!(token_client.balance(&employee), 1500);
+        assert_eq!(client.get_employee_total(&employee), 1500);
 
-        assert_eq!(token_client.balance(&treasury), 7_500);
-        assert_eq!(token_client.balance(&employee), 2_500);
-        assert!(client.is_paid(&employee, &1));
-        assert_eq!(client.get_total_paid(&company_id), 2_500);
+        // Paying exactly up to the cap is allowed.
+        pay(&env, &client, company_id, &employee, 500, 3, 2).unwrap();
+        assert_eq!(client.get_employee_total(&employee), 2000);
+    }
 
-        let events = env.events().all();
-        assert_eq!(events.len(), 5);
-        let event = events.get(4).unwrap();
-        assert_eq!(event.1.len(), 2);
-        let sym: Symbol = event.1.get(0).unwrap().try_into_val(&env.clone()).unwrap();
-        assert_eq!(sym, Symbol::new(&env, "PayrollProcessed"));
+    #[test]
+    fn test_annual_cap_window_rolls_with_each_payment() {
+        let env = Env::default();
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
+        let half_year = ANNUAL_CAP_WINDOW_SECONDS / 2;
 
-        let replay = client.try_execute_payment(
-            &company_id,
-            &employee,
-            &2_500,
-            &proof_a,
-            &proof_b,
-            &proof_c,
-            &nullifier,
-            &1,
-        );
+        client.set_annual_cap(&company_id, &employee, &Some(1000));
+        pay(&env, &client, company_id, &employee, 600, 1, 1).unwrap();
+        client.close_period(&company_id, &1);
 
-        assert_eq!(replay.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
-        assert_eq!(token_client.balance(&treasury), 7_500);
-        assert_eq!(token_client.balance(&employee), 2_500);
-        assert_eq!(client.get_total_paid(&company_id), 2_500);
+        env.ledger().with_mut(|l| l.timestamp = half_year);
+        client.create_period(&company_id);
+        pay(&env, &client, company_id, &employee, 400, 2, 2).unwrap();
+        client.close_period(&company_id, &2);
+
+        // A year after the first payment only the second one still counts.
+        env.ledger()
+            .with_mut(|l| l.timestamp = ANNUAL_CAP_WINDOW_SECONDS);
+        client.create_period(&company_id);
+        let result = pay(&env, &client, company_id, &employee, 700, 3, 3);
+        assert_eq!(result.unwrap_err(), PaymentError::AnnualCapExceeded);
+        pay(&env, &client, company_id, &employee, 600, 4, 3).unwrap();
+        client.close_period(&company_id, &3);
+
+        // Half a year later the second payment has dropped out too.
+        env.ledger()
+            .with_mut(|l| l.timestamp = ANNUAL_CAP_WINDOW_SECONDS + half_year);
+        client.create_period(&company_id);
+        pay(&env, &client, company_id, &employee, 400, 5, 4).unwrap();
+        assert_eq!(client.get_employee_total(&employee), 2000);
     }
 
-    // ── Pause tests ──────────────────────────────────────────────────────────
+    #[test]
+    fn test_annual_cap_is_scoped_to_company() {
+        let env = Env::default();
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
+
+        client.set_annual_cap(&company_id, &employee, &Some(1000));
+        assert_eq!(client.get_annual_cap(&(company_id + 1), &employee), None);
+        env.as_contract(&client.address, || {
+            let other = DataKey::AnnualCap(company_id + 1, employee.clone());
+            assert!(!env.storage().persistent().has(&other));
+        });
+    }
 
-    fn setup_executor_with_pause_manager(
-        env: &Env,
-    ) -> (
-        PaymentExecutorClient<'_>,
-        PauseManagerClient<'_>,
-        u64,
-        Address,
-        Address,
-        Address,
-    ) {
-        env.mock_all_auths();
+    #[test]
+    fn test_clearing_annual_cap_removes_limit() {
+        let env = Env::default();
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
 
-        let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(env, &contract_id);
+        client.set_annual_cap(&company_id, &employee, &Some(100));
+        client.set_annual_cap(&company_id, &employee, &None);
+        assert_eq!(client.get_annual_cap(&company_id, &employee), None);
 
-        let addresses = setup_addresses(env);
-        client.initialize(&addresses);
+        pay(&env, &client, company_id, &employee, 1000, 1, 1).unwrap();
+    }
 
-        let registry_client = PayrollRegistryClient::new(env, &addresses.registry);
-        let commitment_client = SalaryCommitmentContractClient::new(env, &addresses.commitment);
-        let token_client = TokenClient::new(env, &addresses.token);
+    #[test]
+    fn test_repeated_idempotency_key_returns_cached_record() {
+        let env = Env::default();
+        let (client, token_client, company_id, employee) = setup_staged_company(&env);
+        let key = BytesN::from_array(&env, &[7u8; 32]);
+
+        let submit = |nullifier_byte: u8| {
+            client.execute_payment(
+                &company_id,
+                &employee,
+                &1000,
+                &g1_point(&env, 1),
+                &g2_point(&env, 2),
+                &g1_point(&env, nullifier_byte.into()),
+                &BytesN::from_array(&env, &[nullifier_byte; 32]),
+                &1,
+                &key,
+            )
+        };
 
-        let admin = Address::generate(env);
-        let treasury = Address::generate(env);
-        let employee = Address::generate(env);
-        let commitment = BytesN::from_array(env, &[9u8; 32]);
+        let first = submit(4);
+        // A retry with a fresh nullifier would otherwise slip past the
+        // nullifier guard; the key makes it a no-op.
+        let retry = submit(5);
 
-        let company_id = registry_client.register_company(&admin, &treasury);
-        commitment_client.store_commitment(&employee, &commitment);
-        registry_client.add_employee(&company_id, &employee, &commitment);
-        token_client.mint(&treasury, &10_000);
+        assert_eq!(retry.proof_hash, first.proof_hash);
+        assert_eq!(retry.timestamp, first.timestamp);
+        assert_eq!(token_client.balance(&employee), 1000);
+        assert_eq!(client.get_total_paid(&company_id), 1000);
+        assert_eq!(client.get_employee_total(&employee), 1000);
+    }
 
-        // Set executor admin
-        client.set_executor_admin(&admin);
+    #[test]
+    fn test_reused_idempotency_key_with_other_arguments_is_rejected() {
+        let env = Env::default();
+        let (client, token_client, company_id, employee) = setup_staged_company(&env);
+        let key = BytesN::from_array(&env, &[7u8; 32]);
+        let submit = |amount: i128, period: u32| {
+            client.try_execute_payment(
+                &company_id,
+                &employee,
+                &amount,
+                &g1_point(&env, 1),
+                &g2_point(&env, 2),
+                &g1_point(&env, 3),
+                &BytesN::from_array(&env, &[4u8; 32]),
+                &period,
+                &key,
+            )
+        };
 
-        // Register and configure pause manager
-        let pm_id = env.register_contract(None, PauseManager);
-        let pm_client = PauseManagerClient::new(env, &pm_id);
-        let operator = Address::generate(env);
-        pm_client.initialize(&operator);
+        submit(1000, 1).unwrap().unwrap();
+
+        let other_amount = submit(2000, 1);
+        assert_eq!(
+            other_amount.unwrap_err().unwrap(),
+            PaymentError::IdempotencyKeyReused
+        );
+        let other_period = submit(1000, 2);
+        assert_eq!(
+            other_period.unwrap_err().unwrap(),
+            PaymentError::IdempotencyKeyReused
+        );
+        assert_eq!(token_client.balance(&employee), 1000);
+    }
+
+    #[test]
+    fn test_different_idempotency_key_processes_normally() {
+        let env = Env::default();
+        let (client, token_client, company_id, employee) = setup_staged_company(&env);
+        let submit = |key_byte: u8, nullifier_byte: u8, period: u32| {
+            client.try_execute_payment(
+                &company_id,
+                &employee,
+                &1000,
+                &g1_point(&env, 1),
+                &g2_point(&env, 2),
+                &g1_point(&env, nullifier_byte.into()),
+                &BytesN::from_array(&env, &[nullifier_byte; 32]),
+                &period,
+                &BytesN::from_array(&env, &[key_byte; 32]),
+            )
+        };
 
-        client.set_pause_manager(&pm_id);
+        submit(7, 4, 1).unwrap().unwrap();
 
-        (client, pm_client, company_id, admin, treasury, employee)
+        // A new key is a new request: it runs all the usual checks.
+        let duplicate = submit(8, 5, 1);
+        assert_eq!(duplicate.unwrap_err().unwrap(), PaymentError::AlreadyPaid);
+
+        client.create_period(&company_id);
+        submit(9, 6, 2).unwrap().unwrap();
+        assert_eq!(token_client.balance(&employee), 2000);
     }
 
+    // ── Employee proof of payment ─────────────────────────────────────────────
+
     #[test]
-    fn test_paused_executor_rejects_payment() {
+    fn test_employee_can_retrieve_payment_receipt() {
         let env = Env::default();
-        let (client, pm_client, company_id, _admin, _treasury, employee) =
-            setup_executor_with_pause_manager(&env);
-
-        let proof_a = BytesN::from_array(&env, &[1u8; 64]);
-        let proof_b = BytesN::from_array(&env, &[2u8; 128]);
-        let proof_c = BytesN::from_array(&env, &[3u8; 64]);
-        let nullifier = BytesN::from_array(&env, &[4u8; 32]);
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
+        env.ledger().with_mut(|l| l.timestamp = 42);
 
-        pm_client.pause();
+        pay(&env, &client, company_id, &employee, 1000, 1, 1).unwrap();
 
-        let result = client.try_execute_payment(
-            &company_id,
-            &employee,
-            &1000,
-            &proof_a,
-            &proof_b,
-            &proof_c,
-            &nullifier,
-            &1,
+        let receipt = client.prove_payment(&employee, &1);
+        assert_eq!(
+            env.auths()[0].0,
+            employee,
+            "receipt must be authorised by the employee"
+        );
+        assert_eq!(receipt.company_id, company_id);
+        assert_eq!(receipt.employee, employee);
+        assert_eq!(receipt.period, 1);
+        assert_eq!(receipt.timestamp, 42);
+        assert_eq!(
+            receipt.amount_commitment,
+            BytesN::from_array(&env, &[9u8; 32])
         );
-        assert!(result.is_err());
     }
 
     #[test]
-    fn test_unpaused_executor_resumes_payment() {
+    fn test_non_employee_cannot_retrieve_payment_receipt() {
         let env = Env::default();
-        let (client, pm_client, company_id, _admin, _treasury, employee) =
-            setup_executor_with_pause_manager(&env);
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
+        let outsider = Address::generate(&env);
 
-        let proof_a = BytesN::from_array(&env, &[1u8; 64]);
-        let proof_b = BytesN::from_array(&env, &[2u8; 128]);
-        let proof_c = BytesN::from_array(&env, &[3u8; 64]);
-        let nullifier = BytesN::from_array(&env, &[4u8; 32]);
+        pay(&env, &client, company_id, &employee, 1000, 1, 1).unwrap();
 
-        client.create_period(&company_id);
+        let result = client.try_prove_payment(&outsider, &1);
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::PaymentNotFound);
 
-        pm_client.pause();
+        // Nor can the employee obtain a receipt for a period they weren't paid.
+        let result = client.try_prove_payment(&employee, &2);
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::PaymentNotFound);
+    }
 
-        // Verify paused
-        let result = client.try_execute_payment(
-            &company_id,
-            &employee,
-            &1000,
-            &proof_a.clone(),
-            &proof_b.clone(),
-            &proof_c.clone(),
-            &nullifier.clone(),
-            &1,
-        );
-        assert!(result.is_err());
+    // ── Nullifier lookup ──────────────────────────────────────────────────────
 
-        // Unpause
-        pm_client.unpause();
+    #[test]
+    fn test_payment_is_found_by_nullifier() {
+        let env = Env::default();
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
 
-        // Should succeed now
-        client.execute_payment(
-            &company_id,
-            &employee,
-            &1000,
-            &proof_a,
-            &proof_b,
-            &proof_c,
-            &nullifier,
-            &1,
-        );
+        let record = pay(&env, &client, company_id, &employee, 1000, 4, 1).unwrap();
 
-        assert!(client.is_paid(&employee, &1));
+        let found = client
+            .get_payment_by_nullifier(&BytesN::from_array(&env, &[4u8; 32]))
+            .expect("payment must be indexed by its nullifier");
+        assert_eq!(found.employee, employee);
+        assert_eq!(found.period, 1);
+        assert_eq!(found.proof_hash, record.proof_hash);
+
+        let unknown = client.get_payment_by_nullifier(&BytesN::from_array(&env, &[5u8; 32]));
+        assert!(unknown.is_none());
     }
 
-    #[test]
-    fn test_executor_works_without_pause_manager() {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(&env, &contract_id);
+    // ── Storage versioning ────────────────────────────────────────────────────
 
-        let addresses = setup_addresses(&env);
-        client.initialize(&addresses);
+    /// Rewind `client`'s storage to version 0 holding an old-layout record
+    /// for `employee` in each of `periods`.
+    fn seed_v0_records(
+        env: &Env,
+        client: &PaymentExecutorClient<'_>,
+        employee: &Address,
+        periods: core::ops::Range<u32>,
+    ) {
+        env.as_contract(&client.address, || {
+            for period in periods {
+                let old = PaymentRecordV0 {
+                    company_id: 0,
+                    employee: employee.clone(),
+                    proof_hash: BytesN::from_array(env, &[4u8; 32]),
+                    timestamp: 77,
+                    period,
+                };
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::Payment(employee.clone(), period), &old);
+            }
+            env.storage().persistent().remove(&DataKey::StorageVersion);
+        });
+    }
 
-        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
-        let commitment_client = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
-        let token_client = TokenClient::new(&env, &addresses.token);
+    fn seed_v0_record(env: &Env, client: &PaymentExecutorClient<'_>, employee: &Address) {
+        seed_v0_records(env, client, employee, 1..2);
+    }
 
-        let admin = Address::generate(&env);
-        let treasury = Address::generate(&env);
-        let employee = Address::generate(&env);
-        let commitment = BytesN::from_array(&env, &[9u8; 32]);
+    fn is_current_layout(env: &Env, client: &PaymentExecutorClient<'_>, key: DataKey) -> bool {
+        env.as_contract(&client.address, || {
+            let fields: soroban_sdk::Map<Symbol, Val> =
+                env.storage().persistent().get(&key).unwrap();
+            PaymentExecutor::is_current_record(env, &fields)
+        })
+    }
 
-        let company_id = registry_client.register_company(&admin, &treasury);
-        commitment_client.store_commitment(&employee, &commitment);
-        registry_client.add_employee(&company_id, &employee, &commitment);
-        client.create_period(&company_id);
-        token_client.mint(&treasury, &10_000);
+    #[test]
+    fn test_old_records_are_readable_before_migration() {
+        let env = Env::default();
+        let (client, _token_client, company_id, employee) = setup_staged_company(&env);
+        seed_v0_record(&env, &client, &employee);
+        assert_eq!(client.get_storage_version(), 0);
 
-        let proof_a = BytesN::from_array(&env, &[1u8; 64]);
-        let proof_b = BytesN::from_array(&env, &[2u8; 128]);
-        let proof_c = BytesN::from_array(&env, &[3u8; 64]);
-        let nullifier = BytesN::from_array(&env, &[4u8; 32]);
+        let record = client.get_payment(&employee, &1);
+        assert_eq!(record.timestamp, 77);
+        assert_eq!(record.proof_hash, BytesN::from_array(&env, &[4u8; 32]));
+        assert_eq!(record.ledger, 0);
 
-        client.execute_payment(
-            &company_id,
-            &employee,
-            &1000,
-            &proof_a,
-            &proof_b,
-            &proof_c,
-            &nullifier,
-            &1,
+        // Payments keep working without a migration.
+        client.close_period(&company_id, &1);
+        client.create_period(&company_id);
+        pay(&env, &client, company_id, &employee, 1000, 5, 2).unwrap();
+        assert_eq!(
+            client.get_payment(&employee, &2).ledger,
+            env.ledger().sequence()
         );
-
-        assert_eq!(token_client.balance(&treasury), 9_000);
-        assert_eq!(token_client.balance(&employee), 1_000);
     }
 
     #[test]
-    #[should_panic(expected = "authorized")]
-    fn test_set_pause_manager_rejects_unauthorized() {
+    fn test_migrate_storage_upgrades_queued_records() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(&env, &contract_id);
+        let (client, _token_client, _company_id, employee) = setup_staged_company(&env);
+        let admin = Address::generate(&env);
+        client.set_executor_admin(&admin);
+        seed_v0_record(&env, &client, &employee);
+
+        // Missing records are not queued.
+        let missing = Address::generate(&env);
+        let batch = soroban_sdk::vec![&env, (employee.clone(), 1u32), (missing, 1u32)];
+        assert_eq!(client.queue_migration(&admin, &batch), 1);
+        assert_eq!(client.get_pending_migrations(), 1);
+
+        assert_eq!(client.migrate_storage(&admin), 1);
+        assert_eq!(client.get_pending_migrations(), 0);
+        assert_eq!(client.get_storage_version(), STORAGE_VERSION);
+        assert!(is_current_layout(
+            &env,
+            &client,
+            DataKey::Payment(employee.clone(), 1)
+        ));
+        let record = client.get_payment(&employee, &1);
+        assert_eq!(record.timestamp, 77);
+        assert_eq!(record.ledger, 0);
+
+        // Running it again is a no-op, and a migrated record is not requeued.
+        assert_eq!(client.migrate_storage(&admin), 0);
+        assert_eq!(client.queue_migration(&admin, &batch), 0);
+        assert_eq!(client.get_storage_version(), STORAGE_VERSION);
+    }
 
-        let addresses = setup_addresses(&env);
+    #[test]
+    fn test_partial_migration_keeps_old_version() {
+        let env = Env::default();
+        let (client, _token_client, _company_id, employee) = setup_staged_company(&env);
         let admin = Address::generate(&env);
+        client.set_executor_admin(&admin);
+        seed_v0_records(&env, &client, &employee, 0..MAX_MIGRATION_BATCH + 1);
 
-        // Only mock auth for admin during initialize
-        env.mock_auths(&[soroban_sdk::testutils::MockAuth {
-            address: &admin,
-            invoke: &soroban_sdk::testutils::MockAuthInvoke {
-                contract: &contract_id,
-                fn_name: "initialize",
-                args: (addresses.clone(),).into_val(&env),
-                sub_invokes: &[],
-            },
-        }]);
-        client.initialize(&addresses);
+        let mut first = soroban_sdk::Vec::new(&env);
+        for period in 0..MAX_MIGRATION_BATCH {
+            first.push_back((employee.clone(), period));
+        }
+        let last = soroban_sdk::vec![&env, (employee.clone(), MAX_MIGRATION_BATCH)];
+        client.queue_migration(&admin, &first);
+        client.queue_migration(&admin, &last);
+
+        assert_eq!(client.migrate_storage(&admin), MAX_MIGRATION_BATCH);
+        assert_eq!(client.get_pending_migrations(), 1);
+        assert_eq!(client.get_storage_version(), 0);
+        let straggler = DataKey::Payment(employee.clone(), MAX_MIGRATION_BATCH);
+        assert!(!is_current_layout(&env, &client, straggler.clone()));
+
+        assert_eq!(client.migrate_storage(&admin), 1);
+        assert_eq!(client.get_storage_version(), STORAGE_VERSION);
+        assert!(is_current_layout(&env, &client, straggler));
+    }
 
-        // Set executor admin as the legitimate admin
-        env.mock_auths(&[soroban_sdk::testutils::MockAuth {
-            address: &admin,
-            invoke: &soroban_sdk::testutils::MockAuthInvoke {
-                contract: &contract_id,
-                fn_name: "set_executor_admin",
-                args: (admin.clone(),).into_val(&env),
-                sub_invokes: &[],
-            },
-        }]);
+    #[test]
+    fn test_migration_guards_return_typed_errors() {
+        let env = Env::default();
+        let (client, _token_client, _company_id, employee) = setup_staged_company(&env);
+        let admin = Address::generate(&env);
         client.set_executor_admin(&admin);
 
-        // Attacker tries to set pause manager
-        let pm_id = env.register_contract(None, PauseManager);
-        let attacker = Address::generate(&env);
-        env.mock_auths(&[soroban_sdk::testutils::MockAuth {
-            address: &attacker,
-            invoke: &soroban_sdk::testutils::MockAuthInvoke {
-                contract: &contract_id,
-                fn_name: "set_pause_manager",
-                args: (pm_id.clone(),).into_val(&env),
-                sub_invokes: &[],
-            },
-        }]);
-        client.set_pause_manager(&pm_id);
+        let outsider = Address::generate(&env);
+        let batch = soroban_sdk::vec![&env, (employee.clone(), 1u32)];
+        assert_eq!(
+            client
+                .try_queue_migration(&outsider, &batch)
+                .unwrap_err()
+                .unwrap(),
+            PaymentError::Unauthorized
+        );
+        assert_eq!(
+            client.try_migrate_storage(&outsider).unwrap_err().unwrap(),
+            PaymentError::Unauthorized
+        );
+
+        let mut oversized = soroban_sdk::Vec::new(&env);
+        for period in 0..=MAX_MIGRATION_BATCH {
+            oversized.push_back((employee.clone(), period));
+        }
+        assert_eq!(
+            client
+                .try_queue_migration(&admin, &oversized)
+                .unwrap_err()
+                .unwrap(),
+            PaymentError::MigrationBatchTooLarge
+        );
     }
 
-    // ── Issue #77: proof expiration checks ────────────────────────────────────
+    // ── Amount validation ─────────────────────────────────────────────────────
 
     #[test]
-    fn test_fresh_proof_within_expiration_window() {
+    fn test_non_positive_amounts_are_rejected() {
         let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(&env, &contract_id);
+        let (client, token_client, company_id, employee) = setup_staged_company(&env);
 
-        let addresses = setup_addresses(&env);
-        client.initialize(&addresses);
+        for amount in [0i128, -1, i128::MIN] {
+            let result = pay(&env, &client, company_id, &employee, amount, 6, 1);
+            assert_eq!(result.unwrap_err(), PaymentError::InvalidAmount);
+        }
 
-        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
-        let commitment_client = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
-        let token_client = TokenClient::new(&env, &addresses.token);
+        let nullifier_used = env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .has(&DataKey::Nullifier(BytesN::from_array(&env, &[6u8; 32])))
+        });
+        assert!(!nullifier_used);
+        assert!(!client.is_paid(&employee, &1));
+        assert_eq!(token_client.balance(&employee), 0);
 
-        let admin = Address::generate(&env);
-        let treasury = Address::generate(&env);
-        let employee = Address::generate(&env);
-        let commitment = BytesN::from_array(&env, &[9u8; 32]);
+        // The untouched nullifier still works for a genuine payment.
+        pay(&env, &client, company_id, &employee, 1000, 6, 1).unwrap();
+    }
 
-        let company_id = registry_client.register_company(&admin, &treasury);
-        commitment_client.store_commitment(&employee, &commitment);
-        registry_client.add_employee(&company_id, &employee, &commitment);
-        token_client.mint(&treasury, &10000i128);
+    // ── Cross-period duplicate proofs ─────────────────────────────────────────
 
-        // Create a period
-        let period = client.create_period(&company_id);
-        assert_eq!(period.period_id, 1);
+    #[test]
+    fn test_same_proof_in_later_period_is_rejected() {
+        let env = Env::default();
+        let (client, token_client, company_id, employee) = setup_staged_company(&env);
+        let submit = |proof_byte: u8, nullifier_byte: u8, period: u32| {
+            client
+                .try_execute_payment(
+                    &company_id,
+                    &employee,
+                    &1000,
+                    &g1_point(&env, 1),
+                    &g2_point(&env, 2),
+                    &g1_point(&env, proof_byte.into()),
+                    &BytesN::from_array(&env, &[nullifier_byte; 32]),
+                    &period,
+                    &BytesN::random(&env),
+                )
+                .map(|r| r.unwrap())
+                .map_err(|e| e.unwrap())
+        };
 
-        // Execute payment immediately (proof is fresh)
-        let result = client.try_execute_payment(
-            &company_id,
-            &employee,
-            &1000i128,
-            &BytesN::from_array(&env, &[1u8; 64]),
-            &BytesN::from_array(&env, &[2u8; 128]),
-            &BytesN::from_array(&env, &[3u8; 64]),
-            &BytesN::from_array(&env, &[4u8; 32]),
-            &1,
-        );
-        // Should succeed (proof is fresh)
-        assert!(result.is_ok());
+        submit(3, 4, 1).unwrap();
+        client.close_period(&company_id, &1);
+        client.create_period(&company_id);
+
+        // Fresh nullifier, same proof: caught by the recent-proof window.
+        assert_eq!(submit(3, 5, 2).unwrap_err(), PaymentError::ProofAlreadyUsed);
+        assert!(!client.is_paid(&employee, &2));
+
+        submit(8, 5, 2).unwrap();
+        assert_eq!(token_client.balance(&employee), 2000);
     }
 
     #[test]
-    fn test_period_tracks_creation_time() {
+    fn test_recent_proof_window_forgets_oldest() {
         let env = Env::default();
-        env.mock_all_auths();
+        env.budget().reset_unlimited();
         let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(&env, &contract_id);
+        let hash = |i: u32| {
+            let mut bytes = [0u8; 32];
+            bytes[..4].copy_from_slice(&i.to_be_bytes());
+            BytesN::from_array(&env, &bytes)
+        };
 
-        let addresses = setup_addresses(&env);
-        client.initialize(&addresses);
+        env.as_contract(&contract_id, || {
+            for i in 0..=RECENT_PROOF_WINDOW {
+                PaymentExecutor::remember_proof(&env, hash(i));
+            }
+            let seen = |i: u32| {
+                env.storage()
+                    .persistent()
+                    .has(&DataKey::RecentProof(hash(i)))
+            };
+            assert!(!seen(0));
+            assert!(seen(1));
+            assert!(seen(RECENT_PROOF_WINDOW));
+        });
+    }
 
-        let registry_client = PayrollRegistryClient::new(&env, &addresses.registry);
-        let commitment_client = SalaryCommitmentContractClient::new(&env, &addresses.commitment);
-        let token_client = TokenClient::new(&env, &addresses.token);
+    // ── Current period tracking ───────────────────────────────────────────────
 
-        let admin = Address::generate(&env);
-        let treasury = Address::generate(&env);
-        let employee = Address::generate(&env);
-        let commitment = BytesN::from_array(&env, &[9u8; 32]);
+    fn company_admin(env: &Env, client: &PaymentExecutorClient<'_>, company_id: u64) -> Address {
+        let addresses: ContractAddresses = env.as_contract(&client.address, || {
+            env.storage().persistent().get(&DataKey::Addresses).unwrap()
+        });
+        PayrollRegistryClient::new(env, &addresses.registry)
+            .get_company(&company_id)
+            .admin
+    }
 
-        let company_id = registry_client.register_company(&admin, &treasury);
-        commitment_client.store_commitment(&employee, &commitment);
-        registry_client.add_employee(&company_id, &employee, &commitment);
-        token_client.mint(&treasury, &10000i128);
+    fn pay_current(
+        env: &Env,
+        client: &PaymentExecutorClient<'_>,
+        company_id: u64,
+        employee: &Address,
+        nullifier_byte: u8,
+    ) -> Result<PaymentRecord, PaymentError> {
+        client
+            .try_execute_payment_current(
+                &company_id,
+                employee,
+                &1000,
+                &g1_point(env, 1),
+                &g2_point(env, 2),
+                &g1_point(env, nullifier_byte.into()),
+                &BytesN::from_array(env, &[nullifier_byte; 32]),
+                &BytesN::random(env),
+            )
+            .map(|r| r.unwrap())
+            .map_err(|e| e.unwrap())
+    }
 
-        // Create a period
-        let period = client.create_period(&company_id);
+    #[test]
+    fn test_advance_period_moves_forward_onto_created_periods() {
+        let env = Env::default();
+        let (client, _token_client, company_id, _employee) = setup_staged_company(&env);
+        let admin = company_admin(&env, &client, company_id);
+        assert_eq!(client.get_current_period(&company_id), 0);
 
-        // Verify period is created and has correct initial state
-        assert_eq!(period.period_id, 1);
-        assert_eq!(period.company_id, company_id);
-        assert!(!period.closed);
-        assert_eq!(period.payment_count, 0);
-        // created_at is set to current ledger timestamp (can be 0 in test env)
+        assert_eq!(client.advance_period(&admin, &company_id), 1);
+        assert_eq!(client.get_current_period(&company_id), 1);
+
+        // Period 2 has not been created, so the current period stays put.
+        let result = client.try_advance_period(&admin, &company_id);
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::PeriodNotFound);
+        assert_eq!(client.get_current_period(&company_id), 1);
+
+        client.create_period(&company_id);
+        assert_eq!(client.advance_period(&admin, &company_id), 2);
+        assert_eq!(client.get_current_period(&company_id), 2);
     }
 
     #[test]
-    fn test_get_max_proof_age() {
+    fn test_advance_period_rejects_non_admin() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, PaymentExecutor);
-        let client = PaymentExecutorClient::new(&env, &contract_id);
+        let (client, _token_client, company_id, _employee) = setup_staged_company(&env);
 
-        let max_age = client.get_max_proof_age();
-        // Should be 7 days in seconds
-        assert_eq!(max_age, 7 * 24 * 60 * 60);
+        let result = client.try_advance_period(&Address::generate(&env), &company_id);
+        assert_eq!(result.unwrap_err().unwrap(), PaymentError::Unauthorized);
+        assert_eq!(client.get_current_period(&company_id), 0);
+    }
+
+    #[test]
+    fn test_execute_payment_current_uses_latest_period() {
+        let env = Env::default();
+        let (client, token_client, company_id, employee) = setup_staged_company(&env);
+        let admin = company_admin(&env, &client, company_id);
+
+        // No current period yet.
+        assert_eq!(
+            pay_current(&env, &client, company_id, &employee, 1).unwrap_err(),
+            PaymentError::PeriodNotFound
+        );
+
+        client.advance_period(&admin, &company_id);
+        let record = pay_current(&env, &client, company_id, &employee, 1).unwrap();
+        assert_eq!(record.period, 1);
+
+        client.close_period(&company_id, &1);
+        client.create_period(&company_id);
+        client.advance_period(&admin, &company_id);
+        let record = pay_current(&env, &client, company_id, &employee, 2).unwrap();
+        assert_eq!(record.period, 2);
+
+        assert!(client.is_paid(&employee, &1));
+        assert!(client.is_paid(&employee, &2));
+        assert_eq!(token_client.balance(&employee), 2000);
     }
 }
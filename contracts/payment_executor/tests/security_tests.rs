@@ -1,9 +1,10 @@
 use ::token::{Token, TokenClient};
 use payment_executor::{ContractAddresses, PaymentError, PaymentExecutor, PaymentExecutorClient};
 use payroll_registry::{PayrollRegistry, PayrollRegistryClient};
+use proof_verifier::testutils::{g1_point, g2_point};
 use proof_verifier::{ProofVerifier, ProofVerifierClient, VerificationKey};
 use salary_commitment::SalaryCommitmentContract;
-use soroban_sdk::testutils::{Address as _, MockAuth, MockAuthInvoke};
+use soroban_sdk::testutils::{Address as _, BytesN as _, MockAuth, MockAuthInvoke};
 use soroban_sdk::{Address, BytesN, Env, IntoVal, Vec};
 
 fn mock_vk(env: &Env) -> VerificationKey {
@@ -90,17 +91,17 @@ fn setup_system<'a>(
 #[test]
 fn test_proof_replay_protection() {
     let env = Env::default();
-    let (executor, registry, commitment_client, _token, company_id, _admin, _treasury) =
+    let (executor, registry, commitment_client, _token, company_id, admin, _treasury) =
         setup_system(&env);
 
     let employee = Address::generate(&env);
     let commitment = BytesN::from_array(&env, &[9u8; 32]);
     commitment_client.store_commitment(&employee, &commitment);
-    registry.add_employee(&company_id, &employee, &commitment);
+    registry.add_employee(&company_id, &admin, &employee, &commitment);
 
-    let proof_a = BytesN::from_array(&env, &[1u8; 64]);
-    let proof_b = BytesN::from_array(&env, &[2u8; 128]);
-    let proof_c = BytesN::from_array(&env, &[3u8; 64]);
+    let proof_a = g1_point(&env, 1);
+    let proof_b = g2_point(&env, 2);
+    let proof_c = g1_point(&env, 3);
     let nullifier = BytesN::from_array(&env, &[4u8; 32]);
 
     executor.execute_payment(
@@ -112,6 +113,7 @@ fn test_proof_replay_protection() {
         &proof_c,
         &nullifier,
         &1,
+        &BytesN::random(&env),
     );
 
     executor.create_period(&company_id);
@@ -125,6 +127,7 @@ fn test_proof_replay_protection() {
         &proof_c,
         &nullifier,
         &2, // Different period so we know it fails due to ProofAlreadyUsed (nullifier), not AlreadyPaid.
+        &BytesN::random(&env),
     );
 
     assert_eq!(result.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
@@ -165,12 +168,18 @@ fn test_authorization_add_employee_fails_for_non_admin() {
         invoke: &MockAuthInvoke {
             contract: &registry_id,
             fn_name: "add_employee",
-            args: (company_id, employee.clone(), commitment.clone()).into_val(&env),
+            args: (
+                company_id,
+                correct_admin.clone(),
+                employee.clone(),
+                commitment.clone(),
+            )
+                .into_val(&env),
             sub_invokes: &[],
         },
     }]);
 
-    registry.add_employee(&company_id, &employee, &commitment);
+    registry.add_employee(&company_id, &correct_admin, &employee, &commitment);
 }
 
 /// Acceptance Criteria: Reentrancy
@@ -179,17 +188,17 @@ fn test_authorization_add_employee_fails_for_non_admin() {
 #[test]
 fn test_reentrancy_state_updates_before_external_calls() {
     let env = Env::default();
-    let (executor, registry, commitment_client, _token, company_id, _admin, _treasury) =
+    let (executor, registry, commitment_client, _token, company_id, admin, _treasury) =
         setup_system(&env);
 
     let employee = Address::generate(&env);
     let commitment = BytesN::from_array(&env, &[9u8; 32]);
     commitment_client.store_commitment(&employee, &commitment);
-    registry.add_employee(&company_id, &employee, &commitment);
+    registry.add_employee(&company_id, &admin, &employee, &commitment);
 
-    let proof_a = BytesN::from_array(&env, &[5u8; 64]);
-    let proof_b = BytesN::from_array(&env, &[6u8; 128]);
-    let proof_c = BytesN::from_array(&env, &[7u8; 64]);
+    let proof_a = g1_point(&env, 5);
+    let proof_b = g2_point(&env, 6);
+    let proof_c = g1_point(&env, 7);
     let nullifier = BytesN::from_array(&env, &[8u8; 32]);
 
     executor.create_period(&company_id);
@@ -204,6 +213,7 @@ fn test_reentrancy_state_updates_before_external_calls() {
         &proof_c,
         &nullifier,
         &2,
+        &BytesN::random(&env),
     );
 
     assert!(executor.is_paid(&employee, &2));
@@ -218,6 +228,7 @@ fn test_reentrancy_state_updates_before_external_calls() {
         &proof_c,
         &nullifier,
         &2,
+        &BytesN::random(&env),
     );
 
     // ProofAlreadyUsed error would happen first because nullifier checks precede AlreadyPaid checks and token transfers.
@@ -238,19 +249,19 @@ fn test_reentrancy_state_updates_before_external_calls() {
 #[test]
 fn test_retry_across_periods_succeeds_with_new_period() {
     let env = Env::default();
-    let (executor, registry, commitment_client, _token, company_id, _admin, _treasury) =
+    let (executor, registry, commitment_client, _token, company_id, admin, _treasury) =
         setup_system(&env);
 
     let employee = Address::generate(&env);
     let commitment = BytesN::from_array(&env, &[10u8; 32]);
 
     commitment_client.store_commitment(&employee, &commitment);
-    registry.add_employee(&company_id, &employee, &commitment);
+    registry.add_employee(&company_id, &admin, &employee, &commitment);
 
     // Payment 1 in period 1
-    let proof_a_1 = BytesN::from_array(&env, &[20u8; 64]);
-    let proof_b_1 = BytesN::from_array(&env, &[21u8; 128]);
-    let proof_c_1 = BytesN::from_array(&env, &[22u8; 64]);
+    let proof_a_1 = g1_point(&env, 20);
+    let proof_b_1 = g2_point(&env, 21);
+    let proof_c_1 = g1_point(&env, 22);
     let nullifier_1 = BytesN::from_array(&env, &[23u8; 32]);
 
     executor.execute_payment(
@@ -262,6 +273,7 @@ fn test_retry_across_periods_succeeds_with_new_period() {
         &proof_c_1,
         &nullifier_1,
         &1,
+        &BytesN::random(&env),
     );
 
     assert!(executor.is_paid(&employee, &1));
@@ -271,9 +283,9 @@ fn test_retry_across_periods_succeeds_with_new_period() {
     executor.create_period(&company_id);
 
     // Payment 2 in period 2 with different proof/amount
-    let proof_a_2 = BytesN::from_array(&env, &[30u8; 64]);
-    let proof_b_2 = BytesN::from_array(&env, &[31u8; 128]);
-    let proof_c_2 = BytesN::from_array(&env, &[32u8; 64]);
+    let proof_a_2 = g1_point(&env, 30);
+    let proof_b_2 = g2_point(&env, 31);
+    let proof_c_2 = g1_point(&env, 32);
     let nullifier_2 = BytesN::from_array(&env, &[33u8; 32]);
 
     executor.execute_payment(
@@ -285,6 +297,7 @@ fn test_retry_across_periods_succeeds_with_new_period() {
         &proof_c_2,
         &nullifier_2,
         &2,
+        &BytesN::random(&env),
     );
 
     // Both payments recorded
@@ -302,8 +315,12 @@ fn test_retry_across_periods_succeeds_with_new_period() {
         &proof_c_1,
         &nullifier_1,
         &1,
+        &BytesN::random(&env),
+    );
+    assert_eq!(
+        replay_1.unwrap_err().unwrap(),
+        PaymentError::ProofAlreadyUsed
     );
-    assert_eq!(replay_1.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
 
     let replay_2 = executor.try_execute_payment(
         &company_id,
@@ -314,8 +331,12 @@ fn test_retry_across_periods_succeeds_with_new_period() {
         &proof_c_2,
         &nullifier_2,
         &2,
+        &BytesN::random(&env),
+    );
+    assert_eq!(
+        replay_2.unwrap_err().unwrap(),
+        PaymentError::ProofAlreadyUsed
     );
-    assert_eq!(replay_2.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
 }
 
 /// Acceptance Criteria: Idempotent Retry Within Same Period
@@ -326,22 +347,32 @@ fn test_retry_across_periods_succeeds_with_new_period() {
 #[test]
 fn test_retry_same_period_detects_already_paid() {
     let env = Env::default();
-    let (executor, registry, commitment_client, _token, company_id, _admin, _treasury) =
+    let (executor, registry, commitment_client, _token, company_id, admin, _treasury) =
         setup_system(&env);
 
     let employee = Address::generate(&env);
     let commitment = BytesN::from_array(&env, &[50u8; 32]);
 
     commitment_client.store_commitment(&employee, &commitment);
-    registry.add_employee(&company_id, &employee, &commitment);
+    registry.add_employee(&company_id, &admin, &employee, &commitment);
 
-    let proof_a = BytesN::from_array(&env, &[60u8; 64]);
-    let proof_b = BytesN::from_array(&env, &[61u8; 128]);
-    let proof_c = BytesN::from_array(&env, &[62u8; 64]);
+    let proof_a = g1_point(&env, 60);
+    let proof_b = g2_point(&env, 61);
+    let proof_c = g1_point(&env, 62);
     let nullifier = BytesN::from_array(&env, &[63u8; 32]);
 
     // First payment in period 1
-    executor.execute_payment(&company_id, &employee, &1000, &proof_a, &proof_b, &proof_c, &nullifier, &1);
+    executor.execute_payment(
+        &company_id,
+        &employee,
+        &1000,
+        &proof_a,
+        &proof_b,
+        &proof_c,
+        &nullifier,
+        &1,
+        &BytesN::random(&env),
+    );
 
     assert!(executor.is_paid(&employee, &1));
     assert_eq!(executor.get_total_paid(&company_id), 1000);
@@ -356,10 +387,14 @@ fn test_retry_same_period_detects_already_paid() {
         &proof_c,
         &nullifier,
         &1,
+        &BytesN::random(&env),
     );
 
     // Should fail due to ProofAlreadyUsed (nullifier already consumed)
-    assert_eq!(retry_result.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
+    assert_eq!(
+        retry_result.unwrap_err().unwrap(),
+        PaymentError::ProofAlreadyUsed
+    );
 
     // Verify no duplicate payment was recorded
     assert_eq!(executor.get_total_paid(&company_id), 1000);
@@ -373,22 +408,32 @@ fn test_retry_same_period_detects_already_paid() {
 #[test]
 fn test_period_isolation_prevents_cross_period_replay() {
     let env = Env::default();
-    let (executor, registry, commitment_client, _token, company_id, _admin, _treasury) =
+    let (executor, registry, commitment_client, _token, company_id, admin, _treasury) =
         setup_system(&env);
 
     let employee = Address::generate(&env);
     let commitment = BytesN::from_array(&env, &[70u8; 32]);
 
     commitment_client.store_commitment(&employee, &commitment);
-    registry.add_employee(&company_id, &employee, &commitment);
+    registry.add_employee(&company_id, &admin, &employee, &commitment);
 
-    let proof_a = BytesN::from_array(&env, &[80u8; 64]);
-    let proof_b = BytesN::from_array(&env, &[81u8; 128]);
-    let proof_c = BytesN::from_array(&env, &[82u8; 64]);
+    let proof_a = g1_point(&env, 80);
+    let proof_b = g2_point(&env, 81);
+    let proof_c = g1_point(&env, 82);
     let nullifier = BytesN::from_array(&env, &[83u8; 32]);
 
     // Execute payment in period 1
-    executor.execute_payment(&company_id, &employee, &2000, &proof_a, &proof_b, &proof_c, &nullifier, &1);
+    executor.execute_payment(
+        &company_id,
+        &employee,
+        &2000,
+        &proof_a,
+        &proof_b,
+        &proof_c,
+        &nullifier,
+        &1,
+        &BytesN::random(&env),
+    );
     assert!(executor.is_paid(&employee, &1));
 
     // Create a new period (period 2)
@@ -404,10 +449,14 @@ fn test_period_isolation_prevents_cross_period_replay() {
         &proof_c,
         &nullifier,
         &2,
+        &BytesN::random(&env),
     );
 
     // Should fail because nullifier was already consumed in period 1
-    assert_eq!(cross_period_result.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
+    assert_eq!(
+        cross_period_result.unwrap_err().unwrap(),
+        PaymentError::ProofAlreadyUsed
+    );
 
     // Verify employee is not marked as paid in period 2
     assert!(!executor.is_paid(&employee, &2));
@@ -427,7 +476,7 @@ fn test_period_isolation_prevents_cross_period_replay() {
 #[test]
 fn test_retry_multiple_employees_detects_duplicates() {
     let env = Env::default();
-    let (executor, registry, commitment_client, _token, company_id, _admin, _treasury) =
+    let (executor, registry, commitment_client, _token, company_id, admin, _treasury) =
         setup_system(&env);
 
     let employee_a = Address::generate(&env);
@@ -438,13 +487,13 @@ fn test_retry_multiple_employees_detects_duplicates() {
 
     commitment_client.store_commitment(&employee_a, &commitment_a);
     commitment_client.store_commitment(&employee_b, &commitment_b);
-    registry.add_employee(&company_id, &employee_a, &commitment_a);
-    registry.add_employee(&company_id, &employee_b, &commitment_b);
+    registry.add_employee(&company_id, &admin, &employee_a, &commitment_a);
+    registry.add_employee(&company_id, &admin, &employee_b, &commitment_b);
 
     // First employee payment
-    let proof_a_1 = BytesN::from_array(&env, &[100u8; 64]);
-    let proof_b_1 = BytesN::from_array(&env, &[101u8; 128]);
-    let proof_c_1 = BytesN::from_array(&env, &[102u8; 64]);
+    let proof_a_1 = g1_point(&env, 100);
+    let proof_b_1 = g2_point(&env, 101);
+    let proof_c_1 = g1_point(&env, 102);
     let nullifier_1 = BytesN::from_array(&env, &[103u8; 32]);
 
     executor.execute_payment(
@@ -456,14 +505,15 @@ fn test_retry_multiple_employees_detects_duplicates() {
         &proof_c_1,
         &nullifier_1,
         &1,
+        &BytesN::random(&env),
     );
 
     assert_eq!(executor.get_total_paid(&company_id), 500);
 
     // Second employee payment
-    let proof_a_2 = BytesN::from_array(&env, &[110u8; 64]);
-    let proof_b_2 = BytesN::from_array(&env, &[111u8; 128]);
-    let proof_c_2 = BytesN::from_array(&env, &[112u8; 64]);
+    let proof_a_2 = g1_point(&env, 110);
+    let proof_b_2 = g2_point(&env, 111);
+    let proof_c_2 = g1_point(&env, 112);
     let nullifier_2 = BytesN::from_array(&env, &[113u8; 32]);
 
     executor.execute_payment(
@@ -475,6 +525,7 @@ fn test_retry_multiple_employees_detects_duplicates() {
         &proof_c_2,
         &nullifier_2,
         &1,
+        &BytesN::random(&env),
     );
 
     // Both payments recorded
@@ -490,8 +541,12 @@ fn test_retry_multiple_employees_detects_duplicates() {
         &proof_c_1,
         &nullifier_1,
         &1,
+        &BytesN::random(&env),
+    );
+    assert_eq!(
+        replay_1.unwrap_err().unwrap(),
+        PaymentError::ProofAlreadyUsed
     );
-    assert_eq!(replay_1.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
 
     let replay_2 = executor.try_execute_payment(
         &company_id,
@@ -502,13 +557,17 @@ fn test_retry_multiple_employees_detects_duplicates() {
         &proof_c_2,
         &nullifier_2,
         &1,
+        &BytesN::random(&env),
+    );
+    assert_eq!(
+        replay_2.unwrap_err().unwrap(),
+        PaymentError::ProofAlreadyUsed
     );
-    assert_eq!(replay_2.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
 
     // Attempt to pay employee A again with different proof (should fail with AlreadyPaid)
-    let proof_a_3 = BytesN::from_array(&env, &[120u8; 64]);
-    let proof_b_3 = BytesN::from_array(&env, &[121u8; 128]);
-    let proof_c_3 = BytesN::from_array(&env, &[122u8; 64]);
+    let proof_a_3 = g1_point(&env, 120);
+    let proof_b_3 = g2_point(&env, 121);
+    let proof_c_3 = g1_point(&env, 122);
     let nullifier_3 = BytesN::from_array(&env, &[123u8; 32]);
 
     let double_pay_result = executor.try_execute_payment(
@@ -520,8 +579,12 @@ fn test_retry_multiple_employees_detects_duplicates() {
         &proof_c_3,
         &nullifier_3,
         &1,
+        &BytesN::random(&env),
+    );
+    assert_eq!(
+        double_pay_result.unwrap_err().unwrap(),
+        PaymentError::AlreadyPaid
     );
-    assert_eq!(double_pay_result.unwrap_err().unwrap(), PaymentError::AlreadyPaid);
 
     // Final verification: total paid unchanged
     assert_eq!(executor.get_total_paid(&company_id), 800);
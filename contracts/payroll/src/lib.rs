@@ -1,18 +1,31 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, token as soroban_token, Address, BytesN,
-    Env, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token as soroban_token,
+    Address, BytesN, Env, Symbol, Vec,
 };
 
 use pause_manager::PauseManagerClient;
+use payroll_registry::PayrollRegistryClient;
 use proof_verifier::ProofVerifierClient;
 use salary_commitment::SalaryCommitmentContractClient;
 
 const MAX_BATCH: u32 = 50;
 
+/// Length of a treasury drawdown period when none has been configured
+/// (30 days).
+pub const DEFAULT_DRAWDOWN_PERIOD_SECS: u64 = 2_592_000;
+
 #[contract]
 pub struct Payroll;
 
+/// Error returned by a second call to an initializer.
+#[contracterror]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum InitError {
+    AlreadyInitialized = 1,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct ContractAddresses {
@@ -90,6 +103,42 @@ pub struct EmergencyWithdrawalRequest {
     pub approved: bool,
 }
 
+/// One payment of a recipient-bound batch, with its proof's public signals.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BoundPayment {
+    pub proof: BytesN<256>,
+    pub amount: i128,
+    pub employee: Address,
+    /// `Poseidon(blinding, period, recipient_hash)`, as proven.
+    pub nullifier: BytesN<32>,
+    /// The proof's `recipient_hash` signal; must be the employee's.
+    pub recipient_hash: BytesN<32>,
+}
+
+/// Registry company whose enrolled commitments batches are checked against.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegistryCrossCheck {
+    pub registry: Address,
+    pub company_id: u64,
+}
+
+/// What a batch does with a payment whose nullifier is already recorded.
+///
+/// `Skip` lets an operator resubmit a partially processed batch: payments
+/// that already went through are passed over with a `payment_skipped` event
+/// instead of failing the whole batch.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum NullifierPolicy {
+    /// Panic with "Nullifier already used", reverting the batch.
+    Abort = 0,
+    /// Pay nothing for that payment and continue with the rest.
+    Skip = 1,
+}
+
 // ── Issue #89: payroll amendment flow ────────────────────────────────────────
 
 /// Lifecycle state of a payroll run draft.
@@ -163,6 +212,17 @@ pub enum DataKey {
     DraftCommitment(BytesN<32>),
     /// Pending emergency withdrawal request (#104).
     EmergencyRequest,
+    /// Length in seconds of a treasury drawdown period.
+    DrawdownPeriodSecs,
+    /// Total paid out of a treasury during a drawdown period.
+    TreasuryDrawdown(Address, u32),
+    /// Whether batches must use recipient-bound nullifiers; `true` when
+    /// unset.
+    RequireBoundNullifiers,
+    /// Whether batches check the treasury's token allowance up front.
+    TreasuryAllowanceCheck,
+    /// Registry company batches cross-check commitments against.
+    RegistryCrossCheck,
 }
 
 #[contractimpl]
@@ -175,10 +235,10 @@ impl Payroll {
         commitment: Address,
         treasury: Address,
         treasury_owner: Address,
-    ) {
+    ) -> Result<(), InitError> {
         let key = DataKey::Addresses;
         if e.storage().persistent().has(&key) {
-            panic!("Already initialized")
+            return Err(InitError::AlreadyInitialized);
         }
         let addrs = ContractAddresses {
             admin,
@@ -188,11 +248,65 @@ impl Payroll {
             treasury,
             treasury_owner: treasury_owner.clone(),
         };
+        Self::require_live_dependencies(&e, &addrs);
         e.storage().persistent().set(&key, &addrs);
         e.storage()
             .persistent()
             .set(&DataKey::TreasuryOwner, &treasury_owner);
         e.storage().persistent().set(&DataKey::RunCounter, &0u64);
+        Ok(())
+    }
+
+    /// Make one cheap read call into each dependency so a mistyped address
+    /// fails here rather than at the first payroll run.
+    fn require_live_dependencies(e: &Env, addrs: &ContractAddresses) {
+        if !matches!(
+            ProofVerifierClient::new(e, &addrs.verifier).try_is_initialized(),
+            Ok(Ok(_))
+        ) {
+            panic!(
+                "verifier {:?} is not a ProofVerifier contract",
+                addrs.verifier
+            );
+        }
+        if !matches!(
+            SalaryCommitmentContractClient::new(e, &addrs.commitment).try_is_initialized(),
+            Ok(Ok(_))
+        ) {
+            panic!(
+                "commitment {:?} is not a SalaryCommitment contract",
+                addrs.commitment
+            );
+        }
+        if !matches!(
+            soroban_token::Client::new(e, &addrs.token).try_balance(&addrs.treasury),
+            Ok(Ok(_))
+        ) {
+            panic!("token {:?} is not a token contract", addrs.token);
+        }
+    }
+
+    /// Whether `initialize` has been called.
+    pub fn is_initialized(e: Env) -> bool {
+        e.storage().persistent().has(&DataKey::Addresses)
+    }
+
+    /// The addresses this contract was initialized with.
+    pub fn get_addresses(e: Env) -> ContractAddresses {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Addresses)
+            .expect("Not initialized")
+    }
+
+    /// Token balance of the configured treasury.
+    pub fn get_treasury_balance(e: Env) -> i128 {
+        let addrs: ContractAddresses = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Addresses)
+            .expect("Not initialized");
+        soroban_token::Client::new(&e, &addrs.token).balance(&addrs.treasury)
     }
 
     pub fn set_pause_manager(e: Env, pause_manager: Address) {
@@ -339,6 +453,8 @@ impl Payroll {
     /// Only the `admin` may approve. On approval the treasury funds are
     /// transferred to the recipient specified in the request and the pending
     /// request is cleared from storage, ensuring it cannot be replayed.
+    /// Panics, leaving the request pending, if the requested amount exceeds
+    /// the treasury balance.
     pub fn approve_emergency_withdrawal(e: Env, admin: Address) {
         let addrs: ContractAddresses = e
             .storage()
@@ -356,16 +472,22 @@ impl Payroll {
             .get(&DataKey::EmergencyRequest)
             .expect("No pending emergency request");
 
+        let token_client = soroban_token::Client::new(&e, &addrs.token);
+        if token_client.balance(&addrs.treasury) < request.amount {
+            panic!("Amount exceeds treasury balance");
+        }
+
         // Clear before transfer (checks-effects-interactions).
         e.storage().persistent().remove(&DataKey::EmergencyRequest);
 
-        let token_client = soroban_token::Client::new(&e, &addrs.token);
         token_client.transfer(&addrs.treasury, &request.recipient, &request.amount);
 
         e.events().publish(
             (symbol_short!("payroll"), Symbol::new(&e, "emrg_approved")),
             (request.amount, request.recipient),
         );
+        // topics : ("payroll", "emrg_approved")
+        // data   : (amount, recipient)
     }
 
     /// Cancel a pending emergency withdrawal request.
@@ -407,6 +529,49 @@ impl Payroll {
         e.storage().persistent().get(&DataKey::EmergencyRequest)
     }
 
+    /// Withdraw treasury funds to `to` in one call, for winding the
+    /// protocol down.
+    ///
+    /// Needs the `admin`'s auth co-signed by the `treasury_owner`, so
+    /// neither role can drain the treasury alone. Panics if `amount` exceeds
+    /// the treasury balance.
+    pub fn emergency_withdraw(e: Env, admin: Address, to: Address, amount: i128) {
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        let addrs: ContractAddresses = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Addresses)
+            .expect("Not initialized");
+        if admin != addrs.admin {
+            panic!("Unauthorized");
+        }
+        let treasury_owner: Address = e
+            .storage()
+            .persistent()
+            .get(&DataKey::TreasuryOwner)
+            .expect("Treasury owner not set");
+        admin.require_auth();
+        treasury_owner.require_auth();
+
+        let token_client = soroban_token::Client::new(&e, &addrs.token);
+        if token_client.balance(&addrs.treasury) < amount {
+            panic!("Amount exceeds treasury balance");
+        }
+        token_client.transfer(&addrs.treasury, &to, &amount);
+
+        // Registry company IDs start at 1; 0 means no company is configured.
+        let company_id =
+            Self::get_registry_cross_check(e.clone()).map_or(0, |check| check.company_id);
+        e.events().publish(
+            (Symbol::new(&e, "EmergencyWithdraw"), company_id),
+            (amount, to),
+        );
+        // topics : ("EmergencyWithdraw", company_id)
+        // data   : (amount, to)
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // Payroll run cancellation (issue #75)
     // ─────────────────────────────────────────────────────────────────────────
@@ -502,9 +667,7 @@ impl Payroll {
 
     /// Get a pending payroll run, if it exists.
     pub fn get_pending_run(e: Env, run_id: u64) -> Option<PendingPayrollRun> {
-        e.storage()
-            .persistent()
-            .get(&DataKey::PendingRun(run_id))
+        e.storage().persistent().get(&DataKey::PendingRun(run_id))
     }
 
     /// Cancel a pending payroll run without executing any payments.
@@ -543,6 +706,13 @@ impl Payroll {
         );
     }
 
+    /// Execute a payroll batch whose nullifiers are derived from the batch
+    /// index.
+    ///
+    /// Panics when the admin has required recipient-bound nullifiers; use
+    /// [`Self::batch_process_bound_payroll`] instead. `policy` decides what
+    /// happens to payments whose nullifier is already used.
+    #[allow(clippy::too_many_arguments)]
     pub fn batch_process_payroll(
         e: Env,
         proofs: Vec<BytesN<256>>,
@@ -551,6 +721,7 @@ impl Payroll {
         expected_total_spend: i128,
         nonce: BytesN<32>,
         draft_hash: Option<BytesN<32>>,
+        policy: NullifierPolicy,
     ) -> u64 {
         let count = proofs.len();
 
@@ -558,7 +729,165 @@ impl Payroll {
             panic!("Array length mismatch");
         }
 
-        assert!(count <= MAX_BATCH, "Batch too large");
+        if Self::get_require_bound_nullifiers(e.clone()) {
+            panic!("Recipient-bound nullifiers required: use batch_process_bound_payroll");
+        }
+
+        let mut payments = Vec::new(&e);
+        for i in 0..count {
+            let mut nullifier_arr = [0u8; 32];
+            nullifier_arr[0] = (i % 256) as u8;
+            nullifier_arr[1] = (i / 256) as u8;
+            payments.push_back(BoundPayment {
+                proof: proofs.get(i).unwrap(),
+                amount: amounts.get(i).unwrap(),
+                employee: employees.get(i).unwrap(),
+                nullifier: BytesN::from_array(&e, &nullifier_arr),
+                recipient_hash: BytesN::from_array(&e, &[0u8; 32]),
+            });
+        }
+
+        Self::run_batch(
+            &e,
+            payments,
+            false,
+            expected_total_spend,
+            nonce,
+            draft_hash,
+            policy,
+        )
+    }
+
+    /// Execute a payroll batch with prover-supplied, recipient-bound
+    /// nullifiers.
+    ///
+    /// Each payment carries the public signals of its proof. The circuit
+    /// constrains `nullifier = Poseidon(blinding, period, recipient_hash)`
+    /// and the verifier rejects a `recipient_hash` that is not the employee's
+    /// (see [`proof_verifier::recipient_hash`]), so a nullifier proven for one
+    /// employee cannot pay another.
+    pub fn batch_process_bound_payroll(
+        e: Env,
+        payments: Vec<BoundPayment>,
+        expected_total_spend: i128,
+        nonce: BytesN<32>,
+        draft_hash: Option<BytesN<32>>,
+        policy: NullifierPolicy,
+    ) -> u64 {
+        Self::run_batch(
+            &e,
+            payments,
+            true,
+            expected_total_spend,
+            nonce,
+            draft_hash,
+            policy,
+        )
+    }
+
+    /// Require (or stop requiring) recipient-bound nullifiers. Admin only.
+    ///
+    /// Required by default: `batch_process_payroll` is rejected unless the
+    /// admin explicitly opts out with `false`, accepting index-derived
+    /// nullifiers that a payment to another recipient could reuse.
+    pub fn set_require_bound_nullifiers(e: Env, enabled: bool) {
+        let addrs: ContractAddresses = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Addresses)
+            .expect("Not initialized");
+        addrs.admin.require_auth();
+        e.storage()
+            .persistent()
+            .set(&DataKey::RequireBoundNullifiers, &enabled);
+    }
+
+    /// Whether payroll batches must use recipient-bound nullifiers; `true`
+    /// unless the admin opted out.
+    pub fn get_require_bound_nullifiers(e: Env) -> bool {
+        e.storage()
+            .persistent()
+            .get(&DataKey::RequireBoundNullifiers)
+            .unwrap_or(true)
+    }
+
+    /// Switch batches to paying out of the treasury's SEP-41 allowance to
+    /// this contract, or back to plain transfers. Admin only.
+    ///
+    /// While enabled, each batch first checks that the allowance covers the
+    /// batch total, failing fast before any payment, and then pays with
+    /// `transfer_from`. Leave disabled for tokens that do not implement
+    /// allowances.
+    pub fn set_treasury_allowance_check(e: Env, enabled: bool) {
+        let addrs: ContractAddresses = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Addresses)
+            .expect("Not initialized");
+        addrs.admin.require_auth();
+        e.storage()
+            .persistent()
+            .set(&DataKey::TreasuryAllowanceCheck, &enabled);
+    }
+
+    /// Whether batches check the treasury's token allowance up front.
+    pub fn get_treasury_allowance_check(e: Env) -> bool {
+        e.storage()
+            .persistent()
+            .get(&DataKey::TreasuryAllowanceCheck)
+            .unwrap_or(false)
+    }
+
+    /// Cross-check every paid commitment against `check`'s registry
+    /// company, or stop checking with `None`. Admin only.
+    ///
+    /// While set, a batch panics with "Commitment divergence" when the
+    /// commitment contract and the registry disagree on an employee's
+    /// commitment, or the employee is not enrolled in the company.
+    pub fn set_registry_cross_check(e: Env, check: Option<RegistryCrossCheck>) {
+        let addrs: ContractAddresses = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Addresses)
+            .expect("Not initialized");
+        addrs.admin.require_auth();
+        match check {
+            Some(check) => e
+                .storage()
+                .persistent()
+                .set(&DataKey::RegistryCrossCheck, &check),
+            None => e
+                .storage()
+                .persistent()
+                .remove(&DataKey::RegistryCrossCheck),
+        }
+    }
+
+    /// The registry company batches cross-check commitments against, if any.
+    pub fn get_registry_cross_check(e: Env) -> Option<RegistryCrossCheck> {
+        e.storage().persistent().get(&DataKey::RegistryCrossCheck)
+    }
+
+    /// The commitment the registry holds for `employee` in `check`'s
+    /// company, or `None` if they are not enrolled.
+    fn get_employee_commitment_from_registry(
+        e: &Env,
+        check: &RegistryCrossCheck,
+        employee: &Address,
+    ) -> Option<BytesN<32>> {
+        PayrollRegistryClient::new(e, &check.registry).find_employee(&check.company_id, employee)
+    }
+
+    fn run_batch(
+        e: &Env,
+        payments: Vec<BoundPayment>,
+        bound: bool,
+        expected_total_spend: i128,
+        nonce: BytesN<32>,
+        draft_hash: Option<BytesN<32>>,
+        policy: NullifierPolicy,
+    ) -> u64 {
+        assert!(payments.len() <= MAX_BATCH, "Batch too large");
 
         // #103 — reject duplicate run nonces before any other work.
         let nonce_key = DataKey::RunNonce(nonce.clone());
@@ -576,12 +905,12 @@ impl Payroll {
             e.storage().persistent().remove(&commit_key);
             dh.clone()
         } else {
-            BytesN::from_array(&e, &[0u8; 32])
+            BytesN::from_array(e, &[0u8; 32])
         };
 
         let mut total: i128 = 0;
-        for i in 0..count {
-            total += amounts.get(i).unwrap();
+        for payment in payments.iter() {
+            total += payment.amount;
         }
         if total != expected_total_spend {
             panic!(
@@ -602,7 +931,7 @@ impl Payroll {
                 .persistent()
                 .get(&DataKey::PauseManager)
                 .unwrap();
-            let pm_client = PauseManagerClient::new(&e, &pm_addr);
+            let pm_client = PauseManagerClient::new(e, &pm_addr);
             if pm_client.is_paused() {
                 panic!("Payroll is paused");
             }
@@ -610,60 +939,105 @@ impl Payroll {
 
         addrs.admin.require_auth();
 
-        let run_id = Self::derive_run_id(&e);
+        let token_client = soroban_token::Client::new(e, &addrs.token);
+        let allowance_mode = Self::get_treasury_allowance_check(e.clone());
+        if allowance_mode
+            && token_client.allowance(&addrs.treasury, &e.current_contract_address()) < total
+        {
+            panic!("Insufficient treasury allowance");
+        }
+
+        let run_id = Self::derive_run_id(e);
 
         // #103 — mark nonce as consumed (store run_id for auditability).
         e.storage().persistent().set(&nonce_key, &run_id);
 
-        let verifier = ProofVerifierClient::new(&e, &addrs.verifier);
-        let commitment_client = SalaryCommitmentContractClient::new(&e, &addrs.commitment);
-        let token_client = soroban_token::Client::new(&e, &addrs.token);
-
-        for i in 0..count {
-            let proof = proofs.get(i).unwrap();
-            let amount = amounts.get(i).unwrap();
-            let employee = employees.get(i).unwrap();
+        let verifier = ProofVerifierClient::new(e, &addrs.verifier);
+        let commitment_client = SalaryCommitmentContractClient::new(e, &addrs.commitment);
+        let registry_check = Self::get_registry_cross_check(e.clone());
+        // Proofs are checked against the cross-checked company's key. Registry
+        // company IDs start at 1, so 0 selects the verifier's default key.
+        let vk_company = registry_check.as_ref().map_or(0, |check| check.company_id);
+
+        // Skipped payments are not paid, so the run records what was
+        // actually spent.
+        let mut paid_total: i128 = 0;
+        let mut paid_count: u32 = 0;
+
+        for (i, payment) in payments.iter().enumerate() {
+            let employee = payment.employee.clone();
+            let amount = payment.amount;
+            let nullifier = payment.nullifier.clone();
+
+            if policy == NullifierPolicy::Skip && commitment_client.is_nullifier_used(&nullifier) {
+                e.events().publish(
+                    (symbol_short!("payroll"), Symbol::new(e, "payment_skipped")),
+                    (employee, nullifier),
+                );
+                // topics : ("payroll", "payment_skipped")
+                // data   : (employee, nullifier)
+                continue;
+            }
 
             let commitment_struct = commitment_client.get_commitment(&employee);
             let commitment = commitment_struct.commitment;
 
-            let mut nullifier_arr = [0u8; 32];
-            nullifier_arr[0] = (i % 256) as u8;
-            nullifier_arr[1] = (i / 256) as u8;
-            let nullifier = BytesN::from_array(&e, &nullifier_arr);
-            let recipient_hash = BytesN::from_array(&e, &[0u8; 32]);
+            if let Some(check) = &registry_check {
+                let registered = Self::get_employee_commitment_from_registry(e, check, &employee);
+                if registered != Some(commitment.clone()) {
+                    panic!("Commitment divergence");
+                }
+            }
 
-            let mut public_inputs = Vec::new(&e);
+            let mut public_inputs = Vec::new(e);
             public_inputs.push_back(commitment.clone());
             public_inputs.push_back(nullifier.clone());
-            public_inputs.push_back(recipient_hash.clone());
-
-            let ok = verifier.verify_payment_proof(&proof, &public_inputs);
+            public_inputs.push_back(payment.recipient_hash.clone());
+
+            let ok = if bound {
+                verifier.verify_bound_payment_proof(
+                    &vk_company,
+                    &payment.proof,
+                    &public_inputs,
+                    &employee,
+                )
+            } else {
+                verifier.verify_payment_proof(&vk_company, &payment.proof, &public_inputs)
+            };
             if !ok {
                 panic!("Invalid payment proof for employee {}", i);
             }
 
             commitment_client.record_nullifier(&nullifier);
 
-            token_client.transfer(&addrs.treasury, &employee, &amount);
+            if allowance_mode {
+                token_client.transfer_from(
+                    &e.current_contract_address(),
+                    &addrs.treasury,
+                    &employee,
+                    &amount,
+                );
+            } else {
+                token_client.transfer(&addrs.treasury, &employee, &amount);
+            }
 
             e.events().publish(
-                (
-                    symbol_short!("payroll"),
-                    Symbol::new(&e, "payment_executed"),
-                ),
+                (symbol_short!("payroll"), Symbol::new(e, "payment_executed")),
                 (employee.clone(), amount),
             );
             // topics : ("payroll", "payment_executed")
             // data   : (employee, amount)
+
+            paid_total += amount;
+            paid_count += 1;
         }
 
         let run = PayrollRun {
             run_id,
             executed_at: e.ledger().timestamp(),
             admin: addrs.admin.clone(),
-            total_amount: expected_total_spend,
-            employee_count: count,
+            total_amount: paid_total,
+            employee_count: paid_count,
             draft_hash: resolved_draft_hash,
             nonce: nonce.clone(),
             reconciliation_status: ReconciliationStatus::Unreconciled,
@@ -672,14 +1046,67 @@ impl Payroll {
             .persistent()
             .set(&DataKey::PayrollRun(run_id), &run);
 
+        Self::record_drawdown(e, &addrs.treasury, paid_total);
+
         e.events().publish(
-            (symbol_short!("payroll"), Symbol::new(&e, "run_executed")),
-            (run_id, expected_total_spend),
+            (symbol_short!("payroll"), Symbol::new(e, "run_executed")),
+            (run_id, paid_total),
         );
 
         run_id
     }
 
+    // ── Treasury drawdown log ────────────────────────────────────────────────
+
+    /// Set the length of a drawdown period in seconds. Admin only.
+    ///
+    /// Periods are numbered `ledger_timestamp / period_secs`, so changing the
+    /// length renumbers future periods; totals already recorded are kept under
+    /// their old numbers.
+    pub fn set_drawdown_period(e: Env, period_secs: u64) {
+        if period_secs == 0 {
+            panic!("Drawdown period must be positive");
+        }
+        let addrs: ContractAddresses = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Addresses)
+            .expect("Not initialized");
+        addrs.admin.require_auth();
+        e.storage()
+            .persistent()
+            .set(&DataKey::DrawdownPeriodSecs, &period_secs);
+    }
+
+    /// Length of a drawdown period in seconds.
+    pub fn get_drawdown_period(e: Env) -> u64 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::DrawdownPeriodSecs)
+            .unwrap_or(DEFAULT_DRAWDOWN_PERIOD_SECS)
+    }
+
+    /// Number of the drawdown period containing the current ledger time.
+    pub fn current_drawdown_period(e: Env) -> u32 {
+        let period_secs = Self::get_drawdown_period(e.clone());
+        (e.ledger().timestamp() / period_secs) as u32
+    }
+
+    /// Total paid out of `treasury` by executed batches during `period`.
+    pub fn get_drawdown(e: Env, treasury: Address, period: u32) -> i128 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::TreasuryDrawdown(treasury, period))
+            .unwrap_or(0)
+    }
+
+    fn record_drawdown(e: &Env, treasury: &Address, amount: i128) {
+        let period = Self::current_drawdown_period(e.clone());
+        let key = DataKey::TreasuryDrawdown(treasury.clone(), period);
+        let drawn: i128 = e.storage().persistent().get(&key).unwrap_or(0);
+        e.storage().persistent().set(&key, &(drawn + amount));
+    }
+
     // ── Issue #89: payroll amendment flow ────────────────────────────────────
 
     /// Create a correctable payroll run draft.
@@ -750,15 +1177,6 @@ impl Payroll {
         draft_id: u64,
         new_total_amount: i128,
         new_employee_count: u32,
-    /// Update the reconciliation status of a completed payroll run.
-    ///
-    /// Only the `admin` may update the reconciliation status.
-    /// Emits a `reconciliation_updated` event.
-    pub fn update_reconciliation_status(
-        e: Env,
-        admin: Address,
-        run_id: u64,
-        status: ReconciliationStatus,
     ) {
         let addrs: ContractAddresses = e
             .storage()
@@ -858,10 +1276,7 @@ impl Payroll {
         }
         current_admin.require_auth();
 
-        if e.storage()
-            .persistent()
-            .has(&DataKey::PendingAdminRotation)
-        {
+        if e.storage().persistent().has(&DataKey::PendingAdminRotation) {
             panic!("A pending admin rotation already exists");
         }
 
@@ -929,11 +1344,7 @@ impl Payroll {
         }
         current_admin.require_auth();
 
-        if !e
-            .storage()
-            .persistent()
-            .has(&DataKey::PendingAdminRotation)
-        {
+        if !e.storage().persistent().has(&DataKey::PendingAdminRotation) {
             panic!("No pending admin rotation to cancel");
         }
         e.storage()
@@ -941,7 +1352,10 @@ impl Payroll {
             .remove(&DataKey::PendingAdminRotation);
 
         e.events().publish(
-            (symbol_short!("payroll"), Symbol::new(&e, "admin_rot_cancel")),
+            (
+                symbol_short!("payroll"),
+                Symbol::new(&e, "admin_rot_cancel"),
+            ),
             current_admin,
         );
     }
@@ -973,15 +1387,6 @@ impl Payroll {
         e.storage()
             .persistent()
             .set(&DataKey::PendingTreasuryRotation, &proposal);
-        let run_key = DataKey::PayrollRun(run_id);
-        let mut run: PayrollRun = e
-            .storage()
-            .persistent()
-            .get(&run_key)
-            .expect("Run not found");
-
-        run.reconciliation_status = status;
-        e.storage().persistent().set(&run_key, &run);
 
         e.events().publish(
             (
@@ -1070,9 +1475,7 @@ impl Payroll {
 
     /// Return the pending admin rotation proposal, if any.
     pub fn get_pending_admin_rotation(e: Env) -> Option<PendingRotation> {
-        e.storage()
-            .persistent()
-            .get(&DataKey::PendingAdminRotation)
+        e.storage().persistent().get(&DataKey::PendingAdminRotation)
     }
 
     /// Return the pending treasury-owner rotation proposal, if any.
@@ -1081,33 +1484,68 @@ impl Payroll {
             .persistent()
             .get(&DataKey::PendingTreasuryRotation)
     }
-                Symbol::new(&e, "reconciliation_updated"),
-            ),
-            (run_id, status),
-        );
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use ::token::{Token, TokenClient};
-    use pause_manager::{PauseManager, PauseManagerClient};
-    use proof_verifier::{ProofVerifier, VerificationKey};
-    use salary_commitment::SalaryCommitmentContract;
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::{Env, IntoVal};
-
-    fn mock_proof(env: &Env) -> BytesN<256> {
-        BytesN::from_array(env, &[0u8; 256])
-    }
-
-    /// Generates a unique 32-byte nonce from a counter seed for tests.
-    fn test_nonce(env: &Env, seed: u8) -> BytesN<32> {
-        let mut arr = [0u8; 32];
-        arr[0] = seed;
-        BytesN::from_array(env, &arr)
-    }
+
+    /// Update the reconciliation status of a completed payroll run.
+    ///
+    /// Only the `admin` may update the reconciliation status.
+    /// Emits a `reconciliation_updated` event.
+    pub fn update_reconciliation_status(
+        e: Env,
+        admin: Address,
+        run_id: u64,
+        status: ReconciliationStatus,
+    ) {
+        let addrs: ContractAddresses = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Addresses)
+            .expect("Not initialized");
+        if admin != addrs.admin {
+            panic!("Unauthorized");
+        }
+        admin.require_auth();
+
+        let run_key = DataKey::PayrollRun(run_id);
+        let mut run: PayrollRun = e
+            .storage()
+            .persistent()
+            .get(&run_key)
+            .expect("Run not found");
+
+        run.reconciliation_status = status;
+        e.storage().persistent().set(&run_key, &run);
+
+        e.events().publish(
+            (
+                symbol_short!("payroll"),
+                Symbol::new(&e, "reconciliation_updated"),
+            ),
+            (run_id, status),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::token::{Token, TokenClient};
+    use pause_manager::{PauseManager, PauseManagerClient};
+    use proof_verifier::{recipient_hash, ProofVerifier, VerificationKey};
+    use salary_commitment::SalaryCommitmentContract;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::Ledger as _;
+    use soroban_sdk::{Env, IntoVal};
+
+    fn mock_proof(env: &Env) -> BytesN<256> {
+        BytesN::from_array(env, &[0u8; 256])
+    }
+
+    /// Generates a unique 32-byte nonce from a counter seed for tests.
+    fn test_nonce(env: &Env, seed: u8) -> BytesN<32> {
+        let mut arr = [0u8; 32];
+        arr[0] = seed;
+        BytesN::from_array(env, &arr)
+    }
 
     fn mock_vk(env: &Env) -> VerificationKey {
         VerificationKey {
@@ -1162,6 +1600,7 @@ mod tests {
             &treasury,
             &treasury_owner,
         );
+        payroll_client.set_require_bound_nullifiers(&false);
 
         commitment_client.set_payroll_operator(&payroll_id);
 
@@ -1182,6 +1621,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 1),
             &None,
+            &NullifierPolicy::Abort,
         );
         assert_eq!(run_id_1, 1);
 
@@ -1225,6 +1665,7 @@ mod tests {
             &treasury,
             &treasury_owner,
         );
+        payroll_client.set_require_bound_nullifiers(&false);
 
         commitment_client.set_payroll_operator(&payroll_id);
 
@@ -1252,6 +1693,7 @@ mod tests {
             &expected_total_spend,
             &test_nonce(&env, 2),
             &None,
+            &NullifierPolicy::Abort,
         );
         assert!(run_id > 0);
     }
@@ -1289,6 +1731,8 @@ mod tests {
             &treasury,
             &treasury_owner,
         );
+        // Most tests submit index-derived nullifiers.
+        payroll_client.set_require_bound_nullifiers(&false);
 
         commitment_client.set_payroll_operator(&payroll_id);
 
@@ -1334,6 +1778,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 3),
             &None,
+            &NullifierPolicy::Abort,
         );
         assert!(result.is_err());
     }
@@ -1360,6 +1805,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 4),
             &None,
+            &NullifierPolicy::Abort,
         );
         assert!(result.is_err());
     }
@@ -1386,6 +1832,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 5),
             &None,
+            &NullifierPolicy::Abort,
         );
         assert!(result.is_err());
 
@@ -1399,6 +1846,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 6),
             &None,
+            &NullifierPolicy::Abort,
         );
     }
 
@@ -1416,6 +1864,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 7),
             &None,
+            &NullifierPolicy::Abort,
         );
     }
 
@@ -1500,12 +1949,8 @@ mod tests {
         let (payroll_client, admin, _treasury, _treasury_owner, _employee) =
             setup_simple_payroll(&env);
 
-        let id = payroll_client.create_run_draft(
-            &admin,
-            &10_000i128,
-            &20u32,
-            &Symbol::new(&env, "JAN"),
-        );
+        let id =
+            payroll_client.create_run_draft(&admin, &10_000i128, &20u32, &Symbol::new(&env, "JAN"));
         let draft = payroll_client.get_run_draft(&id);
 
         assert_eq!(draft.state, RunDraftState::Pending);
@@ -1520,12 +1965,8 @@ mod tests {
         let (payroll_client, admin, _treasury, _treasury_owner, _employee) =
             setup_simple_payroll(&env);
 
-        let id = payroll_client.create_run_draft(
-            &admin,
-            &10_000i128,
-            &20u32,
-            &Symbol::new(&env, "FEB"),
-        );
+        let id =
+            payroll_client.create_run_draft(&admin, &10_000i128, &20u32, &Symbol::new(&env, "FEB"));
         payroll_client.amend_run_draft(&admin, &id, &12_000i128, &22u32);
 
         let draft = payroll_client.get_run_draft(&id);
@@ -1541,12 +1982,8 @@ mod tests {
         let (payroll_client, admin, _treasury, _treasury_owner, _employee) =
             setup_simple_payroll(&env);
 
-        let id = payroll_client.create_run_draft(
-            &admin,
-            &8_000i128,
-            &15u32,
-            &Symbol::new(&env, "MAR"),
-        );
+        let id =
+            payroll_client.create_run_draft(&admin, &8_000i128, &15u32, &Symbol::new(&env, "MAR"));
         payroll_client.finalize_run_draft(&admin, &id);
 
         let draft = payroll_client.get_run_draft(&id);
@@ -1559,15 +1996,14 @@ mod tests {
         let (payroll_client, admin, _treasury, _treasury_owner, _employee) =
             setup_simple_payroll(&env);
 
-        let id = payroll_client.create_run_draft(
-            &admin,
-            &5_000i128,
-            &10u32,
-            &Symbol::new(&env, "APR"),
-        );
+        let id =
+            payroll_client.create_run_draft(&admin, &5_000i128, &10u32, &Symbol::new(&env, "APR"));
         payroll_client.finalize_run_draft(&admin, &id);
 
         let result = payroll_client.try_amend_run_draft(&admin, &id, &9_000i128, &18u32);
+        assert!(result.is_err());
+    }
+
     // ── Issue #103: per-payroll run nonce uniqueness ───────────────────────────
 
     #[test]
@@ -1578,7 +2014,15 @@ mod tests {
 
         let nonce = test_nonce(&env, 10);
         let (proofs, amounts, employees) = single_payment_batch(&env, &employee, 1000);
-        payroll_client.batch_process_payroll(&proofs, &amounts, &employees, &1000, &nonce, &None);
+        payroll_client.batch_process_payroll(
+            &proofs,
+            &amounts,
+            &employees,
+            &1000,
+            &nonce,
+            &None,
+            &NullifierPolicy::Abort,
+        );
 
         // Second call with the same nonce must fail.
         let (proofs2, amounts2, employees2) = single_payment_batch(&env, &employee, 1000);
@@ -1589,6 +2033,7 @@ mod tests {
             &1000,
             &nonce,
             &None,
+            &NullifierPolicy::Abort,
         );
         assert!(result.is_err());
     }
@@ -1601,11 +2046,27 @@ mod tests {
 
         let (client1, _a1, _t1, _to1, emp1) = setup_simple_payroll(&env);
         let (p1, a1, e1) = single_payment_batch(&env, &emp1, 500);
-        let id1 = client1.batch_process_payroll(&p1, &a1, &e1, &500, &test_nonce(&env, 11), &None);
+        let id1 = client1.batch_process_payroll(
+            &p1,
+            &a1,
+            &e1,
+            &500,
+            &test_nonce(&env, 11),
+            &None,
+            &NullifierPolicy::Abort,
+        );
 
         let (client2, _a2, _t2, _to2, emp2) = setup_simple_payroll(&env);
         let (p2, a2, e2) = single_payment_batch(&env, &emp2, 500);
-        let id2 = client2.batch_process_payroll(&p2, &a2, &e2, &500, &test_nonce(&env, 12), &None);
+        let id2 = client2.batch_process_payroll(
+            &p2,
+            &a2,
+            &e2,
+            &500,
+            &test_nonce(&env, 12),
+            &None,
+            &NullifierPolicy::Abort,
+        );
 
         assert!(id1 > 0);
         assert!(id2 > 0);
@@ -1619,8 +2080,15 @@ mod tests {
 
         let nonce = test_nonce(&env, 13);
         let (proofs, amounts, employees) = single_payment_batch(&env, &employee, 1000);
-        let run_id = payroll_client
-            .batch_process_payroll(&proofs, &amounts, &employees, &1000, &nonce, &None);
+        let run_id = payroll_client.batch_process_payroll(
+            &proofs,
+            &amounts,
+            &employees,
+            &1000,
+            &nonce,
+            &None,
+            &NullifierPolicy::Abort,
+        );
         let run = payroll_client.get_payroll_run(&run_id);
         assert_eq!(run.nonce, nonce);
     }
@@ -1644,6 +2112,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 20),
             &Some(draft_hash.clone()),
+            &NullifierPolicy::Abort,
         );
         let run = payroll_client.get_payroll_run(&run_id);
         assert_eq!(run.draft_hash, draft_hash);
@@ -1664,6 +2133,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 21),
             &Some(unknown_hash),
+            &NullifierPolicy::Abort,
         );
         assert!(result.is_err());
     }
@@ -1685,6 +2155,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 22),
             &Some(draft_hash.clone()),
+            &NullifierPolicy::Abort,
         );
 
         // Second use of the same draft hash must fail (already consumed).
@@ -1696,6 +2167,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 23),
             &Some(draft_hash),
+            &NullifierPolicy::Abort,
         );
         assert!(result.is_err());
     }
@@ -1708,12 +2180,7 @@ mod tests {
             setup_simple_payroll(&env);
 
         let attacker = Address::generate(&env);
-        payroll_client.create_run_draft(
-            &attacker,
-            &1_000i128,
-            &1u32,
-            &Symbol::new(&env, "MAY"),
-        );
+        payroll_client.create_run_draft(&attacker, &1_000i128, &1u32, &Symbol::new(&env, "MAY"));
     }
 
     // ── Issue #91: admin/treasury rotation ───────────────────────────────────
@@ -1779,6 +2246,23 @@ mod tests {
 
     #[test]
     fn test_treasury_rotation_full_flow() {
+        let env = Env::default();
+        let (payroll_client, _admin, _treasury, treasury_owner, _employee) =
+            setup_simple_payroll(&env);
+
+        let new_owner = Address::generate(&env);
+        payroll_client.propose_treasury_rotation(&treasury_owner, &new_owner);
+
+        let proposal = payroll_client
+            .get_pending_treasury_rotation()
+            .expect("proposal should exist");
+        assert_eq!(proposal.new_holder, new_owner);
+
+        payroll_client.accept_treasury_rotation(&new_owner);
+        assert!(payroll_client.get_pending_treasury_rotation().is_none());
+    }
+
+    #[test]
     fn test_batch_runs_without_draft_hash() {
         let env = Env::default();
         let (payroll_client, _admin, _treasury, _treasury_owner, employee) =
@@ -1792,6 +2276,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 24),
             &None,
+            &NullifierPolicy::Abort,
         );
         assert!(run_id > 0);
     }
@@ -1876,6 +2361,82 @@ mod tests {
         assert!(payroll_client.get_emergency_request().is_none());
     }
 
+    #[test]
+    fn test_emergency_approve_pays_out_requested_amount() {
+        let env = Env::default();
+        let (payroll_client, admin, treasury, treasury_owner, _employee) =
+            setup_simple_payroll(&env);
+        let token_client = TokenClient::new(&env, &payroll_client.get_addresses().token);
+
+        let recipient = Address::generate(&env);
+        payroll_client.request_emergency_withdrawal(&treasury_owner, &400_000i128, &recipient);
+        payroll_client.approve_emergency_withdrawal(&admin);
+
+        assert_eq!(token_client.balance(&recipient), 400_000);
+        assert_eq!(token_client.balance(&treasury), 600_000);
+        assert!(payroll_client.get_emergency_request().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Amount exceeds treasury balance")]
+    fn test_emergency_approve_rejects_more_than_balance() {
+        let env = Env::default();
+        let (payroll_client, admin, _treasury, treasury_owner, _employee) =
+            setup_simple_payroll(&env);
+
+        let recipient = Address::generate(&env);
+        payroll_client.request_emergency_withdrawal(&treasury_owner, &1_000_001i128, &recipient);
+        payroll_client.approve_emergency_withdrawal(&admin);
+    }
+
+    #[test]
+    fn test_emergency_withdraw_pays_out_with_owner_cosign() {
+        use soroban_sdk::testutils::Events as _;
+        use soroban_sdk::TryIntoVal;
+
+        let env = Env::default();
+        let (payroll_client, admin, treasury, treasury_owner, _employee) =
+            setup_simple_payroll(&env);
+        let token_client = TokenClient::new(&env, &payroll_client.get_addresses().token);
+
+        let recipient = Address::generate(&env);
+        payroll_client.emergency_withdraw(&admin, &recipient, &400_000i128);
+
+        let auths = env.auths();
+        assert!(auths.iter().any(|(signer, _)| *signer == admin));
+        assert!(auths.iter().any(|(signer, _)| *signer == treasury_owner));
+        assert_eq!(token_client.balance(&recipient), 400_000);
+        assert_eq!(token_client.balance(&treasury), 600_000);
+
+        let event = env.events().all().last().unwrap();
+        let name: Symbol = event.1.get(0).unwrap().try_into_val(&env).unwrap();
+        let company_id: u64 = event.1.get(1).unwrap().try_into_val(&env).unwrap();
+        assert_eq!(name, Symbol::new(&env, "EmergencyWithdraw"));
+        assert_eq!(company_id, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Amount exceeds treasury balance")]
+    fn test_emergency_withdraw_rejects_more_than_balance() {
+        let env = Env::default();
+        let (payroll_client, admin, _treasury, _treasury_owner, _employee) =
+            setup_simple_payroll(&env);
+
+        let recipient = Address::generate(&env);
+        payroll_client.emergency_withdraw(&admin, &recipient, &1_000_001i128);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized")]
+    fn test_emergency_withdraw_rejects_non_admin() {
+        let env = Env::default();
+        let (payroll_client, _admin, _treasury, _treasury_owner, _employee) =
+            setup_simple_payroll(&env);
+
+        let attacker = Address::generate(&env);
+        payroll_client.emergency_withdraw(&attacker, &attacker, &100i128);
+    }
+
     #[test]
     #[should_panic(expected = "A pending emergency request already exists")]
     fn test_duplicate_emergency_request_rejected() {
@@ -1883,16 +2444,9 @@ mod tests {
         let (payroll_client, _admin, _treasury, treasury_owner, _employee) =
             setup_simple_payroll(&env);
 
-        let new_owner = Address::generate(&env);
-        payroll_client.propose_treasury_rotation(&treasury_owner, &new_owner);
-
-        let proposal = payroll_client
-            .get_pending_treasury_rotation()
-            .expect("proposal should exist");
-        assert_eq!(proposal.new_holder, new_owner);
-
-        payroll_client.accept_treasury_rotation(&new_owner);
-        assert!(payroll_client.get_pending_treasury_rotation().is_none());
+        let recipient = Address::generate(&env);
+        payroll_client.request_emergency_withdrawal(&treasury_owner, &100i128, &recipient);
+        payroll_client.request_emergency_withdrawal(&treasury_owner, &200i128, &recipient);
     }
 
     #[test]
@@ -1910,9 +2464,13 @@ mod tests {
     #[test]
     #[should_panic(expected = "A pending admin rotation already exists")]
     fn test_duplicate_admin_rotation_proposal_rejected() {
-        let recipient = Address::generate(&env);
-        payroll_client.request_emergency_withdrawal(&treasury_owner, &100i128, &recipient);
-        payroll_client.request_emergency_withdrawal(&treasury_owner, &200i128, &recipient);
+        let env = Env::default();
+        let (payroll_client, admin, _treasury, _treasury_owner, _employee) =
+            setup_simple_payroll(&env);
+
+        let new_admin = Address::generate(&env);
+        payroll_client.propose_admin_rotation(&admin, &new_admin);
+        payroll_client.propose_admin_rotation(&admin, &new_admin);
     }
 
     // ── Issue #134: reconciliation status tracking ─────────────────────────────
@@ -1931,6 +2489,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 30),
             &None,
+            &NullifierPolicy::Abort,
         );
 
         let run = payroll_client.get_payroll_run(&run_id);
@@ -1954,6 +2513,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 31),
             &None,
+            &NullifierPolicy::Abort,
         );
 
         // Update to Reconciled
@@ -1986,6 +2546,7 @@ mod tests {
             &1000,
             &test_nonce(&env, 32),
             &None,
+            &NullifierPolicy::Abort,
         );
 
         let non_admin = Address::generate(&env);
@@ -2003,9 +2564,6 @@ mod tests {
         let (payroll_client, admin, _treasury, _treasury_owner, _employee) =
             setup_simple_payroll(&env);
 
-        let new_admin = Address::generate(&env);
-        payroll_client.propose_admin_rotation(&admin, &new_admin);
-        payroll_client.propose_admin_rotation(&admin, &new_admin);
         payroll_client.update_reconciliation_status(
             &admin,
             &999u64,
@@ -2109,4 +2667,324 @@ mod tests {
         let result = payroll_client.try_prepare_payroll_run(&p2, &a2, &e2, &1000, &nonce, &None);
         assert!(result.is_err());
     }
+
+    // ── Treasury drawdown log ────────────────────────────────────────────────
+
+    /// Nullifiers are derived from the batch index, so a second batch in the
+    /// same contracts would reuse them. Clear the first batch's nullifier to
+    /// let tests run several batches against one payroll instance.
+    fn clear_first_nullifier(env: &Env, payroll_client: &PayrollClient) {
+        let addrs: ContractAddresses = env.as_contract(&payroll_client.address, || {
+            env.storage().persistent().get(&DataKey::Addresses).unwrap()
+        });
+        env.as_contract(&addrs.commitment, || {
+            env.storage()
+                .persistent()
+                .remove(&salary_commitment::DataKey::Nullifier(BytesN::from_array(
+                    env, &[0u8; 32],
+                )));
+        });
+    }
+
+    #[test]
+    fn test_drawdown_accumulates_within_period() {
+        let env = Env::default();
+        let (payroll_client, _admin, treasury, _treasury_owner, employee) =
+            setup_simple_payroll(&env);
+        let period = payroll_client.current_drawdown_period();
+
+        let (p1, a1, e1) = single_payment_batch(&env, &employee, 1000);
+        payroll_client.batch_process_payroll(
+            &p1,
+            &a1,
+            &e1,
+            &1000,
+            &test_nonce(&env, 50),
+            &None,
+            &NullifierPolicy::Abort,
+        );
+        assert_eq!(payroll_client.get_drawdown(&treasury, &period), 1000);
+
+        clear_first_nullifier(&env, &payroll_client);
+        let (p2, a2, e2) = single_payment_batch(&env, &employee, 250);
+        payroll_client.batch_process_payroll(
+            &p2,
+            &a2,
+            &e2,
+            &250,
+            &test_nonce(&env, 51),
+            &None,
+            &NullifierPolicy::Abort,
+        );
+        assert_eq!(payroll_client.get_drawdown(&treasury, &period), 1250);
+    }
+
+    #[test]
+    fn test_drawdown_tracks_periods_separately() {
+        let env = Env::default();
+        let (payroll_client, _admin, treasury, _treasury_owner, employee) =
+            setup_simple_payroll(&env);
+        payroll_client.set_drawdown_period(&86_400);
+        assert_eq!(payroll_client.get_drawdown_period(), 86_400);
+
+        let first = payroll_client.current_drawdown_period();
+        let (p1, a1, e1) = single_payment_batch(&env, &employee, 1000);
+        payroll_client.batch_process_payroll(
+            &p1,
+            &a1,
+            &e1,
+            &1000,
+            &test_nonce(&env, 52),
+            &None,
+            &NullifierPolicy::Abort,
+        );
+
+        env.ledger().with_mut(|li| li.timestamp += 86_400);
+        let second = payroll_client.current_drawdown_period();
+        assert_eq!(second, first + 1);
+
+        clear_first_nullifier(&env, &payroll_client);
+        let (p2, a2, e2) = single_payment_batch(&env, &employee, 400);
+        payroll_client.batch_process_payroll(
+            &p2,
+            &a2,
+            &e2,
+            &400,
+            &test_nonce(&env, 53),
+            &None,
+            &NullifierPolicy::Abort,
+        );
+
+        assert_eq!(payroll_client.get_drawdown(&treasury, &first), 1000);
+        assert_eq!(payroll_client.get_drawdown(&treasury, &second), 400);
+        let other_treasury = Address::generate(&env);
+        assert_eq!(payroll_client.get_drawdown(&other_treasury, &first), 0);
+    }
+
+    // ── Recipient-bound nullifiers ────────────────────────────────────────────
+
+    #[test]
+    #[should_panic(expected = "Recipient-bound nullifiers required")]
+    fn test_index_nullifier_batch_rejected_when_binding_required() {
+        let env = Env::default();
+        let (payroll_client, _admin, _treasury, _treasury_owner, employee) =
+            setup_simple_payroll(&env);
+        payroll_client.set_require_bound_nullifiers(&true);
+        assert!(payroll_client.get_require_bound_nullifiers());
+
+        let (proofs, amounts, employees) = single_payment_batch(&env, &employee, 1000);
+        payroll_client.batch_process_payroll(
+            &proofs,
+            &amounts,
+            &employees,
+            &1000,
+            &test_nonce(&env, 40),
+            &None,
+            &NullifierPolicy::Abort,
+        );
+    }
+
+    #[test]
+    fn test_bound_batch_pays_employee() {
+        let env = Env::default();
+        let (payroll_client, _admin, _treasury, _treasury_owner, employee) =
+            setup_simple_payroll(&env);
+        payroll_client.set_require_bound_nullifiers(&true);
+
+        let payments = Vec::from_array(
+            &env,
+            [BoundPayment {
+                proof: mock_proof(&env),
+                amount: 1000,
+                employee: employee.clone(),
+                nullifier: BytesN::from_array(&env, &[7u8; 32]),
+                recipient_hash: recipient_hash(&env, &employee),
+            }],
+        );
+        let run_id = payroll_client.batch_process_bound_payroll(
+            &payments,
+            &1000,
+            &test_nonce(&env, 41),
+            &None,
+            &NullifierPolicy::Abort,
+        );
+        assert_eq!(payroll_client.get_payroll_run(&run_id).employee_count, 1);
+    }
+
+    // ── Nullifier policy ─────────────────────────────────────────────────────
+
+    /// Pay `employee` once so that batch-index nullifier 0 is used, then
+    /// return a two-payment batch whose first nullifier is that one.
+    fn batch_with_used_nullifier(
+        env: &Env,
+        payroll_client: &PayrollClient,
+        employee: &Address,
+    ) -> (Vec<BytesN<256>>, Vec<i128>, Vec<Address>) {
+        let (p, a, e) = single_payment_batch(env, employee, 1000);
+        payroll_client.batch_process_payroll(
+            &p,
+            &a,
+            &e,
+            &1000,
+            &test_nonce(env, 60),
+            &None,
+            &NullifierPolicy::Abort,
+        );
+
+        let proofs = Vec::from_array(env, [mock_proof(env), mock_proof(env)]);
+        let amounts = Vec::from_array(env, [300i128, 200]);
+        let employees = Vec::from_array(env, [employee.clone(), employee.clone()]);
+        (proofs, amounts, employees)
+    }
+
+    #[test]
+    #[should_panic(expected = "Nullifier already used")]
+    fn test_abort_policy_panics_on_used_nullifier() {
+        let env = Env::default();
+        let (payroll_client, _admin, _treasury, _treasury_owner, employee) =
+            setup_simple_payroll(&env);
+        let (proofs, amounts, employees) =
+            batch_with_used_nullifier(&env, &payroll_client, &employee);
+
+        payroll_client.batch_process_payroll(
+            &proofs,
+            &amounts,
+            &employees,
+            &500,
+            &test_nonce(&env, 61),
+            &None,
+            &NullifierPolicy::Abort,
+        );
+    }
+
+    #[test]
+    fn test_skip_policy_pays_rest_of_batch() {
+        use soroban_sdk::testutils::Events as _;
+
+        let env = Env::default();
+        let (payroll_client, _admin, _treasury, _treasury_owner, employee) =
+            setup_simple_payroll(&env);
+        let token_client = TokenClient::new(&env, &payroll_client.get_addresses().token);
+        let (proofs, amounts, employees) =
+            batch_with_used_nullifier(&env, &payroll_client, &employee);
+
+        let run_id = payroll_client.batch_process_payroll(
+            &proofs,
+            &amounts,
+            &employees,
+            &500,
+            &test_nonce(&env, 61),
+            &None,
+            &NullifierPolicy::Skip,
+        );
+        let skipped = env
+            .events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| {
+                *topics
+                    == (
+                        symbol_short!("payroll"),
+                        Symbol::new(&env, "payment_skipped"),
+                    )
+                        .into_val(&env)
+            })
+            .count();
+
+        assert_eq!(skipped, 1);
+        // Only the second payment (200) went through.
+        assert_eq!(token_client.balance(&employee), 1200);
+        let run = payroll_client.get_payroll_run(&run_id);
+        assert_eq!(run.total_amount, 200);
+        assert_eq!(run.employee_count, 1);
+    }
+
+    #[test]
+    fn test_second_initialize_returns_already_initialized() {
+        let env = Env::default();
+        let (payroll_client, admin, treasury, treasury_owner, _employee) =
+            setup_simple_payroll(&env);
+        let addrs = payroll_client.get_addresses();
+
+        let result = payroll_client.try_initialize(
+            &admin,
+            &addrs.token,
+            &addrs.verifier,
+            &addrs.commitment,
+            &treasury,
+            &treasury_owner,
+        );
+
+        assert_eq!(result, Err(Ok(InitError::AlreadyInitialized)));
+    }
+
+    // ── Treasury allowance check ─────────────────────────────────────────────
+
+    #[test]
+    fn test_short_allowance_rejects_batch_before_any_transfer() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let verifier_id = env.register_contract(None, ProofVerifier);
+        let verifier_client = ProofVerifierClient::new(&env, &verifier_id);
+        verifier_client.init_verifier_admin(&Address::generate(&env));
+        verifier_client.initialize_verifier(&mock_vk(&env));
+
+        let commitment_id = env.register_contract(None, SalaryCommitmentContract);
+        let commitment_client = SalaryCommitmentContractClient::new(&env, &commitment_id);
+        commitment_client.init_commitment_admin(&Address::generate(&env));
+
+        // A Stellar asset contract, which implements SEP-41 allowances.
+        let treasury = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        soroban_token::StellarAssetClient::new(&env, &token_id).mint(&treasury, &10_000);
+        let token_client = soroban_token::Client::new(&env, &token_id);
+
+        let payroll_id = env.register_contract(None, Payroll);
+        let payroll_client = PayrollClient::new(&env, &payroll_id);
+        payroll_client.initialize(
+            &Address::generate(&env),
+            &token_id,
+            &verifier_id,
+            &commitment_id,
+            &treasury,
+            &Address::generate(&env),
+        );
+        payroll_client.set_require_bound_nullifiers(&false);
+        payroll_client.set_treasury_allowance_check(&true);
+        commitment_client.set_payroll_operator(&payroll_id);
+
+        let employee = Address::generate(&env);
+        commitment_client.store_commitment(&employee, &BytesN::from_array(&env, &[0u8; 32]));
+        let (proofs, amounts, employees) = single_payment_batch(&env, &employee, 1000);
+
+        let expiration = env.ledger().sequence() + 100;
+        token_client.approve(&treasury, &payroll_id, &999, &expiration);
+        let result = payroll_client.try_batch_process_payroll(
+            &proofs,
+            &amounts,
+            &employees,
+            &1000,
+            &test_nonce(&env, 60),
+            &None,
+            &NullifierPolicy::Abort,
+        );
+        assert!(result.is_err());
+        assert_eq!(token_client.balance(&employee), 0);
+        assert_eq!(token_client.balance(&treasury), 10_000);
+
+        token_client.approve(&treasury, &payroll_id, &1000, &expiration);
+        payroll_client.batch_process_payroll(
+            &proofs,
+            &amounts,
+            &employees,
+            &1000,
+            &test_nonce(&env, 61),
+            &None,
+            &NullifierPolicy::Abort,
+        );
+        assert_eq!(token_client.balance(&employee), 1000);
+    }
 }
@@ -1,6 +1,75 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Symbol};
+use salary_commitment::SalaryCommitmentContractClient;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, Map, Symbol, Vec,
+};
+
+// ---------------------------------------------------------------------------
+// Error type
+// ---------------------------------------------------------------------------
+
+/// Errors returned by the registry's entry points.
+#[contracterror]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum RegistryError {
+    /// The commitment being registered differs from the one stored for the
+    /// employee in the company's commitment contract.
+    CommitmentMismatch = 1,
+    /// Adding the employee would exceed the company's `max_employees` cap.
+    CompanyFull = 2,
+    /// The employee's commitment changed less than the company's
+    /// commitment cooldown ago.
+    ChangeTooSoon = 3,
+    /// No company is registered under the given ID.
+    CompanyNotFound = 4,
+    /// The employee is not registered under the company.
+    EmployeeNotFound = 5,
+    /// The caller is not the address the operation must be signed by.
+    NotAuthorized = 6,
+    /// `deactivate_employee` was called for an employee who is already
+    /// inactive.
+    EmployeeInactive = 7,
+    /// `reactivate_employee` was called for an employee who is already
+    /// active.
+    EmployeeActive = 8,
+    /// The company has been deactivated with `deactivate_company`.
+    CompanyInactive = 9,
+    /// `reactivate_company` was called for a company that is already active.
+    CompanyActive = 10,
+    /// `batch_add_employees` was given more than `MAX_ENROLMENT_BATCH`
+    /// entries.
+    BatchTooLarge = 11,
+    /// `batch_add_employees` was given an employee who is already active,
+    /// or the same employee twice, or `merge_companies` found an employee
+    /// registered under both companies.
+    DuplicateEmployee = 12,
+    /// An admin or treasury rotation is already pending for the company.
+    RotationPending = 13,
+    /// No admin or treasury rotation is pending for the company.
+    NoPendingRotation = 14,
+    /// `set_salary_approver` was given the company admin.
+    ApproverIsAdmin = 15,
+    /// The company has no salary approver.
+    ApproverNotSet = 16,
+    /// The company has no commitment contract.
+    CommitmentContractNotSet = 17,
+    /// A salary change is already pending for the employee.
+    SalaryChangePending = 18,
+    /// No salary change is pending for the employee.
+    NoPendingSalaryChange = 19,
+    /// `merge_companies` was given the same company twice.
+    SelfMerge = 20,
+    /// The company has been merged into another one.
+    CompanyMerged = 21,
+    /// `set_max_employees` was given a cap below the current employee count.
+    CapBelowCount = 22,
+    /// `record_payment` was given a timestamp after the current ledger time.
+    TimestampInFuture = 23,
+    /// `remove_role` was given an address holding no granted role.
+    RoleNotFound = 24,
+}
 
 // ---------------------------------------------------------------------------
 // Data types
@@ -33,6 +102,35 @@ pub enum EmployeeStatus {
     Incomplete = 2,
 }
 
+/// Employee cap applied to companies that have not called
+/// `set_max_employees`.
+pub const DEFAULT_MAX_EMPLOYEES: u32 = 10_000;
+
+/// Most companies `list_companies` returns per page.
+pub const MAX_COMPANY_PAGE: u32 = 50;
+
+/// Most employees `get_company_employees` returns per page.
+pub const MAX_EMPLOYEE_PAGE: u32 = 100;
+
+/// Most employees `batch_add_employees` enrols in one call, keeping it
+/// under the per-transaction instruction limit.
+pub const MAX_ENROLMENT_BATCH: u32 = 50;
+
+/// Reason recorded when an employee is deactivated through
+/// `set_employee_status` rather than `deactivate_employee`.
+pub const DEFAULT_DEACTIVATION_REASON: &str = "unspecified";
+
+/// Why and when an employee was last deactivated.
+///
+/// Kept alongside the `Inactive` status for HR audits and cleared when the
+/// employee is reactivated.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmployeeDeactivation {
+    pub reason: Symbol,
+    pub deactivated_at: u64,
+}
+
 // ── Issue #91: privileged-role rotation ──────────────────────────────────────
 
 /// Pending two-step company admin or treasury rotation.
@@ -47,14 +145,65 @@ pub struct PendingCompanyRotation {
     pub proposed_at: u64,
 }
 
+// ── Salary-change approval ───────────────────────────────────────────────────
+
+/// Pending replacement of an employee's commitment.
+///
+/// The company admin proposes the new commitment and a distinct salary
+/// approver must sign off before it is applied to the registry and the
+/// commitment contract.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PendingSalaryChange {
+    pub new_commitment: BytesN<32>,
+    pub proposed_by: Address,
+    pub proposed_at: u64,
+}
+
+// ── Company roles ────────────────────────────────────────────────────────────
+
+/// Delegated authority within a company.
+///
+/// The company admin is always a `Super` admin. An `Hr` admin may enrol
+/// employees and propose commitment changes; a `Finance` admin may change
+/// the treasury. A granted `Super` admin may do both, and may also replace a
+/// commitment without approval.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum AdminRole {
+    Super = 0,
+    Hr = 1,
+    Finance = 2,
+}
+
 /// Storage key space for the payroll registry.
 ///
 /// - `Company(u64)`               → `CompanyInfo`              (Persistent)
 /// - `Employee(u64, Address)`     → `BytesN<32>`               (Persistent, commitment)
 /// - `EmpStatus(u64, Address)`    → `EmployeeStatus`           (Persistent, eligibility)
+/// - `Deactivation(u64, Address)` → `EmployeeDeactivation`     (Persistent, while inactive)
 /// - `CompanySequence`            → `u64`                      (Persistent, counter)
 /// - `PendingAdminRotation(u64)`  → `PendingCompanyRotation`   (Persistent, issue #91)
 /// - `PendingTreasuryRotation(u64)` → `PendingCompanyRotation` (Persistent, issue #91)
+/// - `SalaryApprover(u64)`        → `Address`                  (Persistent)
+/// - `CommitmentContract(u64)`    → `Address`                  (Persistent)
+/// - `PendingSalaryChange(u64, Address)` → `PendingSalaryChange` (Persistent)
+/// - `RosterSlot(u64, u32)`       → `Address`                  (Persistent, roster entry)
+/// - `RosterIndex(u64, Address)`  → `u32`                      (Persistent, roster position)
+/// - `RosterLen(u64)`             → `u32`                      (Persistent, roster size)
+/// - `ActiveCount(u64)`           → `u32`                      (Persistent, `Active` employees)
+/// - `MergedInto(u64)`            → `u64`                      (Persistent, set on merge source)
+/// - `RequireCommitmentMatch(u64)` → `bool`                    (Persistent)
+/// - `MaxEmployees(u64)`          → `u32`                      (Persistent, roster cap)
+/// - `RequireConsent(u64)`        → `bool`                     (Persistent)
+/// - `ConsentedAt(u64, Address)`  → `u64`                      (Persistent, consent timestamp)
+/// - `CommitmentCooldown(u64)`    → `u64`                      (Persistent, seconds)
+/// - `LastCommitmentChange(u64, Address)` → `u64`              (Persistent, change timestamp)
+/// - `LastPayment(u64, Address)`  → `u64`                      (Persistent, payment timestamp)
+/// - `CommitmentVersion(u64, Address)` → `u32`                 (Persistent, 1 on enrolment)
+/// - `CompanyInactive(u64)`       → `u64`                      (Persistent, deactivation timestamp)
+/// - `Role(u64, Address)`         → `AdminRole`              (Persistent, granted role)
 #[contracttype]
 pub enum DataKey {
     Company(u64),
@@ -62,10 +211,48 @@ pub enum DataKey {
     CompanySequence,
     /// Per-employee eligibility status (issue #90).
     EmpStatus(u64, Address),
+    /// Reason and time of the employee's current deactivation.
+    Deactivation(u64, Address),
     /// Pending admin rotation for a company (issue #91).
     PendingAdminRotation(u64),
     /// Pending treasury rotation for a company (issue #91).
     PendingTreasuryRotation(u64),
+    /// Second signer required for salary changes.
+    SalaryApprover(u64),
+    /// Commitment contract that approved salary changes are applied to.
+    CommitmentContract(u64),
+    /// Proposed commitment awaiting approval, per employee.
+    PendingSalaryChange(u64, Address),
+    /// Employee at a position of a company's roster, `0..RosterLen`.
+    RosterSlot(u64, u32),
+    /// Position of an employee in their company's roster.
+    RosterIndex(u64, Address),
+    /// Number of employees on a company's roster.
+    RosterLen(u64),
+    /// Number of a company's employees whose status is `Active`.
+    ActiveCount(u64),
+    /// Company a merged-away company's employees were moved to.
+    MergedInto(u64),
+    /// Whether `add_employee` checks the commitment contract's stored value.
+    RequireCommitmentMatch(u64),
+    /// Maximum roster size; `DEFAULT_MAX_EMPLOYEES` when unset.
+    MaxEmployees(u64),
+    /// Whether `add_employee` needs the employee's own signature.
+    RequireConsent(u64),
+    /// Ledger time the employee consented to enrollment.
+    ConsentedAt(u64, Address),
+    /// Minimum seconds between `update_commitment` calls per employee.
+    CommitmentCooldown(u64),
+    /// Ledger time of the employee's last `update_commitment`.
+    LastCommitmentChange(u64, Address),
+    /// Time of the employee's last payment, as reported by `record_payment`.
+    LastPayment(u64, Address),
+    /// Version of the employee's registry commitment, bumped on every change.
+    CommitmentVersion(u64, Address),
+    /// Set while the company is deactivated; holds when it was deactivated.
+    CompanyInactive(u64),
+    /// Role granted to an address with `add_role`.
+    Role(u64, Address),
 }
 
 // ---------------------------------------------------------------------------
@@ -78,34 +265,74 @@ pub trait PayrollRegistryTrait {
     fn register_company(env: Env, admin: Address, treasury: Address) -> u64;
 
     /// Add an employee commitment under a company.
-    /// Requires authorisation from the company admin.
+    /// Requires authorisation from `caller`, who must hold `Hr` or `Super`,
+    /// and also from the employee while the company requires consent.
     /// The employee's initial status is set to `Active`.
-    fn add_employee(env: Env, company_id: u64, employee: Address, commitment: BytesN<32>);
+    /// Fails with `RegistryError::NotAuthorized` for any other caller, and
+    /// with `RegistryError::CompanyFull` if a new employee would take the
+    /// roster past the company's `max_employees` cap.
+    fn add_employee(
+        env: Env,
+        company_id: u64,
+        caller: Address,
+        employee: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), RegistryError>;
 
     /// Permanently remove an employee record from storage.
     /// Requires authorisation from the company admin.
-    fn remove_employee(env: Env, company_id: u64, employee: Address);
-
-    /// Replace an employee's active Poseidon commitment.
-    /// Requires authorisation from the company admin.
-    fn update_commitment(env: Env, company_id: u64, employee: Address, new_commitment: BytesN<32>);
+    fn remove_employee(env: Env, company_id: u64, employee: Address) -> Result<(), RegistryError>;
+
+    /// Replace an employee's active Poseidon commitment without approval.
+    /// Requires authorisation from `caller`, who must hold `Super`; `Hr`
+    /// admins go through `propose_salary_change` instead.
+    /// Fails with `RegistryError::NotAuthorized` for any other caller, and
+    /// with `RegistryError::ChangeTooSoon` if the employee's commitment was
+    /// updated less than the company's commitment cooldown ago.
+    fn update_commitment(
+        env: Env,
+        company_id: u64,
+        caller: Address,
+        employee: Address,
+        new_commitment: BytesN<32>,
+    ) -> Result<(), RegistryError>;
 
     /// Read company metadata by company ID.
-    fn get_company(env: Env, company_id: u64) -> CompanyInfo;
+    fn get_company(env: Env, company_id: u64) -> Result<CompanyInfo, RegistryError>;
 
     /// Read an employee's active commitment under a company.
-    fn get_commitment(env: Env, company_id: u64, employee: Address) -> BytesN<32>;
+    fn get_commitment(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+    ) -> Result<BytesN<32>, RegistryError>;
+
+    /// Option-returning variant of `get_company`.
+    /// Returns `None` if no company is registered under `company_id`.
+    fn find_company(env: Env, company_id: u64) -> Option<CompanyInfo>;
+
+    /// Page over registered companies in ID order, starting at ID `cursor`.
+    /// Returns up to `limit` (at most `MAX_COMPANY_PAGE`) companies, skipping
+    /// merged ones, and the cursor for the next page, or `None` once every
+    /// ID has been visited.
+    fn list_companies(env: Env, cursor: u32, limit: u32) -> (Vec<(u64, CompanyInfo)>, Option<u32>);
+
+    /// Option-returning variant of `get_commitment`.
+    /// Returns `None` if the employee is not registered under the company.
+    fn find_employee(env: Env, company_id: u64, employee: Address) -> Option<BytesN<32>>;
 
     // ── Issue #90: employee eligibility ──────────────────────────────────────
 
     /// Set the eligibility status for a registered employee.
     /// Requires authorisation from the company admin.
+    /// Setting `Inactive` records `DEFAULT_DEACTIVATION_REASON`; use
+    /// `deactivate_employee` to give a specific reason.
     fn set_employee_status(
         env: Env,
         company_id: u64,
         employee: Address,
         status: EmployeeStatus,
-    );
+    ) -> Result<(), RegistryError>;
 
     /// Return the eligibility status of an employee.
     /// Returns `Incomplete` if no explicit status has been set.
@@ -114,16 +341,62 @@ pub trait PayrollRegistryTrait {
     /// Return `true` iff the employee is registered AND has `Active` status.
     fn is_eligible(env: Env, company_id: u64, employee: Address) -> bool;
 
+    /// Mark an employee `Inactive` and record why (e.g. `resigned`,
+    /// `terminated`, `leave`). Requires authorisation from the company admin.
+    /// Fails with `RegistryError::EmployeeInactive` if already inactive.
+    fn deactivate_employee(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+        reason: Symbol,
+    ) -> Result<(), RegistryError>;
+
+    /// Mark a registered employee `Active` again, e.g. on rehire or return
+    /// from leave, clearing the deactivation record. Requires authorisation
+    /// from the company admin. Fails with `RegistryError::EmployeeActive` if
+    /// the employee is already active.
+    fn reactivate_employee(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+    ) -> Result<(), RegistryError>;
+
+    /// Return the reason and time of the employee's current deactivation.
+    /// Returns `None` if the employee is not deactivated.
+    fn get_deactivation(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+    ) -> Option<EmployeeDeactivation>;
+
     // ── Issue #91: company-level admin/treasury rotation ─────────────────────
 
     /// Propose a new company admin (step 1 of 2).
-    fn propose_admin_rotation(env: Env, company_id: u64, current_admin: Address, new_admin: Address);
+    fn propose_admin_rotation(
+        env: Env,
+        company_id: u64,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), RegistryError>;
 
     /// Accept a pending admin rotation (step 2 of 2).
-    fn accept_admin_rotation(env: Env, company_id: u64, new_admin: Address);
+    fn accept_admin_rotation(
+        env: Env,
+        company_id: u64,
+        new_admin: Address,
+    ) -> Result<(), RegistryError>;
 
     /// Cancel a pending admin rotation.
-    fn cancel_admin_rotation(env: Env, company_id: u64, current_admin: Address);
+    fn cancel_admin_rotation(
+        env: Env,
+        company_id: u64,
+        current_admin: Address,
+    ) -> Result<(), RegistryError>;
+
+    /// Hand the company to `new_admin` in one step, discarding any pending
+    /// admin rotation. Requires authorisation from the current admin. Prefer
+    /// the propose/accept flow unless `new_admin` is known to be correct.
+    fn transfer_admin(env: Env, company_id: u64, new_admin: Address) -> Result<(), RegistryError>;
 
     /// Propose a new company treasury address (step 1 of 2).
     fn propose_treasury_rotation(
@@ -131,10 +404,258 @@ pub trait PayrollRegistryTrait {
         company_id: u64,
         current_admin: Address,
         new_treasury: Address,
-    );
+    ) -> Result<(), RegistryError>;
 
     /// Accept a pending treasury rotation (step 2 of 2).
-    fn accept_treasury_rotation(env: Env, company_id: u64, new_treasury: Address);
+    fn accept_treasury_rotation(
+        env: Env,
+        company_id: u64,
+        new_treasury: Address,
+    ) -> Result<(), RegistryError>;
+
+    /// Point the company at `new_treasury` in one step, discarding any
+    /// pending treasury rotation. Requires authorisation from `caller`, who
+    /// must hold `Finance` or `Super`, and fails with
+    /// `RegistryError::NotAuthorized` otherwise. The payment executor reads
+    /// the treasury on every payment, so the next payment debits
+    /// `new_treasury`.
+    fn update_treasury(
+        env: Env,
+        company_id: u64,
+        caller: Address,
+        new_treasury: Address,
+    ) -> Result<(), RegistryError>;
+
+    // ── Salary-change approval ───────────────────────────────────────────────
+
+    /// Set the address that must approve salary changes.
+    /// Requires authorisation from the company admin; the approver must be a
+    /// different address.
+    fn set_salary_approver(
+        env: Env,
+        company_id: u64,
+        approver: Address,
+    ) -> Result<(), RegistryError>;
+
+    /// Return the company's salary approver, if one has been set.
+    fn get_salary_approver(env: Env, company_id: u64) -> Option<Address>;
+
+    /// Set the commitment contract that approved salary changes are applied to.
+    /// Requires authorisation from the company admin. The registry must be the
+    /// commitment contract's admin for the update to be authorised.
+    fn set_commitment_contract(
+        env: Env,
+        company_id: u64,
+        commitment_contract: Address,
+    ) -> Result<(), RegistryError>;
+
+    /// Enable or disable commitment matching for a company. While enabled,
+    /// `add_employee` fails with `RegistryError::CommitmentMismatch` unless
+    /// the commitment contract already stores the same commitment for the
+    /// employee. Requires authorisation from the company admin; enabling it
+    /// requires the commitment contract to be set.
+    fn set_require_commitment_match(
+        env: Env,
+        company_id: u64,
+        enabled: bool,
+    ) -> Result<(), RegistryError>;
+
+    /// Return whether `add_employee` checks commitments against the
+    /// commitment contract.
+    fn get_require_commitment_match(env: Env, company_id: u64) -> bool;
+
+    /// Propose a new commitment for an employee (step 1 of 2).
+    /// Requires authorisation from `caller`, who must hold `Hr` or `Super`,
+    /// and fails with `RegistryError::NotAuthorized` otherwise.
+    fn propose_salary_change(
+        env: Env,
+        company_id: u64,
+        caller: Address,
+        employee: Address,
+        new_commitment: BytesN<32>,
+    ) -> Result<(), RegistryError>;
+
+    /// Approve a pending salary change (step 2 of 2).
+    /// The approver must be the company's salary approver and must not be the
+    /// proposer. Applies the commitment to the registry and the commitment
+    /// contract.
+    fn approve_salary_change(
+        env: Env,
+        company_id: u64,
+        approver: Address,
+        employee: Address,
+    ) -> Result<(), RegistryError>;
+
+    /// Return the pending salary change for an employee, if any.
+    fn get_pending_salary_change(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+    ) -> Option<PendingSalaryChange>;
+
+    // ── Company merge ────────────────────────────────────────────────────────
+
+    /// Move every employee of `source_id` to `target_id` and deactivate
+    /// `source_id`. Requires authorisation from both company admins.
+    /// Commitments, statuses, deactivation and consent records move with
+    /// each employee; pending salary changes are discarded. Fails with
+    /// `RegistryError::DuplicateEmployee`, moving no one, if any employee is
+    /// already registered under the target.
+    fn merge_companies(env: Env, source_id: u64, target_id: u64) -> Result<(), RegistryError>;
+
+    /// Return the company a merged company's employees were moved to.
+    /// Returns `None` if the company has not been merged.
+    fn get_merged_into(env: Env, company_id: u64) -> Option<u64>;
+
+    /// Return the addresses of the employees registered under a company.
+    fn get_employees(env: Env, company_id: u64) -> Vec<Address>;
+
+    /// Return the number of employees registered under a company.
+    fn get_employee_count(env: Env, company_id: u64) -> u32;
+
+    /// Return the number of registered employees with `Active` status.
+    /// Reads the `ActiveCount` every status change keeps in step.
+    fn get_active_employee_count(env: Env, company_id: u64) -> u32;
+
+    /// Page over a company's roster in enrollment order, returning up to
+    /// `limit` (at most `MAX_EMPLOYEE_PAGE`) addresses starting at `offset`.
+    /// Returns an empty page once `offset` is past the end.
+    fn get_company_employees(env: Env, company_id: u64, offset: u32, limit: u32) -> Vec<Address>;
+
+    // ── Bulk onboarding ──────────────────────────────────────────────────────
+
+    /// Enrol up to `MAX_ENROLMENT_BATCH` employees at once, applying the
+    /// same checks and events as `add_employee` to each entry. Requires
+    /// authorisation from the company admin.
+    ///
+    /// All or nothing: fails with `RegistryError::BatchTooLarge` for an
+    /// oversized batch and `RegistryError::DuplicateEmployee` if an entry
+    /// is already an active employee or repeats an earlier entry, and any
+    /// failure leaves the roster untouched.
+    fn batch_add_employees(
+        env: Env,
+        company_id: u64,
+        entries: Vec<(Address, BytesN<32>)>,
+    ) -> Result<(), RegistryError>;
+
+    // ── Roster cap ───────────────────────────────────────────────────────────
+
+    /// Set the maximum number of employees the company may register.
+    /// Requires authorisation from the company admin. Fails with
+    /// `RegistryError::CapBelowCount` if `max` is below the current employee
+    /// count.
+    fn set_max_employees(env: Env, company_id: u64, max: u32) -> Result<(), RegistryError>;
+
+    /// Return the company's employee cap (`DEFAULT_MAX_EMPLOYEES` unless set).
+    fn get_max_employees(env: Env, company_id: u64) -> u32;
+
+    // ── Employee consent ─────────────────────────────────────────────────────
+
+    /// Require (or stop requiring) the employee's own authorisation in
+    /// `add_employee`, for jurisdictions where salary data may only be
+    /// committed on-chain with the employee's consent.
+    /// Requires authorisation from the company admin.
+    fn set_require_consent(env: Env, company_id: u64, enabled: bool) -> Result<(), RegistryError>;
+
+    /// Return whether `add_employee` requires the employee's consent.
+    fn get_require_consent(env: Env, company_id: u64) -> bool;
+
+    /// Return the ledger timestamp at which the employee consented to
+    /// enrollment. Returns `None` if they were enrolled without consent.
+    fn get_consented_at(env: Env, company_id: u64, employee: Address) -> Option<u64>;
+
+    // ── Commitment cooldown ──────────────────────────────────────────────────
+
+    /// Set the minimum time between `update_commitment` calls for the same
+    /// employee, e.g. one pay period. Zero removes the cooldown.
+    /// Requires authorisation from the company admin.
+    fn set_commitment_cooldown(
+        env: Env,
+        company_id: u64,
+        cooldown_secs: u64,
+    ) -> Result<(), RegistryError>;
+
+    /// Return the company's commitment cooldown in seconds (0 if unset).
+    fn get_commitment_cooldown(env: Env, company_id: u64) -> u64;
+
+    /// Return the ledger timestamp of the employee's last
+    /// `update_commitment`. Returns `None` if it was never updated.
+    fn get_last_commitment_change(env: Env, company_id: u64, employee: Address) -> Option<u64>;
+
+    /// Version of the employee's registry commitment: 1 on enrolment,
+    /// increased by every later change. Returns 0 if not enrolled.
+    fn get_commitment_version(env: Env, company_id: u64, employee: Address) -> u32;
+
+    /// `update_commitment` without the cooldown check, for corrections that
+    /// cannot wait. Requires authorisation from the company admin and emits
+    /// `CommitmentCooldownOverridden` so the override shows in the audit
+    /// trail.
+    fn force_update_commitment(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+        new_commitment: BytesN<32>,
+    ) -> Result<(), RegistryError>;
+
+    // ── Payment timestamps ───────────────────────────────────────────────────
+
+    /// Record that `employee` was paid at `timestamp`. The payment executor
+    /// calls this after each payment. Requires authorisation from the
+    /// company admin. Fails with `RegistryError::EmployeeNotFound` if the
+    /// employee is not enrolled and `RegistryError::TimestampInFuture` if
+    /// `timestamp` is in the future.
+    fn record_payment(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+        timestamp: u64,
+    ) -> Result<(), RegistryError>;
+
+    /// When `employee` was last paid, if `record_payment` has been called.
+    fn get_last_payment_timestamp(env: Env, company_id: u64, employee: Address) -> Option<u64>;
+
+    // ── Company deactivation ─────────────────────────────────────────────────
+
+    /// Deactivate the company, e.g. when its admin key has leaked. Until it
+    /// is reactivated, employee enrolment, commitment changes and payments
+    /// fail with `RegistryError::CompanyInactive` (the payment executor
+    /// checks `is_company_active`). Admin and treasury rotation keep
+    /// working. Requires authorisation from the company admin.
+    fn deactivate_company(env: Env, company_id: u64) -> Result<(), RegistryError>;
+
+    /// Lift a `deactivate_company`. Requires authorisation from the company
+    /// admin. Fails with `RegistryError::CompanyActive` if the company is
+    /// not deactivated.
+    fn reactivate_company(env: Env, company_id: u64) -> Result<(), RegistryError>;
+
+    /// `false` while the company is deactivated. Fails with
+    /// `RegistryError::CompanyNotFound` for an unknown company.
+    fn is_company_active(env: Env, company_id: u64) -> Result<bool, RegistryError>;
+
+    // ── Company roles ────────────────────────────────────────────────────────
+
+    /// Grant `role` to `address`, replacing any role it already holds.
+    /// Requires authorisation from the company admin.
+    fn add_role(
+        env: Env,
+        company_id: u64,
+        address: Address,
+        role: AdminRole,
+    ) -> Result<(), RegistryError>;
+
+    /// Revoke the role granted to `address`. Requires authorisation from
+    /// the company admin. Fails with `RegistryError::RoleNotFound` if
+    /// `address` holds no granted role; the company admin's own `Super` role
+    /// cannot be revoked.
+    fn remove_role(env: Env, company_id: u64, address: Address) -> Result<(), RegistryError>;
+
+    /// Role held by `address`: `Super` for the company admin, otherwise
+    /// the granted role, if any.
+    fn get_role(
+        env: Env,
+        company_id: u64,
+        address: Address,
+    ) -> Result<Option<AdminRole>, RegistryError>;
 }
 
 // ---------------------------------------------------------------------------
@@ -176,41 +697,19 @@ impl PayrollRegistryTrait for PayrollRegistry {
         id
     }
 
-    fn add_employee(env: Env, company_id: u64, employee: Address, commitment: BytesN<32>) {
-        let info: CompanyInfo = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Company(company_id))
-            .expect("Company not found");
-
-        info.admin.require_auth();
-
-        let emp = employee.clone();
-        env.storage()
-            .persistent()
-            .set(&DataKey::Employee(company_id, employee.clone()), &commitment);
-
-        // Default status for newly registered employees is Active (issue #90).
-        env.storage().persistent().set(
-            &DataKey::EmpStatus(company_id, employee),
-            &EmployeeStatus::Active,
-        );
-            .set(&DataKey::Employee(company_id, emp), &commitment);
-
-        env.events().publish(
-            (Symbol::new(&env, "EmployeeAdded"), company_id, employee),
-            (commitment,),
-        );
-        // topics : ("EmployeeAdded", company_id, employee)
-        // data   : (commitment,)
+    fn add_employee(
+        env: Env,
+        company_id: u64,
+        caller: Address,
+        employee: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        Self::require_role(&env, company_id, &caller, AdminRole::Hr)?;
+        Self::enrol_employee(&env, company_id, employee, commitment)
     }
 
-    fn remove_employee(env: Env, company_id: u64, employee: Address) {
-        let info: CompanyInfo = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Company(company_id))
-            .expect("Company not found");
+    fn remove_employee(env: Env, company_id: u64, employee: Address) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
 
         info.admin.require_auth();
 
@@ -218,6 +717,20 @@ impl PayrollRegistryTrait for PayrollRegistry {
         env.storage()
             .persistent()
             .remove(&DataKey::Employee(company_id, emp));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ConsentedAt(company_id, employee.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::LastCommitmentChange(company_id, employee.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::LastPayment(company_id, employee.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::CommitmentVersion(company_id, employee.clone()));
+        Self::write_status(&env, company_id, &employee, None);
+        Self::remove_from_roster(&env, company_id, &employee);
 
         env.events().publish(
             (Symbol::new(&env, "EmployeeRemoved"), company_id, employee),
@@ -225,45 +738,68 @@ impl PayrollRegistryTrait for PayrollRegistry {
         );
         // topics : ("EmployeeRemoved", company_id, employee)
         // data   : ()
+        Ok(())
     }
 
-    fn update_commitment(env: Env, company_id: u64, employee: Address, new_commitment: BytesN<32>) {
-        let info: CompanyInfo = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Company(company_id))
-            .expect("Company not found");
-
-        info.admin.require_auth();
-
-        let emp = employee.clone();
-        let key = DataKey::Employee(company_id, emp);
-        if !env.storage().persistent().has(&key) {
-            panic!("Employee not found");
-        }
+    fn update_commitment(
+        env: Env,
+        company_id: u64,
+        caller: Address,
+        employee: Address,
+        new_commitment: BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        Self::require_role(&env, company_id, &caller, AdminRole::Super)?;
+        Self::replace_commitment(&env, company_id, employee, new_commitment, false)
+    }
 
-        env.storage().persistent().set(&key, &new_commitment);
+    fn get_company(env: Env, company_id: u64) -> Result<CompanyInfo, RegistryError> {
+        Self::load_company(&env, company_id)
+    }
 
-        env.events().publish(
-            (Symbol::new(&env, "CommitmentUpdated"), company_id, employee),
-            (new_commitment,),
-        );
-        // topics : ("CommitmentUpdated", company_id, employee)
-        // data   : (new_commitment,)
+    fn get_commitment(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+    ) -> Result<BytesN<32>, RegistryError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Employee(company_id, employee))
+            .ok_or(RegistryError::EmployeeNotFound)
     }
 
-    fn get_company(env: Env, company_id: u64) -> CompanyInfo {
+    fn find_company(env: Env, company_id: u64) -> Option<CompanyInfo> {
         env.storage()
             .persistent()
             .get(&DataKey::Company(company_id))
-            .expect("Company not found")
     }
 
-    fn get_commitment(env: Env, company_id: u64, employee: Address) -> BytesN<32> {
+    fn list_companies(env: Env, cursor: u32, limit: u32) -> (Vec<(u64, CompanyInfo)>, Option<u32>) {
+        let end: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CompanySequence)
+            .unwrap_or(0u64);
+        let limit = limit.min(MAX_COMPANY_PAGE);
+
+        let mut page = Vec::new(&env);
+        let mut id = u64::from(cursor);
+        while id < end && page.len() < limit {
+            if !Self::is_merged(&env, id) {
+                if let Some(info) = Self::find_company(env.clone(), id) {
+                    page.push_back((id, info));
+                }
+            }
+            id += 1;
+        }
+
+        let next = if id < end { Some(id as u32) } else { None };
+        (page, next)
+    }
+
+    fn find_employee(env: Env, company_id: u64, employee: Address) -> Option<BytesN<32>> {
         env.storage()
             .persistent()
             .get(&DataKey::Employee(company_id, employee))
-            .expect("Employee not found")
     }
 
     // ── Issue #90: employee eligibility ──────────────────────────────────────
@@ -273,12 +809,8 @@ impl PayrollRegistryTrait for PayrollRegistry {
         company_id: u64,
         employee: Address,
         status: EmployeeStatus,
-    ) {
-        let info: CompanyInfo = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Company(company_id))
-            .expect("Company not found");
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
         info.admin.require_auth();
 
         if !env
@@ -286,12 +818,20 @@ impl PayrollRegistryTrait for PayrollRegistry {
             .persistent()
             .has(&DataKey::Employee(company_id, employee.clone()))
         {
-            panic!("Employee not found");
+            return Err(RegistryError::EmployeeNotFound);
+        }
+
+        if status == EmployeeStatus::Inactive {
+            let reason = Symbol::new(&env, DEFAULT_DEACTIVATION_REASON);
+            Self::record_deactivation(&env, company_id, employee, reason);
+            return Ok(());
         }
 
         env.storage()
             .persistent()
-            .set(&DataKey::EmpStatus(company_id, employee), &status);
+            .remove(&DataKey::Deactivation(company_id, employee.clone()));
+        Self::write_status(&env, company_id, &employee, Some(status));
+        Ok(())
     }
 
     fn get_employee_status(env: Env, company_id: u64, employee: Address) -> EmployeeStatus {
@@ -317,6 +857,77 @@ impl PayrollRegistryTrait for PayrollRegistry {
         status == EmployeeStatus::Active
     }
 
+    fn deactivate_employee(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+        reason: Symbol,
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Employee(company_id, employee.clone()))
+        {
+            return Err(RegistryError::EmployeeNotFound);
+        }
+        if Self::get_employee_status(env.clone(), company_id, employee.clone())
+            == EmployeeStatus::Inactive
+        {
+            return Err(RegistryError::EmployeeInactive);
+        }
+
+        Self::record_deactivation(&env, company_id, employee, reason);
+        Ok(())
+    }
+
+    fn reactivate_employee(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Employee(company_id, employee.clone()))
+        {
+            return Err(RegistryError::EmployeeNotFound);
+        }
+        if Self::get_employee_status(env.clone(), company_id, employee.clone())
+            == EmployeeStatus::Active
+        {
+            return Err(RegistryError::EmployeeActive);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Deactivation(company_id, employee.clone()));
+        Self::write_status(&env, company_id, &employee, Some(EmployeeStatus::Active));
+
+        env.events().publish(
+            (Symbol::new(&env, "EmployeeReactivated"), company_id),
+            (employee, env.ledger().timestamp()),
+        );
+        // topics : ("EmployeeReactivated", company_id)
+        // data   : (employee, reactivated_at)
+        Ok(())
+    }
+
+    fn get_deactivation(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+    ) -> Option<EmployeeDeactivation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Deactivation(company_id, employee))
+    }
+
     // ── Issue #91: company-level admin/treasury rotation ─────────────────────
 
     fn propose_admin_rotation(
@@ -324,14 +935,10 @@ impl PayrollRegistryTrait for PayrollRegistry {
         company_id: u64,
         current_admin: Address,
         new_admin: Address,
-    ) {
-        let info: CompanyInfo = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Company(company_id))
-            .expect("Company not found");
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
         if current_admin != info.admin {
-            panic!("Unauthorized: caller is not the company admin");
+            return Err(RegistryError::NotAuthorized);
         }
         current_admin.require_auth();
 
@@ -340,7 +947,7 @@ impl PayrollRegistryTrait for PayrollRegistry {
             .persistent()
             .has(&DataKey::PendingAdminRotation(company_id))
         {
-            panic!("A pending admin rotation already exists for this company");
+            return Err(RegistryError::RotationPending);
         }
 
         let proposal = PendingCompanyRotation {
@@ -351,43 +958,39 @@ impl PayrollRegistryTrait for PayrollRegistry {
         env.storage()
             .persistent()
             .set(&DataKey::PendingAdminRotation(company_id), &proposal);
+        Ok(())
     }
 
-    fn accept_admin_rotation(env: Env, company_id: u64, new_admin: Address) {
+    fn accept_admin_rotation(
+        env: Env,
+        company_id: u64,
+        new_admin: Address,
+    ) -> Result<(), RegistryError> {
         let proposal: PendingCompanyRotation = env
             .storage()
             .persistent()
             .get(&DataKey::PendingAdminRotation(company_id))
-            .expect("No pending admin rotation for this company");
+            .ok_or(RegistryError::NoPendingRotation)?;
 
         if new_admin != proposal.new_holder {
-            panic!("Unauthorized: caller is not the proposed admin");
+            return Err(RegistryError::NotAuthorized);
         }
         new_admin.require_auth();
 
-        let mut info: CompanyInfo = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Company(company_id))
-            .expect("Company not found");
+        let info = Self::load_company(&env, company_id)?;
 
-        info.admin = new_admin;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Company(company_id), &info);
-        env.storage()
-            .persistent()
-            .remove(&DataKey::PendingAdminRotation(company_id));
+        Self::set_company_admin(&env, company_id, info, new_admin);
+        Ok(())
     }
 
-    fn cancel_admin_rotation(env: Env, company_id: u64, current_admin: Address) {
-        let info: CompanyInfo = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Company(company_id))
-            .expect("Company not found");
+    fn cancel_admin_rotation(
+        env: Env,
+        company_id: u64,
+        current_admin: Address,
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
         if current_admin != info.admin {
-            panic!("Unauthorized");
+            return Err(RegistryError::NotAuthorized);
         }
         current_admin.require_auth();
 
@@ -396,11 +999,20 @@ impl PayrollRegistryTrait for PayrollRegistry {
             .persistent()
             .has(&DataKey::PendingAdminRotation(company_id))
         {
-            panic!("No pending admin rotation to cancel");
+            return Err(RegistryError::NoPendingRotation);
         }
         env.storage()
             .persistent()
             .remove(&DataKey::PendingAdminRotation(company_id));
+        Ok(())
+    }
+
+    fn transfer_admin(env: Env, company_id: u64, new_admin: Address) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        Self::set_company_admin(&env, company_id, info, new_admin);
+        Ok(())
     }
 
     fn propose_treasury_rotation(
@@ -408,14 +1020,10 @@ impl PayrollRegistryTrait for PayrollRegistry {
         company_id: u64,
         current_admin: Address,
         new_treasury: Address,
-    ) {
-        let info: CompanyInfo = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Company(company_id))
-            .expect("Company not found");
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
         if current_admin != info.admin {
-            panic!("Unauthorized: caller is not the company admin");
+            return Err(RegistryError::NotAuthorized);
         }
         current_admin.require_auth();
 
@@ -424,7 +1032,7 @@ impl PayrollRegistryTrait for PayrollRegistry {
             .persistent()
             .has(&DataKey::PendingTreasuryRotation(company_id))
         {
-            panic!("A pending treasury rotation already exists for this company");
+            return Err(RegistryError::RotationPending);
         }
 
         let proposal = PendingCompanyRotation {
@@ -435,33 +1043,1033 @@ impl PayrollRegistryTrait for PayrollRegistry {
         env.storage()
             .persistent()
             .set(&DataKey::PendingTreasuryRotation(company_id), &proposal);
+        Ok(())
     }
 
-    fn accept_treasury_rotation(env: Env, company_id: u64, new_treasury: Address) {
+    fn accept_treasury_rotation(
+        env: Env,
+        company_id: u64,
+        new_treasury: Address,
+    ) -> Result<(), RegistryError> {
         let proposal: PendingCompanyRotation = env
             .storage()
             .persistent()
             .get(&DataKey::PendingTreasuryRotation(company_id))
-            .expect("No pending treasury rotation for this company");
+            .ok_or(RegistryError::NoPendingRotation)?;
 
         if new_treasury != proposal.new_holder {
-            panic!("Unauthorized: caller is not the proposed treasury");
+            return Err(RegistryError::NotAuthorized);
         }
         new_treasury.require_auth();
 
-        let mut info: CompanyInfo = env
+        let info = Self::load_company(&env, company_id)?;
+        Self::set_company_treasury(&env, company_id, info, new_treasury);
+        Ok(())
+    }
+
+    fn update_treasury(
+        env: Env,
+        company_id: u64,
+        caller: Address,
+        new_treasury: Address,
+    ) -> Result<(), RegistryError> {
+        let info = Self::require_role(&env, company_id, &caller, AdminRole::Finance)?;
+        Self::set_company_treasury(&env, company_id, info, new_treasury);
+        Ok(())
+    }
+
+    // ── Salary-change approval ───────────────────────────────────────────────
+
+    fn set_salary_approver(
+        env: Env,
+        company_id: u64,
+        approver: Address,
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        if approver == info.admin {
+            return Err(RegistryError::ApproverIsAdmin);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::SalaryApprover(company_id), &approver);
+        Ok(())
+    }
+
+    fn get_salary_approver(env: Env, company_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SalaryApprover(company_id))
+    }
+
+    fn set_commitment_contract(
+        env: Env,
+        company_id: u64,
+        commitment_contract: Address,
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        env.storage().persistent().set(
+            &DataKey::CommitmentContract(company_id),
+            &commitment_contract,
+        );
+        Ok(())
+    }
+
+    fn set_require_commitment_match(
+        env: Env,
+        company_id: u64,
+        enabled: bool,
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        let key = DataKey::RequireCommitmentMatch(company_id);
+        if enabled {
+            if !env
+                .storage()
+                .persistent()
+                .has(&DataKey::CommitmentContract(company_id))
+            {
+                return Err(RegistryError::CommitmentContractNotSet);
+            }
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    fn get_require_commitment_match(env: Env, company_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RequireCommitmentMatch(company_id))
+            .unwrap_or(false)
+    }
+
+    fn propose_salary_change(
+        env: Env,
+        company_id: u64,
+        caller: Address,
+        employee: Address,
+        new_commitment: BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        Self::require_role(&env, company_id, &caller, AdminRole::Hr)?;
+
+        if !env
             .storage()
+            .persistent()
+            .has(&DataKey::Employee(company_id, employee.clone()))
+        {
+            return Err(RegistryError::EmployeeNotFound);
+        }
+
+        let key = DataKey::PendingSalaryChange(company_id, employee.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(RegistryError::SalaryChangePending);
+        }
+
+        let proposal = PendingSalaryChange {
+            new_commitment: new_commitment.clone(),
+            proposed_by: caller.clone(),
+            proposed_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &proposal);
+
+        env.events().publish(
+            (
+                Symbol::new(&env, "SalaryChangeProposed"),
+                company_id,
+                employee,
+            ),
+            (caller, new_commitment),
+        );
+        // topics : ("SalaryChangeProposed", company_id, employee)
+        // data   : (proposed_by, new_commitment)
+        Ok(())
+    }
+
+    fn approve_salary_change(
+        env: Env,
+        company_id: u64,
+        approver: Address,
+        employee: Address,
+    ) -> Result<(), RegistryError> {
+        let key = DataKey::PendingSalaryChange(company_id, employee.clone());
+        let proposal: PendingSalaryChange = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RegistryError::NoPendingSalaryChange)?;
+
+        if approver == proposal.proposed_by {
+            return Err(RegistryError::NotAuthorized);
+        }
+        let expected: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SalaryApprover(company_id))
+            .ok_or(RegistryError::ApproverNotSet)?;
+        if approver != expected {
+            return Err(RegistryError::NotAuthorized);
+        }
+        approver.require_auth();
+        Self::ensure_company_active(&env, company_id)?;
+
+        let commitment_contract: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CommitmentContract(company_id))
+            .ok_or(RegistryError::CommitmentContractNotSet)?;
+
+        env.storage().persistent().set(
+            &DataKey::Employee(company_id, employee.clone()),
+            &proposal.new_commitment,
+        );
+        env.storage().persistent().remove(&key);
+        Self::bump_commitment_version(&env, company_id, &employee);
+
+        SalaryCommitmentContractClient::new(&env, &commitment_contract)
+            .update_commitment(&employee, &proposal.new_commitment);
+
+        env.events().publish(
+            (
+                Symbol::new(&env, "SalaryChangeApproved"),
+                company_id,
+                employee,
+            ),
+            (approver, proposal.new_commitment),
+        );
+        // topics : ("SalaryChangeApproved", company_id, employee)
+        // data   : (approver, new_commitment)
+        Ok(())
+    }
+
+    fn get_pending_salary_change(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+    ) -> Option<PendingSalaryChange> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingSalaryChange(company_id, employee))
+    }
+
+    // ── Company merge ────────────────────────────────────────────────────────
+
+    fn merge_companies(env: Env, source_id: u64, target_id: u64) -> Result<(), RegistryError> {
+        if source_id == target_id {
+            return Err(RegistryError::SelfMerge);
+        }
+        let source = Self::load_company(&env, source_id)?;
+        let target = Self::load_company(&env, target_id)?;
+
+        source.admin.require_auth();
+        if target.admin != source.admin {
+            target.admin.require_auth();
+        }
+
+        if Self::is_merged(&env, source_id) {
+            return Err(RegistryError::CompanyMerged);
+        }
+        if Self::is_merged(&env, target_id) {
+            return Err(RegistryError::CompanyMerged);
+        }
+
+        let employees = Self::get_employees(env.clone(), source_id);
+
+        // Check every address before moving anything.
+        for employee in employees.iter() {
+            if env
+                .storage()
+                .persistent()
+                .has(&DataKey::Employee(target_id, employee))
+            {
+                return Err(RegistryError::DuplicateEmployee);
+            }
+        }
+        Self::ensure_capacity(&env, target_id, employees.len())?;
+
+        for employee in employees.iter() {
+            Self::move_employee(&env, source_id, target_id, &employee);
+        }
+
+        Self::clear_roster(&env, source_id, &employees);
+        env.storage()
+            .persistent()
+            .set(&DataKey::MergedInto(source_id), &target_id);
+
+        env.events().publish(
+            (Symbol::new(&env, "CompaniesMerged"), source_id, target_id),
+            (employees.len(),),
+        );
+        // topics : ("CompaniesMerged", source_id, target_id)
+        // data   : (moved_employees,)
+        Ok(())
+    }
+
+    fn get_merged_into(env: Env, company_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MergedInto(company_id))
+    }
+
+    fn get_employees(env: Env, company_id: u64) -> Vec<Address> {
+        let count = Self::get_employee_count(env.clone(), company_id);
+        Self::roster_range(&env, company_id, 0, count)
+    }
+
+    fn get_employee_count(env: Env, company_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RosterLen(company_id))
+            .unwrap_or(0)
+    }
+
+    fn get_active_employee_count(env: Env, company_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ActiveCount(company_id))
+            .unwrap_or(0)
+    }
+
+    fn get_company_employees(env: Env, company_id: u64, offset: u32, limit: u32) -> Vec<Address> {
+        let count = Self::get_employee_count(env.clone(), company_id);
+        let end = offset.saturating_add(limit.min(MAX_EMPLOYEE_PAGE));
+        Self::roster_range(&env, company_id, offset, end.min(count))
+    }
+
+    // ── Bulk onboarding ──────────────────────────────────────────────────────
+
+    fn batch_add_employees(
+        env: Env,
+        company_id: u64,
+        entries: Vec<(Address, BytesN<32>)>,
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        if entries.len() > MAX_ENROLMENT_BATCH {
+            return Err(RegistryError::BatchTooLarge);
+        }
+        if Self::is_merged(&env, company_id) {
+            return Err(RegistryError::CompanyMerged);
+        }
+        Self::ensure_company_active(&env, company_id)?;
+        let consented = Self::get_require_consent(env.clone(), company_id);
+        let match_required = Self::get_require_commitment_match(env.clone(), company_id);
+
+        // Check every entry before writing anything, collecting the
+        // employees that are not on the roster yet.
+        let mut seen = Map::<Address, ()>::new(&env);
+        let mut joining = Vec::new(&env);
+        for (employee, commitment) in entries.iter() {
+            if seen.contains_key(employee.clone())
+                || Self::is_eligible(env.clone(), company_id, employee.clone())
+            {
+                return Err(RegistryError::DuplicateEmployee);
+            }
+            seen.set(employee.clone(), ());
+            if consented {
+                employee.require_auth();
+            }
+            if match_required {
+                Self::ensure_commitment_matches(&env, company_id, &employee, &commitment)?;
+            }
+            if !Self::on_roster(&env, company_id, &employee) {
+                joining.push_back(employee);
+            }
+        }
+        Self::ensure_capacity(&env, company_id, joining.len())?;
+
+        for (employee, commitment) in entries.iter() {
+            Self::store_enrolment(&env, company_id, employee, commitment, consented);
+        }
+        Self::append_to_roster(&env, company_id, &joining);
+
+        env.events().publish(
+            (Symbol::new(&env, "EmployeesBatchAdded"), company_id),
+            (entries.len(),),
+        );
+        // topics : ("EmployeesBatchAdded", company_id)
+        // data   : (count,)
+        Ok(())
+    }
+
+    // ── Roster cap ───────────────────────────────────────────────────────────
+
+    fn set_max_employees(env: Env, company_id: u64, max: u32) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        if max < Self::get_employee_count(env.clone(), company_id) {
+            return Err(RegistryError::CapBelowCount);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MaxEmployees(company_id), &max);
+        Ok(())
+    }
+
+    fn get_max_employees(env: Env, company_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MaxEmployees(company_id))
+            .unwrap_or(DEFAULT_MAX_EMPLOYEES)
+    }
+
+    // ── Employee consent ─────────────────────────────────────────────────────
+
+    fn set_require_consent(env: Env, company_id: u64, enabled: bool) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        let key = DataKey::RequireConsent(company_id);
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    fn get_require_consent(env: Env, company_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RequireConsent(company_id))
+            .unwrap_or(false)
+    }
+
+    fn get_consented_at(env: Env, company_id: u64, employee: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ConsentedAt(company_id, employee))
+    }
+
+    // ── Commitment cooldown ──────────────────────────────────────────────────
+
+    fn set_commitment_cooldown(
+        env: Env,
+        company_id: u64,
+        cooldown_secs: u64,
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        let key = DataKey::CommitmentCooldown(company_id);
+        if cooldown_secs == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &cooldown_secs);
+        }
+        Ok(())
+    }
+
+    fn get_commitment_cooldown(env: Env, company_id: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CommitmentCooldown(company_id))
+            .unwrap_or(0)
+    }
+
+    fn get_last_commitment_change(env: Env, company_id: u64, employee: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LastCommitmentChange(company_id, employee))
+    }
+
+    fn get_commitment_version(env: Env, company_id: u64, employee: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CommitmentVersion(company_id, employee))
+            .unwrap_or(0)
+    }
+
+    fn force_update_commitment(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+        new_commitment: BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        Self::load_company(&env, company_id)?.admin.require_auth();
+        Self::replace_commitment(&env, company_id, employee, new_commitment, true)
+    }
+
+    // ── Payment timestamps ───────────────────────────────────────────────────
+
+    fn record_payment(
+        env: Env,
+        company_id: u64,
+        employee: Address,
+        timestamp: u64,
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Employee(company_id, employee.clone()))
+        {
+            return Err(RegistryError::EmployeeNotFound);
+        }
+        if timestamp > env.ledger().timestamp() {
+            return Err(RegistryError::TimestampInFuture);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastPayment(company_id, employee), &timestamp);
+        Ok(())
+    }
+
+    fn get_last_payment_timestamp(env: Env, company_id: u64, employee: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LastPayment(company_id, employee))
+    }
+
+    // ── Company deactivation ─────────────────────────────────────────────────
+
+    fn deactivate_company(env: Env, company_id: u64) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+        Self::ensure_company_active(&env, company_id)?;
+
+        let deactivated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::CompanyInactive(company_id), &deactivated_at);
+
+        env.events().publish(
+            (Symbol::new(&env, "CompanyDeactivated"), company_id),
+            (info.admin, deactivated_at),
+        );
+        // topics : ("CompanyDeactivated", company_id)
+        // data   : (admin, deactivated_at)
+        Ok(())
+    }
+
+    fn reactivate_company(env: Env, company_id: u64) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        let key = DataKey::CompanyInactive(company_id);
+        if !env.storage().persistent().has(&key) {
+            return Err(RegistryError::CompanyActive);
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (Symbol::new(&env, "CompanyReactivated"), company_id),
+            (info.admin, env.ledger().timestamp()),
+        );
+        // topics : ("CompanyReactivated", company_id)
+        // data   : (admin, timestamp)
+        Ok(())
+    }
+
+    fn is_company_active(env: Env, company_id: u64) -> Result<bool, RegistryError> {
+        Self::load_company(&env, company_id)?;
+        Ok(!env
+            .storage()
+            .persistent()
+            .has(&DataKey::CompanyInactive(company_id)))
+    }
+
+    // ── Company roles ────────────────────────────────────────────────────────
+
+    fn add_role(
+        env: Env,
+        company_id: u64,
+        address: Address,
+        role: AdminRole,
+    ) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Role(company_id, address.clone()), &role);
+
+        env.events().publish(
+            (Symbol::new(&env, "RoleGranted"), company_id, address),
+            (role,),
+        );
+        // topics : ("RoleGranted", company_id, address)
+        // data   : (role,)
+        Ok(())
+    }
+
+    fn remove_role(env: Env, company_id: u64, address: Address) -> Result<(), RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        info.admin.require_auth();
+
+        let key = DataKey::Role(company_id, address.clone());
+        let role: AdminRole = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RegistryError::RoleNotFound)?;
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (Symbol::new(&env, "RoleRevoked"), company_id, address),
+            (role,),
+        );
+        // topics : ("RoleRevoked", company_id, address)
+        // data   : (role,)
+        Ok(())
+    }
+
+    fn get_role(
+        env: Env,
+        company_id: u64,
+        address: Address,
+    ) -> Result<Option<AdminRole>, RegistryError> {
+        let info = Self::load_company(&env, company_id)?;
+        if address == info.admin {
+            return Ok(Some(AdminRole::Super));
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::Role(company_id, address)))
+    }
+}
+
+impl PayrollRegistry {
+    /// Load a company record, failing with `RegistryError::CompanyNotFound`.
+    fn load_company(env: &Env, company_id: u64) -> Result<CompanyInfo, RegistryError> {
+        env.storage()
             .persistent()
             .get(&DataKey::Company(company_id))
-            .expect("Company not found");
+            .ok_or(RegistryError::CompanyNotFound)
+    }
+
+    /// Make `new_admin` the company admin, drop any pending admin rotation
+    /// and emit `AdminTransferred`. Callers check authorisation.
+    fn set_company_admin(env: &Env, company_id: u64, mut info: CompanyInfo, new_admin: Address) {
+        let old_admin = core::mem::replace(&mut info.admin, new_admin.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Company(company_id), &info);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingAdminRotation(company_id));
 
-        info.treasury = new_treasury;
+        env.events().publish(
+            (Symbol::new(env, "AdminTransferred"), company_id),
+            (old_admin, new_admin),
+        );
+        // topics : ("AdminTransferred", company_id)
+        // data   : (old_admin, new_admin)
+    }
+
+    /// Make `new_treasury` the company treasury, drop any pending treasury
+    /// rotation and emit `TreasuryUpdated`. Callers check authorisation.
+    fn set_company_treasury(
+        env: &Env,
+        company_id: u64,
+        mut info: CompanyInfo,
+        new_treasury: Address,
+    ) {
+        let old_treasury = core::mem::replace(&mut info.treasury, new_treasury.clone());
         env.storage()
             .persistent()
             .set(&DataKey::Company(company_id), &info);
         env.storage()
             .persistent()
             .remove(&DataKey::PendingTreasuryRotation(company_id));
+
+        env.events().publish(
+            (Symbol::new(env, "TreasuryUpdated"), company_id),
+            (old_treasury, new_treasury),
+        );
+        // topics : ("TreasuryUpdated", company_id)
+        // data   : (old_treasury, new_treasury)
+    }
+
+    /// Fail with `RegistryError::NotAuthorized` unless `caller` is the
+    /// company admin or holds `role` or `Super`, then require the
+    /// caller's authorisation.
+    fn require_role(
+        env: &Env,
+        company_id: u64,
+        caller: &Address,
+        role: AdminRole,
+    ) -> Result<CompanyInfo, RegistryError> {
+        let info = Self::load_company(env, company_id)?;
+        let held = Self::get_role(env.clone(), company_id, caller.clone())?;
+        if held != Some(role) && held != Some(AdminRole::Super) {
+            return Err(RegistryError::NotAuthorized);
+        }
+        caller.require_auth();
+        Ok(info)
+    }
+
+    /// Body of `add_employee`; the caller checks authorisation.
+    fn enrol_employee(
+        env: &Env,
+        company_id: u64,
+        employee: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        let consented = Self::get_require_consent(env.clone(), company_id);
+        if consented {
+            employee.require_auth();
+        }
+
+        if Self::is_merged(env, company_id) {
+            return Err(RegistryError::CompanyMerged);
+        }
+        Self::ensure_company_active(env, company_id)?;
+        if Self::get_require_commitment_match(env.clone(), company_id) {
+            Self::ensure_commitment_matches(env, company_id, &employee, &commitment)?;
+        }
+        if !Self::on_roster(env, company_id, &employee) {
+            Self::ensure_capacity(env, company_id, 1)?;
+        }
+
+        Self::add_to_roster(env, company_id, &employee);
+        Self::store_enrolment(env, company_id, employee, commitment, consented);
+        Ok(())
+    }
+
+    /// Write an enrolled employee's records and emit `EmployeeAdded`. Does
+    /// not touch the roster; callers have run every enrolment check.
+    fn store_enrolment(
+        env: &Env,
+        company_id: u64,
+        employee: Address,
+        commitment: BytesN<32>,
+        consented: bool,
+    ) {
+        env.storage().persistent().set(
+            &DataKey::Employee(company_id, employee.clone()),
+            &commitment,
+        );
+
+        // Default status for newly registered employees is Active (issue #90).
+        Self::write_status(env, company_id, &employee, Some(EmployeeStatus::Active));
+        let consent_key = DataKey::ConsentedAt(company_id, employee.clone());
+        if consented {
+            env.storage()
+                .persistent()
+                .set(&consent_key, &env.ledger().timestamp());
+        } else {
+            env.storage().persistent().remove(&consent_key);
+        }
+        Self::bump_commitment_version(env, company_id, &employee);
+
+        env.events().publish(
+            (Symbol::new(env, "EmployeeAdded"), company_id, employee),
+            (commitment,),
+        );
+        // topics : ("EmployeeAdded", company_id, employee)
+        // data   : (commitment,)
+    }
+
+    /// Shared body of `update_commitment` and `force_update_commitment`;
+    /// callers check authorisation. With `force`, a change inside the
+    /// cooldown is applied and reported with a `CommitmentCooldownOverridden`
+    /// event instead of rejected.
+    fn replace_commitment(
+        env: &Env,
+        company_id: u64,
+        employee: Address,
+        new_commitment: BytesN<32>,
+        force: bool,
+    ) -> Result<(), RegistryError> {
+        Self::ensure_company_active(env, company_id)?;
+
+        let emp = employee.clone();
+        let key = DataKey::Employee(company_id, emp);
+        if !env.storage().persistent().has(&key) {
+            return Err(RegistryError::EmployeeNotFound);
+        }
+
+        let now = env.ledger().timestamp();
+        let cooldown = Self::get_commitment_cooldown(env.clone(), company_id);
+        let last_change_key = DataKey::LastCommitmentChange(company_id, employee.clone());
+        let last_change: Option<u64> = env.storage().persistent().get(&last_change_key);
+        if let Some(last_change) = last_change {
+            if now < last_change.saturating_add(cooldown) {
+                if !force {
+                    return Err(RegistryError::ChangeTooSoon);
+                }
+                env.events().publish(
+                    (
+                        Symbol::new(env, "CommitmentCooldownOverridden"),
+                        company_id,
+                        employee.clone(),
+                    ),
+                    (last_change, cooldown),
+                );
+                // topics : ("CommitmentCooldownOverridden", company_id, employee)
+                // data   : (last_change, cooldown_secs)
+            }
+        }
+
+        env.storage().persistent().set(&key, &new_commitment);
+        env.storage().persistent().set(&last_change_key, &now);
+        let version = Self::bump_commitment_version(env, company_id, &employee);
+
+        env.events().publish(
+            (Symbol::new(env, "CommitmentUpdated"), company_id, employee),
+            (new_commitment, version),
+        );
+        // topics : ("CommitmentUpdated", company_id, employee)
+        // data   : (new_commitment, version)
+        Ok(())
+    }
+
+    /// Increase the employee's commitment version and return the new value.
+    /// An employee without a stored version starts at 1.
+    fn bump_commitment_version(env: &Env, company_id: u64, employee: &Address) -> u32 {
+        let key = DataKey::CommitmentVersion(company_id, employee.clone());
+        let version = env
+            .storage()
+            .persistent()
+            .get::<DataKey, u32>(&key)
+            .unwrap_or(0)
+            + 1;
+        env.storage().persistent().set(&key, &version);
+        version
+    }
+
+    /// Store an employee's status, or clear it with `None`, keeping
+    /// `ActiveCount` in step. Every status change goes through here.
+    fn write_status(
+        env: &Env,
+        company_id: u64,
+        employee: &Address,
+        status: Option<EmployeeStatus>,
+    ) {
+        let storage = env.storage().persistent();
+        let key = DataKey::EmpStatus(company_id, employee.clone());
+        let was_active =
+            storage.get::<DataKey, EmployeeStatus>(&key) == Some(EmployeeStatus::Active);
+        let is_active = status == Some(EmployeeStatus::Active);
+        match status {
+            Some(status) => storage.set(&key, &status),
+            None => storage.remove(&key),
+        }
+        if was_active != is_active {
+            let count_key = DataKey::ActiveCount(company_id);
+            let count: u32 = storage.get(&count_key).unwrap_or(0);
+            let count = if is_active { count + 1 } else { count - 1 };
+            storage.set(&count_key, &count);
+        }
+    }
+
+    /// Set `Inactive`, store the deactivation record and emit
+    /// `EmployeeDeactivated`. Callers must have checked admin auth and that the
+    /// employee exists.
+    fn record_deactivation(env: &Env, company_id: u64, employee: Address, reason: Symbol) {
+        let deactivated_at = env.ledger().timestamp();
+        Self::write_status(env, company_id, &employee, Some(EmployeeStatus::Inactive));
+        env.storage().persistent().set(
+            &DataKey::Deactivation(company_id, employee.clone()),
+            &EmployeeDeactivation {
+                reason: reason.clone(),
+                deactivated_at,
+            },
+        );
+
+        env.events().publish(
+            (Symbol::new(env, "EmployeeDeactivated"), company_id),
+            (employee, reason, deactivated_at),
+        );
+        // topics : ("EmployeeDeactivated", company_id)
+        // data   : (employee, reason, deactivated_at)
+    }
+
+    /// Fail with `CommitmentMismatch` unless the company's commitment
+    /// contract stores `commitment` for `employee`.
+    fn ensure_commitment_matches(
+        env: &Env,
+        company_id: u64,
+        employee: &Address,
+        commitment: &BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        let commitment_contract: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CommitmentContract(company_id))
+            .ok_or(RegistryError::CommitmentContractNotSet)?;
+        let client = SalaryCommitmentContractClient::new(env, &commitment_contract);
+        if !client.has_commitment(employee)
+            || client.get_commitment(employee).commitment != *commitment
+        {
+            return Err(RegistryError::CommitmentMismatch);
+        }
+        Ok(())
+    }
+
+    /// Fail with `CompanyFull` unless `additional` more employees fit under
+    /// the company's cap.
+    fn ensure_capacity(env: &Env, company_id: u64, additional: u32) -> Result<(), RegistryError> {
+        let count = Self::get_employee_count(env.clone(), company_id);
+        let max = Self::get_max_employees(env.clone(), company_id);
+        if count.saturating_add(additional) > max {
+            return Err(RegistryError::CompanyFull);
+        }
+        Ok(())
+    }
+
+    /// Fail with `RegistryError::CompanyInactive` while the company is
+    /// deactivated.
+    fn ensure_company_active(env: &Env, company_id: u64) -> Result<(), RegistryError> {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::CompanyInactive(company_id))
+        {
+            return Err(RegistryError::CompanyInactive);
+        }
+        Ok(())
+    }
+
+    fn is_merged(env: &Env, company_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::MergedInto(company_id))
+    }
+
+    fn on_roster(env: &Env, company_id: u64, employee: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::RosterIndex(company_id, employee.clone()))
+    }
+
+    /// Roster entries at positions `start..end`; empty if `start >= end`.
+    fn roster_range(env: &Env, company_id: u64, start: u32, end: u32) -> Vec<Address> {
+        let storage = env.storage().persistent();
+        let mut page = Vec::new(env);
+        for idx in start..end {
+            let employee: Address = storage.get(&DataKey::RosterSlot(company_id, idx)).unwrap();
+            page.push_back(employee);
+        }
+        page
+    }
+
+    /// Append `employee` to the roster unless already on it.
+    fn add_to_roster(env: &Env, company_id: u64, employee: &Address) {
+        if Self::on_roster(env, company_id, employee) {
+            return;
+        }
+        Self::append_to_roster(env, company_id, &Vec::from_array(env, [employee.clone()]));
+    }
+
+    /// Append `employees`, none of which may already be on the roster,
+    /// writing `RosterLen` once.
+    fn append_to_roster(env: &Env, company_id: u64, employees: &Vec<Address>) {
+        if employees.is_empty() {
+            return;
+        }
+        let storage = env.storage().persistent();
+        let mut len = Self::get_employee_count(env.clone(), company_id);
+        for employee in employees.iter() {
+            storage.set(&DataKey::RosterSlot(company_id, len), &employee);
+            storage.set(&DataKey::RosterIndex(company_id, employee), &len);
+            len += 1;
+        }
+        storage.set(&DataKey::RosterLen(company_id), &len);
+    }
+
+    /// Remove `employee` from the roster, moving the last entry into the
+    /// freed position.
+    fn remove_from_roster(env: &Env, company_id: u64, employee: &Address) {
+        let storage = env.storage().persistent();
+        let index_key = DataKey::RosterIndex(company_id, employee.clone());
+        let Some(idx) = storage.get::<DataKey, u32>(&index_key) else {
+            return;
+        };
+        let last = Self::get_employee_count(env.clone(), company_id) - 1;
+        if idx != last {
+            let moved: Address = storage.get(&DataKey::RosterSlot(company_id, last)).unwrap();
+            storage.set(&DataKey::RosterSlot(company_id, idx), &moved);
+            storage.set(&DataKey::RosterIndex(company_id, moved), &idx);
+        }
+        storage.remove(&DataKey::RosterSlot(company_id, last));
+        storage.remove(&index_key);
+        storage.set(&DataKey::RosterLen(company_id), &last);
+    }
+
+    /// Drop every roster entry of `company_id`; `employees` is its roster.
+    fn clear_roster(env: &Env, company_id: u64, employees: &Vec<Address>) {
+        let storage = env.storage().persistent();
+        for (idx, employee) in employees.iter().enumerate() {
+            storage.remove(&DataKey::RosterSlot(company_id, idx as u32));
+            storage.remove(&DataKey::RosterIndex(company_id, employee));
+        }
+        storage.remove(&DataKey::RosterLen(company_id));
+    }
+
+    /// Re-key an employee's records from `source_id` to `target_id`. The
+    /// source roster is left for the caller to clear.
+    fn move_employee(env: &Env, source_id: u64, target_id: u64, employee: &Address) {
+        let storage = env.storage().persistent();
+
+        let commitment_key = DataKey::Employee(source_id, employee.clone());
+        if let Some(commitment) = storage.get::<DataKey, BytesN<32>>(&commitment_key) {
+            storage.set(&DataKey::Employee(target_id, employee.clone()), &commitment);
+            storage.remove(&commitment_key);
+        }
+
+        let status_key = DataKey::EmpStatus(source_id, employee.clone());
+        if let Some(status) = storage.get::<DataKey, EmployeeStatus>(&status_key) {
+            Self::write_status(env, target_id, employee, Some(status));
+            Self::write_status(env, source_id, employee, None);
+        }
+
+        let deactivation_key = DataKey::Deactivation(source_id, employee.clone());
+        if let Some(record) = storage.get::<DataKey, EmployeeDeactivation>(&deactivation_key) {
+            storage.set(&DataKey::Deactivation(target_id, employee.clone()), &record);
+            storage.remove(&deactivation_key);
+        }
+
+        let consent_key = DataKey::ConsentedAt(source_id, employee.clone());
+        if let Some(consented_at) = storage.get::<DataKey, u64>(&consent_key) {
+            storage.set(
+                &DataKey::ConsentedAt(target_id, employee.clone()),
+                &consented_at,
+            );
+            storage.remove(&consent_key);
+        }
+
+        let change_key = DataKey::LastCommitmentChange(source_id, employee.clone());
+        if let Some(changed_at) = storage.get::<DataKey, u64>(&change_key) {
+            storage.set(
+                &DataKey::LastCommitmentChange(target_id, employee.clone()),
+                &changed_at,
+            );
+            storage.remove(&change_key);
+        }
+
+        let payment_key = DataKey::LastPayment(source_id, employee.clone());
+        if let Some(paid_at) = storage.get::<DataKey, u64>(&payment_key) {
+            storage.set(&DataKey::LastPayment(target_id, employee.clone()), &paid_at);
+            storage.remove(&payment_key);
+        }
+
+        let version_key = DataKey::CommitmentVersion(source_id, employee.clone());
+        if let Some(version) = storage.get::<DataKey, u32>(&version_key) {
+            storage.set(
+                &DataKey::CommitmentVersion(target_id, employee.clone()),
+                &version,
+            );
+            storage.remove(&version_key);
+        }
+
+        storage.remove(&DataKey::PendingSalaryChange(source_id, employee.clone()));
+
+        Self::add_to_roster(env, target_id, employee);
     }
 }
 
@@ -1,5 +1,5 @@
 use super::*;
-use soroban_sdk::testutils::{Address as _, Events};
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
 use soroban_sdk::{Env, IntoVal, Symbol, TryIntoVal};
 
 fn setup() -> (Env, Address) {
@@ -69,7 +69,7 @@ fn test_add_employee_stores_commitment() {
     let commitment = BytesN::from_array(&env, &[1u8; 32]);
 
     let company_id = client.register_company(&admin, &treasury);
-    client.add_employee(&company_id, &employee, &commitment);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
 
     let stored: BytesN<32> = env.as_contract(&contract_id, || {
         env.storage()
@@ -90,11 +90,11 @@ fn test_remove_employee_hard_deletes() {
     let commitment = BytesN::from_array(&env, &[2u8; 32]);
 
     let company_id = client.register_company(&admin, &treasury);
-    client.add_employee(&company_id, &employee, &commitment);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
     client.remove_employee(&company_id, &employee);
 
     let new_commitment = BytesN::from_array(&env, &[3u8; 32]);
-    let result = client.try_update_commitment(&company_id, &employee, &new_commitment);
+    let result = client.try_update_commitment(&company_id, &admin, &employee, &new_commitment);
     assert!(result.is_err());
 }
 
@@ -109,8 +109,8 @@ fn test_update_commitment_replaces_value() {
     let new_commitment = BytesN::from_array(&env, &[9u8; 32]);
 
     let company_id = client.register_company(&admin, &treasury);
-    client.add_employee(&company_id, &employee, &old_commitment);
-    client.update_commitment(&company_id, &employee, &new_commitment);
+    client.add_employee(&company_id, &admin, &employee, &old_commitment);
+    client.update_commitment(&company_id, &admin, &employee, &new_commitment);
 
     let stored: BytesN<32> = env.as_contract(&contract_id, || {
         env.storage()
@@ -128,8 +128,8 @@ fn test_add_employee_unknown_company_panics() {
     let employee = Address::generate(&env);
     let commitment = BytesN::from_array(&env, &[0u8; 32]);
 
-    let result = client.try_add_employee(&99u64, &employee, &commitment);
-    assert!(result.is_err());
+    let result = client.try_add_employee(&99u64, &Address::generate(&env), &employee, &commitment);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::CompanyNotFound);
 }
 
 #[test]
@@ -191,7 +191,13 @@ fn test_authorization_add_employee_fails_for_non_admin() {
         invoke: &soroban_sdk::testutils::MockAuthInvoke {
             contract: &contract_id,
             fn_name: "add_employee",
-            args: (company_id, mock_employee.clone(), fake_commitment.clone()).into_val(&env),
+            args: (
+                company_id,
+                correct_admin.clone(),
+                mock_employee.clone(),
+                fake_commitment.clone(),
+            )
+                .into_val(&env),
             sub_invokes: &[],
         },
     }]);
@@ -199,7 +205,12 @@ fn test_authorization_add_employee_fails_for_non_admin() {
     // Attack: call `add_employee`. The registry calls `info.admin.require_auth()`.
     // The attacker's signature is in the auth list, but it does not match `info.admin` (which is `correct_admin`).
     // Expected: Panic from the Soroban host terminating the execution for a missing correct signature.
-    registry.add_employee(&company_id, &mock_employee, &fake_commitment);
+    registry.add_employee(
+        &company_id,
+        &correct_admin,
+        &mock_employee,
+        &fake_commitment,
+    );
 }
 
 #[test]
@@ -226,7 +237,7 @@ fn test_get_commitment_returns_employee_commitment() {
     let commitment = BytesN::from_array(&env, &[7u8; 32]);
 
     let company_id = client.register_company(&admin, &treasury);
-    client.add_employee(&company_id, &employee, &commitment);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
 
     let got = client.get_commitment(&company_id, &employee);
     assert_eq!(got, commitment);
@@ -236,12 +247,6 @@ fn test_get_commitment_returns_employee_commitment() {
 
 #[test]
 fn test_add_employee_sets_active_status() {
-// ---------------------------------------------------------------------------
-// Event emission tests
-// ---------------------------------------------------------------------------
-
-#[test]
-fn test_register_company_emits_event() {
     let (env, contract_id) = setup();
     let client = PayrollRegistryClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
@@ -250,7 +255,7 @@ fn test_register_company_emits_event() {
     let commitment = BytesN::from_array(&env, &[1u8; 32]);
 
     let company_id = client.register_company(&admin, &treasury);
-    client.add_employee(&company_id, &employee, &commitment);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
 
     assert_eq!(
         client.get_employee_status(&company_id, &employee),
@@ -261,22 +266,6 @@ fn test_register_company_emits_event() {
 
 #[test]
 fn test_set_employee_status_inactive_makes_ineligible() {
-
-    let before = env.events().all().len();
-    let company_id = client.register_company(&admin, &treasury);
-    let after = env.events().all().len();
-    assert_eq!(after, before + 1);
-
-    let event = env.events().all().get(after - 1).unwrap();
-    assert_eq!(event.1.len(), 2);
-    let sym0: Symbol = event.1.get(0).unwrap().try_into_val(&env.clone()).unwrap();
-    assert_eq!(sym0, Symbol::new(&env, "CompanyRegistered"));
-    let comp_id: u64 = event.1.get(1).unwrap().try_into_val(&env.clone()).unwrap();
-    assert_eq!(comp_id, company_id);
-}
-
-#[test]
-fn test_add_employee_emits_event() {
     let (env, contract_id) = setup();
     let client = PayrollRegistryClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
@@ -285,7 +274,7 @@ fn test_add_employee_emits_event() {
     let commitment = BytesN::from_array(&env, &[2u8; 32]);
 
     let company_id = client.register_company(&admin, &treasury);
-    client.add_employee(&company_id, &employee, &commitment);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
 
     client.set_employee_status(&company_id, &employee, &EmployeeStatus::Inactive);
 
@@ -298,26 +287,6 @@ fn test_add_employee_emits_event() {
 
 #[test]
 fn test_set_employee_status_incomplete_makes_ineligible() {
-    let commitment = BytesN::from_array(&env, &[1u8; 32]);
-
-    let company_id = client.register_company(&admin, &treasury);
-    let before = env.events().all().len();
-    client.add_employee(&company_id, &employee, &commitment);
-    let after = env.events().all().len();
-    assert_eq!(after, before + 1);
-
-    let event = env.events().all().get(after - 1).unwrap();
-    assert_eq!(event.1.len(), 3);
-    let sym0: Symbol = event.1.get(0).unwrap().try_into_val(&env.clone()).unwrap();
-    assert_eq!(sym0, Symbol::new(&env, "EmployeeAdded"));
-    let comp_id: u64 = event.1.get(1).unwrap().try_into_val(&env.clone()).unwrap();
-    assert_eq!(comp_id, company_id);
-    let emp_addr: Address = event.1.get(2).unwrap().try_into_val(&env.clone()).unwrap();
-    assert_eq!(emp_addr, employee);
-}
-
-#[test]
-fn test_remove_employee_emits_event() {
     let (env, contract_id) = setup();
     let client = PayrollRegistryClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
@@ -326,7 +295,7 @@ fn test_remove_employee_emits_event() {
     let commitment = BytesN::from_array(&env, &[3u8; 32]);
 
     let company_id = client.register_company(&admin, &treasury);
-    client.add_employee(&company_id, &employee, &commitment);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
 
     client.set_employee_status(&company_id, &employee, &EmployeeStatus::Incomplete);
 
@@ -348,27 +317,6 @@ fn test_unregistered_employee_is_not_eligible() {
 
 #[test]
 fn test_reactivating_inactive_employee_restores_eligibility() {
-    let commitment = BytesN::from_array(&env, &[2u8; 32]);
-
-    let company_id = client.register_company(&admin, &treasury);
-    client.add_employee(&company_id, &employee, &commitment);
-    let before = env.events().all().len();
-    client.remove_employee(&company_id, &employee);
-    let after = env.events().all().len();
-    assert_eq!(after, before + 1);
-
-    let event = env.events().all().get(after - 1).unwrap();
-    assert_eq!(event.1.len(), 3);
-    let sym0: Symbol = event.1.get(0).unwrap().try_into_val(&env.clone()).unwrap();
-    assert_eq!(sym0, Symbol::new(&env, "EmployeeRemoved"));
-    let comp_id: u64 = event.1.get(1).unwrap().try_into_val(&env.clone()).unwrap();
-    assert_eq!(comp_id, company_id);
-    let emp_addr: Address = event.1.get(2).unwrap().try_into_val(&env.clone()).unwrap();
-    assert_eq!(emp_addr, employee);
-}
-
-#[test]
-fn test_update_commitment_emits_event() {
     let (env, contract_id) = setup();
     let client = PayrollRegistryClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
@@ -377,7 +325,7 @@ fn test_update_commitment_emits_event() {
     let commitment = BytesN::from_array(&env, &[4u8; 32]);
 
     let company_id = client.register_company(&admin, &treasury);
-    client.add_employee(&company_id, &employee, &commitment);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
     client.set_employee_status(&company_id, &employee, &EmployeeStatus::Inactive);
     assert!(!client.is_eligible(&company_id, &employee));
 
@@ -405,7 +353,6 @@ fn test_admin_rotation_full_flow() {
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: caller is not the company admin")]
 fn test_propose_admin_rotation_rejects_non_admin() {
     let (env, contract_id) = setup();
     let client = PayrollRegistryClient::new(&env, &contract_id);
@@ -416,11 +363,11 @@ fn test_propose_admin_rotation_rejects_non_admin() {
     let attacker = Address::generate(&env);
     let new_admin = Address::generate(&env);
 
-    client.propose_admin_rotation(&company_id, &attacker, &new_admin);
+    let result = client.try_propose_admin_rotation(&company_id, &attacker, &new_admin);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::NotAuthorized);
 }
 
 #[test]
-#[should_panic(expected = "Unauthorized: caller is not the proposed admin")]
 fn test_accept_admin_rotation_rejects_wrong_address() {
     let (env, contract_id) = setup();
     let client = PayrollRegistryClient::new(&env, &contract_id);
@@ -432,7 +379,8 @@ fn test_accept_admin_rotation_rejects_wrong_address() {
     let impostor = Address::generate(&env);
 
     client.propose_admin_rotation(&company_id, &admin, &new_admin);
-    client.accept_admin_rotation(&company_id, &impostor);
+    let result = client.try_accept_admin_rotation(&company_id, &impostor);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::NotAuthorized);
 }
 
 #[test]
@@ -471,7 +419,6 @@ fn test_treasury_rotation_full_flow() {
 }
 
 #[test]
-#[should_panic(expected = "A pending admin rotation already exists for this company")]
 fn test_duplicate_admin_rotation_proposal_rejected() {
     let (env, contract_id) = setup();
     let client = PayrollRegistryClient::new(&env, &contract_id);
@@ -482,14 +429,99 @@ fn test_duplicate_admin_rotation_proposal_rejected() {
     let new_admin = Address::generate(&env);
 
     client.propose_admin_rotation(&company_id, &admin, &new_admin);
-    client.propose_admin_rotation(&company_id, &admin, &new_admin);
+    let result = client.try_propose_admin_rotation(&company_id, &admin, &new_admin);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::RotationPending);
+}
+
+// ---------------------------------------------------------------------------
+// Event emission tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_register_company_emits_event() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+
+    let before = env.events().all().len();
+    let company_id = client.register_company(&admin, &treasury);
+    let after = env.events().all().len();
+    assert_eq!(after, before + 1);
+
+    let event = env.events().all().get(after - 1).unwrap();
+    assert_eq!(event.1.len(), 2);
+    let sym0: Symbol = event.1.get(0).unwrap().try_into_val(&env.clone()).unwrap();
+    assert_eq!(sym0, Symbol::new(&env, "CompanyRegistered"));
+    let comp_id: u64 = event.1.get(1).unwrap().try_into_val(&env.clone()).unwrap();
+    assert_eq!(comp_id, company_id);
+}
+
+#[test]
+fn test_add_employee_emits_event() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+
+    let company_id = client.register_company(&admin, &treasury);
+    let before = env.events().all().len();
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+    let after = env.events().all().len();
+    assert_eq!(after, before + 1);
+
+    let event = env.events().all().get(after - 1).unwrap();
+    assert_eq!(event.1.len(), 3);
+    let sym0: Symbol = event.1.get(0).unwrap().try_into_val(&env.clone()).unwrap();
+    assert_eq!(sym0, Symbol::new(&env, "EmployeeAdded"));
+    let comp_id: u64 = event.1.get(1).unwrap().try_into_val(&env.clone()).unwrap();
+    assert_eq!(comp_id, company_id);
+    let emp_addr: Address = event.1.get(2).unwrap().try_into_val(&env.clone()).unwrap();
+    assert_eq!(emp_addr, employee);
+}
+
+#[test]
+fn test_remove_employee_emits_event() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[2u8; 32]);
+
+    let company_id = client.register_company(&admin, &treasury);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+    let before = env.events().all().len();
+    client.remove_employee(&company_id, &employee);
+    let after = env.events().all().len();
+    assert_eq!(after, before + 1);
+
+    let event = env.events().all().get(after - 1).unwrap();
+    assert_eq!(event.1.len(), 3);
+    let sym0: Symbol = event.1.get(0).unwrap().try_into_val(&env.clone()).unwrap();
+    assert_eq!(sym0, Symbol::new(&env, "EmployeeRemoved"));
+    let comp_id: u64 = event.1.get(1).unwrap().try_into_val(&env.clone()).unwrap();
+    assert_eq!(comp_id, company_id);
+    let emp_addr: Address = event.1.get(2).unwrap().try_into_val(&env.clone()).unwrap();
+    assert_eq!(emp_addr, employee);
+}
+
+#[test]
+fn test_update_commitment_emits_event() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let employee = Address::generate(&env);
     let old_commitment = BytesN::from_array(&env, &[1u8; 32]);
     let new_commitment = BytesN::from_array(&env, &[9u8; 32]);
 
     let company_id = client.register_company(&admin, &treasury);
-    client.add_employee(&company_id, &employee, &old_commitment);
+    client.add_employee(&company_id, &admin, &employee, &old_commitment);
     let before = env.events().all().len();
-    client.update_commitment(&company_id, &employee, &new_commitment);
+    client.update_commitment(&company_id, &admin, &employee, &new_commitment);
     let after = env.events().all().len();
     assert_eq!(after, before + 1);
 
@@ -502,3 +534,1421 @@ fn test_duplicate_admin_rotation_proposal_rejected() {
     let emp_addr: Address = event.1.get(2).unwrap().try_into_val(&env.clone()).unwrap();
     assert_eq!(emp_addr, employee);
 }
+
+// ---------------------------------------------------------------------------
+// Non-panicking lookups
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_find_company_returns_none_for_unknown_id() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+
+    assert!(client.find_company(&42u64).is_none());
+}
+
+#[test]
+fn test_find_company_returns_some_for_registered_company() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+
+    let company_id = client.register_company(&admin, &treasury);
+    let company = client
+        .find_company(&company_id)
+        .expect("company should be found");
+
+    assert_eq!(company.admin, admin);
+    assert_eq!(company.treasury, treasury);
+}
+
+#[test]
+fn test_find_employee_returns_none_for_unknown_employee() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    let company_id = client.register_company(&admin, &treasury);
+
+    assert!(client.find_employee(&company_id, &stranger).is_none());
+    assert!(client.find_employee(&99u64, &stranger).is_none());
+}
+
+#[test]
+fn test_find_employee_returns_some_for_registered_employee() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[5u8; 32]);
+
+    let company_id = client.register_company(&admin, &treasury);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+
+    assert_eq!(
+        client.find_employee(&company_id, &employee),
+        Some(commitment)
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Deactivation reasons
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_deactivate_employee_stores_reason_and_emits_event() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[6u8; 32]);
+
+    let company_id = client.register_company(&admin, &treasury);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+
+    let reason = Symbol::new(&env, "resigned");
+    client.deactivate_employee(&company_id, &employee, &reason);
+
+    assert_eq!(
+        client.get_employee_status(&company_id, &employee),
+        EmployeeStatus::Inactive,
+    );
+    let record = client
+        .get_deactivation(&company_id, &employee)
+        .expect("deactivation should be recorded");
+    assert_eq!(record.reason, reason);
+    assert_eq!(record.deactivated_at, env.ledger().timestamp());
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (Symbol::new(&env, "EmployeeDeactivated"), company_id).into_val(&env)
+    );
+    let (emp_addr, got_reason, at): (Address, Symbol, u64) = event.2.try_into_val(&env).unwrap();
+    assert_eq!(emp_addr, employee);
+    assert_eq!(got_reason, reason);
+    assert_eq!(at, record.deactivated_at);
+}
+
+#[test]
+fn test_set_status_inactive_records_default_reason_until_reactivated() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[7u8; 32]);
+
+    let company_id = client.register_company(&admin, &treasury);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+
+    client.set_employee_status(&company_id, &employee, &EmployeeStatus::Inactive);
+    let record = client.get_deactivation(&company_id, &employee).unwrap();
+    assert_eq!(
+        record.reason,
+        Symbol::new(&env, DEFAULT_DEACTIVATION_REASON)
+    );
+
+    client.set_employee_status(&company_id, &employee, &EmployeeStatus::Active);
+    assert!(client.get_deactivation(&company_id, &employee).is_none());
+}
+
+#[test]
+fn test_max_employees_defaults_to_cap() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let company_id = client.register_company(&Address::generate(&env), &Address::generate(&env));
+
+    assert_eq!(client.get_max_employees(&company_id), DEFAULT_MAX_EMPLOYEES);
+}
+
+#[test]
+fn test_add_employee_up_to_cap_then_company_full() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let commitment = BytesN::from_array(&env, &[7u8; 32]);
+
+    client.set_max_employees(&company_id, &2);
+    let first = Address::generate(&env);
+    client.add_employee(&company_id, &admin, &first, &commitment);
+    client.add_employee(&company_id, &admin, &Address::generate(&env), &commitment);
+
+    let result =
+        client.try_add_employee(&company_id, &admin, &Address::generate(&env), &commitment);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::CompanyFull);
+    assert_eq!(client.get_employee_count(&company_id), 2);
+
+    // Re-adding an existing employee does not grow the roster.
+    client.add_employee(
+        &company_id,
+        &admin,
+        &first,
+        &BytesN::from_array(&env, &[8u8; 32]),
+    );
+}
+
+#[test]
+fn test_raising_max_employees_allows_more() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let commitment = BytesN::from_array(&env, &[7u8; 32]);
+
+    client.set_max_employees(&company_id, &1);
+    client.add_employee(&company_id, &admin, &Address::generate(&env), &commitment);
+    assert!(client
+        .try_add_employee(&company_id, &admin, &Address::generate(&env), &commitment)
+        .is_err());
+
+    client.set_max_employees(&company_id, &2);
+    client.add_employee(&company_id, &admin, &Address::generate(&env), &commitment);
+    assert_eq!(client.get_employee_count(&company_id), 2);
+}
+
+#[test]
+fn test_max_employees_cannot_drop_below_count() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let commitment = BytesN::from_array(&env, &[7u8; 32]);
+
+    client.add_employee(&company_id, &admin, &Address::generate(&env), &commitment);
+    client.add_employee(&company_id, &admin, &Address::generate(&env), &commitment);
+    let result = client.try_set_max_employees(&company_id, &1);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::CapBelowCount);
+}
+
+// ---------------------------------------------------------------------------
+// Employee consent
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_add_employee_with_consent_records_timestamp() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    client.set_require_consent(&company_id, &true);
+    env.ledger().with_mut(|l| l.timestamp = 1_700_000_000);
+
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[7u8; 32]);
+    let args = (
+        company_id,
+        admin.clone(),
+        employee.clone(),
+        commitment.clone(),
+    )
+        .into_val(&env);
+    let invoke = soroban_sdk::testutils::MockAuthInvoke {
+        contract: &contract_id,
+        fn_name: "add_employee",
+        args,
+        sub_invokes: &[],
+    };
+    env.mock_auths(&[
+        soroban_sdk::testutils::MockAuth {
+            address: &admin,
+            invoke: &invoke,
+        },
+        soroban_sdk::testutils::MockAuth {
+            address: &employee,
+            invoke: &invoke,
+        },
+    ]);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+
+    assert_eq!(
+        client.get_consented_at(&company_id, &employee),
+        Some(1_700_000_000)
+    );
+}
+
+#[test]
+fn test_add_employee_without_employee_auth_fails_when_consent_required() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    client.set_require_consent(&company_id, &true);
+
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[7u8; 32]);
+    env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+        address: &admin,
+        invoke: &soroban_sdk::testutils::MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "add_employee",
+            args: (
+                company_id,
+                admin.clone(),
+                employee.clone(),
+                commitment.clone(),
+            )
+                .into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+
+    assert!(client
+        .try_add_employee(&company_id, &admin, &employee, &commitment)
+        .is_err());
+    assert!(client.find_employee(&company_id, &employee).is_none());
+}
+
+#[test]
+fn test_add_employee_without_consent_flag_records_no_timestamp() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+
+    assert!(!client.get_require_consent(&company_id));
+    client.add_employee(
+        &company_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[7u8; 32]),
+    );
+    assert_eq!(client.get_consented_at(&company_id, &employee), None);
+}
+
+// ---------------------------------------------------------------------------
+// Company enumeration
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_list_companies_pages_over_open_companies() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    for _ in 0..5 {
+        client.register_company(&admin, &treasury);
+    }
+    // Merging closes company 2.
+    client.merge_companies(&2, &0);
+
+    let (page, next) = client.list_companies(&0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().0, 0);
+    assert_eq!(page.get(1).unwrap().0, 1);
+    assert_eq!(next, Some(2));
+
+    let (page, next) = client.list_companies(&next.unwrap(), &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().0, 3);
+    assert_eq!(page.get(1).unwrap().0, 4);
+    assert_eq!(page.get(1).unwrap().1.admin, admin);
+    assert_eq!(next, None);
+}
+
+#[test]
+fn test_list_companies_caps_limit() {
+    let (env, contract_id) = setup();
+    env.budget().reset_unlimited();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    for _ in 0..MAX_COMPANY_PAGE + 5 {
+        client.register_company(&admin, &treasury);
+    }
+
+    let (page, next) = client.list_companies(&0, &1_000);
+    assert_eq!(page.len(), MAX_COMPANY_PAGE);
+    assert_eq!(next, Some(MAX_COMPANY_PAGE));
+
+    let (page, next) = client.list_companies(&MAX_COMPANY_PAGE, &1_000);
+    assert_eq!(page.len(), 5);
+    assert_eq!(next, None);
+}
+
+// ---------------------------------------------------------------------------
+// Commitment cooldown
+// ---------------------------------------------------------------------------
+
+const THIRTY_DAYS: u64 = 30 * 24 * 60 * 60;
+
+#[test]
+fn test_commitment_change_within_cooldown_is_rejected() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+    client.add_employee(
+        &company_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    client.set_commitment_cooldown(&company_id, &THIRTY_DAYS);
+
+    env.ledger().with_mut(|l| l.timestamp = 1_700_000_000);
+    client.update_commitment(
+        &company_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+    assert_eq!(
+        client.get_last_commitment_change(&company_id, &employee),
+        Some(1_700_000_000)
+    );
+
+    env.ledger()
+        .with_mut(|l| l.timestamp = 1_700_000_000 + THIRTY_DAYS - 1);
+    let result = client.try_update_commitment(
+        &company_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[3u8; 32]),
+    );
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::ChangeTooSoon);
+    assert_eq!(
+        client.get_commitment(&company_id, &employee),
+        BytesN::from_array(&env, &[2u8; 32])
+    );
+
+    env.ledger()
+        .with_mut(|l| l.timestamp = 1_700_000_000 + THIRTY_DAYS);
+    client.update_commitment(
+        &company_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[3u8; 32]),
+    );
+    assert_eq!(
+        client.get_commitment(&company_id, &employee),
+        BytesN::from_array(&env, &[3u8; 32])
+    );
+}
+
+#[test]
+fn test_force_update_commitment_overrides_cooldown() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+    client.add_employee(
+        &company_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    client.set_commitment_cooldown(&company_id, &THIRTY_DAYS);
+    client.update_commitment(
+        &company_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+
+    client.force_update_commitment(
+        &company_id,
+        &employee,
+        &BytesN::from_array(&env, &[3u8; 32]),
+    );
+
+    assert_eq!(
+        client.get_commitment(&company_id, &employee),
+        BytesN::from_array(&env, &[3u8; 32])
+    );
+    let overridden = env.events().all().iter().any(|event| {
+        let name: Result<Symbol, _> = event.1.get(0).unwrap().try_into_val(&env);
+        name == Ok(Symbol::new(&env, "CommitmentCooldownOverridden"))
+    });
+    assert!(overridden);
+}
+
+// ---------------------------------------------------------------------------
+// Paginated employee listing
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_get_company_employees_pages_through_roster() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    // 150 enrollments overrun the default test budget.
+    env.budget().reset_unlimited();
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+    let mut enrolled = Vec::new(&env);
+    for _ in 0..150 {
+        let employee = Address::generate(&env);
+        client.add_employee(&company_id, &admin, &employee, &commitment);
+        enrolled.push_back(employee);
+    }
+
+    let mut listed = Vec::new(&env);
+    for offset in [0u32, 50, 100] {
+        let page = client.get_company_employees(&company_id, &offset, &50);
+        assert_eq!(page.len(), 50);
+        listed.append(&page);
+    }
+
+    assert_eq!(listed, enrolled);
+    assert!(client
+        .get_company_employees(&company_id, &150, &50)
+        .is_empty());
+}
+
+#[test]
+fn test_get_company_employees_caps_limit() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    // 150 enrollments overrun the default test budget.
+    env.budget().reset_unlimited();
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    for _ in 0..150 {
+        client.add_employee(
+            &company_id,
+            &admin,
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[1u8; 32]),
+        );
+    }
+
+    let first = client.get_company_employees(&company_id, &0, &u32::MAX);
+    let rest = client.get_company_employees(&company_id, &MAX_EMPLOYEE_PAGE, &u32::MAX);
+
+    assert_eq!(first.len(), MAX_EMPLOYEE_PAGE);
+    assert_eq!(rest.len(), 150 - MAX_EMPLOYEE_PAGE);
+}
+
+#[test]
+fn test_get_company_employees_tracks_moves_between_companies() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let first = client.register_company(&admin, &Address::generate(&env));
+    let second = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.add_employee(&first, &admin, &employee, &commitment);
+    client.remove_employee(&first, &employee);
+    client.add_employee(&second, &admin, &employee, &commitment);
+
+    assert!(client.get_company_employees(&first, &0, &10).is_empty());
+    assert_eq!(
+        client.get_company_employees(&second, &0, &10),
+        Vec::from_array(&env, [employee])
+    );
+}
+
+#[test]
+fn test_remove_employee_moves_last_into_freed_position() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+    let employees: [Address; 4] = core::array::from_fn(|_| Address::generate(&env));
+    for employee in employees.iter() {
+        client.add_employee(&company_id, &admin, employee, &commitment);
+    }
+
+    client.remove_employee(&company_id, &employees[1]);
+    assert_eq!(client.get_employee_count(&company_id), 3);
+    assert_eq!(
+        client.get_company_employees(&company_id, &0, &10),
+        Vec::from_array(
+            &env,
+            [
+                employees[0].clone(),
+                employees[3].clone(),
+                employees[2].clone()
+            ]
+        )
+    );
+
+    // Removing the last entry leaves the others in place.
+    client.remove_employee(&company_id, &employees[2]);
+    assert_eq!(
+        client.get_company_employees(&company_id, &1, &10),
+        Vec::from_array(&env, [employees[3].clone()])
+    );
+}
+
+// ---------------------------------------------------------------------------
+// One-step admin transfer
+// ---------------------------------------------------------------------------
+
+/// Only `signer` signs the next call, to `fn_name` with `args`.
+fn sign_only(
+    env: &Env,
+    contract_id: &Address,
+    signer: &Address,
+    fn_name: &str,
+    args: soroban_sdk::Vec<soroban_sdk::Val>,
+) {
+    env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+        address: signer,
+        invoke: &soroban_sdk::testutils::MockAuthInvoke {
+            contract: contract_id,
+            fn_name,
+            args,
+            sub_invokes: &[],
+        },
+    }]);
+}
+
+/// The last event's `AdminTransferred` company ID and (old, new) admins.
+fn last_admin_transfer(env: &Env) -> (u64, Address, Address) {
+    let event = env.events().all().last().unwrap();
+    let sym0: Symbol = event.1.get(0).unwrap().try_into_val(env).unwrap();
+    assert_eq!(sym0, Symbol::new(env, "AdminTransferred"));
+    let company_id: u64 = event.1.get(1).unwrap().try_into_val(env).unwrap();
+    let (old_admin, new_admin): (Address, Address) = event.2.try_into_val(env).unwrap();
+    (company_id, old_admin, new_admin)
+}
+
+#[test]
+fn test_transfer_admin_hands_over_company() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let employee = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+
+    client.transfer_admin(&company_id, &new_admin);
+
+    assert_eq!(client.get_company(&company_id).admin, new_admin);
+    assert_eq!(
+        last_admin_transfer(&env),
+        (company_id, admin, new_admin.clone())
+    );
+
+    // Later admin calls are authorised by the new admin.
+    client.add_employee(
+        &company_id,
+        &new_admin,
+        &employee,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    assert_eq!(env.auths()[0].0, new_admin);
+    client.update_commitment(
+        &company_id,
+        &new_admin,
+        &employee,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+    assert_eq!(env.auths()[0].0, new_admin);
+}
+
+#[test]
+fn test_transfer_admin_rejects_attacker_signature() {
+    let (env, contract_id) = setup_no_auth_mock();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    sign_only(
+        &env,
+        &contract_id,
+        &admin,
+        "register_company",
+        (admin.clone(), treasury.clone()).into_val(&env),
+    );
+    let company_id = client.register_company(&admin, &treasury);
+
+    sign_only(
+        &env,
+        &contract_id,
+        &attacker,
+        "transfer_admin",
+        (company_id, attacker.clone()).into_val(&env),
+    );
+    let result = client.try_transfer_admin(&company_id, &attacker);
+
+    assert!(result.is_err());
+    assert_eq!(client.get_company(&company_id).admin, admin);
+}
+
+#[test]
+fn test_transfer_admin_discards_pending_rotation() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let proposed = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+
+    client.propose_admin_rotation(&company_id, &admin, &proposed);
+    client.transfer_admin(&company_id, &Address::generate(&env));
+
+    let result = client.try_accept_admin_rotation(&company_id, &proposed);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        RegistryError::NoPendingRotation
+    );
+}
+
+#[test]
+fn test_admin_rotation_completes_only_with_proposed_admin_signature() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    client.propose_admin_rotation(&company_id, &admin, &new_admin);
+
+    let args: soroban_sdk::Vec<soroban_sdk::Val> = (company_id, new_admin.clone()).into_val(&env);
+    sign_only(
+        &env,
+        &contract_id,
+        &attacker,
+        "accept_admin_rotation",
+        args.clone(),
+    );
+    assert!(client
+        .try_accept_admin_rotation(&company_id, &new_admin)
+        .is_err());
+    assert_eq!(client.get_company(&company_id).admin, admin);
+
+    sign_only(
+        &env,
+        &contract_id,
+        &new_admin,
+        "accept_admin_rotation",
+        args,
+    );
+    client.accept_admin_rotation(&company_id, &new_admin);
+
+    assert_eq!(client.get_company(&company_id).admin, new_admin);
+    assert_eq!(last_admin_transfer(&env), (company_id, admin, new_admin));
+}
+
+// ---------------------------------------------------------------------------
+// Payment timestamps
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_record_payment_stores_timestamp_until_removal() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+    env.ledger().with_mut(|ledger| ledger.timestamp = 5_000);
+
+    client.record_payment(&company_id, &employee, &4_000);
+    assert_eq!(
+        client.get_last_payment_timestamp(&company_id, &employee),
+        Some(4_000)
+    );
+
+    client.remove_employee(&company_id, &employee);
+    assert_eq!(
+        client.get_last_payment_timestamp(&company_id, &employee),
+        None
+    );
+}
+
+#[test]
+fn test_record_payment_rejects_future_timestamp() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+
+    let result = client.try_record_payment(&company_id, &employee, &(env.ledger().timestamp() + 1));
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        RegistryError::TimestampInFuture
+    );
+}
+
+#[test]
+fn test_record_payment_rejects_unknown_employee() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let company_id = client.register_company(&Address::generate(&env), &Address::generate(&env));
+
+    let result = client.try_record_payment(&company_id, &Address::generate(&env), &0);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        RegistryError::EmployeeNotFound
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Typed errors
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_get_company_unknown_id_returns_company_not_found() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+
+    let result = client.try_get_company(&42u64);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::CompanyNotFound);
+}
+
+#[test]
+fn test_get_commitment_unknown_employee_returns_employee_not_found() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let company_id = client.register_company(&Address::generate(&env), &Address::generate(&env));
+
+    let result = client.try_get_commitment(&company_id, &Address::generate(&env));
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        RegistryError::EmployeeNotFound
+    );
+}
+
+#[test]
+fn test_merge_into_unknown_company_returns_company_not_found() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let source = client.register_company(&Address::generate(&env), &Address::generate(&env));
+
+    let result = client.try_merge_companies(&source, &99u64);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::CompanyNotFound);
+}
+
+// ---------------------------------------------------------------------------
+// Treasury update
+// ---------------------------------------------------------------------------
+
+/// The last event's `TreasuryUpdated` company ID and (old, new) treasuries.
+fn last_treasury_update(env: &Env) -> (u64, Address, Address) {
+    let event = env.events().all().last().unwrap();
+    let sym0: Symbol = event.1.get(0).unwrap().try_into_val(env).unwrap();
+    assert_eq!(sym0, Symbol::new(env, "TreasuryUpdated"));
+    let company_id: u64 = event.1.get(1).unwrap().try_into_val(env).unwrap();
+    let (old_treasury, new_treasury): (Address, Address) = event.2.try_into_val(env).unwrap();
+    (company_id, old_treasury, new_treasury)
+}
+
+#[test]
+fn test_update_treasury_replaces_treasury() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let new_treasury = Address::generate(&env);
+    let company_id = client.register_company(&admin, &treasury);
+
+    client.update_treasury(&company_id, &admin, &new_treasury);
+
+    assert_eq!(env.auths()[0].0, admin);
+    let info = client.get_company(&company_id);
+    assert_eq!(info.treasury, new_treasury);
+    assert_eq!(info.admin, admin);
+    assert_eq!(
+        last_treasury_update(&env),
+        (company_id, treasury, new_treasury)
+    );
+}
+
+#[test]
+fn test_update_treasury_rejects_non_admin_signature() {
+    let (env, contract_id) = setup_no_auth_mock();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    sign_only(
+        &env,
+        &contract_id,
+        &admin,
+        "register_company",
+        (admin.clone(), treasury.clone()).into_val(&env),
+    );
+    let company_id = client.register_company(&admin, &treasury);
+
+    sign_only(
+        &env,
+        &contract_id,
+        &attacker,
+        "update_treasury",
+        (company_id, admin.clone(), attacker.clone()).into_val(&env),
+    );
+    let result = client.try_update_treasury(&company_id, &admin, &attacker);
+
+    assert!(result.is_err());
+    assert_eq!(client.get_company(&company_id).treasury, treasury);
+}
+
+#[test]
+fn test_update_treasury_discards_pending_rotation() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let proposed = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+
+    client.propose_treasury_rotation(&company_id, &admin, &proposed);
+    client.update_treasury(&company_id, &admin, &Address::generate(&env));
+
+    let result = client.try_accept_treasury_rotation(&company_id, &proposed);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        RegistryError::NoPendingRotation
+    );
+}
+
+#[test]
+fn test_accept_treasury_rotation_emits_treasury_updated() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let new_treasury = Address::generate(&env);
+    let company_id = client.register_company(&admin, &treasury);
+
+    client.propose_treasury_rotation(&company_id, &admin, &new_treasury);
+    client.accept_treasury_rotation(&company_id, &new_treasury);
+
+    assert_eq!(
+        last_treasury_update(&env),
+        (company_id, treasury, new_treasury)
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Reactivation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_reactivate_employee_restores_active_status() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+    client.deactivate_employee(&company_id, &employee, &Symbol::new(&env, "leave"));
+
+    client.reactivate_employee(&company_id, &employee);
+
+    assert!(client.is_eligible(&company_id, &employee));
+    assert!(client.get_deactivation(&company_id, &employee).is_none());
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (Symbol::new(&env, "EmployeeReactivated"), company_id).into_val(&env)
+    );
+    let (emp_addr, at): (Address, u64) = event.2.try_into_val(&env).unwrap();
+    assert_eq!(emp_addr, employee);
+    assert_eq!(at, env.ledger().timestamp());
+}
+
+#[test]
+fn test_repeated_status_changes_return_typed_errors() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+    let reason = Symbol::new(&env, "resigned");
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+
+    let result = client.try_reactivate_employee(&company_id, &employee);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::EmployeeActive);
+
+    client.deactivate_employee(&company_id, &employee, &reason);
+    let result = client.try_deactivate_employee(&company_id, &employee, &reason);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        RegistryError::EmployeeInactive
+    );
+    assert_eq!(client.get_active_employee_count(&company_id), 0);
+}
+
+#[test]
+fn test_active_employee_count_tracks_status_changes() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let reason = Symbol::new(&env, "leave");
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+    let employees = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let assert_count_matches = || {
+        let active = employees
+            .iter()
+            .filter(|e| client.is_eligible(&company_id, e))
+            .count() as u32;
+        assert_eq!(client.get_active_employee_count(&company_id), active);
+        active
+    };
+
+    for employee in employees.iter() {
+        client.add_employee(&company_id, &admin, employee, &commitment);
+    }
+    assert_eq!(assert_count_matches(), 3);
+
+    client.deactivate_employee(&company_id, &employees[0], &reason);
+    client.deactivate_employee(&company_id, &employees[1], &reason);
+    assert_eq!(assert_count_matches(), 1);
+
+    // A rejected second deactivation leaves the count alone.
+    let _ = client.try_deactivate_employee(&company_id, &employees[0], &reason);
+    assert_eq!(assert_count_matches(), 1);
+
+    client.reactivate_employee(&company_id, &employees[0]);
+    let _ = client.try_reactivate_employee(&company_id, &employees[0]);
+    assert_eq!(assert_count_matches(), 2);
+
+    // Re-adding an inactive employee marks them active without a second
+    // roster entry.
+    client.add_employee(&company_id, &admin, &employees[1], &commitment);
+    assert_eq!(assert_count_matches(), 3);
+    assert_eq!(client.get_employee_count(&company_id), 3);
+
+    client.remove_employee(&company_id, &employees[2]);
+    assert_eq!(assert_count_matches(), 2);
+
+    client.set_employee_status(&company_id, &employees[0], &EmployeeStatus::Incomplete);
+    assert_eq!(assert_count_matches(), 1);
+
+    client.add_employee(&company_id, &admin, &employees[2], &commitment);
+    assert_eq!(assert_count_matches(), 2);
+
+    // Merging moves the active employees' count to the target company.
+    let target = client.register_company(&admin, &Address::generate(&env));
+    client.merge_companies(&company_id, &target);
+    assert_eq!(client.get_active_employee_count(&company_id), 0);
+    assert_eq!(client.get_active_employee_count(&target), 2);
+}
+
+// ---------------------------------------------------------------------------
+// Commitment versions
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_commitment_version_starts_at_one_and_bumps_on_update() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+
+    assert_eq!(client.get_commitment_version(&company_id, &employee), 0);
+    client.add_employee(
+        &company_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    assert_eq!(client.get_commitment_version(&company_id, &employee), 1);
+
+    client.update_commitment(
+        &company_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+    client.force_update_commitment(
+        &company_id,
+        &employee,
+        &BytesN::from_array(&env, &[3u8; 32]),
+    );
+    assert_eq!(client.get_commitment_version(&company_id, &employee), 3);
+}
+
+#[test]
+fn test_commitment_updated_event_carries_new_version() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+    let new_commitment = BytesN::from_array(&env, &[2u8; 32]);
+    client.add_employee(
+        &company_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+
+    client.update_commitment(&company_id, &admin, &employee, &new_commitment);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (Symbol::new(&env, "CommitmentUpdated"), company_id, employee).into_val(&env)
+    );
+    let data: (BytesN<32>, u32) = event.2.try_into_val(&env).unwrap();
+    assert_eq!(data, (new_commitment, 2));
+}
+
+#[test]
+fn test_commitment_version_restarts_after_removal() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+    client.update_commitment(
+        &company_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+
+    client.remove_employee(&company_id, &employee);
+    assert_eq!(client.get_commitment_version(&company_id, &employee), 0);
+
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+    assert_eq!(client.get_commitment_version(&company_id, &employee), 1);
+}
+
+#[test]
+fn test_commitment_version_moves_with_merged_employee() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let source_id = client.register_company(&admin, &Address::generate(&env));
+    let target_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+    client.add_employee(
+        &source_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    client.update_commitment(
+        &source_id,
+        &admin,
+        &employee,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+
+    client.merge_companies(&source_id, &target_id);
+
+    assert_eq!(client.get_commitment_version(&source_id, &employee), 0);
+    assert_eq!(client.get_commitment_version(&target_id, &employee), 2);
+}
+
+// ---------------------------------------------------------------------------
+// Company deactivation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_deactivated_company_rejects_enrolment_and_commitment_changes() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+
+    client.deactivate_company(&company_id);
+
+    assert!(!client.is_company_active(&company_id));
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (Symbol::new(&env, "CompanyDeactivated"), company_id).into_val(&env)
+    );
+    let data: (Address, u64) = event.2.try_into_val(&env).unwrap();
+    assert_eq!(data, (admin.clone(), env.ledger().timestamp()));
+
+    let inactive = Ok(RegistryError::CompanyInactive);
+    let result =
+        client.try_add_employee(&company_id, &admin, &Address::generate(&env), &commitment);
+    assert_eq!(result.unwrap_err(), inactive);
+    let result = client.try_update_commitment(&company_id, &admin, &employee, &commitment);
+    assert_eq!(result.unwrap_err(), inactive);
+    let result = client.try_force_update_commitment(&company_id, &employee, &commitment);
+    assert_eq!(result.unwrap_err(), inactive);
+    let result = client.try_deactivate_company(&company_id);
+    assert_eq!(result.unwrap_err(), inactive);
+}
+
+#[test]
+fn test_reactivated_company_accepts_enrolment_again() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+
+    let result = client.try_reactivate_company(&company_id);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::CompanyActive);
+
+    client.deactivate_company(&company_id);
+    client.reactivate_company(&company_id);
+
+    assert!(client.is_company_active(&company_id));
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (Symbol::new(&env, "CompanyReactivated"), company_id).into_val(&env)
+    );
+    client.add_employee(&company_id, &admin, &Address::generate(&env), &commitment);
+    assert_eq!(client.get_employee_count(&company_id), 1);
+}
+
+#[test]
+fn test_deactivated_company_can_still_rotate_admin() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let company_id = client.register_company(&Address::generate(&env), &Address::generate(&env));
+    let new_admin = Address::generate(&env);
+    client.deactivate_company(&company_id);
+
+    client.transfer_admin(&company_id, &new_admin);
+
+    assert_eq!(client.get_company(&company_id).admin, new_admin);
+    assert!(!client.is_company_active(&company_id));
+}
+
+// ---------------------------------------------------------------------------
+// Company roles
+// ---------------------------------------------------------------------------
+
+/// Register a company and grant one HR and one finance admin.
+fn setup_roles(env: &Env, client: &PayrollRegistryClient) -> (u64, Address, Address, Address) {
+    let admin = Address::generate(env);
+    let company_id = client.register_company(&admin, &Address::generate(env));
+    let hr = Address::generate(env);
+    let finance = Address::generate(env);
+    client.add_role(&company_id, &hr, &AdminRole::Hr);
+    client.add_role(&company_id, &finance, &AdminRole::Finance);
+    (company_id, admin, hr, finance)
+}
+
+#[test]
+fn test_company_admin_is_super_admin() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let (company_id, admin, hr, _) = setup_roles(&env, &client);
+
+    assert_eq!(client.get_role(&company_id, &admin), Some(AdminRole::Super));
+    assert_eq!(client.get_role(&company_id, &hr), Some(AdminRole::Hr));
+    assert_eq!(client.get_role(&company_id, &Address::generate(&env)), None);
+
+    let employee = Address::generate(&env);
+    let new_treasury = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+    client.add_employee(&company_id, &admin, &employee, &commitment);
+    client.update_treasury(&company_id, &admin, &new_treasury);
+
+    assert_eq!(client.get_commitment(&company_id, &employee), commitment);
+    assert_eq!(client.get_company(&company_id).treasury, new_treasury);
+}
+
+#[test]
+fn test_hr_admin_onboards_but_cannot_change_treasury() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let (company_id, _, hr, _) = setup_roles(&env, &client);
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.add_employee(&company_id, &hr, &employee, &commitment);
+    assert_eq!(client.get_commitment(&company_id, &employee), commitment);
+
+    let treasury = client.get_company(&company_id).treasury;
+    let result = client.try_update_treasury(&company_id, &hr, &Address::generate(&env));
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::NotAuthorized);
+    assert_eq!(client.get_company(&company_id).treasury, treasury);
+}
+
+#[test]
+fn test_hr_admin_changes_commitment_only_through_proposal() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let (company_id, _, hr, _) = setup_roles(&env, &client);
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+    client.add_employee(&company_id, &hr, &employee, &commitment);
+
+    let updated = BytesN::from_array(&env, &[2u8; 32]);
+    let result = client.try_update_commitment(&company_id, &hr, &employee, &updated);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::NotAuthorized);
+    assert_eq!(client.get_commitment(&company_id, &employee), commitment);
+
+    client.propose_salary_change(&company_id, &hr, &employee, &updated);
+    let pending = client
+        .get_pending_salary_change(&company_id, &employee)
+        .unwrap();
+    assert_eq!(pending.proposed_by, hr);
+    assert_eq!(pending.new_commitment, updated);
+    assert_eq!(client.get_commitment(&company_id, &employee), commitment);
+}
+
+#[test]
+fn test_finance_admin_changes_treasury_but_cannot_onboard() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let (company_id, _, _, finance) = setup_roles(&env, &client);
+    let employee = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[1u8; 32]);
+
+    let result = client.try_add_employee(&company_id, &finance, &employee, &commitment);
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::NotAuthorized);
+    assert_eq!(client.find_employee(&company_id, &employee), None);
+
+    let new_treasury = Address::generate(&env);
+    client.update_treasury(&company_id, &finance, &new_treasury);
+    assert_eq!(client.get_company(&company_id).treasury, new_treasury);
+}
+
+#[test]
+fn test_removed_role_no_longer_authorises() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let (company_id, _, hr, _) = setup_roles(&env, &client);
+
+    client.remove_role(&company_id, &hr);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (Symbol::new(&env, "RoleRevoked"), company_id, hr.clone()).into_val(&env)
+    );
+    assert_eq!(client.get_role(&company_id, &hr), None);
+    let result = client.try_add_employee(
+        &company_id,
+        &hr,
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::NotAuthorized);
+}
+
+// ---------------------------------------------------------------------------
+// Bulk onboarding
+// ---------------------------------------------------------------------------
+
+/// `n` fresh employees, each with a distinct commitment.
+fn enrolment_entries(env: &Env, n: u32) -> Vec<(Address, BytesN<32>)> {
+    let mut entries = Vec::new(env);
+    for i in 0..n {
+        let mut commitment = [0u8; 32];
+        commitment[..4].copy_from_slice(&i.to_be_bytes());
+        entries.push_back((Address::generate(env), BytesN::from_array(env, &commitment)));
+    }
+    entries
+}
+
+#[test]
+fn test_batch_add_employees_enrols_fifty() {
+    let (env, contract_id) = setup();
+    env.budget().reset_unlimited();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let company_id = client.register_company(&Address::generate(&env), &Address::generate(&env));
+    let entries = enrolment_entries(&env, MAX_ENROLMENT_BATCH);
+
+    client.batch_add_employees(&company_id, &entries);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (Symbol::new(&env, "EmployeesBatchAdded"), company_id).into_val(&env)
+    );
+    let data: (u32,) = event.2.try_into_val(&env).unwrap();
+    assert_eq!(data, (MAX_ENROLMENT_BATCH,));
+    assert_eq!(client.get_employee_count(&company_id), MAX_ENROLMENT_BATCH);
+    assert_eq!(
+        client.get_active_employee_count(&company_id),
+        MAX_ENROLMENT_BATCH
+    );
+    let roster = client.get_company_employees(&company_id, &0, &MAX_ENROLMENT_BATCH);
+    for (idx, (employee, commitment)) in entries.iter().enumerate() {
+        assert_eq!(roster.get(idx as u32), Some(employee.clone()));
+        assert_eq!(client.get_commitment(&company_id, &employee), commitment);
+        assert_eq!(client.get_commitment_version(&company_id, &employee), 1);
+    }
+}
+
+#[test]
+fn test_batch_add_employees_rejects_batch_past_cap() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let company_id = client.register_company(&Address::generate(&env), &Address::generate(&env));
+    client.set_max_employees(&company_id, &2);
+    let entries = enrolment_entries(&env, 3);
+
+    let result = client.try_batch_add_employees(&company_id, &entries);
+
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::CompanyFull);
+    assert_eq!(client.get_employee_count(&company_id), 0);
+}
+
+#[test]
+fn test_batch_add_employees_rejects_duplicate_in_batch() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let company_id = client.register_company(&Address::generate(&env), &Address::generate(&env));
+    let mut entries = enrolment_entries(&env, 3);
+    entries.push_back(entries.get(0).unwrap());
+
+    let result = client.try_batch_add_employees(&company_id, &entries);
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        RegistryError::DuplicateEmployee
+    );
+    assert_eq!(client.get_employee_count(&company_id), 0);
+    let (first, _) = entries.get(0).unwrap();
+    assert_eq!(client.find_employee(&company_id, &first), None);
+}
+
+#[test]
+fn test_batch_add_employees_rejects_existing_active_employee() {
+    let (env, contract_id) = setup();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let company_id = client.register_company(&admin, &Address::generate(&env));
+    let entries = enrolment_entries(&env, 2);
+    let (existing, commitment) = entries.get(1).unwrap();
+    client.add_employee(&company_id, &admin, &existing, &commitment);
+
+    let result = client.try_batch_add_employees(&company_id, &entries);
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        RegistryError::DuplicateEmployee
+    );
+    assert_eq!(client.get_employee_count(&company_id), 1);
+}
+
+#[test]
+fn test_batch_add_employees_rejects_oversized_batch() {
+    let (env, contract_id) = setup();
+    env.budget().reset_unlimited();
+    let client = PayrollRegistryClient::new(&env, &contract_id);
+    let company_id = client.register_company(&Address::generate(&env), &Address::generate(&env));
+    let entries = enrolment_entries(&env, MAX_ENROLMENT_BATCH + 1);
+
+    let result = client.try_batch_add_employees(&company_id, &entries);
+
+    assert_eq!(result.unwrap_err().unwrap(), RegistryError::BatchTooLarge);
+    assert_eq!(client.get_employee_count(&company_id), 0);
+}
@@ -1,6 +1,136 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, BytesN, Env, Vec};
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ff::{BigInt, PrimeField};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Bytes, BytesN,
+    Env, Map, Symbol, Vec,
+};
+
+/// Position of `recipient_hash` in a payment proof's public inputs
+/// `[salary_commitment, payment_nullifier, recipient_hash]`.
+pub const RECIPIENT_HASH_INPUT: u32 = 2;
+
+/// Public input carrying an entry's position in an indexed batch.
+///
+/// `index` as a big-endian integer in the low four bytes, upper bytes zero.
+/// A circuit exposing this signal binds each proof to its slot, so proofs
+/// and inputs assembled in different orders fail verification.
+pub fn batch_index_input(env: &Env, index: u32) -> BytesN<32> {
+    let mut input = [0u8; 32];
+    input[28..].copy_from_slice(&index.to_be_bytes());
+    BytesN::from_array(env, &input)
+}
+
+/// Hash identifying `recipient` inside the payment circuit.
+///
+/// SHA-256 of the address's XDR encoding with the top three bits cleared, so
+/// the big-endian value is always a canonical BN254 scalar. Off-chain provers
+/// must compute `recipient_hash` the same way.
+pub fn recipient_hash(env: &Env, recipient: &Address) -> BytesN<32> {
+    let digest: BytesN<32> = env.crypto().sha256(&recipient.clone().to_xdr(env)).into();
+    let mut hash = digest.to_array();
+    hash[0] &= 0x1f;
+    BytesN::from_array(env, &hash)
+}
+
+/// Whether `bytes` (`x ‖ y`, 32-byte big-endian coordinates) encode a BN254
+/// G1 point. All zeros is the point at infinity; anything else must have
+/// canonical coordinates and lie on the curve. G1 has cofactor 1, so that
+/// also puts it in the prime-order subgroup.
+pub fn is_valid_g1(bytes: &[u8; 64]) -> bool {
+    if bytes.iter().all(|b| *b == 0) {
+        return true;
+    }
+    let (Some(x), Some(y)) = (field_element(&bytes[..32]), field_element(&bytes[32..])) else {
+        return false;
+    };
+    G1Affine::new_unchecked(x, y).is_on_curve()
+}
+
+/// Whether `bytes` (`x0 ‖ x1 ‖ y0 ‖ y1`, where `x = x0 + x1·u`) encode a
+/// BN254 G2 point. All zeros is the point at infinity; anything else must
+/// have canonical coordinates and lie on the twist.
+///
+/// Subgroup membership is deliberately not checked. It needs a full scalar
+/// multiplication in guest Wasm, whose CPU cost and code size have not been
+/// measured against the network limits, so it is left to the pairing check.
+pub fn is_valid_g2(bytes: &[u8; 128]) -> bool {
+    if bytes.iter().all(|b| *b == 0) {
+        return true;
+    }
+    let mut coords = bytes.chunks(32).map(field_element);
+    let (Some(Some(x0)), Some(Some(x1)), Some(Some(y0)), Some(Some(y1))) =
+        (coords.next(), coords.next(), coords.next(), coords.next())
+    else {
+        return false;
+    };
+    G2Affine::new_unchecked(Fq2::new(x0, x1), Fq2::new(y0, y1)).is_on_curve()
+}
+
+/// Whether every point of a packed proof (see `Groth16Proof`) is valid.
+///
+/// A host pairing check traps on malformed points, so proofs failing this
+/// are reported invalid before the pairing check runs.
+pub fn proof_points_valid(proof: &BytesN<256>) -> bool {
+    let bytes = proof.to_array();
+    let mut a = [0u8; 64];
+    let mut b = [0u8; 128];
+    let mut c = [0u8; 64];
+    a.copy_from_slice(&bytes[..64]);
+    b.copy_from_slice(&bytes[64..192]);
+    c.copy_from_slice(&bytes[192..]);
+    is_valid_g1(&a) && is_valid_g2(&b) && is_valid_g1(&c)
+}
+
+/// A 32-byte big-endian base field element, or `None` if not below the
+/// field modulus.
+fn field_element(bytes: &[u8]) -> Option<Fq> {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks(8)) {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(chunk);
+        *limb = u64::from_be_bytes(word);
+    }
+    Fq::from_bigint(BigInt::new(limbs))
+}
+
+/// Helpers for building mock proofs that pass [`proof_points_valid`].
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils {
+    use ark_bn254::{Fq, Fr, G1Affine, G2Affine};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::PrimeField;
+    use soroban_sdk::{BytesN, Env};
+
+    /// `seed`·G1 encoded as `Groth16Proof::a` (`0` gives the point at infinity).
+    pub fn g1_point(env: &Env, seed: u64) -> BytesN<64> {
+        let p = (G1Affine::generator() * Fr::from(seed)).into_affine();
+        let mut out = [0u8; 64];
+        out[..32].copy_from_slice(&fq_bytes(&p.x));
+        out[32..].copy_from_slice(&fq_bytes(&p.y));
+        BytesN::from_array(env, &out)
+    }
+
+    /// `seed`·G2 encoded as `Groth16Proof::b` (`0` gives the point at infinity).
+    pub fn g2_point(env: &Env, seed: u64) -> BytesN<128> {
+        let p = (G2Affine::generator() * Fr::from(seed)).into_affine();
+        let mut out = [0u8; 128];
+        for (i, c) in [p.x.c0, p.x.c1, p.y.c0, p.y.c1].iter().enumerate() {
+            out[i * 32..(i + 1) * 32].copy_from_slice(&fq_bytes(c));
+        }
+        BytesN::from_array(env, &out)
+    }
+
+    fn fq_bytes(x: &Fq) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        for (chunk, limb) in out.rchunks_mut(8).zip(x.into_bigint().0) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        out
+    }
+}
 
 /// Groth16 proof components (G1 A, G2 B, G1 C) for BN254.
 #[contracttype]
@@ -21,10 +151,44 @@ pub struct VerificationKey {
     pub ic: Vec<BytesN<64>>,
 }
 
+/// Proof system a company's circuit was compiled for.
+///
+/// Groth16 needs a trusted setup per circuit; PLONK uses a universal setup,
+/// which suits circuits that change often.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofSystem {
+    Groth16,
+    /// Not verifiable yet; proofs fail with
+    /// `VerifierError::UnsupportedProofSystem`.
+    Plonk,
+}
+
 #[contracttype]
 pub enum DataKey {
-    VerificationKey,
+    /// Key registered with `register_vk` for a payroll registry company.
+    VerificationKey(u64),
     Admin,
+    /// `ProofSystem` of a company's circuit; `Groth16` when unset.
+    ProofSystem(u64),
+}
+
+/// Error returned by a second call to `init_verifier_admin` or
+/// `initialize_verifier`.
+#[contracterror]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum InitError {
+    AlreadyInitialized = 1,
+}
+
+/// Typed errors raised during verification with `panic_with_error!`.
+#[contracterror]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum VerifierError {
+    /// The circuit's proof system has no verification routine yet.
+    UnsupportedProofSystem = 1,
 }
 
 #[contract]
@@ -32,11 +196,12 @@ pub struct ProofVerifier;
 
 #[contractimpl]
 impl ProofVerifier {
-    pub fn init_verifier_admin(env: Env, admin: soroban_sdk::Address) {
+    pub fn init_verifier_admin(env: Env, admin: soroban_sdk::Address) -> Result<(), InitError> {
         if env.storage().persistent().has(&DataKey::Admin) {
-            panic!("Already initialized");
+            return Err(InitError::AlreadyInitialized);
         }
         env.storage().persistent().set(&DataKey::Admin, &admin);
+        Ok(())
     }
 
     pub fn get_verifier_admin(env: Env) -> soroban_sdk::Address {
@@ -46,45 +211,204 @@ impl ProofVerifier {
             .expect("Not initialized")
     }
 
-    pub fn initialize_verifier(env: Env, vk: VerificationKey) {
+    /// Install the default verification key, used by every company without
+    /// a key of its own.
+    pub fn initialize_verifier(env: Env, vk: VerificationKey) -> Result<(), InitError> {
         Self::require_admin(&env);
 
-        if env.storage().persistent().has(&DataKey::VerificationKey) {
-            panic!("Verifier already initialized");
+        let key = Self::default_vk_key(&env);
+        if env.storage().persistent().has(&key) {
+            return Err(InitError::AlreadyInitialized);
         }
-        env.storage()
-            .persistent()
-            .set(&DataKey::VerificationKey, &vk);
+        env.storage().persistent().set(&key, &vk);
+        Ok(())
     }
 
+    /// Whether a default verification key has been installed.
+    pub fn is_initialized(env: Env) -> bool {
+        env.storage().persistent().has(&Self::default_vk_key(&env))
+    }
+
+    /// The default verification key.
     pub fn get_verification_key(env: Env) -> VerificationKey {
         env.storage()
             .persistent()
-            .get(&DataKey::VerificationKey)
+            .get(&Self::default_vk_key(&env))
             .expect("Verifier not initialized")
     }
 
+    /// Register `company_id`'s verification key, replacing any earlier one.
+    /// Requires the verifier admin's authorisation.
+    ///
+    /// Lets companies use circuits with different parameters; payment
+    /// proofs for the company are then checked against this key instead of
+    /// the default one.
+    pub fn register_vk(env: Env, company_id: u64, vk: VerificationKey) {
+        Self::require_admin(&env);
+        env.storage()
+            .persistent()
+            .set(&DataKey::VerificationKey(company_id), &vk);
+    }
+
+    /// The key `company_id`'s payment proofs are checked against: its own
+    /// if registered, otherwise the default key.
+    pub fn get_company_vk(env: Env, company_id: u64) -> VerificationKey {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VerificationKey(company_id))
+            .unwrap_or_else(|| Self::get_verification_key(env))
+    }
+
+    /// Select the proof system `company_id`'s proofs are verified with,
+    /// stored next to its verification key. Requires the verifier admin's
+    /// authorisation.
+    pub fn set_proof_system(env: Env, company_id: u64, system: ProofSystem) {
+        Self::require_admin(&env);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProofSystem(company_id), &system);
+    }
+
+    /// `company_id`'s proof system; `Groth16` unless set otherwise.
+    pub fn get_proof_system(env: Env, company_id: u64) -> ProofSystem {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ProofSystem(company_id))
+            .unwrap_or(ProofSystem::Groth16)
+    }
+
+    /// Verify `proof` against the default verification key, which is always
+    /// a Groth16 circuit.
     pub fn verify(env: Env, proof: Groth16Proof, public_inputs: Vec<BytesN<32>>) -> bool {
         let proof_bytes = Self::pack_groth16_proof(&env, &proof);
-        Self::verify_payment_proof(env, proof_bytes, public_inputs)
+        let vk = Self::get_verification_key(env.clone());
+
+        Self::check_proof(&env, ProofSystem::Groth16, &vk, proof_bytes, public_inputs)
     }
 
+    /// Verify a payment proof against `company_id`'s verification key (see
+    /// [`Self::get_company_vk`]) with the company's proof system.
     pub fn verify_payment_proof(
         env: Env,
+        company_id: u64,
         proof: BytesN<256>,
         public_inputs: Vec<BytesN<32>>,
     ) -> bool {
-        let vk: VerificationKey = env
-            .storage()
-            .persistent()
-            .get(&DataKey::VerificationKey)
-            .expect("Verifier not initialized");
+        let vk = Self::get_company_vk(env.clone(), company_id);
+        let system = Self::get_proof_system(env.clone(), company_id);
+
+        Self::check_proof(&env, system, &vk, proof, public_inputs)
+    }
 
-        if public_inputs.len() + 1 != vk.ic.len() {
+    /// Verify a payment proof whose nullifier is bound to `recipient`.
+    ///
+    /// `public_inputs` are the prover's public signals. The circuit constrains
+    /// `payment_nullifier = Poseidon(blinding, period, recipient_hash)`; this
+    /// check pins `recipient_hash` to `recipient`, so a nullifier proven for
+    /// one recipient cannot be used to pay another.
+    pub fn verify_bound_payment_proof(
+        env: Env,
+        company_id: u64,
+        proof: BytesN<256>,
+        public_inputs: Vec<BytesN<32>>,
+        recipient: Address,
+    ) -> bool {
+        if public_inputs.get(RECIPIENT_HASH_INPUT) != Some(recipient_hash(&env, &recipient)) {
             return false;
         }
+        Self::verify_payment_proof(env, company_id, proof, public_inputs)
+    }
 
-        Self::simulated_verify_groth16(&env, &vk, proof, public_inputs)
+    /// Verify a batch of proofs against `company_id`'s verification key,
+    /// returning one result per position.
+    ///
+    /// Identical `(proof, public_inputs)` pairs are only checked once: each
+    /// pair is keyed by the SHA-256 digest of the packed proof and inputs, and
+    /// the first result is reused for every later occurrence.
+    pub fn verify_batch_proofs(
+        env: Env,
+        company_id: u64,
+        proofs: Vec<Groth16Proof>,
+        public_inputs: Vec<Vec<BytesN<32>>>,
+    ) -> Vec<bool> {
+        Self::verify_batch(&env, company_id, proofs, public_inputs, false)
+    }
+
+    /// Verify a batch whose entries are bound to their positions.
+    ///
+    /// As [`Self::verify_batch_proofs`], but the last public input of entry
+    /// `i` must be [`batch_index_input`]`(i)`; an entry carrying another
+    /// index is `false` without a pairing check, so a batch whose inputs
+    /// were reordered relative to its proofs cannot verify.
+    pub fn verify_indexed_batch_proofs(
+        env: Env,
+        company_id: u64,
+        proofs: Vec<Groth16Proof>,
+        public_inputs: Vec<Vec<BytesN<32>>>,
+    ) -> Vec<bool> {
+        Self::verify_batch(&env, company_id, proofs, public_inputs, true)
+    }
+
+    fn verify_batch(
+        env: &Env,
+        company_id: u64,
+        proofs: Vec<Groth16Proof>,
+        public_inputs: Vec<Vec<BytesN<32>>>,
+        indexed: bool,
+    ) -> Vec<bool> {
+        if proofs.len() != public_inputs.len() {
+            panic!("Array length mismatch");
+        }
+
+        let vk = Self::get_company_vk(env.clone(), company_id);
+        let system = Self::get_proof_system(env.clone(), company_id);
+
+        let mut seen: Map<BytesN<32>, bool> = Map::new(env);
+        let mut results = Vec::new(env);
+        for (i, (proof, inputs)) in proofs.iter().zip(public_inputs.iter()).enumerate() {
+            if indexed && inputs.last() != Some(batch_index_input(env, i as u32)) {
+                results.push_back(false);
+                continue;
+            }
+            let packed = Self::pack_groth16_proof(env, &proof);
+            let digest = Self::proof_digest(env, &packed, &inputs);
+            let valid = match seen.get(digest.clone()) {
+                Some(valid) => valid,
+                None => {
+                    let valid = Self::check_proof(env, system, &vk, packed, inputs);
+                    seen.set(digest, valid);
+                    valid
+                }
+            };
+            results.push_back(valid);
+        }
+        results
+    }
+
+    fn check_proof(
+        env: &Env,
+        system: ProofSystem,
+        vk: &VerificationKey,
+        proof: BytesN<256>,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> bool {
+        match system {
+            ProofSystem::Groth16 => {
+                if public_inputs.len() + 1 != vk.ic.len() || !proof_points_valid(&proof) {
+                    return false;
+                }
+                Self::simulated_verify_groth16(env, vk, proof, public_inputs)
+            }
+            ProofSystem::Plonk => panic_with_error!(env, VerifierError::UnsupportedProofSystem),
+        }
+    }
+
+    fn proof_digest(env: &Env, proof: &BytesN<256>, public_inputs: &Vec<BytesN<32>>) -> BytesN<32> {
+        let mut preimage = Bytes::from_array(env, &proof.to_array());
+        for input in public_inputs.iter() {
+            preimage.extend_from_array(&input.to_array());
+        }
+        env.crypto().sha256(&preimage).into()
     }
 
     fn pack_groth16_proof(env: &Env, proof: &Groth16Proof) -> BytesN<256> {
@@ -101,9 +425,20 @@ impl ProofVerifier {
         _proof: BytesN<256>,
         _public_inputs: Vec<BytesN<32>>,
     ) -> bool {
+        #[cfg(test)]
+        tests::PAIRING_CHECKS.with(|n| n.set(n.get() + 1));
         true
     }
 
+    /// Storage key of the default verification key.
+    ///
+    /// Encoded as the unit `DataKey::VerificationKey` was before keys were
+    /// registered per company, so a key installed by an earlier version is
+    /// still found and serves as the default.
+    fn default_vk_key(env: &Env) -> Vec<Symbol> {
+        Vec::from_array(env, [Symbol::new(env, "VerificationKey")])
+    }
+
     fn require_admin(env: &Env) {
         let admin: soroban_sdk::Address = env
             .storage()
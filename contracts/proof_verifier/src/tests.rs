@@ -1,6 +1,14 @@
 use super::*;
 use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{Env, Vec};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Env, Symbol, Vec};
+
+extern crate std;
+
+std::thread_local! {
+    /// Pairing checks run on this thread; bumped by `simulated_verify_groth16`.
+    pub(crate) static PAIRING_CHECKS: core::cell::Cell<u32> = const { core::cell::Cell::new(0) };
+}
 
 fn mock_verification_key(env: &Env) -> VerificationKey {
     VerificationKey {
@@ -20,7 +28,12 @@ fn mock_verification_key(env: &Env) -> VerificationKey {
 }
 
 fn mock_snarkjs_proof(env: &Env) -> BytesN<256> {
-    BytesN::from_array(env, &[8u8; 256])
+    packed_proof(
+        env,
+        &testutils::g1_point(env, 8).to_array(),
+        &testutils::g2_point(env, 8).to_array(),
+        &testutils::g1_point(env, 9).to_array(),
+    )
 }
 
 #[test]
@@ -56,8 +69,7 @@ fn test_initialize_verifier_stores_vk() {
 }
 
 #[test]
-#[should_panic(expected = "Verifier already initialized")]
-fn test_initialize_verifier_twice_panics() {
+fn test_initialize_verifier_twice_returns_already_initialized() {
     let env = Env::default();
     env.mock_all_auths();
     let contract_id = env.register_contract(None, ProofVerifier);
@@ -68,7 +80,25 @@ fn test_initialize_verifier_twice_panics() {
 
     let vk = mock_verification_key(&env);
     client.initialize_verifier(&vk);
-    client.initialize_verifier(&vk);
+    assert_eq!(
+        client.try_initialize_verifier(&vk),
+        Err(Ok(InitError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_init_verifier_admin_twice_returns_already_initialized() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, ProofVerifier);
+    let client = ProofVerifierClient::new(&env, &contract_id);
+
+    let admin = soroban_sdk::Address::generate(&env);
+    client.init_verifier_admin(&admin);
+    assert_eq!(
+        client.try_init_verifier_admin(&admin),
+        Err(Ok(InitError::AlreadyInitialized))
+    );
+    assert_eq!(client.get_verifier_admin(), admin);
 }
 
 #[test]
@@ -103,7 +133,7 @@ fn test_verify_payment_proof_interface() {
         ],
     );
 
-    let is_valid = client.verify_payment_proof(&proof, &public_inputs);
+    let is_valid = client.verify_payment_proof(&1, &proof, &public_inputs);
     assert!(is_valid);
 }
 
@@ -123,7 +153,7 @@ fn test_verify_payment_proof_rejects_wrong_input_length() {
     let proof = mock_snarkjs_proof(&env);
     let short_inputs = Vec::from_array(&env, [BytesN::from_array(&env, &[11u8; 32])]);
 
-    let is_valid = client.verify_payment_proof(&proof, &short_inputs);
+    let is_valid = client.verify_payment_proof(&1, &proof, &short_inputs);
     assert!(!is_valid);
 }
 
@@ -140,3 +170,563 @@ fn test_unauthorized_initialize_verifier_fails() {
     let vk = mock_verification_key(&env);
     client.initialize_verifier(&vk);
 }
+
+fn setup_initialized(env: &Env) -> ProofVerifierClient<'_> {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ProofVerifier);
+    let client = ProofVerifierClient::new(env, &contract_id);
+    client.init_verifier_admin(&soroban_sdk::Address::generate(env));
+    client.initialize_verifier(&mock_verification_key(env));
+    client
+}
+
+/// Public inputs `[commitment, nullifier, recipient_hash]` for `recipient`,
+/// verified against a key sized for three inputs.
+fn setup_bound<'a>(
+    env: &'a Env,
+    recipient: &soroban_sdk::Address,
+) -> (ProofVerifierClient<'a>, Vec<BytesN<32>>) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ProofVerifier);
+    let client = ProofVerifierClient::new(env, &contract_id);
+    client.init_verifier_admin(&soroban_sdk::Address::generate(env));
+    let mut vk = mock_verification_key(env);
+    vk.ic.push_back(BytesN::from_array(env, &[9u8; 64]));
+    client.initialize_verifier(&vk);
+
+    let inputs = Vec::from_array(
+        env,
+        [
+            BytesN::from_array(env, &[1u8; 32]),
+            BytesN::from_array(env, &[2u8; 32]),
+            recipient_hash(env, recipient),
+        ],
+    );
+    (client, inputs)
+}
+
+#[test]
+fn test_recipient_hash_is_a_canonical_scalar_per_address() {
+    let env = Env::default();
+    let alice = soroban_sdk::Address::generate(&env);
+    let bob = soroban_sdk::Address::generate(&env);
+
+    let hash = recipient_hash(&env, &alice);
+    assert_eq!(hash, recipient_hash(&env, &alice));
+    assert_ne!(hash, recipient_hash(&env, &bob));
+    assert_eq!(hash.to_array()[0] & 0xe0, 0, "must fit the BN254 field");
+}
+
+/// Known vector shared with the CLI's `crypto::recipient_hash`, which must
+/// produce the same bytes off-chain.
+#[test]
+fn test_recipient_hash_known_vector() {
+    let env = Env::default();
+    let account = soroban_sdk::Address::from_string(&soroban_sdk::String::from_str(
+        &env,
+        "GADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOZPI",
+    ));
+
+    let expected: [u8; 32] = [
+        0x14, 0xc3, 0x99, 0x82, 0x31, 0x7f, 0xee, 0x7c, 0x3c, 0x08, 0x4a, 0x7c, 0x35, 0x8e, 0xe8,
+        0xe1, 0x2b, 0x4d, 0xe9, 0x71, 0x12, 0xf9, 0x5b, 0xfe, 0x3f, 0xeb, 0x98, 0xb0, 0x66, 0x06,
+        0x5b, 0x5a,
+    ];
+    assert_eq!(recipient_hash(&env, &account).to_array(), expected);
+}
+
+#[test]
+fn test_verify_bound_payment_proof_pins_recipient() {
+    let env = Env::default();
+    let alice = soroban_sdk::Address::generate(&env);
+    let bob = soroban_sdk::Address::generate(&env);
+    let (client, alice_inputs) = setup_bound(&env, &alice);
+    let proof = mock_snarkjs_proof(&env);
+
+    assert!(client.verify_bound_payment_proof(&1, &proof, &alice_inputs, &alice));
+    assert!(!client.verify_bound_payment_proof(&1, &proof, &alice_inputs, &bob));
+
+    // Inputs without a recipient hash cannot be bound to anyone.
+    let short = Vec::from_array(
+        &env,
+        [alice_inputs.get(0).unwrap(), alice_inputs.get(1).unwrap()],
+    );
+    assert!(!client.verify_bound_payment_proof(&1, &proof, &short, &alice));
+}
+
+fn mock_groth16_proof(env: &Env, seed: u8) -> Groth16Proof {
+    Groth16Proof {
+        a: testutils::g1_point(env, seed.into()),
+        b: testutils::g2_point(env, seed.into()),
+        c: testutils::g1_point(env, seed.into()),
+    }
+}
+
+fn mock_inputs(env: &Env, seed: u8) -> Vec<BytesN<32>> {
+    Vec::from_array(
+        env,
+        [
+            BytesN::from_array(env, &[seed; 32]),
+            BytesN::from_array(env, &[seed.wrapping_add(1); 32]),
+        ],
+    )
+}
+
+#[test]
+fn test_verify_batch_proofs_checks_duplicate_once() {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+    PAIRING_CHECKS.with(|n| n.set(0));
+
+    let proof = mock_groth16_proof(&env, 1);
+    let inputs = mock_inputs(&env, 10);
+    let proofs = Vec::from_array(&env, [proof.clone(), proof]);
+    let public_inputs = Vec::from_array(&env, [inputs.clone(), inputs]);
+
+    let results = client.verify_batch_proofs(&1, &proofs, &public_inputs);
+
+    assert_eq!(results, Vec::from_array(&env, [true, true]));
+    assert_eq!(PAIRING_CHECKS.with(|n| n.get()), 1);
+}
+
+#[test]
+fn test_verify_batch_proofs_distinct_entries_checked_individually() {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+    PAIRING_CHECKS.with(|n| n.set(0));
+
+    let proof = mock_groth16_proof(&env, 1);
+    // Same proof with different inputs is a distinct entry.
+    let proofs = Vec::from_array(&env, [proof.clone(), proof, mock_groth16_proof(&env, 2)]);
+    let short_inputs = Vec::from_array(&env, [BytesN::from_array(&env, &[11u8; 32])]);
+    let public_inputs = Vec::from_array(
+        &env,
+        [mock_inputs(&env, 10), mock_inputs(&env, 20), short_inputs],
+    );
+
+    let results = client.verify_batch_proofs(&1, &proofs, &public_inputs);
+
+    assert_eq!(results, Vec::from_array(&env, [true, true, false]));
+    assert_eq!(PAIRING_CHECKS.with(|n| n.get()), 2);
+}
+
+#[test]
+#[should_panic(expected = "Array length mismatch")]
+fn test_verify_batch_proofs_rejects_length_mismatch() {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+
+    let proofs = Vec::from_array(&env, [mock_groth16_proof(&env, 1)]);
+    let public_inputs: Vec<Vec<BytesN<32>>> = Vec::new(&env);
+
+    client.verify_batch_proofs(&1, &proofs, &public_inputs);
+}
+
+/// `count` distinct proofs, each with a data input and its batch index.
+fn indexed_batch(env: &Env, count: u32) -> (Vec<Groth16Proof>, Vec<Vec<BytesN<32>>>) {
+    let mut proofs = Vec::new(env);
+    let mut public_inputs = Vec::new(env);
+    for i in 0..count {
+        proofs.push_back(mock_groth16_proof(env, i as u8));
+        public_inputs.push_back(Vec::from_array(
+            env,
+            [
+                BytesN::from_array(env, &[i as u8 + 10; 32]),
+                batch_index_input(env, i),
+            ],
+        ));
+    }
+    (proofs, public_inputs)
+}
+
+#[test]
+fn test_verify_indexed_batch_proofs_accepts_ordered_batch() {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+    let (proofs, public_inputs) = indexed_batch(&env, 3);
+
+    let results = client.verify_indexed_batch_proofs(&1, &proofs, &public_inputs);
+
+    assert_eq!(results, Vec::from_array(&env, [true, true, true]));
+}
+
+#[test]
+fn test_verify_indexed_batch_proofs_rejects_shuffled_batch() {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+    let (proofs, public_inputs) = indexed_batch(&env, 3);
+
+    // Inputs for entries 0 and 1 swapped relative to their proofs.
+    let mut shuffled = public_inputs.clone();
+    shuffled.set(0, public_inputs.get(1).unwrap());
+    shuffled.set(1, public_inputs.get(0).unwrap());
+    let results = client.verify_indexed_batch_proofs(&1, &proofs, &shuffled);
+    assert_eq!(results, Vec::from_array(&env, [false, false, true]));
+
+    // Whole entries moved: each still carries its original index.
+    let mut reordered_proofs = Vec::new(&env);
+    let mut reordered_inputs = Vec::new(&env);
+    for i in [2, 0, 1] {
+        reordered_proofs.push_back(proofs.get(i).unwrap());
+        reordered_inputs.push_back(public_inputs.get(i).unwrap());
+    }
+    let results = client.verify_indexed_batch_proofs(&1, &reordered_proofs, &reordered_inputs);
+    assert_eq!(results, Vec::from_array(&env, [false, false, false]));
+}
+
+#[test]
+fn test_batch_index_input_encodes_index_big_endian() {
+    let env = Env::default();
+    let mut expected = [0u8; 32];
+    expected[30] = 0x01;
+    expected[31] = 0x02;
+
+    assert_eq!(batch_index_input(&env, 0x0102).to_array(), expected);
+}
+
+// ---------------------------------------------------------------------------
+// Proof-system selection
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_groth16_is_the_default_proof_system() {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+
+    assert_eq!(client.get_proof_system(&1), ProofSystem::Groth16);
+    let inputs = mock_inputs(&env, 1);
+    assert!(client.verify_payment_proof(&1, &mock_snarkjs_proof(&env), &inputs));
+}
+
+#[test]
+fn test_plonk_proofs_are_rejected_as_unsupported() {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+    client.set_proof_system(&1, &ProofSystem::Plonk);
+    assert_eq!(client.get_proof_system(&1), ProofSystem::Plonk);
+
+    let result =
+        client.try_verify_payment_proof(&1, &mock_snarkjs_proof(&env), &mock_inputs(&env, 1));
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        VerifierError::UnsupportedProofSystem.into()
+    );
+    let result = client.try_verify_batch_proofs(
+        &1,
+        &Vec::from_array(&env, [mock_groth16_proof(&env, 1)]),
+        &Vec::from_array(&env, [mock_inputs(&env, 1)]),
+    );
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        VerifierError::UnsupportedProofSystem.into()
+    );
+
+    // Switching back restores Groth16 verification.
+    client.set_proof_system(&1, &ProofSystem::Groth16);
+    let inputs = mock_inputs(&env, 1);
+    assert!(client.verify_payment_proof(&1, &mock_snarkjs_proof(&env), &inputs));
+}
+
+#[test]
+fn test_proof_system_is_chosen_per_company() {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+    client.set_proof_system(&1, &ProofSystem::Plonk);
+
+    assert_eq!(client.get_proof_system(&2), ProofSystem::Groth16);
+    let inputs = mock_inputs(&env, 1);
+    assert!(client.verify_payment_proof(&2, &mock_snarkjs_proof(&env), &inputs));
+
+    // The default key stays Groth16 whatever a company selects.
+    assert!(client.verify(&mock_groth16_proof(&env, 1), &inputs));
+    let result = client.try_verify_payment_proof(&1, &mock_snarkjs_proof(&env), &inputs);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        VerifierError::UnsupportedProofSystem.into()
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_set_proof_system_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, ProofVerifier);
+    let client = ProofVerifierClient::new(&env, &contract_id);
+    client.init_verifier_admin(&soroban_sdk::Address::generate(&env));
+
+    client.set_proof_system(&1, &ProofSystem::Plonk);
+}
+
+// ---------------------------------------------------------------------------
+// Host verification cost
+// ---------------------------------------------------------------------------
+
+/// Host CPU instructions `verify_batch_proofs` may spend on a single proof.
+///
+/// This runs natively against the mock verification key with the pairing
+/// check simulated. The native budget only meters host functions, so the
+/// contract's own code (including the point checks) is not counted. It is a
+/// regression guard for host-side work only and says nothing about how many
+/// real proofs fit in a transaction or about payroll's `MAX_BATCH`.
+const SINGLE_PROOF_CPU_LIMIT: u64 = 150_000;
+
+/// Host CPU instructions each additional proof in a batch may add.
+const PER_PROOF_CPU_LIMIT: u64 = 75_000;
+
+/// Batch size used to show how verification cost scales.
+const BENCH_BATCH_SIZE: u32 = 10;
+
+/// Host CPU instructions spent verifying `n` distinct proofs in one batch.
+fn batch_verification_cpu_cost(n: u32) -> u64 {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+
+    let mut proofs = Vec::new(&env);
+    let mut public_inputs = Vec::new(&env);
+    for i in 0..n {
+        proofs.push_back(mock_groth16_proof(&env, i as u8));
+        public_inputs.push_back(mock_inputs(&env, i as u8));
+    }
+
+    env.budget().reset_unlimited();
+    let results = client.verify_batch_proofs(&1, &proofs, &public_inputs);
+    let cost = env.budget().cpu_instruction_cost();
+
+    assert_eq!(results.len(), n);
+    cost
+}
+
+#[test]
+fn test_host_verification_cost_stays_bounded() {
+    let single = batch_verification_cpu_cost(1);
+    let batch = batch_verification_cpu_cost(BENCH_BATCH_SIZE);
+    let per_proof = (batch - single) / u64::from(BENCH_BATCH_SIZE - 1);
+    assert!(single <= SINGLE_PROOF_CPU_LIMIT, "single proof: {}", single);
+    assert!(
+        per_proof <= PER_PROOF_CPU_LIMIT,
+        "per extra proof: {}",
+        per_proof
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Point validation
+// ---------------------------------------------------------------------------
+
+use ark_ec::short_weierstrass::SWCurveConfig as _;
+use ark_ec::AffineRepr as _;
+use ark_ff::Field as _;
+
+fn fq_bytes(x: &ark_bn254::Fq) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (chunk, limb) in out.rchunks_mut(8).zip(x.into_bigint().0) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    out
+}
+
+fn g1_bytes(p: &ark_bn254::G1Affine) -> [u8; 64] {
+    let mut out = [0u8; 64];
+    out[..32].copy_from_slice(&fq_bytes(&p.x));
+    out[32..].copy_from_slice(&fq_bytes(&p.y));
+    out
+}
+
+fn g2_bytes(p: &ark_bn254::G2Affine) -> [u8; 128] {
+    let mut out = [0u8; 128];
+    for (i, c) in [p.x.c0, p.x.c1, p.y.c0, p.y.c1].iter().enumerate() {
+        out[i * 32..(i + 1) * 32].copy_from_slice(&fq_bytes(c));
+    }
+    out
+}
+
+fn packed_proof(env: &Env, a: &[u8; 64], b: &[u8; 128], c: &[u8; 64]) -> BytesN<256> {
+    let mut bytes = [0u8; 256];
+    bytes[..64].copy_from_slice(a);
+    bytes[64..192].copy_from_slice(b);
+    bytes[192..].copy_from_slice(c);
+    BytesN::from_array(env, &bytes)
+}
+
+#[test]
+fn test_generators_and_infinity_are_valid_points() {
+    let env = Env::default();
+    let g1 = g1_bytes(&ark_bn254::G1Affine::generator());
+    let g2 = g2_bytes(&ark_bn254::G2Affine::generator());
+
+    assert!(is_valid_g1(&g1));
+    assert!(is_valid_g2(&g2));
+    assert!(is_valid_g1(&[0u8; 64]));
+    assert!(is_valid_g2(&[0u8; 128]));
+    assert!(proof_points_valid(&packed_proof(&env, &g1, &g2, &g1)));
+    assert!(proof_points_valid(&BytesN::from_array(&env, &[0u8; 256])));
+}
+
+#[test]
+fn test_all_ff_bytes_are_rejected() {
+    let env = Env::default();
+
+    assert!(!is_valid_g1(&[0xFF; 64]));
+    assert!(!is_valid_g2(&[0xFF; 128]));
+    assert!(!proof_points_valid(&BytesN::from_array(&env, &[0xFF; 256])));
+}
+
+#[test]
+fn test_off_curve_points_are_rejected() {
+    let env = Env::default();
+    let g1 = g1_bytes(&ark_bn254::G1Affine::generator());
+    let g2 = g2_bytes(&ark_bn254::G2Affine::generator());
+
+    // (1, 3): the generator is (1, 2).
+    let mut off_curve = g1;
+    off_curve[63] = 3;
+    assert!(!is_valid_g1(&off_curve));
+    let bad_a = packed_proof(&env, &off_curve, &g2, &g1);
+    let bad_c = packed_proof(&env, &g1, &g2, &off_curve);
+    assert!(!proof_points_valid(&bad_a));
+    assert!(!proof_points_valid(&bad_c));
+
+    let mut off_twist = g2;
+    off_twist[127] ^= 1;
+    assert!(!is_valid_g2(&off_twist));
+    let bad_b = packed_proof(&env, &g1, &off_twist, &g1);
+    assert!(!proof_points_valid(&bad_b));
+}
+
+#[test]
+fn test_verify_payment_proof_rejects_invalid_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ProofVerifier);
+    let client = ProofVerifierClient::new(&env, &contract_id);
+    client.init_verifier_admin(&soroban_sdk::Address::generate(&env));
+    client.initialize_verifier(&mock_verification_key(&env));
+
+    let inputs = Vec::from_array(
+        &env,
+        [
+            BytesN::from_array(&env, &[11u8; 32]),
+            BytesN::from_array(&env, &[12u8; 32]),
+        ],
+    );
+    let mut bytes = mock_snarkjs_proof(&env).to_array();
+    bytes[63] ^= 1;
+    let off_curve = BytesN::from_array(&env, &bytes);
+
+    assert!(!client.verify_payment_proof(&1, &off_curve, &inputs));
+    assert!(!client.verify_payment_proof(&1, &BytesN::from_array(&env, &[0xFF; 256]), &inputs));
+}
+
+#[test]
+fn test_twist_point_outside_subgroup_is_left_to_the_pairing_check() {
+    // The first x = n on the twist y^2 = x^3 + b'; the twist's cofactor is
+    // huge, so such a point is not in the prime-order subgroup. Only the
+    // on-curve check runs in the contract, so it is accepted here.
+    let b = ark_bn254::g2::Config::COEFF_B;
+    let point = (1u64..)
+        .find_map(|n| {
+            let x = ark_bn254::Fq2::from(n);
+            let y = (x.square() * x + b).sqrt()?;
+            Some(ark_bn254::G2Affine::new_unchecked(x, y))
+        })
+        .unwrap();
+    assert!(point.is_on_curve());
+    assert!(!point.is_in_correct_subgroup_assuming_on_curve());
+
+    assert!(is_valid_g2(&g2_bytes(&point)));
+}
+
+// ---------------------------------------------------------------------------
+// Per-company verification keys
+// ---------------------------------------------------------------------------
+
+/// A key sized for three public inputs, unlike the default key's two.
+fn three_input_key(env: &Env) -> VerificationKey {
+    let mut vk = mock_verification_key(env);
+    vk.ic.push_back(BytesN::from_array(env, &[9u8; 64]));
+    vk
+}
+
+#[test]
+fn test_company_without_key_uses_default() {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+
+    assert_eq!(client.get_company_vk(&7), mock_verification_key(&env));
+    let inputs = mock_inputs(&env, 1);
+    assert!(client.verify_payment_proof(&7, &mock_snarkjs_proof(&env), &inputs));
+}
+
+#[test]
+fn test_register_vk_selects_company_key() {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+    client.register_vk(&7, &three_input_key(&env));
+    let proof = mock_snarkjs_proof(&env);
+    let two_inputs = mock_inputs(&env, 1);
+    let mut three_inputs = two_inputs.clone();
+    three_inputs.push_back(BytesN::from_array(&env, &[3u8; 32]));
+
+    assert_eq!(client.get_company_vk(&7), three_input_key(&env));
+    assert!(client.verify_payment_proof(&7, &proof, &three_inputs));
+    assert!(!client.verify_payment_proof(&7, &proof, &two_inputs));
+
+    // Other companies and the default key are unaffected.
+    assert!(client.verify_payment_proof(&8, &proof, &two_inputs));
+    assert!(!client.verify_payment_proof(&8, &proof, &three_inputs));
+    assert_eq!(client.get_verification_key(), mock_verification_key(&env));
+}
+
+#[test]
+fn test_batch_proofs_are_checked_against_company_key() {
+    let env = Env::default();
+    let client = setup_initialized(&env);
+    client.register_vk(&7, &three_input_key(&env));
+    let proofs = Vec::from_array(&env, [mock_groth16_proof(&env, 1)]);
+    let public_inputs = Vec::from_array(&env, [mock_inputs(&env, 1)]);
+
+    let results = client.verify_batch_proofs(&7, &proofs, &public_inputs);
+    assert_eq!(results, Vec::from_array(&env, [false]));
+    let results = client.verify_batch_proofs(&8, &proofs, &public_inputs);
+    assert_eq!(results, Vec::from_array(&env, [true]));
+}
+
+#[test]
+#[should_panic]
+fn test_register_vk_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, ProofVerifier);
+    let client = ProofVerifierClient::new(&env, &contract_id);
+    client.init_verifier_admin(&soroban_sdk::Address::generate(&env));
+
+    client.register_vk(&7, &mock_verification_key(&env));
+}
+
+#[test]
+fn test_key_in_former_single_key_slot_is_the_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ProofVerifier);
+    let client = ProofVerifierClient::new(&env, &contract_id);
+    client.init_verifier_admin(&soroban_sdk::Address::generate(&env));
+
+    // A unit `DataKey` variant is stored as a one-symbol vector, which is
+    // how the former `DataKey::VerificationKey` slot was written.
+    assert_eq!(
+        DataKey::Admin.to_xdr(&env),
+        Vec::from_array(&env, [Symbol::new(&env, "Admin")]).to_xdr(&env)
+    );
+    let old_slot = Vec::from_array(&env, [Symbol::new(&env, "VerificationKey")]);
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&old_slot, &mock_verification_key(&env));
+    });
+
+    assert!(client.is_initialized());
+    assert_eq!(client.get_company_vk(&7), mock_verification_key(&env));
+    assert_eq!(
+        client.try_initialize_verifier(&three_input_key(&env)),
+        Err(Ok(InitError::AlreadyInitialized))
+    );
+}
@@ -1,6 +1,10 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Symbol, Vec};
+use proof_verifier::{Groth16Proof, ProofVerifierClient};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Bytes, BytesN,
+    Env, Symbol, Vec,
+};
 
 // ---------------------------------------------------------------------------
 // Operational roles
@@ -17,6 +21,36 @@ use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Sy
 // Unauthorized role actions fail with `require_auth()` / explicit role checks.
 // ---------------------------------------------------------------------------
 
+/// Ledgers a commitment entry is kept live for after each write (~30 days at
+/// 5-second ledgers). Commitments that are never rewritten must be bumped with
+/// `bump_commitment_ttl` before this runs out, or payroll for the employee
+/// fails once the entry is archived.
+pub const COMMITMENT_TTL_LEDGERS: u32 = 518_400;
+
+/// Most updates `batch_update_commitments` accepts in one call unless the
+/// admin sets another cap with `set_max_update_batch`.
+pub const DEFAULT_MAX_UPDATE_BATCH: u32 = 100;
+
+/// Domain-separation tag prepended to the SHA-256 commitment preimage.
+pub const COMMITMENT_DOMAIN: &[u8] = b"ZKP_COMMIT_V1";
+
+/// Domain-separation tag of the simulated Poseidon host.
+#[cfg(feature = "simulate-poseidon-host")]
+pub const POSEIDON_SIMULATION_DOMAIN: &[u8] = b"ZKP_POSEIDON_SIM";
+
+/// SHA-256 placeholder commitment, `sha256(COMMITMENT_DOMAIN ‖ amount ‖
+/// blinding)` with `amount` as 16 little-endian bytes.
+///
+/// Used until the network provides Poseidon (CAP-0075). The audit module
+/// opens commitments with this same function, so the two always agree.
+pub fn sha256_commitment(env: &Env, amount: i128, blinding: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, COMMITMENT_DOMAIN);
+    preimage.extend_from_array(&amount.to_le_bytes());
+    let blinding_bytes: [u8; 32] = blinding.into();
+    preimage.extend_from_array(&blinding_bytes);
+    env.crypto().sha256(&preimage).into()
+}
+
 /// Commitment data structure
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -47,6 +81,25 @@ pub struct CommitmentSnapshot {
     pub rotated_at: u64,
 }
 
+/// Hash function a commitment was computed with.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum CommitmentAlgorithm {
+    /// SHA-256(salary ‖ blinding), used while the host has no Poseidon.
+    Sha256Placeholder = 0,
+    /// Poseidon(salary, blinding) via the CAP-0075 host function.
+    Poseidon = 1,
+}
+
+/// A computed commitment and the algorithm that produced it.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComputedCommitment {
+    pub commitment: BytesN<32>,
+    pub algorithm: CommitmentAlgorithm,
+}
+
 /// Storage keys
 #[contracttype]
 pub enum DataKey {
@@ -63,6 +116,44 @@ pub enum DataKey {
     EmployeeReferenceId(Address),
     /// Reverse mapping to detect collisions (ref_id -> employee).
     ReferenceIdIndex(soroban_sdk::String),
+    /// proof_verifier instance holding the commitment-opening circuit's key.
+    OpeningVerifier,
+    /// Number of employees with a stored commitment record.
+    CommitmentCount,
+    /// Ledger up to which the employee's commitment entry has been extended.
+    CommitmentLiveUntil(Address),
+    /// Cap on `batch_update_commitments`; `DEFAULT_MAX_UPDATE_BATCH` when unset.
+    MaxUpdateBatch,
+    /// Address allowed to pause and unpause commitment writes.
+    Guardian,
+    /// Whether commitment and nullifier writes are paused; `false` when unset.
+    Paused,
+}
+
+/// Error returned by a second call to an initializer.
+#[contracterror]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum InitError {
+    AlreadyInitialized = 1,
+}
+
+/// Errors returned by `batch_update_commitments`, and raised by the other
+/// write entrypoints while paused.
+#[contracterror]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum CommitmentError {
+    /// The caller is not the HR admin.
+    Unauthorized = 1,
+    /// The batch holds more updates than the configured cap.
+    BatchTooLarge = 2,
+    /// `employees` and `new_commitments` differ in length.
+    LengthMismatch = 3,
+    /// The guardian has paused commitment writes.
+    Paused = 4,
+    /// `set_opening_verifier` has not been called.
+    OpeningVerifierNotSet = 5,
 }
 
 #[contract]
@@ -73,11 +164,12 @@ impl SalaryCommitmentContract {
     /// Initialize the contract with an HR admin address.
     /// Must be called once. The admin is the only address allowed to
     /// store / update / revoke commitments.
-    pub fn init_commitment_admin(env: Env, admin: Address) {
+    pub fn init_commitment_admin(env: Env, admin: Address) -> Result<(), InitError> {
         if env.storage().persistent().has(&DataKey::Admin) {
-            panic!("Already initialized");
+            return Err(InitError::AlreadyInitialized);
         }
         env.storage().persistent().set(&DataKey::Admin, &admin);
+        Ok(())
     }
 
     /// Set a delegated payroll operator that may record nullifiers
@@ -97,11 +189,60 @@ impl SalaryCommitmentContract {
             .expect("Not initialized")
     }
 
+    /// Whether `init_commitment_admin` has been called.
+    pub fn is_initialized(env: Env) -> bool {
+        env.storage().persistent().has(&DataKey::Admin)
+    }
+
     /// Get the payroll operator address (if set).
     pub fn get_payroll_operator(env: Env) -> Option<Address> {
         env.storage().persistent().get(&DataKey::PayrollOperator)
     }
 
+    /// Set the guardian that may pause and unpause commitment writes.
+    /// Only the admin may call.
+    pub fn set_guardian(env: Env, guardian: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Guardian, &guardian);
+    }
+
+    /// Get the guardian address (if set).
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Guardian)
+    }
+
+    /// Freeze commitment and nullifier writes, which then fail with
+    /// `CommitmentError::Paused`. Reads stay available. Only the guardian
+    /// may call.
+    pub fn pause(env: Env) {
+        let guardian = Self::require_guardian(&env);
+        env.storage().persistent().set(&DataKey::Paused, &true);
+        env.events()
+            .publish((Symbol::new(&env, "CommitmentsPaused"),), (guardian,));
+        // topics : ("CommitmentsPaused",)
+        // data   : (guardian,)
+    }
+
+    /// Lift a `pause`. Only the guardian may call.
+    pub fn unpause(env: Env) {
+        let guardian = Self::require_guardian(&env);
+        env.storage().persistent().set(&DataKey::Paused, &false);
+        env.events()
+            .publish((Symbol::new(&env, "CommitmentsUnpaused"),), (guardian,));
+        // topics : ("CommitmentsUnpaused",)
+        // data   : (guardian,)
+    }
+
+    /// Whether commitment writes are paused.
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
     /// Store a new salary commitment for an employee.
     /// Only the HR admin may call.
     pub fn store_commitment(
@@ -110,29 +251,88 @@ impl SalaryCommitmentContract {
         commitment: BytesN<32>,
     ) -> SalaryCommitment {
         Self::require_admin(&env);
+        Self::ensure_not_paused(&env);
+        Self::write_commitment(&env, employee, commitment)
+    }
 
-        let timestamp = env.ledger().timestamp();
+    /// Store commitments for several employees in one call.
+    /// Only the HR admin may call.
+    pub fn batch_store_commitments(
+        env: Env,
+        employees: Vec<Address>,
+        commitments: Vec<BytesN<32>>,
+    ) -> Vec<SalaryCommitment> {
+        Self::require_admin(&env);
+        Self::ensure_not_paused(&env);
 
-        let salary_commitment = SalaryCommitment {
-            commitment: commitment.clone(),
-            created_at: timestamp,
-            updated_at: timestamp,
-            version: 1,
-            revoked: false,
-        };
+        if employees.len() != commitments.len() {
+            panic!("Array length mismatch");
+        }
+
+        let mut stored = Vec::new(&env);
+        for (employee, commitment) in employees.iter().zip(commitments.iter()) {
+            stored.push_back(Self::write_commitment(&env, employee, commitment));
+        }
+        stored
+    }
+
+    /// Remove an employee's active commitment record.
+    /// Only the HR admin may call. Archived history is kept for audit.
+    pub fn remove_commitment(env: Env, employee: Address) {
+        Self::require_admin(&env);
+        Self::ensure_not_paused(&env);
 
         let key = DataKey::Commitment(employee.clone());
-        env.storage().persistent().set(&key, &salary_commitment);
+        if !env.storage().persistent().has(&key) {
+            panic!("Commitment not found");
+        }
+        env.storage().persistent().remove(&key);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::CommitmentLiveUntil(employee.clone()));
 
-        // Emit CommitmentUpdated event so off-chain indexers track commitment history.
-        env.events().publish(
-            (Symbol::new(&env, "CommitmentUpdated"), employee),
-            (commitment,),
-        );
-        // topics : ("CommitmentUpdated", employee)
-        // data   : (commitment,)
+        let count = Self::get_commitment_count(env.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::CommitmentCount, &count.saturating_sub(1));
 
-        salary_commitment
+        env.events()
+            .publish((Symbol::new(&env, "CommitmentRemoved"), employee), ());
+        // topics : ("CommitmentRemoved", employee)
+        // data   : ()
+    }
+
+    /// Keep an employee's commitment live for at least `ledgers` more ledgers.
+    /// Anyone may call; extending storage rent cannot change the commitment.
+    pub fn bump_commitment_ttl(env: Env, employee: Address, ledgers: u32) {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Commitment(employee.clone()))
+        {
+            panic!("Commitment not found");
+        }
+        Self::extend_commitment_ttl(&env, &employee, ledgers);
+    }
+
+    /// Remaining ledgers before the employee's commitment entry is archived,
+    /// as last extended by this contract. Returns 0 if there is no commitment.
+    pub fn get_commitment_ttl(env: Env, employee: Address) -> u32 {
+        let live_until: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CommitmentLiveUntil(employee))
+            .unwrap_or(0);
+        live_until.saturating_sub(env.ledger().sequence())
+    }
+
+    /// Number of employees that currently have a stored commitment record.
+    /// Operators can use this to budget persistent-storage rent.
+    pub fn get_commitment_count(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CommitmentCount)
+            .unwrap_or(0)
     }
 
     /// Update an existing salary commitment (rotation for compensation changes).
@@ -147,35 +347,61 @@ impl SalaryCommitmentContract {
         new_commitment: BytesN<32>,
     ) -> SalaryCommitment {
         Self::require_admin(&env);
+        Self::ensure_not_paused(&env);
+        Self::apply_update(&env, employee, new_commitment)
+    }
 
-        let key = DataKey::Commitment(employee.clone());
-        let existing: SalaryCommitment = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .expect("Commitment not found");
-
-        // Archive current commitment before replacing
-        Self::archive_commitment(&env, &employee, &existing.commitment, existing.version);
+    /// Update several employees' commitments in one call, as
+    /// `update_commitment` does for each.
+    ///
+    /// `updater` must be the HR admin and sign the call. Batches larger than
+    /// `get_max_update_batch` are rejected before any commitment is touched.
+    pub fn batch_update_commitments(
+        env: Env,
+        updater: Address,
+        employees: Vec<Address>,
+        new_commitments: Vec<BytesN<32>>,
+    ) -> Result<Vec<SalaryCommitment>, CommitmentError> {
+        if updater != Self::get_commitment_admin(env.clone()) {
+            return Err(CommitmentError::Unauthorized);
+        }
+        updater.require_auth();
 
-        let updated = SalaryCommitment {
-            commitment: new_commitment.clone(),
-            created_at: existing.created_at,
-            updated_at: env.ledger().timestamp(),
-            version: existing.version + 1,
-            revoked: false,
-        };
+        if Self::is_paused(env.clone()) {
+            return Err(CommitmentError::Paused);
+        }
+        if employees.len() > Self::get_max_update_batch(env.clone()) {
+            return Err(CommitmentError::BatchTooLarge);
+        }
+        if employees.len() != new_commitments.len() {
+            return Err(CommitmentError::LengthMismatch);
+        }
 
-        env.storage().persistent().set(&key, &updated);
+        let mut updated = Vec::new(&env);
+        for (employee, commitment) in employees.iter().zip(new_commitments.iter()) {
+            updated.push_back(Self::apply_update(&env, employee, commitment));
+        }
+        Ok(updated)
+    }
 
-        env.events().publish(
-            (Symbol::new(&env, "CommitmentUpdated"), employee),
-            (new_commitment,),
-        );
-        // topics : ("CommitmentUpdated", employee)
-        // data   : (new_commitment,)
+    /// Set the most updates `batch_update_commitments` accepts in one call.
+    /// Only the HR admin may call.
+    pub fn set_max_update_batch(env: Env, max: u32) {
+        Self::require_admin(&env);
+        if max == 0 {
+            panic!("Batch cap must be positive");
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::MaxUpdateBatch, &max);
+    }
 
-        updated
+    /// Cap on `batch_update_commitments` (`DEFAULT_MAX_UPDATE_BATCH` unless set).
+    pub fn get_max_update_batch(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MaxUpdateBatch)
+            .unwrap_or(DEFAULT_MAX_UPDATE_BATCH)
     }
 
     /// Rotate a salary commitment: archive the old one with `revoked = true`
@@ -188,6 +414,7 @@ impl SalaryCommitmentContract {
         new_commitment: BytesN<32>,
     ) -> SalaryCommitment {
         Self::require_admin(&env);
+        Self::ensure_not_paused(&env);
 
         let key = DataKey::Commitment(employee.clone());
         let mut existing: SalaryCommitment = env
@@ -258,6 +485,13 @@ impl SalaryCommitmentContract {
             .expect("Commitment not found")
     }
 
+    /// Version of an employee's active commitment. Starts at 1 and is
+    /// incremented by every `update_commitment`, so a holder of an older
+    /// commitment can tell the salary has changed since.
+    pub fn get_commitment_version(env: Env, employee: Address) -> u32 {
+        Self::get_commitment(env, employee).version
+    }
+
     /// Check if a commitment exists
     pub fn has_commitment(env: Env, employee: Address) -> bool {
         let key = DataKey::Commitment(employee);
@@ -336,6 +570,7 @@ impl SalaryCommitmentContract {
     /// Authorized for both the HR admin and the delegated payroll operator.
     pub fn record_nullifier(env: Env, nullifier: BytesN<32>) {
         Self::require_admin_or_operator(&env);
+        Self::ensure_not_paused(&env);
 
         let key = DataKey::Nullifier(nullifier.clone());
 
@@ -358,13 +593,35 @@ impl SalaryCommitmentContract {
     }
 
     /// Compute a commitment hash for a salary and blinding factor.
+    ///
+    /// Uses the Poseidon host function where the network provides one and
+    /// the SHA-256 placeholder otherwise; see
+    /// [`Self::compute_commitment_with_metadata`] for which was used.
     pub fn compute_commitment(env: Env, salary: u64, blinding_factor: BytesN<32>) -> BytesN<32> {
-        let mut preimage = soroban_sdk::Bytes::new(&env);
-        preimage.extend_from_array(&salary.to_le_bytes());
-        let blinding_bytes: [u8; 32] = blinding_factor.into();
-        preimage.extend_from_array(&blinding_bytes);
+        Self::compute_commitment_with_metadata(env, salary, blinding_factor).commitment
+    }
+
+    /// Compute a commitment, recording the algorithm that produced it.
+    ///
+    /// Tries the Poseidon host function first and falls back to
+    /// [`sha256_commitment`] when the host reports it unavailable, so the
+    /// contract keeps working on networks without CAP-0075.
+    pub fn compute_commitment_with_metadata(
+        env: Env,
+        salary: u64,
+        blinding_factor: BytesN<32>,
+    ) -> ComputedCommitment {
+        if let Some(commitment) = Self::poseidon_host(&env, salary, &blinding_factor) {
+            return ComputedCommitment {
+                commitment,
+                algorithm: CommitmentAlgorithm::Poseidon,
+            };
+        }
 
-        env.crypto().sha256(&preimage).into()
+        ComputedCommitment {
+            commitment: sha256_commitment(&env, i128::from(salary), &blinding_factor),
+            algorithm: CommitmentAlgorithm::Sha256Placeholder,
+        }
     }
 
     /// Verify a commitment matches a salary (with proof)
@@ -380,6 +637,53 @@ impl SalaryCommitmentContract {
         stored.commitment == computed && !stored.revoked
     }
 
+    /// Set the proof_verifier contract used by `verify_commitment_zk`.
+    /// Only the admin may call.
+    ///
+    /// This must be a verifier initialised with the commitment-opening
+    /// circuit's verification key (one public input: the commitment), not
+    /// the payment circuit's.
+    pub fn set_opening_verifier(env: Env, verifier: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .persistent()
+            .set(&DataKey::OpeningVerifier, &verifier);
+    }
+
+    /// Get the commitment-opening verifier address (if set).
+    pub fn get_opening_verifier(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::OpeningVerifier)
+    }
+
+    /// Verify a Groth16 proof of knowledge of an opening `(salary, blinding)`
+    /// to the employee's stored commitment.
+    ///
+    /// Unlike `verify_commitment`, the caller never reveals the salary or
+    /// blinding factor; the stored commitment is the only public input.
+    /// Returns `false` for revoked commitments, and fails with
+    /// `CommitmentError::OpeningVerifierNotSet` before `set_opening_verifier`.
+    pub fn verify_commitment_zk(
+        env: Env,
+        employee: Address,
+        proof: Groth16Proof,
+    ) -> Result<bool, CommitmentError> {
+        let verifier: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OpeningVerifier)
+            .ok_or(CommitmentError::OpeningVerifierNotSet)?;
+
+        let stored = Self::get_commitment(env.clone(), employee);
+        if stored.revoked {
+            return Ok(false);
+        }
+
+        let mut public_inputs = Vec::new(&env);
+        public_inputs.push_back(stored.commitment);
+
+        Ok(ProofVerifierClient::new(&env, &verifier).verify(&proof, &public_inputs))
+    }
+
     // -----------------------------------------------------------------------
     // Role guards
     // -----------------------------------------------------------------------
@@ -393,6 +697,23 @@ impl SalaryCommitmentContract {
         admin.require_auth();
     }
 
+    /// Require the guardian's authorisation and return the guardian.
+    fn require_guardian(env: &Env) -> Address {
+        let guardian: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Guardian)
+            .expect("Guardian not set");
+        guardian.require_auth();
+        guardian
+    }
+
+    fn ensure_not_paused(env: &Env) {
+        if Self::is_paused(env.clone()) {
+            panic_with_error!(env, CommitmentError::Paused);
+        }
+    }
+
     fn require_admin_or_operator(env: &Env) {
         let admin: Address = env
             .storage()
@@ -412,6 +733,111 @@ impl SalaryCommitmentContract {
     // Internal helpers
     // -----------------------------------------------------------------------
 
+    /// Poseidon(salary, blinding) from the host, or `None` where the host
+    /// does not provide it.
+    ///
+    /// This SDK version exposes no CAP-0075 host function, so the host is
+    /// reported unavailable unless the `simulate-poseidon-host` feature
+    /// stands one in for tests.
+    #[cfg(not(feature = "simulate-poseidon-host"))]
+    fn poseidon_host(_env: &Env, _salary: u64, _blinding: &BytesN<32>) -> Option<BytesN<32>> {
+        None
+    }
+
+    /// Simulated host: a domain-separated SHA-256, distinct from the
+    /// placeholder so tests can tell which path produced a commitment.
+    #[cfg(feature = "simulate-poseidon-host")]
+    fn poseidon_host(env: &Env, salary: u64, blinding: &BytesN<32>) -> Option<BytesN<32>> {
+        let mut preimage = Bytes::from_slice(env, POSEIDON_SIMULATION_DOMAIN);
+        preimage.extend_from_array(&salary.to_le_bytes());
+        preimage.extend_from_array(&blinding.to_array());
+        Some(env.crypto().sha256(&preimage).into())
+    }
+
+    fn write_commitment(env: &Env, employee: Address, commitment: BytesN<32>) -> SalaryCommitment {
+        let timestamp = env.ledger().timestamp();
+
+        let salary_commitment = SalaryCommitment {
+            commitment: commitment.clone(),
+            created_at: timestamp,
+            updated_at: timestamp,
+            version: 1,
+            revoked: false,
+        };
+
+        let key = DataKey::Commitment(employee.clone());
+        // Overwrites (e.g. rotation) replace an existing record and do not
+        // change the live count.
+        if !env.storage().persistent().has(&key) {
+            let count = Self::get_commitment_count(env.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::CommitmentCount, &(count + 1));
+        }
+        env.storage().persistent().set(&key, &salary_commitment);
+        Self::extend_commitment_ttl(env, &employee, COMMITMENT_TTL_LEDGERS);
+
+        // Emit CommitmentUpdated event so off-chain indexers track commitment history.
+        env.events().publish(
+            (Symbol::new(env, "CommitmentUpdated"), employee),
+            (commitment,),
+        );
+        // topics : ("CommitmentUpdated", employee)
+        // data   : (commitment,)
+
+        salary_commitment
+    }
+
+    /// Archive the employee's active commitment and replace it with
+    /// `new_commitment` at the next version. Callers check authorisation.
+    fn apply_update(env: &Env, employee: Address, new_commitment: BytesN<32>) -> SalaryCommitment {
+        let key = DataKey::Commitment(employee.clone());
+        let existing: SalaryCommitment = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Commitment not found");
+
+        // Archive current commitment before replacing
+        Self::archive_commitment(env, &employee, &existing.commitment, existing.version);
+
+        let updated = SalaryCommitment {
+            commitment: new_commitment.clone(),
+            created_at: existing.created_at,
+            updated_at: env.ledger().timestamp(),
+            version: existing.version + 1,
+            revoked: false,
+        };
+
+        env.storage().persistent().set(&key, &updated);
+        Self::extend_commitment_ttl(env, &employee, COMMITMENT_TTL_LEDGERS);
+
+        env.events().publish(
+            (Symbol::new(env, "CommitmentUpdated"), employee),
+            (new_commitment,),
+        );
+        // topics : ("CommitmentUpdated", employee)
+        // data   : (new_commitment,)
+
+        updated
+    }
+
+    /// Extend the commitment entry, and the record of its expiry, so both stay
+    /// live for at least `ledgers` more ledgers. Never shortens the TTL.
+    fn extend_commitment_ttl(env: &Env, employee: &Address, ledgers: u32) {
+        let live_until_key = DataKey::CommitmentLiveUntil(employee.clone());
+        let live_until: u32 = env.storage().persistent().get(&live_until_key).unwrap_or(0);
+        let target = env.ledger().sequence().saturating_add(ledgers);
+        if target <= live_until {
+            return;
+        }
+
+        let storage = env.storage().persistent();
+        storage.set(&live_until_key, &target);
+        storage.extend_ttl(&DataKey::Commitment(employee.clone()), ledgers, ledgers);
+        storage.extend_ttl(&live_until_key, ledgers, ledgers);
+    }
+
     fn archive_commitment(env: &Env, employee: &Address, commitment: &BytesN<32>, version: u32) {
         let mut idx: u32 = 0;
         loop {
@@ -433,7 +859,8 @@ impl SalaryCommitmentContract {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::testutils::{Address as _, Events};
+    use soroban_sdk::testutils::storage::Persistent as _;
+    use soroban_sdk::testutils::{Address as _, Events, Ledger};
     use soroban_sdk::{Env, Symbol, TryIntoVal};
 
     fn setup_with_admin() -> (Env, soroban_sdk::Address, Address) {
@@ -446,6 +873,19 @@ mod tests {
         (env, contract_id, admin)
     }
 
+    #[test]
+    fn test_second_init_returns_already_initialized() {
+        let (env, contract_id, admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        let other = Address::generate(&env);
+        assert_eq!(
+            client.try_init_commitment_admin(&other),
+            Err(Ok(InitError::AlreadyInitialized))
+        );
+        assert_eq!(client.get_commitment_admin(), admin);
+    }
+
     #[test]
     fn test_store_commitment() {
         let (env, contract_id, admin) = setup_with_admin();
@@ -489,6 +929,19 @@ mod tests {
         assert_eq!(result.version, 2);
     }
 
+    #[test]
+    fn test_commitment_version_tracks_updates() {
+        let (env, contract_id, _admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        let employee = Address::generate(&env);
+        client.store_commitment(&employee, &BytesN::from_array(&env, &[1u8; 32]));
+        assert_eq!(client.get_commitment_version(&employee), 1);
+
+        client.update_commitment(&employee, &BytesN::from_array(&env, &[2u8; 32]));
+        assert_eq!(client.get_commitment_version(&employee), 2);
+    }
+
     #[test]
     fn test_nullifier() {
         let (env, contract_id, _admin) = setup_with_admin();
@@ -600,4 +1053,325 @@ mod tests {
         let commitment = BytesN::from_array(&env, &[99u8; 32]);
         client.store_commitment(&employee, &commitment);
     }
+
+    #[test]
+    fn test_commitment_count_tracks_stores_and_removals() {
+        let (env, contract_id, _admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        assert_eq!(client.get_commitment_count(), 0);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        client.store_commitment(&alice, &BytesN::from_array(&env, &[1u8; 32]));
+        client.store_commitment(&bob, &BytesN::from_array(&env, &[2u8; 32]));
+        assert_eq!(client.get_commitment_count(), 2);
+
+        // Rotation and re-storing replace a record without adding one.
+        client.rotate_commitment(&alice, &BytesN::from_array(&env, &[3u8; 32]));
+        client.store_commitment(&bob, &BytesN::from_array(&env, &[4u8; 32]));
+        assert_eq!(client.get_commitment_count(), 2);
+
+        client.remove_commitment(&alice);
+        assert_eq!(client.get_commitment_count(), 1);
+        assert!(!client.has_commitment(&alice));
+    }
+
+    #[test]
+    fn test_commitment_count_tracks_batch_stores() {
+        let (env, contract_id, _admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        let existing = Address::generate(&env);
+        client.store_commitment(&existing, &BytesN::from_array(&env, &[1u8; 32]));
+
+        let new_a = Address::generate(&env);
+        let new_b = Address::generate(&env);
+        let employees = Vec::from_array(&env, [existing.clone(), new_a.clone(), new_b.clone()]);
+        let commitments = Vec::from_array(
+            &env,
+            [
+                BytesN::from_array(&env, &[5u8; 32]),
+                BytesN::from_array(&env, &[6u8; 32]),
+                BytesN::from_array(&env, &[7u8; 32]),
+            ],
+        );
+        let stored = client.batch_store_commitments(&employees, &commitments);
+
+        assert_eq!(stored.len(), 3);
+        assert_eq!(client.get_commitment_count(), 3);
+        assert_eq!(
+            client.get_commitment(&new_b).commitment,
+            BytesN::from_array(&env, &[7u8; 32])
+        );
+    }
+
+    /// Two employees with stored commitments, and the batch updating both.
+    fn update_batch(
+        env: &Env,
+        client: &SalaryCommitmentContractClient,
+    ) -> (Vec<Address>, Vec<BytesN<32>>) {
+        let employees = Vec::from_array(env, [Address::generate(env), Address::generate(env)]);
+        for employee in employees.iter() {
+            client.store_commitment(&employee, &BytesN::from_array(env, &[1u8; 32]));
+        }
+        let commitments = Vec::from_array(
+            env,
+            [
+                BytesN::from_array(env, &[2u8; 32]),
+                BytesN::from_array(env, &[3u8; 32]),
+            ],
+        );
+        (employees, commitments)
+    }
+
+    #[test]
+    fn test_batch_update_commitments_by_admin() {
+        let (env, contract_id, admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+        let (employees, commitments) = update_batch(&env, &client);
+
+        let updated = client.batch_update_commitments(&admin, &employees, &commitments);
+
+        assert_eq!(env.auths()[0].0, admin);
+        assert_eq!(updated.len(), 2);
+        for (employee, commitment) in employees.iter().zip(commitments.iter()) {
+            let stored = client.get_commitment(&employee);
+            assert_eq!(stored.commitment, commitment);
+            assert_eq!(stored.version, 2);
+            assert_eq!(client.get_commitment_history(&employee).len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_batch_update_commitments_rejects_non_admin() {
+        let (env, contract_id, _admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+        let (employees, commitments) = update_batch(&env, &client);
+
+        let result =
+            client.try_batch_update_commitments(&Address::generate(&env), &employees, &commitments);
+
+        assert_eq!(result, Err(Ok(CommitmentError::Unauthorized)));
+        assert_eq!(client.get_commitment_version(&employees.get(0).unwrap()), 1);
+    }
+
+    #[test]
+    fn test_batch_update_commitments_rejects_oversized_batch() {
+        let (env, contract_id, admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+        let (employees, commitments) = update_batch(&env, &client);
+        assert_eq!(client.get_max_update_batch(), DEFAULT_MAX_UPDATE_BATCH);
+
+        client.set_max_update_batch(&1);
+        let result = client.try_batch_update_commitments(&admin, &employees, &commitments);
+
+        assert_eq!(result, Err(Ok(CommitmentError::BatchTooLarge)));
+        assert_eq!(client.get_commitment_version(&employees.get(0).unwrap()), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Commitment not found")]
+    fn test_remove_missing_commitment_panics() {
+        let (env, contract_id, _admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        client.remove_commitment(&Address::generate(&env));
+    }
+
+    #[test]
+    fn test_store_commitment_extends_ttl_to_horizon() {
+        let (env, contract_id, _admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        let employee = Address::generate(&env);
+        client.store_commitment(&employee, &BytesN::from_array(&env, &[1u8; 32]));
+
+        assert_eq!(client.get_commitment_ttl(&employee), COMMITMENT_TTL_LEDGERS);
+        let host_ttl = env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .get_ttl(&DataKey::Commitment(employee.clone()))
+        });
+        assert_eq!(host_ttl, COMMITMENT_TTL_LEDGERS);
+    }
+
+    /// Store two commitments, bump only the first well past the default
+    /// horizon, then advance beyond that horizon.
+    fn setup_after_ttl_horizon() -> (Env, Address, Address, Address) {
+        let (env, contract_id, _admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        let bumped = Address::generate(&env);
+        let stale = Address::generate(&env);
+        client.store_commitment(&bumped, &BytesN::from_array(&env, &[1u8; 32]));
+        client.store_commitment(&stale, &BytesN::from_array(&env, &[2u8; 32]));
+
+        client.bump_commitment_ttl(&bumped, &(COMMITMENT_TTL_LEDGERS * 2));
+        // Keep the contract instance itself alive across the jump.
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .instance()
+                .extend_ttl(COMMITMENT_TTL_LEDGERS * 2, COMMITMENT_TTL_LEDGERS * 2)
+        });
+
+        env.ledger()
+            .with_mut(|l| l.sequence_number += COMMITMENT_TTL_LEDGERS + 1);
+
+        (env, contract_id, bumped, stale)
+    }
+
+    #[test]
+    fn test_bumped_commitment_readable_after_horizon() {
+        let (env, contract_id, bumped, _stale) = setup_after_ttl_horizon();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        assert_eq!(
+            client.get_commitment(&bumped).commitment,
+            BytesN::from_array(&env, &[1u8; 32])
+        );
+        assert_eq!(
+            client.get_commitment_ttl(&bumped),
+            COMMITMENT_TTL_LEDGERS - 1
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "has been archived")]
+    fn test_unbumped_commitment_archived_after_horizon() {
+        let (env, contract_id, _bumped, stale) = setup_after_ttl_horizon();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        client.get_commitment(&stale);
+    }
+
+    #[test]
+    fn test_bump_never_shortens_ttl() {
+        let (env, contract_id, _admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        let employee = Address::generate(&env);
+        client.store_commitment(&employee, &BytesN::from_array(&env, &[1u8; 32]));
+        client.bump_commitment_ttl(&employee, &10);
+
+        assert_eq!(client.get_commitment_ttl(&employee), COMMITMENT_TTL_LEDGERS);
+    }
+
+    #[test]
+    #[cfg(not(feature = "simulate-poseidon-host"))]
+    fn test_compute_commitment_falls_back_to_sha256_without_poseidon_host() {
+        let (env, contract_id, _admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        let blinding = BytesN::from_array(&env, &[7u8; 32]);
+        let computed = client.compute_commitment_with_metadata(&5000, &blinding);
+
+        let mut preimage = soroban_sdk::Bytes::from_slice(&env, COMMITMENT_DOMAIN);
+        preimage.extend_from_array(&5000i128.to_le_bytes());
+        preimage.extend_from_array(&[7u8; 32]);
+        let sha256: BytesN<32> = env.crypto().sha256(&preimage).into();
+        assert_eq!(computed.algorithm, CommitmentAlgorithm::Sha256Placeholder);
+        assert_eq!(computed.commitment, sha256);
+        assert_eq!(client.compute_commitment(&5000, &blinding), sha256);
+    }
+
+    #[test]
+    #[cfg(feature = "simulate-poseidon-host")]
+    fn test_compute_commitment_uses_poseidon_host_when_available() {
+        let (env, contract_id, _admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        let blinding = BytesN::from_array(&env, &[7u8; 32]);
+        let computed = client.compute_commitment_with_metadata(&5000, &blinding);
+
+        let mut preimage = soroban_sdk::Bytes::from_slice(&env, POSEIDON_SIMULATION_DOMAIN);
+        preimage.extend_from_array(&5000u64.to_le_bytes());
+        preimage.extend_from_array(&[7u8; 32]);
+        let simulated: BytesN<32> = env.crypto().sha256(&preimage).into();
+        assert_eq!(computed.algorithm, CommitmentAlgorithm::Poseidon);
+        assert_eq!(computed.commitment, simulated);
+        assert_ne!(
+            computed.commitment,
+            sha256_commitment(&env, 5000, &blinding)
+        );
+        assert_eq!(client.compute_commitment(&5000, &blinding), simulated);
+    }
+
+    /// Contract with a guardian set and one stored commitment.
+    fn setup_with_guardian() -> (Env, Address, Address, Address) {
+        let (env, contract_id, _admin) = setup_with_admin();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+        let guardian = Address::generate(&env);
+        client.set_guardian(&guardian);
+        let employee = Address::generate(&env);
+        client.store_commitment(&employee, &BytesN::from_array(&env, &[1u8; 32]));
+        (env, contract_id, guardian, employee)
+    }
+
+    #[test]
+    fn test_paused_contract_rejects_writes_and_serves_reads() {
+        let (env, contract_id, _guardian, employee) = setup_with_guardian();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+        let nullifier = BytesN::from_array(&env, &[5u8; 32]);
+        let commitment = BytesN::from_array(&env, &[2u8; 32]);
+
+        client.pause();
+        assert!(client.is_paused());
+
+        let paused: soroban_sdk::Error = CommitmentError::Paused.into();
+        let newcomer = Address::generate(&env);
+        let result = client.try_store_commitment(&newcomer, &commitment);
+        assert_eq!(result.unwrap_err().unwrap(), paused);
+        let result = client.try_update_commitment(&employee, &commitment);
+        assert_eq!(result.unwrap_err().unwrap(), paused);
+        let result = client.try_record_nullifier(&nullifier);
+        assert_eq!(result.unwrap_err().unwrap(), paused);
+        let employees = Vec::from_array(&env, [employee.clone()]);
+        let commitments = Vec::from_array(&env, [commitment.clone()]);
+        let admin = client.get_commitment_admin();
+        assert_eq!(
+            client.try_batch_update_commitments(&admin, &employees, &commitments),
+            Err(Ok(CommitmentError::Paused))
+        );
+
+        assert_eq!(
+            client.get_commitment(&employee).commitment,
+            BytesN::from_array(&env, &[1u8; 32])
+        );
+        assert_eq!(client.get_commitment_version(&employee), 1);
+        assert!(!client.has_commitment(&newcomer));
+        assert!(!client.is_nullifier_used(&nullifier));
+    }
+
+    #[test]
+    fn test_unpause_resumes_writes() {
+        let (env, contract_id, _guardian, employee) = setup_with_guardian();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+        let nullifier = BytesN::from_array(&env, &[5u8; 32]);
+        let commitment = BytesN::from_array(&env, &[2u8; 32]);
+
+        client.pause();
+        client.unpause();
+
+        assert!(!client.is_paused());
+        assert_eq!(client.update_commitment(&employee, &commitment).version, 2);
+        client.record_nullifier(&nullifier);
+        assert!(client.is_nullifier_used(&nullifier));
+    }
+
+    #[test]
+    fn test_only_guardian_can_toggle_pause() {
+        let (env, contract_id, guardian, _employee) = setup_with_guardian();
+        let client = SalaryCommitmentContractClient::new(&env, &contract_id);
+
+        client.pause();
+        assert_eq!(env.auths()[0].0, guardian);
+        client.unpause();
+        assert_eq!(env.auths()[0].0, guardian);
+
+        // With no authorisations mocked, nobody else can pause.
+        env.set_auths(&[]);
+        assert!(client.try_pause().is_err());
+        assert!(!client.is_paused());
+    }
 }
@@ -1,5 +1,11 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
+
+/// Most accounts a single `balances` call may query.
+///
+/// Each account is its own persistent read, and a transaction may read at
+/// most 40 ledger entries, the contract's instance and code included.
+pub const MAX_BALANCES_QUERY: u32 = 32;
 
 #[contracttype]
 pub enum DataKey {
@@ -29,6 +35,17 @@ impl Token {
         e.storage().persistent().get(&key).unwrap_or(0)
     }
 
+    /// Balances of `accounts`, in the same order. At most
+    /// `MAX_BALANCES_QUERY` accounts per call.
+    pub fn balances(e: Env, accounts: Vec<Address>) -> Vec<i128> {
+        assert!(accounts.len() <= MAX_BALANCES_QUERY, "Too many accounts");
+        let mut out = Vec::new(&e);
+        for id in accounts.iter() {
+            out.push_back(Self::balance(e.clone(), id));
+        }
+        out
+    }
+
     pub fn transfer(e: Env, from: Address, to: Address, amount: i128) {
         if amount < 0 {
             panic!("Transfer amount must be non-negative");
@@ -38,6 +55,8 @@ impl Token {
         // `from.require_auth()`. This placeholder omits the call because Soroban's
         // mock-auth mode (`mock_all_auths`) cannot satisfy non-root `require_auth()`
         // calls that originate from nested contract invocations (payroll → token).
+        // The enforcing path is covered in `integration_tests::nested_auth`, which
+        // runs payroll against a Stellar Asset Contract with explicit `mock_auths`.
 
         let from_key = DataKey::Balance(from);
         let from_balance: i128 = e.storage().persistent().get(&from_key).unwrap_or(0);
@@ -55,3 +74,43 @@ impl Token {
             .set(&to_key, &(to_balance + amount));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+
+    #[test]
+    fn test_balances_match_individual_balance_calls() {
+        let env = Env::default();
+        let client = TokenClient::new(&env, &env.register_contract(None, Token));
+
+        let funded = Address::generate(&env);
+        let other = Address::generate(&env);
+        let empty = Address::generate(&env);
+        client.mint(&funded, &5_000);
+        client.mint(&other, &250);
+
+        let accounts = Vec::from_array(&env, [other.clone(), empty.clone(), funded.clone(), other]);
+        let balances = client.balances(&accounts);
+
+        assert_eq!(balances.len(), accounts.len());
+        for (account, balance) in accounts.iter().zip(balances.iter()) {
+            assert_eq!(balance, client.balance(&account));
+        }
+        assert_eq!(balances, Vec::from_array(&env, [250i128, 0, 5_000, 250]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Too many accounts")]
+    fn test_balances_rejects_oversized_query() {
+        let env = Env::default();
+        let client = TokenClient::new(&env, &env.register_contract(None, Token));
+
+        let mut accounts = Vec::new(&env);
+        for _ in 0..=MAX_BALANCES_QUERY {
+            accounts.push_back(Address::generate(&env));
+        }
+        client.balances(&accounts);
+    }
+}
@@ -226,7 +226,7 @@ fuzz_target!(|input: FuzzInput| {
                 inputs.push_back(BytesN::from_array(&env, input_bytes));
             }
 
-            let _ = verifier_client.try_verify_payment_proof(&proof, &inputs);
+            let _ = verifier_client.try_verify_payment_proof(&1, &proof, &inputs);
         }
 
         FuzzAction::ExecutePayment {
@@ -258,6 +258,7 @@ fuzz_target!(|input: FuzzInput| {
 
             sys.registry.add_employee(
                 &sys.company_id,
+                &sys.admin,
                 &employee,
                 &BytesN::from_array(&env, &commitment),
             );
@@ -274,6 +275,7 @@ fuzz_target!(|input: FuzzInput| {
                 &BytesN::from_array(&env, &proof_c),
                 &BytesN::from_array(&env, &nullifier),
                 &test_period,
+                &BytesN::from_array(&env, &[0xAAu8; 32]),
             );
 
             if let Ok(Ok(_record)) = res {
@@ -294,6 +296,7 @@ fuzz_target!(|input: FuzzInput| {
                     &BytesN::from_array(&env, &proof_c),
                     &BytesN::from_array(&env, &nullifier),
                     &test_period,
+                    &BytesN::from_array(&env, &[0xBBu8; 32]),
                 );
                 assert_eq!(replay_res.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
             } else {
@@ -344,6 +347,7 @@ fuzz_target!(|input: FuzzInput| {
                 let employee = Address::generate(&env);
                 sys.registry.add_employee(
                     &sys.company_id,
+                    &sys.admin,
                     &employee,
                     &BytesN::from_array(&env, &commitments[i]),
                 );
@@ -438,6 +442,7 @@ fuzz_target!(|input: FuzzInput| {
                             &test_proofs_c[i],
                             &test_nullifiers[i],
                             &period,
+                            &BytesN::from_array(&env, &[0xBBu8; 32]),
                         );
                         assert_eq!(replay_res.unwrap_err().unwrap(), PaymentError::ProofAlreadyUsed);
                     }
@@ -134,7 +134,7 @@ echo "Generated commitment: $COMMITMENT"
 echo "Adding employee..."
 stellar contract invoke \
   $REGISTRY_ID add_employee \
-  --args company_id=$COMPANY_ID employee=$EMPLOYEE_PUBLIC commitment=$COMMITMENT \
+  --args company_id=$COMPANY_ID caller=$ADMIN_PUBLIC employee=$EMPLOYEE_PUBLIC commitment=$COMMITMENT \
   --network testnet \
   --source $ADMIN_SECRET >/dev/null 2>&1 || error "Adding employee failed"
 